@@ -1,13 +1,13 @@
+use chrono::{DateTime, Utc};
 use directories::ProjectDirs;
 use phenopackets::schema::v2::Phenopacket;
-use phenopackets::schema::v2::core::genomic_interpretation::Call;
 use phenoxtract::config::table_context::{AliasMap, OutputDataType};
+use phenoxtract::load::FileSystemLoader;
 use phenoxtract::ontology::error::RegistryError;
 use phenoxtract::ontology::resource_references::ResourceRef;
 use pivotal::hgnc::{CachedHGNCClient, HGNCClient};
 use pivotal::hgvs::{CachedHGVSClient, HGVSClient};
 use rstest::fixture;
-use serde_json::Value;
 use std::collections::HashMap;
 use std::env::home_dir;
 use std::ffi::OsStr;
@@ -45,6 +45,12 @@ pub fn pato_ref() -> ResourceRef {
     ResourceRef::pato().with_version("2025-05-14")
 }
 
+/// The `MetaData.created` of the expected phenopackets. The integration pipelines run deterministically with it.
+#[fixture]
+pub fn deterministic_created() -> DateTime<Utc> {
+    DateTime::from_timestamp(1_735_689_600, 0).unwrap()
+}
+
 #[fixture]
 pub fn tests_assets() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/assets")
@@ -74,39 +80,12 @@ pub fn build_hgvs_test_client(temp_dir: &Path) -> CachedHGVSClient {
 }
 
 fn assert_phenopackets(actual: &mut Phenopacket, expected: &mut Phenopacket) {
-    remove_created_from_metadata(actual);
-    remove_created_from_metadata(expected);
-
-    remove_id_from_variation_descriptor(actual);
-    remove_id_from_variation_descriptor(expected);
-
     remove_version_from_loinc(actual);
     remove_version_from_loinc(expected);
 
     pretty_assertions::assert_eq!(actual, expected);
 }
 
-fn remove_created_from_metadata(pp: &mut Phenopacket) {
-    if let Some(meta) = &mut pp.meta_data {
-        meta.created = None;
-    }
-}
-
-fn remove_id_from_variation_descriptor(pp: &mut Phenopacket) {
-    for interpretation in pp.interpretations.iter_mut() {
-        if let Some(diagnosis) = &mut interpretation.diagnosis {
-            for gi in diagnosis.genomic_interpretations.iter_mut() {
-                if let Some(call) = &mut gi.call
-                    && let Call::VariantInterpretation(vi) = call
-                    && let Some(vi) = &mut vi.variation_descriptor
-                {
-                    vi.id = "TEST_ID".to_string();
-                }
-            }
-        }
-    }
-}
-
 fn remove_version_from_loinc(pp: &mut Phenopacket) {
     if let Some(metadata) = &mut pp.meta_data {
         let loinc_resource = metadata
@@ -120,27 +99,9 @@ fn remove_version_from_loinc(pp: &mut Phenopacket) {
     }
 }
 
-// We remove the survival time in the loader. However, the Phenopacket struct can not be constructed if that field is missing.
-fn ensure_survival_time(pp: &mut Value) {
-    #[allow(clippy::collapsible_if)]
-    if let Some(individual) = pp.get_mut("subject") {
-        if let Some(vital_status_value) = individual.get_mut("vitalStatus") {
-            if let Some(vital_status) = vital_status_value.as_object_mut() {
-                if vital_status.get("survivalTimeInDays").is_none() {
-                    vital_status.insert("survivalTimeInDays".to_string(), Value::Number(0.into()));
-                }
-            }
-        }
-    }
-}
-
 fn load_phenopacket(path: PathBuf) -> Phenopacket {
     let data = fs::read_to_string(path).unwrap();
-    let mut expected_pp: Value = serde_json::from_str(&data).unwrap();
-
-    ensure_survival_time(&mut expected_pp);
-
-    serde_json::from_value::<Phenopacket>(expected_pp).unwrap()
+    FileSystemLoader::phenopacket_from_json(&data).unwrap()
 }
 
 pub fn ontology_registry_dir() -> Result<PathBuf, RegistryError> {
@@ -171,24 +132,36 @@ pub fn compare_expected_and_extracted_phenopackets(
     output_dir_name: &str,
     expected_dir_name: &str,
 ) {
-    let mut expected_phenopackets: HashMap<String, Phenopacket> =
-        fs::read_dir(assets_dir.join(expected_dir_name))
-            .unwrap()
-            .map(|entry| {
-                let phenopacket = load_phenopacket(entry.unwrap().path());
-                (phenopacket.id.clone(), phenopacket)
-            })
-            .collect();
-
-    let output_dir = assets_dir.join(output_dir_name);
-    for extracted_pp_file in fs::read_dir(output_dir).unwrap() {
-        if let Ok(extracted_pp_file) = extracted_pp_file
-            && extracted_pp_file.path().extension() == Some(OsStr::new("json"))
-        {
-            let mut extracted_pp = load_phenopacket(extracted_pp_file.path());
-            let expected_pp = expected_phenopackets.get_mut(&extracted_pp.id).unwrap();
-
-            assert_phenopackets(&mut extracted_pp, expected_pp);
-        }
+    let mut expected_phenopackets =
+        load_phenopackets_sorted_by_id(&assets_dir.join(expected_dir_name));
+    let mut extracted_phenopackets =
+        load_phenopackets_sorted_by_id(&assets_dir.join(output_dir_name));
+
+    pretty_assertions::assert_eq!(
+        extracted_phenopackets
+            .iter()
+            .map(|pp| pp.id.as_str())
+            .collect::<Vec<_>>(),
+        expected_phenopackets
+            .iter()
+            .map(|pp| pp.id.as_str())
+            .collect::<Vec<_>>()
+    );
+    for (extracted_pp, expected_pp) in extracted_phenopackets
+        .iter_mut()
+        .zip(expected_phenopackets.iter_mut())
+    {
+        assert_phenopackets(extracted_pp, expected_pp);
     }
 }
+
+fn load_phenopackets_sorted_by_id(dir: &Path) -> Vec<Phenopacket> {
+    let mut phenopackets: Vec<Phenopacket> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension() == Some(OsStr::new("json")))
+        .map(load_phenopacket)
+        .collect();
+    phenopackets.sort_by(|a, b| a.id.cmp(&b.id));
+    phenopackets
+}
@@ -8,11 +8,12 @@ use phenoxtract::extract::{CsvDataSource, DataSource};
 use phenoxtract::load::FileSystemLoader;
 use phenoxtract::ontology::resource_references::ResourceRef;
 
+use chrono::{DateTime, Utc};
 use dotenvy::dotenv;
 use integration_tests::{
     build_hgnc_test_client, build_hgvs_test_client, cohort_name,
-    compare_expected_and_extracted_phenopackets, ontology_registry_dir, pato_ref, temp_dir,
-    tests_assets, vital_status_aliases,
+    compare_expected_and_extracted_phenopackets, deterministic_created, ontology_registry_dir,
+    pato_ref, temp_dir, tests_assets, vital_status_aliases,
 };
 use ontology_registry::blocking::bio_registry_metadata_provider::BioRegistryMetadataProvider;
 use ontology_registry::blocking::file_system_ontology_registry::FileSystemOntologyRegistry;
@@ -120,6 +121,7 @@ fn big_null_test(
     pato_ref: ResourceRef,
     tests_assets: PathBuf,
     cohort_name: String,
+    deterministic_created: DateTime<Utc>,
 ) {
     // Set up
     let mut onto_factory = CachedOntologyFactory::new(FileSystemOntologyRegistry::new(
@@ -178,6 +180,7 @@ fn big_null_test(
     let loader = Box::new(FileSystemLoader::new(output_dir.clone(), true));
 
     let mut pipeline = Pipeline::new(transformer_module, loader);
+    pipeline.set_deterministic(deterministic_created);
 
     //Run the pipeline on the data sources
     pipeline.run(&mut data_sources).expect("Pipeline failed");
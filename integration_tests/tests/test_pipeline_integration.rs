@@ -1,9 +1,11 @@
 #![allow(clippy::too_many_arguments)]
+use chrono::{DateTime, Utc};
 use dotenvy::dotenv;
 use integration_tests::{
     build_hgnc_test_client, build_hgvs_test_client, cohort_name,
-    compare_expected_and_extracted_phenopackets, hp_ref, mondo_ref, no_info_alias,
-    ontology_registry_dir, pato_ref, temp_dir, tests_assets, uo_ref, vital_status_aliases,
+    compare_expected_and_extracted_phenopackets, deterministic_created, hp_ref, mondo_ref,
+    no_info_alias, ontology_registry_dir, pato_ref, temp_dir, tests_assets, uo_ref,
+    vital_status_aliases,
 };
 use ontology_registry::blocking::bio_registry_metadata_provider::BioRegistryMetadataProvider;
 use ontology_registry::blocking::file_system_ontology_registry::FileSystemOntologyRegistry;
@@ -196,6 +198,7 @@ fn test_pipeline_integration(
     pato_ref: ResourceRef,
     tests_assets: PathBuf,
     cohort_name: String,
+    deterministic_created: DateTime<Utc>,
 ) {
     // Set up
     let mut onto_factory = CachedOntologyFactory::new(FileSystemOntologyRegistry::new(
@@ -309,6 +312,7 @@ fn test_pipeline_integration(
     let loader = Box::new(FileSystemLoader::new(output_dir.clone(), true));
 
     let mut pipeline = Pipeline::new(transformer_module, loader);
+    pipeline.set_deterministic(deterministic_created);
 
     //Run the pipeline on the data sources
     pipeline.run(&mut data_sources).unwrap();
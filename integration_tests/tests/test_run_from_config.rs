@@ -0,0 +1,71 @@
+use integration_tests::{deterministic_created, temp_dir};
+use phenopackets::schema::v2::core::Sex;
+use phenoxtract::config::{ConfigLoader, PhenoXtractConfig};
+use phenoxtract::phenoxtract::Phenoxtract;
+use rstest::rstest;
+use std::fs;
+use tempfile::TempDir;
+
+#[rstest]
+fn test_run_from_config_in_memory(temp_dir: TempDir) {
+    let csv_path = temp_dir.path().join("patients.csv");
+    fs::write(&csv_path, "patient_id,sex\nP001,MALE\nP002,FEMALE\n").unwrap();
+
+    let output_dir = temp_dir.path().join("out");
+    let config_path = temp_dir.path().join("config.yaml");
+    fs::write(
+        &config_path,
+        format!(
+            r#"
+data_sources:
+  - type: "csv"
+    source: "{}"
+    has_headers: true
+    patients_are_rows: true
+    series_contexts:
+      - identifier:
+          single: "patient_id"
+        data_context: subject_id
+      - identifier:
+          single: "sex"
+        data_context: subject_sex
+pipeline:
+  cache_dir: "{}"
+  strategies: []
+  deterministic_created: "2025-01-01T00:00:00Z"
+  loader:
+    file_system:
+      output_dir: "{}"
+      create_dir: true
+  meta_data:
+    cohort_name: "embedded_cohort"
+"#,
+            csv_path.display(),
+            temp_dir.path().join("cache").display(),
+            output_dir.display(),
+        ),
+    )
+    .unwrap();
+
+    let config: PhenoXtractConfig = ConfigLoader::load(config_path).unwrap();
+    let mut phenoxtract = Phenoxtract::try_from(config).unwrap();
+    let mut phenopackets = phenoxtract.run_in_memory().unwrap().phenopackets;
+    phenopackets.sort_by(|a, b| a.id.cmp(&b.id));
+
+    assert_eq!(
+        phenopackets
+            .iter()
+            .map(|pp| pp.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["embedded_cohort-P001", "embedded_cohort-P002"]
+    );
+    assert_eq!(
+        phenopackets[1].subject.as_ref().unwrap().sex,
+        Sex::Female as i32
+    );
+    assert!(phenopackets.iter().all(|pp| {
+        let created = pp.meta_data.as_ref().unwrap().created.as_ref().unwrap();
+        created.seconds == deterministic_created().timestamp()
+    }));
+    assert!(!output_dir.exists());
+}
@@ -0,0 +1,33 @@
+use crate::config::datasource_config::DataSourceConfig;
+use crate::config::loader_config::LoaderConfig;
+use crate::config::meta_data::MetaData;
+use crate::validation::phenoxtractor_config_validation::validate_unique_data_sources;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// An additional cohort, which runs through the same strategies as the main pipeline,
+/// but with its own metadata, data sources and output.
+///
+/// Ontologies and network clients are shared with the main pipeline, so nothing is loaded twice.
+#[derive(Debug, Deserialize, Serialize, Validate, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CohortConfig {
+    pub meta_data: MetaData,
+    #[validate(custom(function = "validate_unique_data_sources"))]
+    pub data_sources: Vec<DataSourceConfig>,
+    pub loader: LoaderConfig,
+}
+
+impl CohortConfig {
+    pub fn new(
+        meta_data: MetaData,
+        data_sources: Vec<DataSourceConfig>,
+        loader: LoaderConfig,
+    ) -> Self {
+        Self {
+            meta_data,
+            data_sources,
+            loader,
+        }
+    }
+}
@@ -47,6 +47,7 @@ mod tests {
     use crate::config::context::{Context, TimeElementType};
     use crate::config::datasource_config::IdentifierConfig;
     use crate::config::loader_config::LoaderConfig;
+    use crate::load::OutputFormat;
 
     use crate::config::datasource_config::{
         AliasMapConfig, CsvConfig, ExcelSheetConfig, ExcelWorkbookConfig, MappingsConfig,
@@ -165,6 +166,8 @@ blah: "blahblah"
                 LoaderConfig::FileSystem {
                     output_dir: PathBuf::from("some/dir"),
                     create_dir: true,
+                    output_format: OutputFormat::Json,
+                    resume: false,
                 },
                 Some("./src/test_suite/test_cache".parse().unwrap()),
             ),
@@ -173,8 +176,12 @@ blah: "blahblah"
                 DataSourceConfig::Csv(CsvConfig {
                     source: PathBuf::from("./data/example.csv"),
                     separator: Some(','),
+                    quote_char: None,
+                    null_values: vec![],
+                    subject_id_separator: None,
                     has_headers: true,
                     patients_are_rows: true,
+                    chunk_size: None,
                     series_contexts: vec![
                         SeriesContextConfig::new(IdentifierConfig::Single(
                             "patient_id".to_string(),
@@ -211,6 +218,7 @@ blah: "blahblah"
                             sheet_name: "Sheet1".to_string(),
                             has_headers: true,
                             patients_are_rows: true,
+                            subject_id_separator: None,
                             series_contexts: vec![SeriesContextConfig {
                                 identifier: IdentifierConfig::Regex("lab_result_.*".to_string()),
                                 header_context: Context::SubjectId,
@@ -226,12 +234,15 @@ blah: "blahblah"
                                     output_data_type: OutputDataType::Float64,
                                 }),
                                 building_block_id: None,
+                                keep_blank_cells: false,
+                                null_values: vec![],
                             }],
                         },
                         ExcelSheetConfig {
                             sheet_name: "Sheet2".to_string(),
                             has_headers: true,
                             patients_are_rows: true,
+                            subject_id_separator: None,
                             series_contexts: vec![SeriesContextConfig {
                                 identifier: IdentifierConfig::Multi(vec![
                                     "Col_1".to_string(),
@@ -251,11 +262,14 @@ blah: "blahblah"
                                     output_data_type: OutputDataType::Boolean,
                                 }),
                                 building_block_id: None,
+                                keep_blank_cells: false,
+                                null_values: vec![],
                             }],
                         },
                     ],
                 }),
             ],
+            cohorts: vec![],
         };
 
         assert_eq!(config, expected_config);
@@ -48,10 +48,16 @@ pub enum Boundary {
 pub enum Context {
     // Individual
     SubjectId,
+    AlternateId,
     SubjectSex,
+    Gender,
+    Taxonomy,
     DateOfBirth,
     VitalStatus,
     TimeAtLastEncounter(TimeElementType),
+    /// The date of the last encounter. If there is no time at last encounter, it is derived from this date and
+    /// the date of birth.
+    EncounterDate,
     TimeOfDeath(TimeElementType),
     CauseOfDeath,
     SurvivalTimeDays,
@@ -64,12 +70,31 @@ pub enum Context {
     HpoOrDisease,
     TimeOfResolution(TimeElementType),
     Severity,
+    EvidenceCode,
+    /// An external reference (e.g. "PMID:12345678"), which supports the evidence code in the same row of the
+    /// building block.
+    Reference,
     ObservationStatus,
     PrimarySite,
+    ClinicalTnmFinding,
+    DiseaseStage,
+    Laterality,
 
     // Genetic Data
     Hgvs,
     Hgnc,
+    /// The parental origin (maternal or paternal) of the variant in the HGVS column at the same position
+    /// within the building block.
+    VariantOrigin,
+    /// The ACMG pathogenicity classification (e.g. "LIKELY_PATHOGENIC" or "VUS") of the variant in the HGVS column
+    /// at the same position within the building block.
+    AcmgClassification,
+    /// The therapeutic actionability (e.g. "ACTIONABLE" or "NOT_ACTIONABLE") of the variant in the HGVS column
+    /// at the same position within the building block.
+    TherapeuticActionability,
+    /// The genome assembly (e.g. "hg19" or "GRCh38") of the variants in the same row of the building block.
+    /// Overrides the genome assembly of the pipeline config.
+    GenomeAssembly,
 
     // Measurements
     QuantitativeMeasurement {
@@ -79,9 +104,25 @@ pub enum Context {
     QualitativeMeasurement {
         assay_id: String,
     },
+    /// The numerator of a ratio measurement (e.g. the albumin of an albumin/creatinine ratio).
+    /// The `assay_id` belongs to the ratio, while the `type_id` is the assay of the numerator itself.
+    MeasurementNumerator {
+        assay_id: String,
+        type_id: String,
+        unit_ontology_id: String,
+    },
+    /// The denominator of a ratio measurement. It is linked to its numerator by the building block.
+    MeasurementDenominator {
+        type_id: String,
+        unit_ontology_id: String,
+    },
     TimeOfMeasurement(TimeElementType),
     ReferenceRange(Boundary),
 
+    // Biosamples
+    BiosampleId,
+    SampledTissue,
+
     // Medical Actions
     TreatmentAgent,
     RouteOfAdministration,
@@ -145,20 +186,35 @@ impl Context {
 
                 // Ensures that we see a compile error, when we add another context type
                 ContextKind::SubjectId
+                | ContextKind::AlternateId
                 | ContextKind::SubjectSex
+                | ContextKind::Gender
+                | ContextKind::Taxonomy
                 | ContextKind::DateOfBirth
+                | ContextKind::EncounterDate
                 | ContextKind::VitalStatus
                 | ContextKind::CauseOfDeath
                 | ContextKind::SurvivalTimeDays
                 | ContextKind::Hpo
                 | ContextKind::Disease
                 | ContextKind::PrimarySite
+                | ContextKind::ClinicalTnmFinding
+                | ContextKind::DiseaseStage
+                | ContextKind::Laterality
                 | ContextKind::Hgnc
                 | ContextKind::HpoOrDisease
                 | ContextKind::Hgvs
+                | ContextKind::VariantOrigin
+                | ContextKind::AcmgClassification
+                | ContextKind::TherapeuticActionability
+                | ContextKind::GenomeAssembly
                 | ContextKind::QuantitativeMeasurement
                 | ContextKind::QualitativeMeasurement
+                | ContextKind::MeasurementNumerator
+                | ContextKind::MeasurementDenominator
                 | ContextKind::ReferenceRange
+                | ContextKind::BiosampleId
+                | ContextKind::SampledTissue
                 | ContextKind::TreatmentTarget
                 | ContextKind::TreatmentIntent
                 | ContextKind::ResponseToTreatment
@@ -168,6 +224,8 @@ impl Context {
                 | ContextKind::ObservationStatus
                 | ContextKind::MultiHpoId
                 | ContextKind::Severity
+                | ContextKind::EvidenceCode
+                | ContextKind::Reference
                 | ContextKind::QuantityValue
                 | ContextKind::QuantityUnit
                 | ContextKind::TreatmentAgent
@@ -11,18 +11,34 @@ use std::path::PathBuf;
 pub enum DataSourceConfig {
     Csv(CsvConfig),
     Excel(ExcelWorkbookConfig),
+    Phenopacket(PhenopacketSourceConfig),
+    Json(JsonSourceConfig),
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct CsvConfig {
     pub source: PathBuf,
-    #[serde(default)]
+    /// Defaults to ','. Use '\t' for TSV files.
+    #[serde(default, alias = "delimiter")]
     pub separator: Option<char>,
+    /// Defaults to '"'.
+    #[serde(default)]
+    pub quote_char: Option<char>,
+    /// Cell values, which are read as null, e.g. "NA" or "N/A".
+    #[serde(default)]
+    pub null_values: Vec<String>,
     #[serde(default)]
     pub series_contexts: Vec<SeriesContextConfig>,
+    /// If set, the columns of the subject id series context are concatenated with this separator.
+    #[serde(default)]
+    pub subject_id_separator: Option<String>,
     pub has_headers: bool,
     pub patients_are_rows: bool,
+    /// If set, the file is read in chunks of about this many rows instead of at once.
+    /// The rows of each patient should be consecutive, and the patients must be rows.
+    #[serde(default)]
+    pub chunk_size: Option<usize>,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize, PartialEq)]
@@ -33,12 +49,34 @@ pub struct ExcelWorkbookConfig {
     pub sheets: Vec<ExcelSheetConfig>,
 }
 
+/// A directory of phenopacket JSON files. The flattened table uses a fixed set of series contexts.
+#[derive(Debug, Deserialize, Clone, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PhenopacketSourceConfig {
+    pub source: PathBuf,
+}
+
+/// A JSON array of records or a newline-delimited JSON file. Nested keys are addressed by dotted column names.
+#[derive(Debug, Deserialize, Clone, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct JsonSourceConfig {
+    pub source: PathBuf,
+    #[serde(default)]
+    pub series_contexts: Vec<SeriesContextConfig>,
+    /// If set, the columns of the subject id series context are concatenated with this separator.
+    #[serde(default)]
+    pub subject_id_separator: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Clone, Serialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct ExcelSheetConfig {
     pub sheet_name: String,
     #[serde(default)]
     pub series_contexts: Vec<SeriesContextConfig>,
+    /// If set, the columns of the subject id series context are concatenated with this separator.
+    #[serde(default)]
+    pub subject_id_separator: Option<String>,
     pub has_headers: bool,
     pub patients_are_rows: bool,
 }
@@ -58,6 +96,11 @@ pub struct SeriesContextConfig {
     pub alias_map_config: Option<AliasMapConfig>,
     #[serde(default)]
     pub building_block_id: Option<String>,
+    #[serde(default)]
+    pub keep_blank_cells: bool,
+    /// Cell values, which are treated as null, e.g. "NA" or "unknown".
+    #[serde(default)]
+    pub null_values: Vec<String>,
 }
 
 impl SeriesContextConfig {
@@ -71,6 +114,9 @@ impl SeriesContextConfig {
 pub enum IdentifierConfig {
     Single(String),
     Regex(String),
+    /// A regex, which has to match the whole column name.
+    #[serde(rename = "anchored_regex")]
+    AnchoredRegex(String),
     Multi(Vec<String>),
 }
 
@@ -112,6 +158,8 @@ impl SeriesContextBuilding<IdentifierConfig, AliasMapConfig> for SeriesContextCo
             fill_missing: None,
             alias_map_config: None,
             building_block_id: None,
+            keep_blank_cells: false,
+            null_values: vec![],
         }
     }
 
@@ -149,6 +197,16 @@ impl SeriesContextBuilding<IdentifierConfig, AliasMapConfig> for SeriesContextCo
             self
         }
     }
+
+    fn with_keep_blank_cells(mut self, keep_blank_cells: bool) -> Self {
+        self.keep_blank_cells = keep_blank_cells;
+        self
+    }
+
+    fn with_null_values(mut self, null_values: Vec<String>) -> Self {
+        self.null_values = null_values;
+        self
+    }
 }
 
 impl SeriesContextConfig {
@@ -160,6 +218,8 @@ impl SeriesContextConfig {
             fill_missing: None,
             alias_map_config: None,
             building_block_id: None,
+            keep_blank_cells: false,
+            null_values: vec![],
         }
     }
 
@@ -187,6 +247,16 @@ impl SeriesContextConfig {
         self.building_block_id = Some(building_block_id);
         self
     }
+
+    pub fn keep_blank_cells(mut self, keep_blank_cells: bool) -> Self {
+        self.keep_blank_cells = keep_blank_cells;
+        self
+    }
+
+    pub fn null_values(mut self, null_values: Vec<String>) -> Self {
+        self.null_values = null_values;
+        self
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize, PartialEq)]
@@ -223,7 +293,10 @@ impl CsvConfig {
         Self {
             source,
             separator,
+            quote_char: None,
+            null_values: vec![],
             series_contexts: contexts,
+            subject_id_separator: None,
             has_headers,
             patients_are_rows,
         }
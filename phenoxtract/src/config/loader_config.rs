@@ -1,5 +1,6 @@
+use crate::load::OutputFormat;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Deserialize, Clone, Serialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -8,5 +9,27 @@ pub enum LoaderConfig {
     FileSystem {
         output_dir: PathBuf,
         create_dir: bool,
+        #[serde(default)]
+        output_format: OutputFormat,
+        /// Writes every phenopacket as soon as it is built and skips the already written ones on a restart.
+        #[serde(default)]
+        resume: bool,
     },
+    /// Writes every phenopacket as a minimal FHIR bundle. Requires the `fhir` feature.
+    #[cfg(feature = "fhir")]
+    Fhir {
+        output_dir: PathBuf,
+        create_dir: bool,
+    },
+}
+
+impl LoaderConfig {
+    /// The directory, which the loader writes to.
+    pub fn output_dir(&self) -> &Path {
+        match self {
+            LoaderConfig::FileSystem { output_dir, .. } => output_dir,
+            #[cfg(feature = "fhir")]
+            LoaderConfig::Fhir { output_dir, .. } => output_dir,
+        }
+    }
 }
@@ -28,6 +28,12 @@ pub struct MetaData {
     pub drug_resources: Vec<ResourceConfig>,
     #[serde(default)]
     pub treatment_attributes_resources: Vec<ResourceConfig>,
+    #[serde(default)]
+    pub evidence_resources: Vec<ResourceConfig>,
+    #[serde(default)]
+    pub gender_resources: Vec<ResourceConfig>,
+    #[serde(default)]
+    pub disease_attributes_resources: Vec<ResourceConfig>,
 }
 impl MetaData {
     #[allow(clippy::too_many_arguments)]
@@ -64,6 +70,9 @@ impl MetaData {
             anatomy_resources,
             drug_resources,
             treatment_attributes_resources,
+            evidence_resources: vec![],
+            gender_resources: vec![],
+            disease_attributes_resources: vec![],
         }
     }
 }
@@ -83,6 +92,9 @@ impl Default for MetaData {
             anatomy_resources: vec![],
             drug_resources: vec![],
             treatment_attributes_resources: vec![],
+            evidence_resources: vec![],
+            gender_resources: vec![],
+            disease_attributes_resources: vec![],
         }
     }
 }
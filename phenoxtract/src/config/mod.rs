@@ -1,3 +1,5 @@
+pub mod cohort_config;
+pub use self::cohort_config::CohortConfig;
 pub mod meta_data;
 pub use self::meta_data::MetaData;
 pub mod phenoxtract_config;
@@ -1,3 +1,4 @@
+use crate::config::cohort_config::CohortConfig;
 use crate::config::datasource_config::DataSourceConfig;
 use crate::config::pipeline_config::PipelineConfig;
 use crate::validation::phenoxtractor_config_validation::validate_unique_data_sources;
@@ -12,6 +13,11 @@ pub struct PhenoXtractConfig {
     pub data_sources: Vec<DataSourceConfig>,
     #[serde(rename = "pipeline")]
     pub pipeline_config: PipelineConfig,
+    /// Further cohorts, which are run with the strategies of `pipeline_config`, but have their own
+    /// `meta_data`, `data_sources` and `loader`.
+    #[serde(default)]
+    #[validate(nested)]
+    pub cohorts: Vec<CohortConfig>,
 }
 
 impl PhenoXtractConfig {
@@ -21,4 +27,7 @@ impl PhenoXtractConfig {
     pub fn data_sources(&self) -> Vec<DataSourceConfig> {
         self.data_sources.clone()
     }
+    pub fn cohorts(&self) -> Vec<CohortConfig> {
+        self.cohorts.clone()
+    }
 }
@@ -1,8 +1,15 @@
 use crate::config::loader_config::LoaderConfig;
 use crate::config::meta_data::MetaData;
 use crate::config::strategy_config::StrategyConfig;
+use crate::ontology::concurrency_limiter::DEFAULT_MAX_CONCURRENT_REQUESTS;
+use crate::transform::collecting::cdf_collector_broker::CohortSizeGuard;
+use crate::transform::pathogenic_gene_variant_info::GenomeAssembly;
+use crate::transform::transform_context::UnknownSexPolicy;
 use crate::utils::default_cache_dir;
+use crate::validation::lint_report::Severity;
+use crate::validation::linter::{LintLevel, PlausibleRange};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Represents the configuration for a data processing pipeline.
@@ -21,6 +28,83 @@ pub struct PipelineConfig {
     pub loader: LoaderConfig,
     #[serde(default = "config_cache_dir")]
     pub cache_dir: Option<PathBuf>,
+    /// Upper bound for in-flight requests against network services (HGNC, HGVS, BioPortal, LOINC). Has to be at least 1.
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
+    /// How sex values, which are not a known `Sex` name, are handled. Strict by default.
+    #[serde(default)]
+    pub unknown_sex_policy: UnknownSexPolicy,
+    /// Additional sex values of the source data (e.g. "männlich") and the `Sex` name they map to (e.g. "MALE").
+    /// They extend the default vocabulary, which already knows values like "M", "female" or "2".
+    #[serde(default)]
+    pub sex_vocabulary: HashMap<String, String>,
+    /// If set, the pipeline runs deterministically: `MetaData.created` is fixed to this RFC 3339 timestamp.
+    #[serde(default)]
+    pub deterministic_created: Option<String>,
+    /// Opt-in guard, which errors if the number of subjects looks like a misconfigured subject id column.
+    #[serde(default)]
+    pub cohort_size_guard: Option<CohortSizeGuard>,
+    /// If set, labels, which can't be resolved, are reported together with up to three of the closest known labels.
+    /// Off by default, because it compares the label to every label of the ontologies.
+    #[serde(default)]
+    pub label_suggestions: bool,
+    /// If set, phenotypes and diseases, whose term can't be resolved, are dropped and recorded instead of aborting the run.
+    /// Strict by default.
+    #[serde(default)]
+    pub lenient: bool,
+    /// If set, gene symbols must be current HGNC symbols. By default, previous and alias symbols are remapped
+    /// to the current symbol of their gene.
+    #[serde(default)]
+    pub strict_gene_symbols: bool,
+    /// If set, the causative genes are resolved with one HGNC search request per 50 genes, before the phenopackets
+    /// are built. The genes, which it doesn't resolve, are requested one by one.
+    #[serde(default)]
+    pub batch_gene_requests: bool,
+    /// Numeric scores in the columns, which have an HPO term in their header, mean the phenotype was observed, if they
+    /// are above this threshold, and excluded otherwise. 0 by default.
+    #[serde(default)]
+    pub hpo_observed_threshold: f64,
+    /// Opt-in: if set, genes and variants, which can't be resolved, are remembered for this many hours
+    /// in the cache directory, so that later runs fail fast instead of querying HGNC or HGVS again.
+    #[serde(default)]
+    pub negative_cache_ttl_hours: Option<u64>,
+    /// If set, resolved genes are fetched again from HGNC once they were cached for this many hours, so that renamed
+    /// genes don't go stale. By default, they are cached for the whole run.
+    #[serde(default)]
+    pub hgnc_cache_ttl_hours: Option<u64>,
+    /// If set, variants, whose VCF coordinates are on another genome assembly, are an error.
+    #[serde(default)]
+    pub genome_assembly: Option<GenomeAssembly>,
+    /// The order, in which the disease ontologies are asked for a label or id (e.g. `["MONDO", "OMIM", "ORPHA"]`).
+    /// Ontologies, which are not listed, are asked last. By default, the order of `disease_resources` is used.
+    #[serde(default)]
+    pub disease_priority: Vec<String>,
+    /// If set, every disease row becomes its own disease. By default, a disease, which repeats an earlier disease
+    /// of the patient without contradicting it, is merged into it.
+    #[serde(default)]
+    pub keep_duplicate_diseases: bool,
+    /// If set, a cohort, whose phenopackets reference different versions of the same resource, is an error.
+    /// By default, the conflicting versions are only logged.
+    #[serde(default)]
+    pub strict_resource_versions: bool,
+    /// If set, phenotypic features, whose cell value was changed by the strategies, carry the original value
+    /// in their description. Off by default.
+    #[serde(default)]
+    pub preserve_raw_values: bool,
+    /// The plausible range of the values of each assay, e.g. `{"LOINC:718-7": {min: 0, max: 250}}`. Measurement
+    /// values outside of the range of their assay are reported in the lint report. By default, no range is checked.
+    #[serde(default)]
+    pub plausible_measurement_ranges: HashMap<String, PlausibleRange>,
+    /// Overrides the severity of single lint rules with `off`, `warn` or `error`, e.g. `{"missing_resource": off}`.
+    #[serde(default)]
+    pub lint_rules: HashMap<String, LintLevel>,
+    /// The run aborts on the first lint finding, which is at least this severe. `error` by default.
+    #[serde(default = "default_fail_on")]
+    pub fail_on: Severity,
+    /// If set, the ontologies are only taken from the registry on disk and the resources from the snapshot of earlier
+    /// online runs, without any request to BioRegistry or the ontology sources. Off by default.
+    #[serde(default)]
+    pub offline: bool,
 }
 
 impl PipelineConfig {
@@ -39,8 +123,145 @@ impl PipelineConfig {
             strategies,
             loader,
             cache_dir,
+            max_concurrent_requests: default_max_concurrent_requests(),
+            unknown_sex_policy: UnknownSexPolicy::default(),
+            sex_vocabulary: HashMap::new(),
+            deterministic_created: None,
+            cohort_size_guard: None,
+            label_suggestions: false,
+            lenient: false,
+            strict_gene_symbols: false,
+            batch_gene_requests: false,
+            hpo_observed_threshold: 0.0,
+            negative_cache_ttl_hours: None,
+            hgnc_cache_ttl_hours: None,
+            genome_assembly: None,
+            disease_priority: vec![],
+            keep_duplicate_diseases: false,
+            strict_resource_versions: false,
+            preserve_raw_values: false,
+            plausible_measurement_ranges: HashMap::new(),
+            lint_rules: HashMap::new(),
+            fail_on: default_fail_on(),
+            offline: false,
         }
     }
+
+    pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests;
+        self
+    }
+
+    pub fn with_unknown_sex_policy(mut self, unknown_sex_policy: UnknownSexPolicy) -> Self {
+        self.unknown_sex_policy = unknown_sex_policy;
+        self
+    }
+
+    pub fn with_sex_vocabulary(mut self, sex_vocabulary: HashMap<String, String>) -> Self {
+        self.sex_vocabulary = sex_vocabulary;
+        self
+    }
+
+    pub fn with_cohort_size_guard(mut self, cohort_size_guard: CohortSizeGuard) -> Self {
+        self.cohort_size_guard = Some(cohort_size_guard);
+        self
+    }
+
+    pub fn with_label_suggestions(mut self, label_suggestions: bool) -> Self {
+        self.label_suggestions = label_suggestions;
+        self
+    }
+
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    pub fn with_strict_gene_symbols(mut self, strict_gene_symbols: bool) -> Self {
+        self.strict_gene_symbols = strict_gene_symbols;
+        self
+    }
+
+    pub fn with_batch_gene_requests(mut self, batch_gene_requests: bool) -> Self {
+        self.batch_gene_requests = batch_gene_requests;
+        self
+    }
+
+    pub fn with_hpo_observed_threshold(mut self, hpo_observed_threshold: f64) -> Self {
+        self.hpo_observed_threshold = hpo_observed_threshold;
+        self
+    }
+
+    pub fn with_negative_cache_ttl_hours(mut self, ttl_hours: u64) -> Self {
+        self.negative_cache_ttl_hours = Some(ttl_hours);
+        self
+    }
+
+    pub fn with_hgnc_cache_ttl_hours(mut self, ttl_hours: u64) -> Self {
+        self.hgnc_cache_ttl_hours = Some(ttl_hours);
+        self
+    }
+
+    pub fn with_genome_assembly(mut self, genome_assembly: GenomeAssembly) -> Self {
+        self.genome_assembly = Some(genome_assembly);
+        self
+    }
+
+    pub fn with_disease_priority(mut self, disease_priority: Vec<String>) -> Self {
+        self.disease_priority = disease_priority;
+        self
+    }
+
+    pub fn with_keep_duplicate_diseases(mut self, keep_duplicate_diseases: bool) -> Self {
+        self.keep_duplicate_diseases = keep_duplicate_diseases;
+        self
+    }
+
+    pub fn with_strict_resource_versions(mut self, strict_resource_versions: bool) -> Self {
+        self.strict_resource_versions = strict_resource_versions;
+        self
+    }
+
+    pub fn with_preserve_raw_values(mut self, preserve_raw_values: bool) -> Self {
+        self.preserve_raw_values = preserve_raw_values;
+        self
+    }
+
+    pub fn with_plausible_measurement_ranges(
+        mut self,
+        plausible_measurement_ranges: HashMap<String, PlausibleRange>,
+    ) -> Self {
+        self.plausible_measurement_ranges = plausible_measurement_ranges;
+        self
+    }
+
+    pub fn with_lint_rules(mut self, lint_rules: HashMap<String, LintLevel>) -> Self {
+        self.lint_rules = lint_rules;
+        self
+    }
+
+    pub fn with_fail_on(mut self, fail_on: Severity) -> Self {
+        self.fail_on = fail_on;
+        self
+    }
+
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    pub fn with_deterministic_created(mut self, created: impl Into<String>) -> Self {
+        self.deterministic_created = Some(created.into());
+        self
+    }
+}
+
+fn default_max_concurrent_requests() -> usize {
+    DEFAULT_MAX_CONCURRENT_REQUESTS
+}
+
+fn default_fail_on() -> Severity {
+    Severity::Error
 }
 
 fn config_cache_dir() -> Option<PathBuf> {
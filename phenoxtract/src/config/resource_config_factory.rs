@@ -1,28 +1,52 @@
 use crate::config::resource_config::{ResourceConfig, Secrets};
 use crate::ontology::CachedOntologyFactory;
 use crate::ontology::bioportal_client::BioPortalClient;
+use crate::ontology::concurrency_limiter::ConcurrencyLimiter;
 use crate::ontology::error::FactoryError;
 use crate::ontology::loinc_client::LoincClient;
 use crate::ontology::resource_references::{KnownResourcePrefixes, ResourceRef};
-use crate::ontology::traits::BiDict;
+use crate::ontology::traits::{BiDict, OntologyLike};
 use ontology_registry::OntologyRegistration;
 use ontology_registry::blocking::bio_registry_metadata_provider::BioRegistryMetadataProvider;
 use ontology_registry::blocking::file_system_ontology_registry::FileSystemOntologyRegistry;
 use ontology_registry::blocking::obolib_ontology_provider::OboLibraryProvider;
+use std::sync::Arc;
 use strum::VariantNames;
 
 pub(crate) struct ResourceConfigFactory<OR: OntologyRegistration> {
     ontology_factory: CachedOntologyFactory<OR>,
+    request_limiter: Arc<ConcurrencyLimiter>,
 }
 
 impl<OR> ResourceConfigFactory<OR>
 where
     OR: OntologyRegistration,
 {
-    const NON_CONFIGURABLE: [KnownResourcePrefixes; 1] = [KnownResourcePrefixes::HGNC];
+    const NON_CONFIGURABLE: [KnownResourcePrefixes; 2] = [
+        KnownResourcePrefixes::HGNC,
+        KnownResourcePrefixes::NCBITaxon,
+    ];
 
     pub fn new(ontology_factory: CachedOntologyFactory<OR>) -> Self {
-        Self { ontology_factory }
+        Self {
+            ontology_factory,
+            request_limiter: Arc::new(ConcurrencyLimiter::default()),
+        }
+    }
+
+    /// Network backed resources (LOINC, BioPortal) will share this limiter for their requests.
+    pub fn with_request_limiter(mut self, request_limiter: Arc<ConcurrencyLimiter>) -> Self {
+        self.request_limiter = request_limiter;
+        self
+    }
+
+    /// Builds or retrieves the ontology behind a resource, e.g. to query its hierarchy.
+    pub fn build_ontology(
+        &mut self,
+        config: &ResourceConfig,
+    ) -> Result<Arc<dyn OntologyLike>, FactoryError> {
+        self.ontology_factory
+            .build_ontology(&ResourceRef::new(config.id.clone(), config.version.clone()))
     }
 
     pub fn build(&mut self, config: &ResourceConfig) -> Result<Box<dyn BiDict>, FactoryError> {
@@ -30,12 +54,12 @@ where
             .id
             .eq_ignore_ascii_case(KnownResourcePrefixes::LOINC.as_ref())
         {
-            Self::build_loinc_client(config)
+            self.build_loinc_client(config)
         } else if config
             .id
             .eq_ignore_ascii_case(KnownResourcePrefixes::OMIM.as_ref())
         {
-            Self::build_bioportal_client(config)
+            self.build_bioportal_client(config)
         } else {
             match self
                 .ontology_factory
@@ -83,7 +107,10 @@ where
         }
     }
 
-    fn build_loinc_client(config: &ResourceConfig) -> Result<Box<dyn BiDict>, FactoryError> {
+    fn build_loinc_client(
+        &self,
+        config: &ResourceConfig,
+    ) -> Result<Box<dyn BiDict>, FactoryError> {
         match &config.secrets {
             None => Err(FactoryError::CantBuild {
                 reason: "No LOINC credentials provided.".to_string(),
@@ -95,11 +122,10 @@ where
                         .as_ref()
                         .map(|version| ResourceRef::loinc().with_version(version));
 
-                    Ok(Box::new(LoincClient::new(
-                        user.clone(),
-                        password.clone(),
-                        loinc_ref,
-                    )))
+                    Ok(Box::new(
+                        LoincClient::new(user.clone(), password.clone(), loinc_ref)
+                            .with_request_limiter(self.request_limiter.clone()),
+                    ))
                 }
                 Secrets::Token { .. } => Err(FactoryError::CantBuild {
                     reason:
@@ -110,7 +136,10 @@ where
         }
     }
 
-    fn build_bioportal_client(config: &ResourceConfig) -> Result<Box<dyn BiDict>, FactoryError> {
+    fn build_bioportal_client(
+        &self,
+        config: &ResourceConfig,
+    ) -> Result<Box<dyn BiDict>, FactoryError> {
         let secrets = config
             .secrets
             .as_ref()
@@ -142,7 +171,7 @@ where
                 }
             })?;
 
-        Ok(Box::new(client))
+        Ok(Box::new(client.with_request_limiter(self.request_limiter.clone())))
     }
 
     pub fn into_ontology_factory(self) -> CachedOntologyFactory<OR> {
@@ -168,9 +197,7 @@ mod tests {
     use crate::test_suite::mocks::MockOntologyRegistry;
 
     fn get_factory() -> ResourceConfigFactory<MockOntologyRegistry> {
-        ResourceConfigFactory {
-            ontology_factory: CachedOntologyFactory::new(MockOntologyRegistry::default()),
-        }
+        ResourceConfigFactory::new(CachedOntologyFactory::new(MockOntologyRegistry::default()))
     }
 
     #[test]
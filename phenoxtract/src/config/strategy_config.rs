@@ -1,11 +1,22 @@
-use crate::config::context::ContextKind;
+use crate::config::context::{Context, ContextKind, TimeElementType};
 use crate::ontology::resource_references::ResourceRef;
+use crate::transform::strategies::column_concatenation::ColumnConcatenation;
+use crate::transform::strategies::deduplicate_rows::DeduplicateRows;
 use crate::transform::strategies::mapping::DefaultMapping;
+use crate::transform::strategies::normalize_subject_id::SubjectIdFormat;
+use crate::transform::strategies::numeric_range_bucketing::NumericRangeBucketing;
+use crate::transform::strategies::observation_status_encoding::ObservationStatusEncoding;
+use crate::transform::strategies::regex_extract::RegexExtract;
+use crate::transform::strategies::split_delimited_column::SplitDelimitedColumn;
+use crate::transform::strategies::unit_normalisation::UnitConversion;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use strum_macros::IntoStaticStr;
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, IntoStaticStr)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
+#[strum(serialize_all = "snake_case")]
 pub enum StrategyConfig {
     AliasMap,
     DefaultMapping(DefaultMapping),
@@ -13,10 +24,127 @@ pub enum StrategyConfig {
     OntologyNormaliser {
         ontology: ResourceRef,
         data_context_kind: ContextKind,
+        /// Replaces the ids of obsolete terms with the id of the term they were replaced by.
+        #[serde(default)]
+        replace_obsolete: bool,
     },
     AgeToIso8601,
     DateToAge {
         strict: bool,
     },
     HpoDiseaseSplitter,
+    /// Maps onset categories (e.g. "congenital") to HPO onset terms or ISO8601 durations.
+    OnsetCategory {
+        categories: HashMap<String, String>,
+    },
+    /// Brings subject ids into one format, so that e.g. "P1" and "P001" refer to the same patient.
+    NormalizeSubjectId(SubjectIdFormat),
+    /// Maps the numeric values of a column to category labels, e.g. ages to "pediatric" or "adult".
+    NumericRangeBucketing(NumericRangeBucketing),
+    /// Joins several columns into a new column, e.g. a transcript and a variant into an HGVS string.
+    ColumnConcatenation(ColumnConcatenation),
+    /// Replaces the cells of a column with the substring captured by a regex, e.g. an HPO id in free text.
+    RegexExtract(RegexExtract),
+    /// Explodes a column with delimited lists, e.g. "HP:0001250; HP:0001166", into one row per element.
+    SplitDelimitedColumn(SplitDelimitedColumn),
+    /// Drops rows, which repeat an earlier row of the same table.
+    DeduplicateRows(DeduplicateRows),
+    /// Trims cells and collapses runs of whitespace in the columns with the given data context kinds.
+    WhitespaceNormalisation {
+        data_context_kinds: Vec<ContextKind>,
+        /// Additionally brings the cells into Unicode normalization form C.
+        #[serde(default)]
+        unicode_nfc: bool,
+    },
+    /// Rescales measurements into a common unit, e.g. mg/dL to mmol/L. The conversions are keyed by the source unit CURIE.
+    UnitNormalisation {
+        conversions: HashMap<String, UnitConversion>,
+    },
+    /// Converts string observation statuses (e.g. "Y"/"N" or "present"/"absent") into booleans.
+    ObservationStatusEncoding(ObservationStatusEncoding),
+}
+
+impl StrategyConfig {
+    /// The name of the strategy, as it is written in the config.
+    pub fn name(&self) -> &'static str {
+        self.into()
+    }
+
+    /// The data contexts, which have to be present for the strategy to have an effect.
+    ///
+    /// Strategies, which act on the columns they are configured with, or on whichever columns of a context kind are
+    /// present, require no context.
+    pub fn required_contexts(&self) -> Vec<Context> {
+        match self {
+            StrategyConfig::DateToAge { .. } => vec![Context::DateOfBirth],
+            StrategyConfig::MultiHpoColExpansion => vec![Context::MultiHpoId],
+            StrategyConfig::HpoDiseaseSplitter => vec![Context::HpoOrDisease],
+            StrategyConfig::DefaultMapping(DefaultMapping::SexMapping) => vec![Context::SubjectSex],
+            StrategyConfig::NormalizeSubjectId(_) => vec![Context::SubjectId],
+            StrategyConfig::AliasMap
+            | StrategyConfig::OntologyNormaliser { .. }
+            | StrategyConfig::AgeToIso8601
+            | StrategyConfig::OnsetCategory { .. }
+            | StrategyConfig::NumericRangeBucketing(_)
+            | StrategyConfig::ColumnConcatenation(_)
+            | StrategyConfig::RegexExtract(_)
+            | StrategyConfig::SplitDelimitedColumn(_)
+            | StrategyConfig::DeduplicateRows(_)
+            | StrategyConfig::WhitespaceNormalisation { .. }
+            | StrategyConfig::UnitNormalisation { .. }
+            | StrategyConfig::ObservationStatusEncoding(_) => vec![],
+        }
+    }
+
+    /// The data contexts, which the strategy adds to the tables.
+    pub fn produced_contexts(&self) -> Vec<Context> {
+        match self {
+            StrategyConfig::DateToAge { .. } => {
+                Context::time_element_context_variants(TimeElementType::Age)
+            }
+            StrategyConfig::MultiHpoColExpansion => vec![Context::ObservationStatus],
+            StrategyConfig::HpoDiseaseSplitter => vec![Context::Hpo, Context::Disease],
+            StrategyConfig::ColumnConcatenation(config) => {
+                vec![config.output_data_context.clone()]
+            }
+            StrategyConfig::AliasMap
+            | StrategyConfig::DefaultMapping(_)
+            | StrategyConfig::OntologyNormaliser { .. }
+            | StrategyConfig::AgeToIso8601
+            | StrategyConfig::OnsetCategory { .. }
+            | StrategyConfig::NormalizeSubjectId(_)
+            | StrategyConfig::NumericRangeBucketing(_)
+            | StrategyConfig::RegexExtract(_)
+            | StrategyConfig::SplitDelimitedColumn(_)
+            | StrategyConfig::DeduplicateRows(_)
+            | StrategyConfig::WhitespaceNormalisation { .. }
+            | StrategyConfig::UnitNormalisation { .. }
+            | StrategyConfig::ObservationStatusEncoding(_) => vec![],
+        }
+    }
+
+    /// The data contexts, which the strategy removes from the tables.
+    pub fn consumed_contexts(&self) -> Vec<Context> {
+        match self {
+            StrategyConfig::DateToAge { .. } => {
+                Context::time_element_context_variants(TimeElementType::Date)
+            }
+            StrategyConfig::MultiHpoColExpansion => vec![Context::MultiHpoId],
+            StrategyConfig::HpoDiseaseSplitter => vec![Context::HpoOrDisease],
+            StrategyConfig::AliasMap
+            | StrategyConfig::DefaultMapping(_)
+            | StrategyConfig::OntologyNormaliser { .. }
+            | StrategyConfig::AgeToIso8601
+            | StrategyConfig::OnsetCategory { .. }
+            | StrategyConfig::NormalizeSubjectId(_)
+            | StrategyConfig::NumericRangeBucketing(_)
+            | StrategyConfig::ColumnConcatenation(_)
+            | StrategyConfig::RegexExtract(_)
+            | StrategyConfig::SplitDelimitedColumn(_)
+            | StrategyConfig::DeduplicateRows(_)
+            | StrategyConfig::WhitespaceNormalisation { .. }
+            | StrategyConfig::UnitNormalisation { .. }
+            | StrategyConfig::ObservationStatusEncoding(_) => vec![],
+        }
+    }
 }
@@ -28,6 +28,10 @@ pub struct TableContext {
     name: String,
     #[validate(custom(function = "validate_unique_identifiers"))]
     series_contexts: Vec<SeriesContext>,
+    /// If set, the columns of the subject id series context are concatenated with this separator
+    /// to form the subject id, e.g. a site and a local id column.
+    #[serde(default)]
+    subject_id_separator: Option<String>,
 }
 
 impl TableContext {
@@ -35,6 +39,7 @@ impl TableContext {
         TableContext {
             name: name.into(),
             series_contexts: context,
+            subject_id_separator: None,
         }
     }
 
@@ -57,6 +62,15 @@ impl TableContext {
         self
     }
 
+    pub fn subject_id_separator(&self) -> Option<&str> {
+        self.subject_id_separator.as_deref()
+    }
+
+    pub fn with_subject_id_separator(mut self, separator: impl IntoOptionalString) -> Self {
+        self.subject_id_separator = separator.into_opt_string();
+        self
+    }
+
     pub fn filter_series_context(&'_ self) -> SeriesContextFilter<'_> {
         SeriesContextFilter::new(self.series_contexts.as_ref())
     }
@@ -79,6 +93,7 @@ pub enum CellValue {
 ///
 /// If it has Single type, then it will determine a column with exactly that header.
 /// If it has Regex type, then the columns will be determined by the regular expression.
+/// The regex may match any part of a column name, unless it is anchored (see [`Identifier::anchored_regex_from_str`]).
 /// If it has Multi type, then the strings within the vector will be the headers of the relevant columns.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(into = "IdentifierConfig", try_from = "IdentifierConfig")]
@@ -153,6 +168,15 @@ impl Identifier {
             })?;
         Ok(Identifier::Regex(regex))
     }
+
+    /// Creates a regex identifier, which only matches column names as a whole.
+    ///
+    /// For example, `age` matches the column `age`, but not `average_age`.
+    pub fn anchored_regex_from_str(
+        value: impl Into<String>,
+    ) -> Result<Identifier, ConstructionError> {
+        Identifier::regex_from_str(format!("^(?:{})$", value.into()))
+    }
 }
 
 impl From<&str> for Identifier {
@@ -220,6 +244,32 @@ impl Identifier {
                 .collect(),
         }
     }
+
+    /// Finds the column name with the smallest edit distance to the identifier, e.g. `subject_id` for `subject_idd`.
+    ///
+    /// Column names, which differ in more than half of their characters, are not considered a match.
+    pub fn closest_match<'a>(&self, col_names: &[&'a str]) -> Option<&'a str> {
+        let names: Vec<&str> = match self {
+            Identifier::Single(id) => vec![id.as_str()],
+            Identifier::Regex(regex) => vec![regex.as_str()],
+            Identifier::Multi(ids) => ids.iter().map(String::as_str).collect(),
+        };
+
+        names
+            .iter()
+            .flat_map(|name| {
+                col_names.iter().map(move |col| {
+                    (
+                        strsim::levenshtein(name, col),
+                        name.len().max(col.len()),
+                        *col,
+                    )
+                })
+            })
+            .filter(|(distance, len, _)| distance * 2 <= *len)
+            .min_by_key(|(distance, _, _)| *distance)
+            .map(|(_, _, col)| col)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -299,6 +349,15 @@ pub struct SeriesContext {
 
     /// An ID that associates this series with a building block of a phenopacket. If the same ID is shared with other series, the pipeline will try to construct a building block from them.
     building_block_id: Option<String>,
+
+    /// By default, string cells that are empty or only contain whitespace are treated as null.
+    /// If set, these cells are kept as they are.
+    #[serde(default)]
+    keep_blank_cells: bool,
+
+    /// Cell values, which mean that the value is missing (e.g. "NA" or "unknown"), and are treated as null.
+    #[serde(default)]
+    null_values: Vec<String>,
 }
 
 impl SeriesContext {
@@ -309,6 +368,8 @@ impl SeriesContext {
         fill_missing: Option<CellValue>,
         alias_map: Option<AliasMap>,
         building_block_id: Option<String>,
+        keep_blank_cells: bool,
+        null_values: Vec<String>,
     ) -> Self {
         SeriesContext {
             identifier,
@@ -317,6 +378,8 @@ impl SeriesContext {
             fill_missing,
             alias_map,
             building_block_id,
+            keep_blank_cells,
+            null_values,
         }
     }
 
@@ -350,6 +413,14 @@ impl SeriesContext {
     pub fn get_fill_missing(&self) -> Option<&CellValue> {
         self.fill_missing.as_ref()
     }
+
+    pub fn keeps_blank_cells(&self) -> bool {
+        self.keep_blank_cells
+    }
+
+    pub fn get_null_values(&self) -> &[String] {
+        &self.null_values
+    }
 }
 impl SeriesContextBuilding<Identifier, AliasMap> for SeriesContext {
     fn from_identifier(identifier: impl Into<Identifier>) -> Self {
@@ -360,6 +431,8 @@ impl SeriesContextBuilding<Identifier, AliasMap> for SeriesContext {
             fill_missing: None,
             alias_map: None,
             building_block_id: None,
+            keep_blank_cells: false,
+            null_values: vec![],
         }
     }
 
@@ -397,12 +470,47 @@ impl SeriesContextBuilding<Identifier, AliasMap> for SeriesContext {
             self
         }
     }
+
+    fn with_keep_blank_cells(mut self, keep_blank_cells: bool) -> Self {
+        self.keep_blank_cells = keep_blank_cells;
+        self
+    }
+
+    fn with_null_values(mut self, null_values: Vec<String>) -> Self {
+        self.null_values = null_values;
+        self
+    }
 }
 #[cfg(test)]
 mod tests {
     use super::*;
     use rstest::rstest;
 
+    #[rstest]
+    fn test_anchored_regex_excludes_partial_matches() {
+        let col_names = ["age", "average_age", "age_at_onset", "sex"];
+
+        let unanchored = Identifier::regex_from_str("age|sex").unwrap();
+        let anchored = Identifier::anchored_regex_from_str("age|sex").unwrap();
+
+        assert_eq!(
+            unanchored.identify(&col_names),
+            vec!["age", "average_age", "age_at_onset", "sex"]
+        );
+        assert_eq!(anchored.identify(&col_names), vec!["age", "sex"]);
+    }
+
+    #[rstest]
+    #[case(Identifier::from("subject_idd"), Some("subject_id"))]
+    #[case(Identifier::from("Age"), Some("age"))]
+    #[case(Identifier::from(vec!["sexx", "unrelated_name"]), Some("sex"))]
+    #[case(Identifier::from("onset"), None)]
+    fn test_closest_match(#[case] id: Identifier, #[case] expected: Option<&str>) {
+        let col_names = ["subject_id", "age", "sex"];
+
+        assert_eq!(id.closest_match(&col_names), expected);
+    }
+
     #[rstest]
     fn test_output_datatype_as_polars() {
         assert_eq!(OutputDataType::Boolean.as_polars(), DataType::Boolean);
@@ -13,6 +13,10 @@ pub trait SeriesContextBuilding<ID, AliasMapType>: Sized {
     fn with_alias_map(self, alias_map: AliasMapType) -> Self;
 
     fn with_building_block_id(self, building_block_id: impl IntoOptionalString) -> Self;
+
+    fn with_keep_blank_cells(self, keep_blank_cells: bool) -> Self;
+
+    fn with_null_values(self, null_values: Vec<String>) -> Self;
 }
 
 pub trait IntoOptionalString {
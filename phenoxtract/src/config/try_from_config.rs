@@ -1,31 +1,50 @@
 use crate::Pipeline;
 use crate::config::datasource_config::{
     AliasMapConfig, CsvConfig, ExcelSheetConfig, ExcelWorkbookConfig, IdentifierConfig,
-    MappingsConfig, MappingsCsvConfig, SeriesContextConfig,
+    JsonSourceConfig, MappingsConfig, MappingsCsvConfig, SeriesContextConfig,
 };
+use crate::config::loader_config::LoaderConfig;
 use crate::config::resource_config_factory::ResourceConfigFactory;
 use crate::config::table_context::{AliasMap, Identifier, SeriesContext};
 use crate::config::{
-    ConfigLoader, DataSourceConfig, PhenoXtractConfig, PipelineConfig, TableContext,
+    ConfigLoader, DataSourceConfig, MetaData, PhenoXtractConfig, PipelineConfig, StrategyConfig,
+    TableContext,
 };
 use crate::error::ConstructionError;
 use crate::extract::extraction_config::ExtractionConfig;
-use crate::extract::{CsvDataSource, DataSource, ExcelDataSource};
+use crate::extract::{
+    CsvDataSource, DataSource, ExcelDataSource, JsonDataSource, PhenopacketDataSource,
+};
 use crate::load::loader_factory::LoaderFactory;
 use crate::ontology::CachedOntologyFactory;
+use crate::ontology::concurrency_limiter::{
+    ConcurrencyLimitedHgncClient, ConcurrencyLimitedHgvsClient, ConcurrencyLimiter,
+};
+use crate::ontology::hgnc_alias_client::{GeneSymbolAliases, HgncAliasClient};
+use crate::ontology::hgnc_batch_client::{GeneBatchLookup, HgncBatchClient};
 use crate::phenoxtract::Phenoxtract;
-use crate::transform::collecting::cdf_collector_broker::CdfCollectorBroker;
+use crate::transform::cached_resource_resolver::CachedResourceResolver;
+use crate::transform::clock::FixedClock;
+use crate::transform::collecting::cdf_collector_broker::{CdfCollectorBroker, CohortSizeGuard};
+use crate::transform::hgnc_gene_cache::HgncGeneCache;
+use crate::transform::hgvs_variant_cache::HgvsVariantCache;
+use crate::transform::negative_lookup_cache::NegativeLookupCache;
+use crate::transform::pathogenic_gene_variant_info::GenomeAssembly;
 use crate::transform::strategies::strategy_factory::StrategyFactory;
 use crate::transform::strategies::traits::Strategy;
-use crate::transform::transform_context::TransformContext;
+use crate::transform::transform_context::{SexVocabulary, TransformContext, UnknownSexPolicy};
 use crate::transform::{PhenopacketBuilder, TransformerModule};
+use crate::validation::lint_report::Severity;
+use crate::validation::linter::{LintLevel, Linter, PlausibleRange};
+use chrono::{DateTime, Duration, Utc};
 use ontology_registry::blocking::bio_registry_metadata_provider::BioRegistryMetadataProvider;
 use ontology_registry::blocking::file_system_ontology_registry::FileSystemOntologyRegistry;
 use ontology_registry::blocking::obolib_ontology_provider::OboLibraryProvider;
-use pivotal::hgnc::CachedHGNCClient;
-use pivotal::hgvs::CachedHGVSClient;
+use phenopackets::schema::v2::core::Sex;
+use pivotal::hgnc::{HGNCClient, HGNCData};
+use pivotal::hgvs::{HGVSClient, HGVSData};
 use polars::prelude::{CsvReadOptions, SerReader};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -35,13 +54,30 @@ impl TryFrom<PhenoXtractConfig> for Phenoxtract {
     type Error = ConstructionError;
 
     fn try_from(config: PhenoXtractConfig) -> Result<Self, Self::Error> {
-        let pipeline = Pipeline::try_from(config.pipeline_config)?;
-        let data_sources = config
-            .data_sources
-            .into_iter()
-            .map(DataSource::try_from)
-            .collect::<Result<Vec<DataSource>, ConstructionError>>()?;
-        Ok(Phenoxtract::new(pipeline, data_sources))
+        ensure_distinct_cohorts(&config)?;
+        let mut shared = SharedPipelineResources::try_from(&config.pipeline_config)?;
+
+        let pipeline = shared.build_pipeline(
+            &config.pipeline_config.meta_data,
+            &config.pipeline_config.strategies,
+            config.pipeline_config.loader,
+        )?;
+        let data_sources = try_data_sources_from_configs(config.data_sources)?;
+        let mut phenoxtract = Phenoxtract::new(pipeline, data_sources);
+
+        for cohort in config.cohorts {
+            let pipeline = shared.build_pipeline(
+                &cohort.meta_data,
+                &config.pipeline_config.strategies,
+                cohort.loader,
+            )?;
+            phenoxtract.add_cohort(
+                pipeline,
+                try_data_sources_from_configs(cohort.data_sources)?,
+            );
+        }
+
+        Ok(phenoxtract)
     }
 }
 
@@ -54,14 +90,92 @@ impl TryFrom<PathBuf> for Phenoxtract {
     }
 }
 
+/// Ensures that no two cohorts share a name or an output directory, because their phenopackets would
+/// otherwise overwrite each other.
+fn ensure_distinct_cohorts(config: &PhenoXtractConfig) -> Result<(), ConstructionError> {
+    let mut cohort_names = HashSet::new();
+    let mut output_dirs = HashSet::new();
+
+    let cohorts = std::iter::once((
+        &config.pipeline_config.meta_data,
+        &config.pipeline_config.loader,
+    ))
+    .chain(
+        config
+            .cohorts
+            .iter()
+            .map(|cohort| (&cohort.meta_data, &cohort.loader)),
+    );
+    for (meta_data, loader) in cohorts {
+        if !cohort_names.insert(meta_data.cohort_name.as_str()) {
+            return Err(ConstructionError::DuplicateCohortName {
+                name: meta_data.cohort_name.clone(),
+            });
+        }
+        if !output_dirs.insert(loader.output_dir()) {
+            return Err(ConstructionError::DuplicateOutputDir {
+                path: loader.output_dir().to_path_buf(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn try_data_sources_from_configs(
+    configs: Vec<DataSourceConfig>,
+) -> Result<Vec<DataSource>, ConstructionError> {
+    configs
+        .into_iter()
+        .map(DataSource::try_from)
+        .collect::<Result<Vec<DataSource>, ConstructionError>>()
+}
+
 // --- PIPELINE FROM CONFIG ---
 
-impl TryFrom<PipelineConfig> for Pipeline {
+type DefaultOntologyRegistry =
+    FileSystemOntologyRegistry<BioRegistryMetadataProvider, OboLibraryProvider>;
+
+/// The ontology cache and network clients, which are shared between all pipelines built from one config.
+struct SharedPipelineResources {
+    ontology_factory: Option<CachedOntologyFactory<DefaultOntologyRegistry>>,
+    hgnc_client: Arc<dyn HGNCData + Send + Sync>,
+    hgvs_client: Arc<dyn HGVSData + Send + Sync>,
+    request_limiter: Arc<ConcurrencyLimiter>,
+    unknown_sex_policy: UnknownSexPolicy,
+    sex_vocabulary: SexVocabulary,
+    deterministic_created: Option<DateTime<Utc>>,
+    cohort_size_guard: Option<CohortSizeGuard>,
+    hpo_observed_threshold: f64,
+    label_suggestions: bool,
+    lenient: bool,
+    gene_symbol_aliases: Option<Arc<dyn GeneSymbolAliases>>,
+    gene_batch_lookup: Option<Arc<dyn GeneBatchLookup>>,
+    negative_lookup_cache: Option<Arc<NegativeLookupCache>>,
+    hgnc_gene_cache: HgncGeneCache,
+    hgvs_variant_cache: HgvsVariantCache,
+    resource_resolver: Arc<CachedResourceResolver>,
+    genome_assembly: Option<GenomeAssembly>,
+    disease_priority: Vec<String>,
+    keep_duplicate_diseases: bool,
+    strict_resource_versions: bool,
+    preserve_raw_values: bool,
+    plausible_measurement_ranges: HashMap<String, PlausibleRange>,
+    lint_rules: HashMap<String, LintLevel>,
+    fail_on: Severity,
+}
+
+impl TryFrom<&PipelineConfig> for SharedPipelineResources {
     type Error = ConstructionError;
 
-    fn try_from(config: PipelineConfig) -> Result<Self, Self::Error> {
+    fn try_from(config: &PipelineConfig) -> Result<Self, Self::Error> {
+        if config.max_concurrent_requests == 0 {
+            return Err(ConstructionError::InvalidMaxConcurrentRequests);
+        }
+
         let cache_dir = config
             .cache_dir
+            .as_ref()
             .expect("Pipeline config missing cache_dir.");
         let ontology_registry_dir = cache_dir.join("ontology_registry");
 
@@ -75,18 +189,154 @@ impl TryFrom<PipelineConfig> for Pipeline {
             OboLibraryProvider::default(),
         );
 
-        let mut resource_factory =
-            ResourceConfigFactory::new(CachedOntologyFactory::new(ontology_registry));
+        let deterministic_created = config
+            .deterministic_created
+            .as_ref()
+            .map(|created| {
+                DateTime::parse_from_rfc3339(created)
+                    .map(|created| created.with_timezone(&Utc))
+                    .map_err(|err| ConstructionError::InvalidTimestamp {
+                        value: created.clone(),
+                        reason: err.to_string(),
+                    })
+            })
+            .transpose()?;
 
-        let mut ctx_builder = TransformContext::builder(
-            config.meta_data.clone().into(),
-            Arc::new(CachedHGNCClient::new_with_defaults()?),
-            Arc::new(CachedHGVSClient::new_with_defaults()?),
+        let mut sex_vocabulary = SexVocabulary::default();
+        for (value, sex) in &config.sex_vocabulary {
+            let sex = Sex::from_str_name(sex).ok_or_else(|| ConstructionError::InvalidSexVocabulary {
+                value: value.clone(),
+                sex: sex.clone(),
+            })?;
+            sex_vocabulary = sex_vocabulary.with_term(value, sex);
+        }
+
+        if let Some(rule) = config
+            .lint_rules
+            .keys()
+            .find(|rule| !Linter::RULES.contains(&rule.as_str()))
+        {
+            return Err(ConstructionError::UnknownLintRule { rule: rule.clone() });
+        }
+
+        // The clients are uncached, so that only their requests acquire a permit of the limiter.
+        // The genes and variants, which they resolve, are cached on disk in front of them.
+        let request_limiter = Arc::new(ConcurrencyLimiter::new(config.max_concurrent_requests));
+        let hgnc_gene_cache = HgncGeneCache::with_file(
+            config
+                .hgnc_cache_ttl_hours
+                .map(|ttl_hours| Duration::hours(ttl_hours as i64)),
+            cache_dir.join("hgnc_genes.json"),
         );
 
-        if let Some(hpo_resource) = &config.meta_data.hpo_resource {
+        Ok(Self {
+            ontology_factory: Some(CachedOntologyFactory::new_with_offline(
+                ontology_registry,
+                config.offline,
+            )),
+            hgnc_client: Arc::new(ConcurrencyLimitedHgncClient::new(
+                HGNCClient::default(),
+                request_limiter.clone(),
+            )),
+            hgvs_client: Arc::new(ConcurrencyLimitedHgvsClient::new(
+                HGVSClient::default(),
+                request_limiter.clone(),
+            )),
+            request_limiter,
+            unknown_sex_policy: config.unknown_sex_policy,
+            sex_vocabulary,
+            deterministic_created,
+            cohort_size_guard: config.cohort_size_guard.clone(),
+            hpo_observed_threshold: config.hpo_observed_threshold,
+            label_suggestions: config.label_suggestions,
+            lenient: config.lenient,
+            gene_symbol_aliases: (!config.strict_gene_symbols)
+                .then(|| Arc::new(HgncAliasClient::default()) as Arc<dyn GeneSymbolAliases>),
+            gene_batch_lookup: config
+                .batch_gene_requests
+                .then(|| Arc::new(HgncBatchClient::default()) as Arc<dyn GeneBatchLookup>),
+            genome_assembly: config.genome_assembly,
+            disease_priority: config.disease_priority.clone(),
+            keep_duplicate_diseases: config.keep_duplicate_diseases,
+            strict_resource_versions: config.strict_resource_versions,
+            preserve_raw_values: config.preserve_raw_values,
+            plausible_measurement_ranges: config.plausible_measurement_ranges.clone(),
+            lint_rules: config.lint_rules.clone(),
+            fail_on: config.fail_on,
+            hgnc_gene_cache,
+            hgvs_variant_cache: HgvsVariantCache::with_file(cache_dir.join("hgvs_variants.json")),
+            resource_resolver: Arc::new(CachedResourceResolver::with_snapshot(
+                cache_dir.join("bioregistry_resources.json"),
+                config.offline,
+            )),
+            negative_lookup_cache: config.negative_cache_ttl_hours.map(|ttl_hours| {
+                Arc::new(NegativeLookupCache::with_file(
+                    Duration::hours(ttl_hours as i64),
+                    cache_dir.join("negative_lookups.json"),
+                ))
+            }),
+        })
+    }
+}
+
+impl SharedPipelineResources {
+    fn build_pipeline(
+        &mut self,
+        meta_data: &MetaData,
+        strategy_configs: &[StrategyConfig],
+        loader: LoaderConfig,
+    ) -> Result<Pipeline, ConstructionError> {
+        let ontology_factory = self
+            .ontology_factory
+            .take()
+            .expect("Ontology factory is returned after every build.");
+
+        let mut resource_factory = ResourceConfigFactory::new(ontology_factory)
+            .with_request_limiter(self.request_limiter.clone());
+
+        let mut ctx_builder = TransformContext::builder(
+            meta_data.clone().into(),
+            self.hgnc_client.clone(),
+            self.hgvs_client.clone(),
+        )
+        .request_limiter(self.request_limiter.clone())
+        .unknown_sex_policy(self.unknown_sex_policy)
+        .sex_vocabulary(self.sex_vocabulary.clone())
+        .label_suggestions(self.label_suggestions)
+        .lenient(self.lenient)
+        .disease_priority(self.disease_priority.clone())
+        .keep_duplicate_diseases(self.keep_duplicate_diseases)
+        .hpo_observed_threshold(self.hpo_observed_threshold)
+        .hgnc_gene_cache(self.hgnc_gene_cache.clone())
+        .hgvs_variant_cache(self.hgvs_variant_cache.clone())
+        .resource_resolver(self.resource_resolver.clone());
+
+        if let Some(aliases) = &self.gene_symbol_aliases {
+            ctx_builder = ctx_builder.gene_symbol_aliases(aliases.clone());
+        }
+
+        if let Some(gene_batch_lookup) = &self.gene_batch_lookup {
+            ctx_builder = ctx_builder.gene_batch_lookup(gene_batch_lookup.clone());
+        }
+
+        if let Some(genome_assembly) = self.genome_assembly {
+            ctx_builder = ctx_builder.genome_assembly(genome_assembly);
+        }
+
+        if let Some(cache) = &self.negative_lookup_cache {
+            ctx_builder = ctx_builder.negative_lookup_cache(cache.clone());
+        }
+
+
+        if let Some(created) = self.deterministic_created {
+            ctx_builder = ctx_builder.clock(Arc::new(FixedClock::new(created)));
+        }
+
+        let mut hpo = None;
+        if let Some(hpo_resource) = &meta_data.hpo_resource {
             let hpo_bidict = resource_factory.build(hpo_resource)?;
             ctx_builder.add_hpo_bidict(hpo_bidict);
+            hpo = Some(resource_factory.build_ontology(hpo_resource)?);
         };
 
         // Adds and loads BiDicts to the TransformContextBuilder
@@ -98,46 +348,74 @@ impl TryFrom<PipelineConfig> for Pipeline {
             };
         }
 
-        load_and_add!(&config.meta_data.disease_resources, add_disease_bidict);
-        load_and_add!(&config.meta_data.assay_resources, add_assay_bidict);
-        load_and_add!(&config.meta_data.unit_resources, add_unit_bidict);
+        load_and_add!(&meta_data.disease_resources, add_disease_bidict);
+        load_and_add!(&meta_data.assay_resources, add_assay_bidict);
+        load_and_add!(&meta_data.unit_resources, add_unit_bidict);
         load_and_add!(
-            &config.meta_data.qualitative_measurement_resources,
+            &meta_data.qualitative_measurement_resources,
             add_qualitative_measurement_bidict
         );
-        load_and_add!(&config.meta_data.procedure_resources, add_procedure_bidict);
+        load_and_add!(&meta_data.procedure_resources, add_procedure_bidict);
 
-        load_and_add!(&config.meta_data.anatomy_resources, add_anatomy_bidict);
+        load_and_add!(&meta_data.anatomy_resources, add_anatomy_bidict);
 
-        load_and_add!(&config.meta_data.drug_resources, add_drug_bidict);
+        load_and_add!(&meta_data.drug_resources, add_drug_bidict);
 
         load_and_add!(
-            &config.meta_data.treatment_attributes_resources,
+            &meta_data.treatment_attributes_resources,
             add_treatment_attributes_bidict
         );
 
+        load_and_add!(&meta_data.evidence_resources, add_evidence_bidict);
+        load_and_add!(&meta_data.gender_resources, add_gender_bidict);
+        load_and_add!(
+            &meta_data.disease_attributes_resources,
+            add_disease_attributes_bidict
+        );
+
         let ctx = ctx_builder.build();
 
         let mut strategy_factory =
             StrategyFactory::new(resource_factory.into_ontology_factory(), ctx);
-        let strategies: Vec<Box<dyn Strategy>> = config
-            .strategies
-            .iter()
-            .map(|strat| strategy_factory.try_from_config(strat))
-            .collect::<Result<Vec<_>, _>>()?;
+        let strategies: Vec<Box<dyn Strategy>> =
+            strategy_factory.try_from_configs(strategy_configs)?;
 
-        let (_, ctx) = strategy_factory.into_components();
+        let (ontology_factory, ctx) = strategy_factory.into_components();
+        self.ontology_factory = Some(ontology_factory);
 
         let phenopacket_builder = PhenopacketBuilder::new(ctx);
 
-        let tf_module = TransformerModule::new(
-            strategies,
-            CdfCollectorBroker::with_default_collectors(phenopacket_builder),
-        );
+        let mut broker = CdfCollectorBroker::with_default_collectors(phenopacket_builder);
+        if let Some(cohort_size_guard) = &self.cohort_size_guard {
+            broker = broker.with_cohort_size_guard(cohort_size_guard.clone());
+        }
+
+        let tf_module = TransformerModule::new(strategies, broker)
+            .with_preserve_raw_values(self.preserve_raw_values);
 
-        let loader_module = LoaderFactory::try_from_config(config.loader)?;
+        let loader_module = LoaderFactory::try_from_config(loader)?;
 
-        Ok(Pipeline::new(tf_module, loader_module))
+        let mut pipeline = Pipeline::new(tf_module, loader_module);
+        pipeline.set_strict_resource_versions(self.strict_resource_versions);
+        pipeline.set_plausible_measurement_ranges(self.plausible_measurement_ranges.clone());
+        pipeline.set_lint_rules(self.lint_rules.clone());
+        pipeline.set_fail_on(self.fail_on);
+        if let Some(hpo) = hpo {
+            pipeline.set_hpo(hpo);
+        }
+        Ok(pipeline)
+    }
+}
+
+impl TryFrom<PipelineConfig> for Pipeline {
+    type Error = ConstructionError;
+
+    fn try_from(config: PipelineConfig) -> Result<Self, Self::Error> {
+        SharedPipelineResources::try_from(&config)?.build_pipeline(
+            &config.meta_data,
+            &config.strategies,
+            config.loader,
+        )
     }
 }
 
@@ -154,6 +432,12 @@ impl TryFrom<DataSourceConfig> for DataSource {
             DataSourceConfig::Csv(csv_config) => {
                 Ok(DataSource::Csv(CsvDataSource::try_from(csv_config)?))
             }
+            DataSourceConfig::Phenopacket(phenopacket_config) => Ok(DataSource::Phenopacket(
+                PhenopacketDataSource::from_dir(phenopacket_config.source),
+            )),
+            DataSourceConfig::Json(json_config) => {
+                Ok(DataSource::Json(JsonDataSource::try_from(json_config)?))
+            }
         }
     }
 }
@@ -197,7 +481,8 @@ impl TryFrom<ExcelSheetConfig> for TableContext {
             .map(SeriesContext::try_from)
             .collect::<Result<Vec<SeriesContext>, ConstructionError>>()?;
 
-        Ok(TableContext::new(config.sheet_name, scs))
+        Ok(TableContext::new(config.sheet_name, scs)
+            .with_subject_id_separator(config.subject_id_separator))
     }
 }
 
@@ -213,21 +498,42 @@ impl TryFrom<CsvConfig> for CsvDataSource {
 
         let path_string = config.source.display().to_string();
 
-        let tc = TableContext::new(path_string.clone(), scs);
+        let tc = TableContext::new(path_string.clone(), scs)
+            .with_subject_id_separator(config.subject_id_separator);
 
         Ok(CsvDataSource {
             source: config.source,
             separator: config.separator,
+            quote_char: config.quote_char,
+            null_values: config.null_values,
             extraction_config: ExtractionConfig {
                 name: path_string,
                 has_headers: config.has_headers,
                 patients_are_rows: config.patients_are_rows,
             },
             context: tc,
+            chunk_size: config.chunk_size,
         })
     }
 }
 
+impl TryFrom<JsonSourceConfig> for JsonDataSource {
+    type Error = ConstructionError;
+
+    fn try_from(config: JsonSourceConfig) -> Result<Self, Self::Error> {
+        let scs = config
+            .series_contexts
+            .into_iter()
+            .map(SeriesContext::try_from)
+            .collect::<Result<Vec<SeriesContext>, ConstructionError>>()?;
+
+        let tc = TableContext::new(config.source.display().to_string(), scs)
+            .with_subject_id_separator(config.subject_id_separator);
+
+        Ok(JsonDataSource::new(config.source, tc))
+    }
+}
+
 impl TryFrom<IdentifierConfig> for Identifier {
     type Error = ConstructionError;
 
@@ -235,6 +541,9 @@ impl TryFrom<IdentifierConfig> for Identifier {
         match value {
             IdentifierConfig::Single(single) => Ok(Identifier::Single(single)),
             IdentifierConfig::Regex(regex_str) => Identifier::regex_from_str(regex_str),
+            IdentifierConfig::AnchoredRegex(regex_str) => {
+                Identifier::anchored_regex_from_str(regex_str)
+            }
             IdentifierConfig::Multi(multi) => Ok(Identifier::Multi(multi)),
         }
     }
@@ -256,6 +565,8 @@ impl TryFrom<SeriesContextConfig> for SeriesContext {
             config.fill_missing,
             alias_map,
             config.building_block_id,
+            config.keep_blank_cells,
+            config.null_values,
         ))
     }
 }
@@ -317,14 +628,18 @@ impl TryFrom<MappingsCsvConfig> for HashMap<String, Option<String>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::context::Context;
     use crate::config::table_context::OutputDataType;
-    use crate::config::{ConfigLoader, PhenoXtractConfig};
+    use crate::config::traits::SeriesContextBuilding;
+    use crate::config::{CohortConfig, ConfigLoader, PhenoXtractConfig};
+    use crate::extract::traits::Extractable;
     use crate::test_suite::config::{
         CSV_DATASOURCE_CONFIG_FILE, EXCEL_DATASOURCE_CONFIG_FILE, PIPELINE_CONFIG_FILE,
         get_full_config_bytes,
     };
     use dotenvy::dotenv;
     use rstest::{fixture, rstest};
+    use rust_xlsxwriter::Workbook;
     use std::fmt::Write;
     use std::fs::{File as StdFile, File};
     use std::io::Write as StdWrite;
@@ -363,6 +678,51 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case("other_cohort", "other/dir", None)]
+    #[case(
+        "Cohort-1",
+        "other/dir",
+        Some("The cohort name 'Cohort-1' is used by more than one cohort.")
+    )]
+    #[case(
+        "other_cohort",
+        "some/dir",
+        Some("The output directory \"some/dir\" is used by more than one loader.")
+    )]
+    fn test_ensure_distinct_cohorts(
+        temp_dir: TempDir,
+        #[case] cohort_name: &str,
+        #[case] output_dir: &str,
+        #[case] expected_err: Option<&str>,
+    ) {
+        let file_path = temp_dir.path().join("config.yaml");
+        let mut file = StdFile::create(&file_path).expect("Failed to create config file");
+        file.write_all(get_full_config_bytes().as_slice())
+            .expect("Failed to write config file");
+        let mut config: PhenoXtractConfig =
+            ConfigLoader::load(file_path).expect("Failed to load config loader");
+        let mut meta_data = config.pipeline_config.meta_data.clone();
+        meta_data.cohort_name = cohort_name.to_string();
+        config.cohorts.push(CohortConfig::new(
+            meta_data,
+            vec![],
+            LoaderConfig::FileSystem {
+                output_dir: PathBuf::from(output_dir),
+                create_dir: true,
+                output_format: Default::default(),
+                resume: false,
+            },
+        ));
+
+        let result = ensure_distinct_cohorts(&config);
+
+        assert_eq!(
+            result.err().map(|err| err.to_string()),
+            expected_err.map(str::to_string)
+        );
+    }
+
     #[rstest]
     fn test_try_from_pipeline_config(temp_dir: TempDir) {
         dotenv().ok();
@@ -379,6 +739,23 @@ mod tests {
         assert_eq!(pipeline_from_config.transformer_module.strategies.len(), 2);
     }
 
+    #[rstest]
+    fn test_try_from_pipeline_config_zero_max_concurrent_requests(temp_dir: TempDir) {
+        let file_path = temp_dir.path().join("config.yaml");
+        let mut file = StdFile::create(&file_path).expect("Failed to create config file");
+        file.write_all(PIPELINE_CONFIG_FILE)
+            .expect("Failed to write config file");
+        let config: PipelineConfig =
+            ConfigLoader::load(file_path).expect("Failed to load config loader");
+
+        let result = Pipeline::try_from(config.with_max_concurrent_requests(0));
+
+        assert!(matches!(
+            result,
+            Err(ConstructionError::InvalidMaxConcurrentRequests)
+        ));
+    }
+
     #[rstest]
     fn test_try_from_csv_datasource_config(temp_dir: TempDir) {
         let file_path = temp_dir.path().join("config.yaml");
@@ -395,8 +772,8 @@ mod tests {
             DataSource::Csv(csv_source) => {
                 assert_eq!(csv_source.context.context().len(), 3);
             }
-            DataSource::Excel(_) => {
-                panic!("Loaded Excel Datasource instead of Csv!")
+            _ => {
+                panic!("Loaded other Datasource instead of Csv!")
             }
         }
     }
@@ -414,15 +791,98 @@ mod tests {
             DataSource::try_from(config.clone()).expect("Failed to convert config from config");
 
         match excel_datasource_from_config {
-            DataSource::Csv(_) => {
-                panic!("Loaded Csv Datasource instead of Excel!")
-            }
             DataSource::Excel(excel_source) => {
                 assert_eq!(excel_source.contexts.len(), 2)
             }
+            _ => {
+                panic!("Loaded other Datasource instead of Excel!")
+            }
         }
     }
 
+    #[rstest]
+    fn test_excel_datasource_config_multiple_sheets(temp_dir: TempDir) {
+        let workbook_path = temp_dir.path().join("cohort.xlsx");
+        let mut workbook = Workbook::new();
+        let phenotypes = workbook.add_worksheet().set_name("Phenotypes").unwrap();
+        phenotypes
+            .write_column(0, 0, ["patient_id", "P001", "P002"])
+            .unwrap();
+        phenotypes
+            .write_column(0, 1, ["phenotype", "HP:0001250", "HP:0001166"])
+            .unwrap();
+        let demographics = workbook.add_worksheet().set_name("Demographics").unwrap();
+        demographics
+            .write_column(0, 0, ["patient_id", "P001", "P002"])
+            .unwrap();
+        demographics
+            .write_column(0, 1, ["sex", "MALE", "FEMALE"])
+            .unwrap();
+        workbook.save(&workbook_path).unwrap();
+
+        let config_yaml = format!(
+            r#"
+type: "excel"
+source: "{}"
+sheets:
+  - sheet_name: "Phenotypes"
+    has_headers: true
+    patients_are_rows: true
+    series_contexts:
+      - identifier: "patient_id"
+        data_context: subject_id
+      - identifier: "phenotype"
+        data_context: hpo
+  - sheet_name: "Demographics"
+    has_headers: true
+    patients_are_rows: true
+    series_contexts:
+      - identifier: "patient_id"
+        data_context: subject_id
+      - identifier: "sex"
+        data_context: subject_sex
+"#,
+            workbook_path.display()
+        );
+        let config_path = temp_dir.path().join("config.yaml");
+        fs::write(&config_path, config_yaml).unwrap();
+        let config: DataSourceConfig =
+            ConfigLoader::load(config_path).expect("Failed to load config loader");
+
+        let cdfs = DataSource::try_from(config).unwrap().extract().unwrap();
+
+        let expected_contexts = vec![
+            TableContext::new(
+                "Phenotypes",
+                vec![
+                    SeriesContext::from_identifier("patient_id")
+                        .with_data_context(Context::SubjectId),
+                    SeriesContext::from_identifier("phenotype").with_data_context(Context::Hpo),
+                ],
+            ),
+            TableContext::new(
+                "Demographics",
+                vec![
+                    SeriesContext::from_identifier("patient_id")
+                        .with_data_context(Context::SubjectId),
+                    SeriesContext::from_identifier("sex").with_data_context(Context::SubjectSex),
+                ],
+            ),
+        ];
+        assert_eq!(
+            cdfs.iter()
+                .map(|cdf| cdf.context().clone())
+                .collect::<Vec<_>>(),
+            expected_contexts
+        );
+        assert_eq!(
+            cdfs.iter()
+                .map(|cdf| cdf.data().get_column_names_str())
+                .collect::<Vec<_>>(),
+            vec![vec!["patient_id", "phenotype"], vec!["patient_id", "sex"]]
+        );
+    }
+
     #[rstest]
     fn test_try_from_alias_map_config_hash_map(temp_dir: TempDir) {
         let file_path = temp_dir.path().join("config.yaml");
@@ -441,8 +901,8 @@ mod tests {
                 let am = sc.get_alias_map().unwrap();
                 assert_eq!(am.get_hash_map().len(), 5);
             }
-            DataSource::Excel(_) => {
-                panic!("Loaded Excel Datasource instead of Csv!")
+            _ => {
+                panic!("Loaded other Datasource instead of Csv!")
             }
         }
     }
@@ -21,6 +21,53 @@ pub const DATETIME_FORMATS: &[&str] = &[
 
 pub const ISO8601_DUR_PATTERN: &str = r"^P(\d+Y)?(\d+M)?(\d+D)?(T(\d+H)?(\d+M)?(\d+S)?)?$";
 
+/// The terms of the HPO "Severity" (HP:0012824) subhierarchy.
+pub const HPO_SEVERITY_TERMS: &[&str] = &[
+    "HP:0012824",
+    "HP:0012825",
+    "HP:0012826",
+    "HP:0012827",
+    "HP:0012828",
+    "HP:0012829",
+];
+
+/// The NCBITaxon id and scientific name of human, which is the taxonomy assumed for individuals.
+pub const HUMAN_TAXON: (&str, &str) = ("NCBITaxon:9606", "Homo sapiens");
+
+/// NCBITaxon ids, scientific names and common names of the organisms individuals can be derived from.
+pub const NCBI_TAXA: &[(&str, &str, &str)] = &[
+    ("NCBITaxon:9606", "Homo sapiens", "human"),
+    ("NCBITaxon:10090", "Mus musculus", "mouse"),
+    ("NCBITaxon:10116", "Rattus norvegicus", "rat"),
+    ("NCBITaxon:7955", "Danio rerio", "zebrafish"),
+    ("NCBITaxon:7227", "Drosophila melanogaster", "fruit fly"),
+    ("NCBITaxon:6239", "Caenorhabditis elegans", "nematode"),
+    ("NCBITaxon:9615", "Canis lupus familiaris", "dog"),
+    ("NCBITaxon:9823", "Sus scrofa", "pig"),
+    ("NCBITaxon:9544", "Macaca mulatta", "rhesus monkey"),
+];
+
+/// The HPO id of "Onset", the root of the terms, which can be used as an onset time element.
+pub const HPO_ONSET_ID: &str = "HP:0003674";
+
+/// The ids of the terms of the HPO "Onset" (HP:0003674) subhierarchy, which can be used as an onset time element.
+pub const HPO_ONSET_TERMS: &[&str] = &[
+    "HP:0003674",
+    "HP:0030674",
+    "HP:0011460",
+    "HP:0011461",
+    "HP:0003577",
+    "HP:0003623",
+    "HP:0410280",
+    "HP:0003593",
+    "HP:0011463",
+    "HP:0003621",
+    "HP:0003581",
+    "HP:0011462",
+    "HP:0003596",
+    "HP:0003584",
+];
+
 pub(crate) struct PolarsNumericTypes;
 
 impl PolarsNumericTypes {
@@ -1,6 +1,7 @@
+use crate::config::context::Context;
 use crate::extract::error::ExtractionError;
 use crate::ontology::error::{FactoryError, RegistryError};
-use crate::transform::error::TransformError;
+use crate::transform::error::{CollectorError, TransformError};
 use config::ConfigError;
 use pivotal::hgnc::HGNCError;
 use pivotal::hgvs::HGVSError;
@@ -9,6 +10,7 @@ use std::path::PathBuf;
 use thiserror::Error;
 
 use crate::load::error::LoadError;
+use crate::validation::lint_report::LintFinding;
 use validator::ValidationErrors;
 
 #[derive(Debug, Error)]
@@ -35,16 +37,64 @@ pub enum ConstructionError {
     HgncError(#[from] HGNCError),
     #[error(transparent)]
     HgvsError(#[from] HGVSError),
+    #[error("Could not parse '{value}' as an RFC 3339 timestamp: {reason}")]
+    InvalidTimestamp { value: String, reason: String },
+    #[error(
+        "Onset category '{category}' maps to '{target}', which is neither an HPO onset term nor an ISO8601 duration."
+    )]
+    InvalidOnsetCategory { category: String, target: String },
+    #[error("The sex value '{value}' maps to '{sex}', which is not a Sex name.")]
+    InvalidSexVocabulary { value: String, sex: String },
+    #[error("max_concurrent_requests has to be at least 1.")]
+    InvalidMaxConcurrentRequests,
+    #[error("Unknown lint rule '{rule}'.")]
+    UnknownLintRule { rule: String },
+    #[error("Invalid regex '{pattern}': {reason}")]
+    InvalidRegex { pattern: String, reason: String },
+    #[error("Invalid bucket '{label}': {reason}")]
+    InvalidBucket { label: String, reason: String },
+    #[error("The buckets '{first}' and '{second}' overlap.")]
+    OverlappingBuckets { first: String, second: String },
+    #[error("Strategy '{strategy}' at position {position} requires '{context}', but {reason}.")]
+    UnmetStrategyDependency {
+        strategy: String,
+        position: usize,
+        context: Context,
+        reason: String,
+    },
+    #[error("The cohort name '{name}' is used by more than one cohort.")]
+    DuplicateCohortName { name: String },
+    #[error("The output directory {path:?} is used by more than one loader.")]
+    DuplicateOutputDir { path: PathBuf },
 }
 
+/// The errors, which can occur while running a [`crate::Pipeline`] or [`crate::phenoxtract::Phenoxtract`].
 #[derive(Debug, Error)]
 pub enum PipelineError {
+    /// A data source could not be read or turned into a table.
     #[error(transparent)]
     Extraction(#[from] ExtractionError),
+    /// A strategy or collector failed, e.g. because a term could not be resolved in strict mode.
     #[error(transparent)]
     Transform(#[from] TransformError),
+    /// A data source or an extracted table is invalid.
     #[error(transparent)]
     Validation(#[from] ValidationErrors),
+    /// The phenopackets could not be written.
     #[error(transparent)]
     Load(#[from] LoadError),
+    /// A check of the built phenopackets found an error. All findings are in the lint report of the pipeline.
+    #[error("{0}")]
+    Lint(LintFinding),
+    /// Resumed runs build every phenopacket as soon as its patient is collected, which requires all data at once.
+    #[error(
+        "Data sources, which are read in chunks, can't be combined with resuming runs. Remove their chunk_size or disable resume."
+    )]
+    ChunkedResume,
+}
+
+impl From<CollectorError> for PipelineError {
+    fn from(err: CollectorError) -> Self {
+        PipelineError::Transform(err.into())
+    }
 }
@@ -9,13 +9,18 @@ use crate::validation::cdf_checks::check_orphaned_columns;
 use crate::validation::contextualised_dataframe_validation::validate_dangling_sc;
 use crate::validation::contextualised_dataframe_validation::validate_one_context_per_column;
 use crate::validation::contextualised_dataframe_validation::validate_subject_id_col_no_nulls;
-use ordermap::OrderSet;
+use log::warn;
+use ordermap::{OrderMap, OrderSet};
 use polars::datatypes::{BooleanChunked, StringChunked};
 use polars::prelude::{Column, DataFrame, DataType, Float64Chunked, PolarsError, Series};
 use std::collections::HashMap;
 use std::mem::ManuallyDrop;
 use std::ptr;
 use thiserror::Error;
+
+/// Columns, whose name starts with this prefix, hold the original cell values of another column, before the
+/// strategies were applied. They are never identified by a series context, so strategies leave them untouched.
+pub(crate) const RAW_VALUE_COLUMN_PREFIX: &str = "__raw__";
 use validator::{Validate, ValidationError, ValidationErrors};
 
 /// A structure that combines a `DataFrame` with its corresponding `TableContext`.
@@ -69,7 +74,7 @@ impl ContextualizedDataFrame {
     ///
     /// # Returns
     /// A `Vec<&Column>` containing references to the columns that match the given identifier(s).
-    /// If no columns match, an empty vector is returned.
+    /// If no columns match, an empty vector is returned. Columns holding raw values are never returned.
     ///
     /// # Examples
     /// ```ignore
@@ -82,6 +87,7 @@ impl ContextualizedDataFrame {
             .columns()
             .iter()
             .map(|col| col.name().as_str())
+            .filter(|col_name| !col_name.starts_with(RAW_VALUE_COLUMN_PREFIX))
             .collect();
 
         let identified_col_names = id.identify(&cols);
@@ -96,6 +102,15 @@ impl ContextualizedDataFrame {
             .collect()
     }
 
+    pub(crate) fn raw_value_column_name(col_name: &str) -> String {
+        format!("{RAW_VALUE_COLUMN_PREFIX}{col_name}")
+    }
+
+    /// The original cell values of a column, if they were preserved before the strategies were applied.
+    pub(crate) fn get_raw_value_column(&self, col_name: &str) -> Option<&Column> {
+        self.data.column(&Self::raw_value_column_name(col_name)).ok()
+    }
+
     pub fn filter_series_context(&'_ self) -> SeriesContextFilter<'_> {
         SeriesContextFilter::new(self.context.context())
     }
@@ -115,6 +130,50 @@ impl ContextualizedDataFrame {
             .collect()
     }
 
+    /// Groups the row indices by building block.
+    ///
+    /// Different building blocks are independent groups, which may repeat within a patient (e.g. two measurements
+    /// in the same row). A row therefore belongs to every building block, which has a value in it.
+    /// Columns without a building block are grouped under `None`. Building blocks without any values are left out.
+    pub fn building_block_row_groups(&self) -> OrderMap<Option<&str>, Vec<usize>> {
+        self.get_building_block_ids()
+            .into_iter()
+            .map(Some)
+            .chain([None])
+            .map(|bb_id| (bb_id, self.building_block_rows(bb_id)))
+            .filter(|(_, row_idxs)| !row_idxs.is_empty())
+            .collect()
+    }
+
+    /// The indices of the rows, in which at least one column of the building block has a value.
+    ///
+    /// The subject id column is not taken into account.
+    pub fn building_block_rows(&self, bb_id: Option<&str>) -> Vec<usize> {
+        let filter = self
+            .filter_columns()
+            .where_data_context(Filter::IsNot(&Context::SubjectId));
+        let block_cols = match bb_id {
+            Some(bb_id) => filter.where_building_block(Filter::Is(bb_id)),
+            None => filter.where_building_block(Filter::IsNone),
+        }
+        .collect();
+
+        let mut has_value = vec![false; self.data().height()];
+        for col in block_cols {
+            for (row_idx, is_not_null) in col.is_not_null().iter().enumerate() {
+                if is_not_null == Some(true) {
+                    has_value[row_idx] = true;
+                }
+            }
+        }
+
+        has_value
+            .into_iter()
+            .enumerate()
+            .filter_map(|(row_idx, has_value)| has_value.then_some(row_idx))
+            .collect()
+    }
+
     pub fn get_subject_id_col(&self) -> &Column {
         self.filter_columns()
             .where_header_context(Filter::Is(&Context::None))
@@ -320,6 +379,23 @@ impl ContextualizedDataFrame {
         Ok(stringified_cols)
     }
 
+    /// Describes why an identifier points to no column: the closest column name, if any, and all column names.
+    pub(crate) fn describe_unmatched_identifier(&self, id: &Identifier) -> String {
+        let cols: Vec<&str> = self
+            .data
+            .columns()
+            .iter()
+            .map(|col| col.name().as_str())
+            .collect();
+
+        match id.closest_match(&cols) {
+            Some(closest) => format!(
+                "{id} matches no column. Did you mean '{closest}'? Available columns: {cols:?}"
+            ),
+            None => format!("{id} matches no column. Available columns: {cols:?}"),
+        }
+    }
+
     pub(crate) fn get_dangling_scs(&self) -> Vec<Identifier> {
         let mut dangling_scs = vec![];
         for sc in self.series_contexts() {
@@ -456,6 +532,45 @@ mod tests {
         assert_eq!(cdf.get_building_block_ids(), expected_bb_ids);
     }
 
+    #[rstest]
+    fn test_building_block_row_groups() {
+        let df = df!(
+            "subject_id" => &["P001", "P001", "P001", "P002"],
+            "glucose" => &[Some(5.1), None, Some(6.3), None],
+            "glucose_time" => &[Some("P1Y"), None, None, None],
+            "creatinine" => &[None, Some(0.9), Some(1.1), None],
+            "sex" => &[Some("MALE"), None, None, None],
+            "unused" => &[None::<&str>, None, None, None],
+        )
+        .unwrap();
+        let tc = TableContext::new(
+            "measurements".to_string(),
+            vec![
+                SeriesContext::from_identifier("subject_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("glucose").with_building_block_id("glucose"),
+                SeriesContext::from_identifier("glucose_time")
+                    .with_data_context(Context::TimeOfMeasurement(TimeElementType::Age))
+                    .with_building_block_id("glucose"),
+                SeriesContext::from_identifier("creatinine").with_building_block_id("creatinine"),
+                SeriesContext::from_identifier("sex").with_data_context(Context::SubjectSex),
+                SeriesContext::from_identifier("unused").with_building_block_id("unused"),
+            ],
+        );
+        let cdf = ContextualizedDataFrame::new(tc, df).unwrap();
+
+        let groups = cdf.building_block_row_groups();
+
+        assert_eq!(
+            groups.into_iter().collect::<Vec<_>>(),
+            vec![
+                (Some("glucose"), vec![0, 2]),
+                (Some("creatinine"), vec![1, 2]),
+                (None, vec![0]),
+            ]
+        );
+        assert_eq!(cdf.building_block_rows(Some("unused")), Vec::<usize>::new());
+    }
+
     #[rstest]
     fn test_group_column_by_subject_id_no_cast() {
         let df = sample_df();
@@ -793,6 +908,13 @@ impl<'a> ContextualizedDataFrameBuilder<'a> {
 
     fn drop_dangling_scs(mut self) -> Self {
         let dangling_scs = self.cdf.get_dangling_scs();
+        for sc_id in &dangling_scs {
+            warn!(
+                "Dropping SeriesContext in table {}: {}",
+                self.cdf.context().name(),
+                self.cdf.describe_unmatched_identifier(sc_id)
+            );
+        }
         self = self.drop_scs(dangling_scs.as_slice());
         self.mark_dirty()
     }
@@ -1,6 +1,14 @@
 use crate::config::table_context::TableContext;
+use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
+use crate::extract::data_source::DataSource;
+use crate::extract::error::ExtractionError;
 use crate::extract::extraction_config::ExtractionConfig;
 use crate::extract::traits::HasSource;
+use crate::extract::utils::open_decompressed;
+use polars::io::SerReader;
+use polars::prelude::SchemaRef;
+use std::fmt;
+use std::io::{BufRead, Cursor};
 use std::path::PathBuf;
 use validator::Validate;
 
@@ -11,10 +19,16 @@ pub struct CsvDataSource {
     pub(crate) source: PathBuf,
     /// The character used to separate fields in the CSV file (e.g., ',').
     pub(crate) separator: Option<char>,
+    /// The character used to quote fields in the CSV file (e.g., '"').
+    pub(crate) quote_char: Option<char>,
+    /// Cell values, which are read as null (e.g., "NA").
+    pub(crate) null_values: Vec<String>,
     /// The context describing how to interpret the resulting DataFrame.
     pub(crate) context: TableContext,
     /// This configures how the DataFrame is extracted.
     pub(crate) extraction_config: ExtractionConfig,
+    /// If set, the file is read in chunks of about this many rows instead of at once.
+    pub(crate) chunk_size: Option<usize>,
 }
 
 impl CsvDataSource {
@@ -27,10 +41,35 @@ impl CsvDataSource {
         Self {
             source,
             separator,
+            quote_char: None,
+            null_values: vec![],
             context: table,
             extraction_config,
+            chunk_size: None,
         }
     }
+
+    /// Reads the file in chunks of about `chunk_size` rows, so that only one chunk has to be in memory at a time.
+    pub fn with_chunk_size(mut self, chunk_size: Option<usize>) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// The chunks of the file, if it is read in chunks.
+    pub(crate) fn chunks(&self) -> Option<CsvChunks<'_>> {
+        self.chunk_size
+            .map(|chunk_size| CsvChunks::new(self, chunk_size))
+    }
+
+    pub fn with_quote_char(mut self, quote_char: Option<char>) -> Self {
+        self.quote_char = quote_char;
+        self
+    }
+
+    pub fn with_null_values(mut self, null_values: Vec<String>) -> Self {
+        self.null_values = null_values;
+        self
+    }
 }
 
 impl HasSource for CsvDataSource {
@@ -45,3 +84,298 @@ impl HasSource for CsvDataSource {
         self
     }
 }
+
+/// Reads a CSV file, in which the patients are rows, in chunks of rows.
+///
+/// The file is opened once and read from front to back, so every row is read and decompressed only once.
+/// All chunks are parsed with the schema of the first chunk, so that a column keeps its type, even if a later chunk
+/// would be inferred differently, e.g. because the column is empty in it.
+///
+/// A chunk never ends in the middle of the consecutive rows of a patient: the rows of the last patient are moved
+/// to the next chunk instead. If the rows of a single patient exceed the chunk size, the chunk grows until it holds all of them.
+#[derive(Debug)]
+pub struct CsvChunks<'a> {
+    source: &'a CsvDataSource,
+    chunk_size: usize,
+    /// The records of the file, which are opened with the first chunk.
+    records: Option<CsvRecordReader>,
+    /// The schema of the first chunk.
+    schema: Option<SchemaRef>,
+    /// The rows of the last patient of the previous chunk, whose rows might continue in the next chunk.
+    pending: Option<ContextualizedDataFrame>,
+    done: bool,
+}
+
+impl<'a> CsvChunks<'a> {
+    fn new(source: &'a CsvDataSource, chunk_size: usize) -> Self {
+        Self {
+            source,
+            chunk_size: chunk_size.max(1),
+            records: None,
+            schema: None,
+            pending: None,
+            done: false,
+        }
+    }
+
+    fn next_chunk(&mut self) -> Result<Option<ContextualizedDataFrame>, ExtractionError> {
+        if !self.source.extraction_config.patients_are_rows {
+            return Err(ExtractionError::UnsupportedChunking(
+                self.source.context.name().to_string(),
+            ));
+        }
+
+        loop {
+            let (csv, n_records) = self.records()?.read_records(self.chunk_size)?;
+            let is_last_chunk = n_records < self.chunk_size;
+            let cdf = match (self.pending.take(), self.parse(csv, n_records)?) {
+                (Some(pending), Some(cdf)) => {
+                    let data = pending.data().vstack(cdf.data())?;
+                    ContextualizedDataFrame::new(pending.context().clone(), data)?
+                }
+                (Some(cdf), None) | (None, Some(cdf)) => cdf,
+                (None, None) if is_last_chunk => {
+                    self.done = true;
+                    return Ok(None);
+                }
+                (None, None) => continue,
+            };
+
+            if is_last_chunk {
+                self.done = true;
+                return Ok(Some(cdf));
+            }
+
+            let height = cdf.data().height();
+            let subject_ids = cdf.get_subject_id_col();
+            let last_subject_id = subject_ids.get(height - 1)?;
+            let mut last_patient_start = height - 1;
+            while last_patient_start > 0
+                && subject_ids.get(last_patient_start - 1)? == last_subject_id
+            {
+                last_patient_start -= 1;
+            }
+
+            let (context, data) = (cdf.context().clone(), cdf.into_data());
+            self.pending = Some(ContextualizedDataFrame::new(
+                context.clone(),
+                data.slice(last_patient_start as i64, height - last_patient_start),
+            )?);
+            if last_patient_start > 0 {
+                return Ok(Some(ContextualizedDataFrame::new(
+                    context,
+                    data.slice(0, last_patient_start),
+                )?));
+            }
+        }
+    }
+
+    fn records(&mut self) -> Result<&mut CsvRecordReader, ExtractionError> {
+        let records = match self.records.take() {
+            Some(records) => records,
+            None => CsvRecordReader::open(self.source)?,
+        };
+        Ok(self.records.insert(records))
+    }
+
+    /// Parses the records of a chunk. The schema of the first chunk is kept for all later chunks.
+    ///
+    /// Returns `None`, if the chunk has no rows, e.g. because all of its records are empty lines.
+    fn parse(
+        &mut self,
+        csv: Vec<u8>,
+        n_records: usize,
+    ) -> Result<Option<ContextualizedDataFrame>, ExtractionError> {
+        if n_records == 0 {
+            return Ok(None);
+        }
+
+        let csv_data = DataSource::csv_read_options(self.source, self.schema.clone())
+            .into_reader_with_file_handle(Cursor::new(csv))
+            .finish()?;
+        if csv_data.height() == 0 {
+            return Ok(None);
+        }
+        if self.schema.is_none() {
+            self.schema = Some(csv_data.schema().clone());
+        }
+        Ok(Some(DataSource::csv_data_to_cdf(self.source, csv_data)?))
+    }
+}
+
+/// Reads the records of a CSV file one after the other, without parsing them.
+///
+/// Every chunk of records is prefixed with the header, so that it can be parsed on its own.
+struct CsvRecordReader {
+    reader: Box<dyn BufRead>,
+    header: Vec<u8>,
+    quote_char: u8,
+}
+
+impl CsvRecordReader {
+    fn open(source: &CsvDataSource) -> Result<Self, ExtractionError> {
+        let mut records = Self {
+            reader: open_decompressed(&source.source)?,
+            header: vec![],
+            quote_char: source.quote_char.map_or(b'"', |quote_char| quote_char as u8),
+        };
+        if source.extraction_config.has_headers {
+            let mut header = vec![];
+            records.read_record(&mut header)?;
+            records.header = header;
+        }
+        Ok(records)
+    }
+
+    /// Reads up to `n_records` records. Returns them after the header and the number of records, which were read.
+    fn read_records(&mut self, n_records: usize) -> Result<(Vec<u8>, usize), std::io::Error> {
+        let mut csv = self.header.clone();
+        let mut n_read = 0;
+        while n_read < n_records && self.read_record(&mut csv)? {
+            n_read += 1;
+        }
+        Ok((csv, n_read))
+    }
+
+    /// Appends the next record to the buffer. A record continues over line breaks, which are inside of quotes.
+    /// Returns `false` at the end of the file.
+    fn read_record(&mut self, buf: &mut Vec<u8>) -> Result<bool, std::io::Error> {
+        let start = buf.len();
+        loop {
+            if self.reader.read_until(b'\n', buf)? == 0 {
+                return Ok(buf.len() > start);
+            }
+            let n_quotes = buf[start..]
+                .iter()
+                .filter(|byte| **byte == self.quote_char)
+                .count();
+            if n_quotes % 2 == 0 {
+                return Ok(true);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for CsvRecordReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CsvRecordReader")
+            .field("header", &String::from_utf8_lossy(&self.header))
+            .finish_non_exhaustive()
+    }
+}
+
+impl Iterator for CsvChunks<'_> {
+    type Item = Result<ContextualizedDataFrame, ExtractionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let chunk = self.next_chunk();
+        if chunk.is_err() {
+            self.done = true;
+        }
+        chunk.transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::context::Context;
+    use crate::config::table_context::SeriesContext;
+    use crate::config::traits::SeriesContextBuilding;
+    use crate::extract::traits::Extractable;
+    use polars::prelude::{AnyValue, DataType};
+    use rstest::rstest;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn csv_source(path: PathBuf, chunk_size: usize) -> CsvDataSource {
+        CsvDataSource::new(
+            path,
+            None,
+            TableContext::new(
+                "patients".to_string(),
+                vec![
+                    SeriesContext::from_identifier("subject_id")
+                        .with_data_context(Context::SubjectId),
+                ],
+            ),
+            ExtractionConfig::new("patients".to_string(), true, true),
+        )
+        .with_chunk_size(Some(chunk_size))
+    }
+
+    fn write_csv(temp_dir: &TempDir, content: &str) -> PathBuf {
+        let path = temp_dir.path().join("patients.csv");
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[rstest]
+    #[case("patients.csv")]
+    #[case("patients.csv.gz")]
+    #[case("patients.csv.zst")]
+    fn test_chunks_match_eager_extraction(#[case] file_name: &str) {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src/test_suite/test_data")
+            .join(file_name);
+        let source = csv_source(path, 1);
+
+        let chunks: Vec<ContextualizedDataFrame> =
+            source.chunks().unwrap().collect::<Result<_, _>>().unwrap();
+        let eager = DataSource::Csv(source).extract().unwrap().pop().unwrap();
+
+        assert_eq!(chunks.len(), 3);
+        let mut data = chunks[0].data().clone();
+        for chunk in &chunks[1..] {
+            data.vstack_mut(chunk.data()).unwrap();
+        }
+        assert_eq!(&data, eager.data());
+    }
+
+    #[rstest]
+    fn test_chunks_keep_schema_of_first_chunk() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_csv(&temp_dir, "subject_id,age\nP001,30\nP002,\nP003,41\n");
+
+        let chunks: Vec<ContextualizedDataFrame> = csv_source(path, 1)
+            .chunks()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(chunks.len(), 3);
+        for chunk in chunks {
+            assert_eq!(
+                chunk.data().column("age").unwrap().dtype(),
+                &DataType::Int64
+            );
+        }
+    }
+
+    #[rstest]
+    fn test_chunks_keep_rows_of_a_patient_together() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_csv(
+            &temp_dir,
+            "subject_id,note\nP001,\"first\nline\"\nP001,second\nP002,third\n",
+        );
+
+        let chunks: Vec<ContextualizedDataFrame> = csv_source(path, 1)
+            .chunks()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].data().height(), 2);
+        assert_eq!(
+            chunks[0].data().column("note").unwrap().get(0).unwrap(),
+            AnyValue::String("first\nline")
+        );
+        assert_eq!(chunks[1].data().height(), 1);
+    }
+}
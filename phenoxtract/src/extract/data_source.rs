@@ -1,17 +1,21 @@
 use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
 use crate::extract::csv_data_source::CsvDataSource;
 use polars::io::SerReader;
-use polars::prelude::{CsvReadOptions, DataFrame};
+use polars::prelude::{CsvReadOptions, DataFrame, NullValues, SchemaRef};
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Cursor};
 
 use crate::extract::error::ExtractionError;
 use crate::extract::excel_data_source::ExcelDataSource;
-use crate::extract::traits::Extractable;
+use crate::extract::json_data_source::JsonDataSource;
+use crate::extract::phenopacket_data_source::PhenopacketDataSource;
+use crate::extract::traits::{Extractable, ExtractedChunks};
 use log::info;
 
 use crate::extract::excel_range_reader::ExcelRangeReader;
-use crate::extract::utils::generate_default_column_names;
+use crate::extract::utils::{
+    generate_default_column_names, merge_subject_id_columns, read_decompressed,
+};
 use calamine::{Reader, Xlsx, open_workbook};
 use either::Either;
 use std::sync::Arc;
@@ -22,6 +26,8 @@ use validator::{Validate, ValidationErrors};
 pub enum DataSource {
     Csv(CsvDataSource),
     Excel(ExcelDataSource),
+    Phenopacket(PhenopacketDataSource),
+    Json(JsonDataSource),
 }
 
 impl Validate for DataSource {
@@ -29,6 +35,8 @@ impl Validate for DataSource {
         match self {
             DataSource::Csv(csv) => csv.validate()?,
             DataSource::Excel(excel) => excel.validate()?,
+            DataSource::Phenopacket(phenopacket) => phenopacket.validate()?,
+            DataSource::Json(json) => json.validate()?,
         };
         Ok(())
     }
@@ -74,6 +82,84 @@ impl DataSource {
 
         Ok(df)
     }
+
+    /// Reads a CSV file into a CDF.
+    ///
+    /// Gzip or zstd compressed files are decompressed in memory first.
+    pub(crate) fn read_csv(
+        csv_source: &CsvDataSource,
+    ) -> Result<ContextualizedDataFrame, ExtractionError> {
+        let csv_read_options = DataSource::csv_read_options(csv_source, None);
+        let csv_data = match read_decompressed(&csv_source.source)? {
+            Some(decompressed) => csv_read_options
+                .into_reader_with_file_handle(Cursor::new(decompressed))
+                .finish()?,
+            None => csv_read_options
+                .try_into_reader_with_file_path(Some(csv_source.source.clone()))?
+                .finish()?,
+        };
+        DataSource::csv_data_to_cdf(csv_source, csv_data)
+    }
+
+    /// The options, with which the CSV file is parsed. If a schema is given, it is used instead of inferring one.
+    pub(crate) fn csv_read_options(
+        csv_source: &CsvDataSource,
+        schema: Option<SchemaRef>,
+    ) -> CsvReadOptions {
+        let mut csv_read_options = CsvReadOptions::default()
+            .with_has_header(
+                csv_source.extraction_config.patients_are_rows
+                    && csv_source.extraction_config.has_headers,
+            )
+            .with_schema(schema);
+
+        let mut parse_options = (*csv_read_options.parse_options).clone();
+        if let Some(sep) = csv_source.separator {
+            parse_options = parse_options.with_separator(sep as u8);
+        }
+        if let Some(quote_char) = csv_source.quote_char {
+            parse_options = parse_options.with_quote_char(Some(quote_char as u8));
+        }
+        if !csv_source.null_values.is_empty() {
+            parse_options = parse_options.with_null_values(Some(NullValues::AllColumns(
+                csv_source
+                    .null_values
+                    .iter()
+                    .map(|null_value| null_value.as_str().into())
+                    .collect(),
+            )));
+        }
+        csv_read_options.parse_options = Arc::from(parse_options);
+        csv_read_options
+    }
+
+    /// Turns the parsed CSV data into a CDF, e.g. by transposing it, if the patients are columns.
+    pub(crate) fn csv_data_to_cdf(
+        csv_source: &CsvDataSource,
+        csv_data: DataFrame,
+    ) -> Result<ContextualizedDataFrame, ExtractionError> {
+        let mut csv_data = DataSource::conditional_transpose(
+            csv_data,
+            csv_source.context.name(),
+            &csv_source.extraction_config.patients_are_rows,
+            &csv_source.extraction_config.has_headers,
+        )?;
+
+        if !csv_source.extraction_config.has_headers {
+            let default_column_names = generate_default_column_names(csv_data.width() as i64);
+            let current_column_names: Vec<String> = csv_data
+                .get_column_names()
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+
+            for (col_name, new_col_name) in current_column_names.iter().zip(default_column_names) {
+                csv_data.rename(col_name.as_str(), new_col_name.into())?;
+            }
+        }
+        let (csv_context, csv_data) = merge_subject_id_columns(&csv_source.context, csv_data)?;
+        Ok(ContextualizedDataFrame::new(csv_context, csv_data)?)
+    }
 }
 
 impl Extractable for DataSource {
@@ -85,44 +171,7 @@ impl Extractable for DataSource {
                     csv_source.source.display()
                 );
 
-                let mut csv_read_options = CsvReadOptions::default().with_has_header(
-                    csv_source.extraction_config.patients_are_rows
-                        && csv_source.extraction_config.has_headers,
-                );
-
-                if let Some(sep) = csv_source.separator {
-                    let new_parse_options = (*csv_read_options.parse_options)
-                        .clone()
-                        .with_separator(sep as u8);
-                    csv_read_options.parse_options = Arc::from(new_parse_options);
-                }
-                let csv_data = csv_read_options
-                    .try_into_reader_with_file_path(Some(csv_source.source.clone()))?
-                    .finish()?;
-
-                let mut csv_data = DataSource::conditional_transpose(
-                    csv_data,
-                    csv_source.context.name(),
-                    &csv_source.extraction_config.patients_are_rows,
-                    &csv_source.extraction_config.has_headers,
-                )?;
-
-                if !csv_source.extraction_config.has_headers {
-                    let default_column_names =
-                        generate_default_column_names(csv_data.width() as i64);
-                    let current_column_names: Vec<String> = csv_data
-                        .get_column_names()
-                        .iter()
-                        .map(|s| s.to_string())
-                        .collect();
-
-                    for (col_name, new_col_name) in
-                        current_column_names.iter().zip(default_column_names)
-                    {
-                        csv_data.rename(col_name.as_str(), new_col_name.into())?;
-                    }
-                }
-                let cdf = ContextualizedDataFrame::new(csv_source.context.clone(), csv_data)?;
+                let cdf = DataSource::read_csv(csv_source)?;
 
                 info!("Extracted CSV data from {}", csv_source.source.display());
                 Ok(vec![cdf])
@@ -157,7 +206,9 @@ impl Extractable for DataSource {
 
                     let sheet_data = excel_range_reader.extract_to_df()?;
 
-                    let cdf = ContextualizedDataFrame::new(sheet_context.clone(), sheet_data)?;
+                    let (sheet_context, sheet_data) =
+                        merge_subject_id_columns(sheet_context, sheet_data)?;
+                    let cdf = ContextualizedDataFrame::new(sheet_context, sheet_data)?;
 
                     cdf_vec.push(cdf);
                     info!(
@@ -169,6 +220,52 @@ impl Extractable for DataSource {
 
                 Ok(cdf_vec)
             }
+            DataSource::Phenopacket(phenopacket_source) => {
+                info!(
+                    "Attempting to extract phenopackets from: {}",
+                    phenopacket_source.source.display()
+                );
+
+                let phenopacket_data = phenopacket_source.extract_to_df()?;
+                let cdf = ContextualizedDataFrame::new(
+                    phenopacket_source.context.clone(),
+                    phenopacket_data,
+                )?;
+
+                info!(
+                    "Extracted phenopackets from {}",
+                    phenopacket_source.source.display()
+                );
+                Ok(vec![cdf])
+            }
+            DataSource::Json(json_source) => {
+                info!(
+                    "Attempting to extract JSON data from: {}",
+                    json_source.source.display()
+                );
+
+                let json_data = json_source.extract_to_df()?;
+                let (json_context, json_data) =
+                    merge_subject_id_columns(&json_source.context, json_data)?;
+                let cdf = ContextualizedDataFrame::new(json_context, json_data)?;
+
+                info!("Extracted JSON data from {}", json_source.source.display());
+                Ok(vec![cdf])
+            }
+        }
+    }
+
+    fn extract_chunks(&self) -> Option<ExtractedChunks<'_>> {
+        match self {
+            DataSource::Csv(csv_source) => {
+                let chunks = csv_source.chunks()?;
+                info!(
+                    "Extracting CSV data from {} in chunks",
+                    csv_source.source.display()
+                );
+                Some(Box::new(chunks))
+            }
+            _ => None,
         }
     }
 }
@@ -190,6 +287,7 @@ mod tests {
     use std::fmt::Write;
     use std::fs::File;
     use std::io::Write as StdWrite;
+    use std::path::PathBuf;
     use tempfile::TempDir;
 
     #[fixture]
@@ -441,6 +539,136 @@ mod tests {
         }
     }
 
+    fn extract_subject_csv(
+        temp_dir: &TempDir,
+        test_data: &str,
+        configure: impl FnOnce(CsvDataSource) -> CsvDataSource,
+    ) -> DataFrame {
+        let table_context = TableContext::new(
+            "subjects".to_string(),
+            vec![
+                SeriesContext::from_identifier("subject_id").with_data_context(Context::SubjectId),
+            ],
+        );
+        let file_path = temp_dir.path().join("subjects.csv");
+        let mut file = File::create(&file_path).unwrap();
+        file.write_all(test_data.as_bytes()).unwrap();
+
+        let data_source = DataSource::Csv(configure(CsvDataSource::new(
+            file_path,
+            None,
+            table_context,
+            ExtractionConfig::new("subjects".to_string(), true, true),
+        )));
+
+        data_source.extract().unwrap().pop().unwrap().into_data()
+    }
+
+    #[rstest]
+    #[case("patients.csv.gz")]
+    #[case("patients.csv.zst")]
+    fn test_extract_compressed_csv(#[case] file_name: &str) {
+        let test_data_dir =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/test_suite/test_data");
+        let csv_source = |file_name: &str| {
+            DataSource::Csv(CsvDataSource::new(
+                test_data_dir.join(file_name),
+                None,
+                TableContext::new(
+                    "patients".to_string(),
+                    vec![
+                        SeriesContext::from_identifier("subject_id")
+                            .with_data_context(Context::SubjectId),
+                    ],
+                ),
+                ExtractionConfig::new("patients".to_string(), true, true),
+            ))
+        };
+
+        let plaintext_cdf = csv_source("patients.csv").extract().unwrap().pop().unwrap();
+        let compressed_cdf = csv_source(file_name).extract().unwrap().pop().unwrap();
+
+        assert_eq!(compressed_cdf, plaintext_cdf);
+        assert_eq!(compressed_cdf.data().height(), 3);
+    }
+
+    #[rstest]
+    fn test_extract_tsv(temp_dir: TempDir) {
+        let test_data = "subject_id\tsex\nP001\tM\nP002\tF";
+
+        let extracted_df = extract_subject_csv(&temp_dir, test_data, |csv_source| CsvDataSource {
+            separator: Some('\t'),
+            ..csv_source
+        });
+
+        let expected_df = df![
+            "subject_id" => &["P001", "P002"],
+            "sex" => &["M", "F"]
+        ]
+        .unwrap();
+        assert_eq!(extracted_df, expected_df);
+    }
+
+    #[rstest]
+    fn test_extract_csv_composite_subject_id(temp_dir: TempDir) {
+        let test_data = "site,sex,local_id\nSITE1,M,P001\nSITE2,F,P001";
+
+        let extracted_df = extract_subject_csv(&temp_dir, test_data, |csv_source| CsvDataSource {
+            context: TableContext::new(
+                "subjects".to_string(),
+                vec![
+                    SeriesContext::from_identifier(vec!["site", "local_id"])
+                        .with_data_context(Context::SubjectId),
+                ],
+            )
+            .with_subject_id_separator("_"),
+            ..csv_source
+        });
+
+        let expected_df = df![
+            "site_local_id" => &["SITE1_P001", "SITE2_P001"],
+            "sex" => &["M", "F"]
+        ]
+        .unwrap();
+        assert_eq!(extracted_df, expected_df);
+    }
+
+    #[rstest]
+    fn test_extract_csv_null_values(temp_dir: TempDir) {
+        let test_data = "subject_id,sex\nP001,NA\nP002,F\nP003,N/A";
+
+        let extracted_df = extract_subject_csv(&temp_dir, test_data, |csv_source| {
+            csv_source.with_null_values(vec!["NA".to_string(), "N/A".to_string()])
+        });
+
+        let expected_df = df![
+            "subject_id" => &["P001", "P002", "P003"],
+            "sex" => &[None, Some("F"), None]
+        ]
+        .unwrap();
+        assert_eq!(extracted_df, expected_df);
+    }
+
+    #[rstest]
+    fn test_extract_csv_quote_char(temp_dir: TempDir) {
+        let test_data = "subject_id;description\nP001;'tall; thin'";
+
+        let extracted_df = extract_subject_csv(&temp_dir, test_data, |csv_source| {
+            CsvDataSource {
+                separator: Some(';'),
+                ..csv_source
+            }
+            .with_quote_char(Some('\''))
+        });
+
+        let expected_df = df![
+            "subject_id" => &["P001"],
+            "description" => &["tall; thin"]
+        ]
+        .unwrap();
+        assert_eq!(extracted_df, expected_df);
+    }
+
     #[rstest]
     fn test_extract_csv_no_heads_patients_in_columns(
         temp_dir: TempDir,
@@ -23,4 +23,12 @@ pub enum ExtractionError {
     Calamine(#[from] XlsxError),
     #[error(transparent)]
     Validation(#[from] ValidationErrors),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Can't read phenopacket from {path}: {reason}")]
+    InvalidPhenopacket { path: String, reason: String },
+    #[error("Can't read JSON from {path}: {reason}")]
+    InvalidJson { path: String, reason: String },
+    #[error("Table {0} can only be read in chunks, if the patients are rows.")]
+    UnsupportedChunking(String),
 }
@@ -0,0 +1,341 @@
+use crate::config::table_context::TableContext;
+use crate::extract::error::ExtractionError;
+use crate::extract::traits::HasSource;
+use crate::extract::utils::read_decompressed;
+use log::debug;
+use polars::prelude::{Column, DataFrame, PolarsError};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::PathBuf;
+use validator::Validate;
+
+/// Defines a JSON file of patient records as a data source.
+///
+/// The file is either a single top-level array of objects, or newline-delimited JSON (NDJSON) with one object
+/// per line. The format is detected by the first non-whitespace character. Gzip or zstd compressed files are
+/// decompressed transparently.
+///
+/// Every record becomes a row. Nested objects are flattened to dotted column names, so that
+/// `{"measurement": {"value": 1.2}}` ends up in the column `measurement.value`. Arrays are kept as JSON strings.
+/// The columns are ordered by their first appearance.
+#[derive(Debug, Clone, PartialEq, Validate)]
+pub struct JsonDataSource {
+    /// The file path to the JSON source.
+    pub(crate) source: PathBuf,
+    /// The context describing how to interpret the resulting DataFrame.
+    pub(crate) context: TableContext,
+}
+
+impl JsonDataSource {
+    pub fn new(source: PathBuf, context: TableContext) -> Self {
+        Self { source, context }
+    }
+
+    pub(crate) fn extract_to_df(&self) -> Result<DataFrame, ExtractionError> {
+        let content = match read_decompressed(&self.source)? {
+            Some(decompressed) => String::from_utf8(decompressed).map_err(|err| {
+                ExtractionError::InvalidJson {
+                    path: self.source.display().to_string(),
+                    reason: err.to_string(),
+                }
+            })?,
+            None => fs::read_to_string(&self.source)?,
+        };
+        let records = self.parse_records(&content)?;
+        debug!(
+            "Read {} records from {}",
+            records.len(),
+            self.source.display()
+        );
+
+        Ok(FlattenedRecords::from_records(&records).into_df()?)
+    }
+
+    fn parse_records(&self, content: &str) -> Result<Vec<Map<String, Value>>, ExtractionError> {
+        let invalid = |reason: String| ExtractionError::InvalidJson {
+            path: self.source.display().to_string(),
+            reason,
+        };
+
+        let values: Vec<Value> = if content.trim_start().starts_with('[') {
+            serde_json::from_str(content).map_err(|err| invalid(err.to_string()))?
+        } else {
+            content
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(serde_json::from_str)
+                .collect::<Result<_, _>>()
+                .map_err(|err| invalid(err.to_string()))?
+        };
+
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(idx, value)| match value {
+                Value::Object(record) => Ok(record),
+                other => Err(invalid(format!(
+                    "record {idx} is not an object, but '{other}'"
+                ))),
+            })
+            .collect()
+    }
+}
+
+impl HasSource for JsonDataSource {
+    type Source = PathBuf;
+
+    fn source(&self) -> &Self::Source {
+        &self.source
+    }
+
+    fn with_source(mut self, source: &Self::Source) -> Self {
+        self.source = source.clone();
+        self
+    }
+}
+
+#[derive(Default)]
+struct FlattenedRecords {
+    column_names: Vec<String>,
+    columns: Vec<Vec<Value>>,
+    height: usize,
+}
+
+impl FlattenedRecords {
+    fn from_records(records: &[Map<String, Value>]) -> Self {
+        let mut flattened = Self::default();
+        for record in records {
+            flattened.push_record(record);
+        }
+        flattened
+    }
+
+    fn push_record(&mut self, record: &Map<String, Value>) {
+        let mut cells = vec![];
+        Self::flatten(None, record, &mut cells);
+
+        for (column_name, value) in cells {
+            let col_idx = match self
+                .column_names
+                .iter()
+                .position(|name| name == &column_name)
+            {
+                Some(col_idx) => col_idx,
+                None => {
+                    self.column_names.push(column_name);
+                    self.columns.push(vec![Value::Null; self.height]);
+                    self.columns.len() - 1
+                }
+            };
+            self.columns[col_idx].push(value);
+        }
+
+        self.height += 1;
+        for column in self.columns.iter_mut() {
+            column.resize(self.height, Value::Null);
+        }
+    }
+
+    fn flatten(
+        prefix: Option<&str>,
+        object: &Map<String, Value>,
+        cells: &mut Vec<(String, Value)>,
+    ) {
+        for (key, value) in object {
+            let column_name = match prefix {
+                Some(prefix) => format!("{prefix}.{key}"),
+                None => key.clone(),
+            };
+            match value {
+                Value::Object(nested) => Self::flatten(Some(&column_name), nested, cells),
+                _ => cells.push((column_name, value.clone())),
+            }
+        }
+    }
+
+    fn into_df(self) -> Result<DataFrame, PolarsError> {
+        let columns = self
+            .column_names
+            .iter()
+            .zip(self.columns.iter())
+            .map(|(name, values)| Self::to_column(name, values))
+            .collect();
+        DataFrame::new(self.height, columns)
+    }
+
+    /// Builds a column with the narrowest type, that fits all values: bool, i64, f64 or string.
+    fn to_column(name: &str, values: &[Value]) -> Column {
+        let present = || values.iter().filter(|value| !value.is_null());
+
+        if present().all(Value::is_boolean) {
+            Column::new(
+                name.into(),
+                values.iter().map(Value::as_bool).collect::<Vec<_>>(),
+            )
+        } else if present().all(Value::is_i64) {
+            Column::new(
+                name.into(),
+                values.iter().map(Value::as_i64).collect::<Vec<_>>(),
+            )
+        } else if present().all(Value::is_number) {
+            Column::new(
+                name.into(),
+                values.iter().map(Value::as_f64).collect::<Vec<_>>(),
+            )
+        } else {
+            let strings: Vec<Option<String>> = values
+                .iter()
+                .map(|value| match value {
+                    Value::Null => None,
+                    Value::String(string) => Some(string.clone()),
+                    other => Some(other.to_string()),
+                })
+                .collect();
+            Column::new(name.into(), strings)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pipeline;
+    use crate::config::context::Context;
+    use crate::config::table_context::{Identifier, SeriesContext};
+    use crate::config::traits::SeriesContextBuilding;
+    use crate::extract::DataSource;
+    use crate::extract::traits::Extractable;
+    use crate::load::FileSystemLoader;
+    use crate::transform::collecting::cdf_collector_broker::CdfCollectorBroker;
+    use crate::transform::phenopacket_builder::BuilderMetaData;
+    use crate::transform::transform_context::TransformContext;
+    use crate::transform::{PhenopacketBuilder, TransformerModule};
+    use pivotal::hgnc::MockHGNCClient;
+    use pivotal::hgvs::MockHGVSClient;
+    use polars::df;
+    use pretty_assertions::assert_eq;
+    use regex::Regex;
+    use rstest::rstest;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn test_data_path(file_name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src/test_suite/test_data")
+            .join(file_name)
+    }
+
+    fn patient_table_context() -> TableContext {
+        TableContext::new(
+            "patients",
+            vec![
+                SeriesContext::from_identifier("patient.id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("patient.sex")
+                    .with_data_context(Context::SubjectSex),
+            ],
+        )
+    }
+
+    fn expected_patient_df() -> DataFrame {
+        df![
+            "patient.id" => &["P001", "P002", "P003"],
+            "patient.sex" => &["MALE", "FEMALE", "MALE"],
+            "measurement.value" => &[Some(1.2), Some(3.0), None],
+            "measurement.unit" => &[Some("mg/dL"), Some("mg/dL"), None],
+            "hpo" => &[Some(r#"["HP:0001250"]"#), None, Some("[]")],
+            "has_consented" => &[Some(true), Some(false), None]
+        ]
+        .unwrap()
+    }
+
+    #[rstest]
+    #[case("patients.json")]
+    #[case("patients.ndjson")]
+    #[case("patients.json.gz")]
+    #[case("patients.ndjson.zst")]
+    fn test_extract_json(#[case] file_name: &str) {
+        let source = JsonDataSource::new(test_data_path(file_name), patient_table_context());
+
+        let extracted_df = source.extract_to_df().unwrap();
+
+        assert_eq!(extracted_df, expected_patient_df());
+    }
+
+    #[rstest]
+    fn test_extract_json_regex_identifier() {
+        let measurement_id = Identifier::Regex(Regex::new(r"^measurement\.").unwrap());
+        let table_context = TableContext::new(
+            "patients",
+            vec![
+                SeriesContext::from_identifier("patient.id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier(measurement_id.clone()),
+            ],
+        );
+
+        let mut cdfs = DataSource::Json(JsonDataSource::new(
+            test_data_path("patients.json"),
+            table_context,
+        ))
+        .extract()
+        .unwrap();
+        let cdf = cdfs.pop().unwrap();
+
+        let measurement_cols: Vec<&str> = cdf
+            .identify_columns(&measurement_id)
+            .iter()
+            .map(|col| col.name().as_str())
+            .collect();
+        assert_eq!(
+            measurement_cols,
+            vec!["measurement.value", "measurement.unit"]
+        );
+    }
+
+    #[rstest]
+    #[case("[1, 2]")]
+    #[case("{\"id\": \"P001\"}\nnot json")]
+    fn test_extract_invalid_json(#[case] content: &str) {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("invalid.json");
+        fs::write(&file_path, content).unwrap();
+
+        let result = JsonDataSource::new(file_path, patient_table_context()).extract_to_df();
+
+        assert!(matches!(result, Err(ExtractionError::InvalidJson { .. })));
+    }
+
+    #[rstest]
+    fn test_json_round_trip_through_pipeline() {
+        let temp_dir = TempDir::new().unwrap();
+        let out_dir = temp_dir.path().join("out");
+
+        let ctx = TransformContext::builder(
+            BuilderMetaData::new("json_cohort", "PhenoXtract Test Suite", "Someone"),
+            Arc::new(MockHGNCClient::default()),
+            Arc::new(MockHGVSClient::default()),
+        )
+        .build();
+        let tf_module = TransformerModule::new(
+            vec![],
+            CdfCollectorBroker::with_default_collectors(PhenopacketBuilder::new(ctx)),
+        );
+        let mut pipeline = Pipeline::new(
+            tf_module,
+            Box::new(FileSystemLoader::new(out_dir.clone(), true)),
+        );
+
+        let mut data_sources = vec![DataSource::Json(JsonDataSource::new(
+            test_data_path("patients.ndjson"),
+            patient_table_context(),
+        ))];
+        pipeline.run(data_sources.as_mut_slice()).unwrap();
+
+        let p002: Value = serde_json::from_str(
+            &fs::read_to_string(out_dir.join("json_cohort-P002.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(p002.pointer("/subject/id"), Some(&Value::from("P002")));
+        assert_eq!(p002.pointer("/subject/sex"), Some(&Value::from("FEMALE")));
+        assert_eq!(fs::read_dir(&out_dir).unwrap().count(), 3);
+    }
+}
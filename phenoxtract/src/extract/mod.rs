@@ -8,6 +8,10 @@ pub use data_source::DataSource;
 pub mod error;
 pub mod excel_data_source;
 pub use excel_data_source::ExcelDataSource;
+pub mod json_data_source;
+pub use json_data_source::JsonDataSource;
+pub mod phenopacket_data_source;
+pub use phenopacket_data_source::PhenopacketDataSource;
 
 pub mod column_filter;
 pub mod enums;
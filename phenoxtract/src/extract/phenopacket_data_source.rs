@@ -0,0 +1,430 @@
+use crate::config::context::{Context, TimeElementType};
+use crate::config::table_context::{SeriesContext, TableContext};
+use crate::config::traits::SeriesContextBuilding;
+use crate::extract::error::ExtractionError;
+use crate::extract::traits::HasSource;
+use crate::load::FileSystemLoader;
+use chrono::DateTime;
+use log::debug;
+use phenopackets::schema::v2::Phenopacket;
+use phenopackets::schema::v2::core::TimeElement;
+use phenopackets::schema::v2::core::time_element::Element;
+use polars::prelude::{Column, DataFrame};
+use prost_types::Timestamp;
+use std::fs;
+use std::path::{Path, PathBuf};
+use validator::Validate;
+
+pub const SUBJECT_ID_COL: &str = "subject_id";
+pub const SEX_COL: &str = "sex";
+pub const DATE_OF_BIRTH_COL: &str = "date_of_birth";
+pub const VITAL_STATUS_COL: &str = "vital_status";
+pub const PHENOTYPE_ID_COL: &str = "phenotype_id";
+pub const PHENOTYPE_LABEL_COL: &str = "phenotype_label";
+pub const PHENOTYPE_OBSERVED_COL: &str = "phenotype_observed";
+pub const PHENOTYPE_ONSET_COL: &str = "phenotype_onset";
+pub const DISEASE_ID_COL: &str = "disease_id";
+pub const DISEASE_LABEL_COL: &str = "disease_label";
+pub const DISEASE_ONSET_COL: &str = "disease_onset";
+
+/// Defines a directory of phenopacket JSON files (e.g. the output of the `FileSystemLoader`) as a data source.
+///
+/// The phenopackets are flattened into a single table with a stable schema, the columns are always
+/// the `*_COL` constants of this module in their declared order:
+/// - Every phenotypic feature and every disease gets its own row.
+/// - The subject fields are repeated on every row of a phenopacket.
+/// - A phenopacket without phenotypic features and diseases is a single row, only carrying the subject fields.
+/// - Onsets are only kept, if they are given as an age.
+///
+/// Archives are not supported and need to be unpacked first.
+#[derive(Debug, Clone, PartialEq, Validate)]
+pub struct PhenopacketDataSource {
+    /// The directory containing the phenopacket JSON files.
+    pub(crate) source: PathBuf,
+    /// The context describing how to interpret the resulting DataFrame.
+    pub(crate) context: TableContext,
+}
+
+impl PhenopacketDataSource {
+    pub fn new(source: PathBuf, context: TableContext) -> Self {
+        Self { source, context }
+    }
+
+    /// Creates a data source, which uses the `default_table_context` for the flattened schema.
+    pub fn from_dir(source: PathBuf) -> Self {
+        let context = Self::default_table_context(source.display().to_string());
+        Self::new(source, context)
+    }
+
+    /// Links every column of the flattened schema to its context, so the table can be run through a pipeline as is.
+    pub fn default_table_context(name: impl Into<String>) -> TableContext {
+        TableContext::new(
+            name,
+            vec![
+                SeriesContext::from_identifier(SUBJECT_ID_COL)
+                    .with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier(SEX_COL).with_data_context(Context::SubjectSex),
+                SeriesContext::from_identifier(DATE_OF_BIRTH_COL)
+                    .with_data_context(Context::DateOfBirth),
+                SeriesContext::from_identifier(VITAL_STATUS_COL)
+                    .with_data_context(Context::VitalStatus),
+                SeriesContext::from_identifier(PHENOTYPE_ID_COL)
+                    .with_data_context(Context::Hpo)
+                    .with_building_block_id("phenotype"),
+                SeriesContext::from_identifier(PHENOTYPE_OBSERVED_COL)
+                    .with_data_context(Context::ObservationStatus)
+                    .with_building_block_id("phenotype"),
+                SeriesContext::from_identifier(PHENOTYPE_ONSET_COL)
+                    .with_data_context(Context::Onset(TimeElementType::Age))
+                    .with_building_block_id("phenotype"),
+                SeriesContext::from_identifier(DISEASE_ID_COL)
+                    .with_data_context(Context::Disease)
+                    .with_building_block_id("disease"),
+                SeriesContext::from_identifier(DISEASE_ONSET_COL)
+                    .with_data_context(Context::Onset(TimeElementType::Age))
+                    .with_building_block_id("disease"),
+            ],
+        )
+    }
+
+    pub(crate) fn extract_to_df(&self) -> Result<DataFrame, ExtractionError> {
+        let phenopackets = self.read_phenopackets()?;
+        Ok(FlattenedPhenopackets::from_phenopackets(&phenopackets).into_df()?)
+    }
+
+    /// Reads all `.json` files of the source directory, sorted by their path.
+    fn read_phenopackets(&self) -> Result<Vec<Phenopacket>, ExtractionError> {
+        let mut paths = fs::read_dir(&self.source)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<PathBuf>, _>>()?;
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "json"));
+        paths.sort();
+
+        paths
+            .iter()
+            .map(|path| Self::read_phenopacket(path))
+            .collect()
+    }
+
+    fn read_phenopacket(path: &Path) -> Result<Phenopacket, ExtractionError> {
+        debug!("Reading phenopacket from {}", path.display());
+        let content = fs::read_to_string(path)?;
+        FileSystemLoader::phenopacket_from_json(&content).map_err(|err| {
+            ExtractionError::InvalidPhenopacket {
+                path: path.display().to_string(),
+                reason: err.to_string(),
+            }
+        })
+    }
+}
+
+impl HasSource for PhenopacketDataSource {
+    type Source = PathBuf;
+
+    fn source(&self) -> &Self::Source {
+        &self.source
+    }
+
+    fn with_source(mut self, source: &Self::Source) -> Self {
+        self.source = source.clone();
+        self
+    }
+}
+
+#[derive(Default)]
+struct FlattenedPhenopackets {
+    subject_ids: Vec<Option<String>>,
+    sexes: Vec<Option<String>>,
+    dates_of_birth: Vec<Option<String>>,
+    vital_statuses: Vec<Option<String>>,
+    phenotype_ids: Vec<Option<String>>,
+    phenotype_labels: Vec<Option<String>>,
+    phenotypes_observed: Vec<Option<bool>>,
+    phenotype_onsets: Vec<Option<String>>,
+    disease_ids: Vec<Option<String>>,
+    disease_labels: Vec<Option<String>>,
+    disease_onsets: Vec<Option<String>>,
+}
+
+impl FlattenedPhenopackets {
+    fn from_phenopackets(phenopackets: &[Phenopacket]) -> Self {
+        let mut flattened = Self::default();
+
+        for pp in phenopackets {
+            let subject = pp.subject.as_ref();
+            let subject_id = subject
+                .map(|subject| subject.id.clone())
+                .filter(|id| !id.is_empty())
+                .unwrap_or_else(|| pp.id.clone());
+            let sex = subject.map(|subject| subject.sex().as_str_name().to_string());
+            let date_of_birth = subject
+                .and_then(|subject| subject.date_of_birth.as_ref())
+                .and_then(format_timestamp);
+            let vital_status = subject
+                .and_then(|subject| subject.vital_status.as_ref())
+                .map(|vital_status| vital_status.status().as_str_name().to_string());
+
+            let push_subject = |flattened: &mut Self| {
+                flattened.subject_ids.push(Some(subject_id.clone()));
+                flattened.sexes.push(sex.clone());
+                flattened.dates_of_birth.push(date_of_birth.clone());
+                flattened.vital_statuses.push(vital_status.clone());
+            };
+
+            for feature in &pp.phenotypic_features {
+                push_subject(&mut flattened);
+                flattened.push_phenotype(
+                    feature.r#type.as_ref().map(|oc| oc.id.clone()),
+                    feature.r#type.as_ref().map(|oc| oc.label.clone()),
+                    Some(!feature.excluded),
+                    feature.onset.as_ref().and_then(age_of),
+                );
+                flattened.push_disease(None, None, None);
+            }
+
+            for disease in &pp.diseases {
+                push_subject(&mut flattened);
+                flattened.push_phenotype(None, None, None, None);
+                flattened.push_disease(
+                    disease.term.as_ref().map(|oc| oc.id.clone()),
+                    disease.term.as_ref().map(|oc| oc.label.clone()),
+                    disease.onset.as_ref().and_then(age_of),
+                );
+            }
+
+            if pp.phenotypic_features.is_empty() && pp.diseases.is_empty() {
+                push_subject(&mut flattened);
+                flattened.push_phenotype(None, None, None, None);
+                flattened.push_disease(None, None, None);
+            }
+        }
+
+        flattened
+    }
+
+    fn push_phenotype(
+        &mut self,
+        id: Option<String>,
+        label: Option<String>,
+        observed: Option<bool>,
+        onset: Option<String>,
+    ) {
+        self.phenotype_ids.push(id);
+        self.phenotype_labels.push(label);
+        self.phenotypes_observed.push(observed);
+        self.phenotype_onsets.push(onset);
+    }
+
+    fn push_disease(&mut self, id: Option<String>, label: Option<String>, onset: Option<String>) {
+        self.disease_ids.push(id);
+        self.disease_labels.push(label);
+        self.disease_onsets.push(onset);
+    }
+
+    fn into_df(self) -> Result<DataFrame, polars::prelude::PolarsError> {
+        let height = self.subject_ids.len();
+        DataFrame::new(
+            height,
+            vec![
+                Column::new(SUBJECT_ID_COL.into(), self.subject_ids),
+                Column::new(SEX_COL.into(), self.sexes),
+                Column::new(DATE_OF_BIRTH_COL.into(), self.dates_of_birth),
+                Column::new(VITAL_STATUS_COL.into(), self.vital_statuses),
+                Column::new(PHENOTYPE_ID_COL.into(), self.phenotype_ids),
+                Column::new(PHENOTYPE_LABEL_COL.into(), self.phenotype_labels),
+                Column::new(PHENOTYPE_OBSERVED_COL.into(), self.phenotypes_observed),
+                Column::new(PHENOTYPE_ONSET_COL.into(), self.phenotype_onsets),
+                Column::new(DISEASE_ID_COL.into(), self.disease_ids),
+                Column::new(DISEASE_LABEL_COL.into(), self.disease_labels),
+                Column::new(DISEASE_ONSET_COL.into(), self.disease_onsets),
+            ],
+        )
+    }
+}
+
+fn format_timestamp(ts: &Timestamp) -> Option<String> {
+    DateTime::from_timestamp(ts.seconds, ts.nanos as u32)
+        .map(|dt| dt.format("%Y-%m-%dT%H:%M:%S").to_string())
+}
+
+fn age_of(time_element: &TimeElement) -> Option<String> {
+    match &time_element.element {
+        Some(Element::Age(age)) => Some(age.iso8601duration.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load::FileSystemLoader;
+    use crate::load::traits::Loadable;
+    use phenopackets::schema::v2::core::{
+        Age, Disease, Individual, OntologyClass, PhenotypicFeature, Sex, VitalStatus,
+        vital_status::Status,
+    };
+    use rstest::rstest;
+    use tempfile::TempDir;
+
+    fn phenopacket_with_phenotypes() -> Phenopacket {
+        Phenopacket {
+            id: "cohort-P1".to_string(),
+            subject: Some(Individual {
+                id: "P1".to_string(),
+                sex: Sex::Female.into(),
+                date_of_birth: Some(Timestamp {
+                    seconds: 946684800,
+                    nanos: 0,
+                }),
+                vital_status: Some(VitalStatus {
+                    status: Status::Alive.into(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            phenotypic_features: vec![
+                PhenotypicFeature {
+                    r#type: Some(OntologyClass {
+                        id: "HP:0041249".to_string(),
+                        label: "Fractured nose".to_string(),
+                    }),
+                    onset: Some(TimeElement {
+                        element: Some(Element::Age(Age {
+                            iso8601duration: "P12Y".to_string(),
+                        })),
+                    }),
+                    ..Default::default()
+                },
+                PhenotypicFeature {
+                    r#type: Some(OntologyClass {
+                        id: "HP:0001250".to_string(),
+                        label: "Seizure".to_string(),
+                    }),
+                    excluded: true,
+                    ..Default::default()
+                },
+            ],
+            diseases: vec![Disease {
+                term: Some(OntologyClass {
+                    id: "MONDO:0000359".to_string(),
+                    label: "spondylocostal dysostosis".to_string(),
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn bare_phenopacket() -> Phenopacket {
+        Phenopacket {
+            id: "cohort-P2".to_string(),
+            subject: Some(Individual {
+                id: "P2".to_string(),
+                sex: Sex::Male.into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    fn test_extract_emitted_phenopackets() {
+        let temp_dir = TempDir::new().unwrap();
+        FileSystemLoader::new(temp_dir.path().to_path_buf(), true)
+            .load(&[phenopacket_with_phenotypes(), bare_phenopacket()])
+            .unwrap();
+
+        let df = PhenopacketDataSource::from_dir(temp_dir.path().to_path_buf())
+            .extract_to_df()
+            .unwrap();
+
+        assert_eq!(
+            df.get_column_names_str(),
+            vec![
+                SUBJECT_ID_COL,
+                SEX_COL,
+                DATE_OF_BIRTH_COL,
+                VITAL_STATUS_COL,
+                PHENOTYPE_ID_COL,
+                PHENOTYPE_LABEL_COL,
+                PHENOTYPE_OBSERVED_COL,
+                PHENOTYPE_ONSET_COL,
+                DISEASE_ID_COL,
+                DISEASE_LABEL_COL,
+                DISEASE_ONSET_COL,
+            ]
+        );
+        assert_eq!(df.height(), 4);
+
+        let str_col = |name: &str| -> Vec<Option<String>> {
+            df.column(name)
+                .unwrap()
+                .str()
+                .unwrap()
+                .into_iter()
+                .map(|s| s.map(str::to_string))
+                .collect()
+        };
+        let some = |s: &str| Some(s.to_string());
+
+        assert_eq!(
+            str_col(SUBJECT_ID_COL),
+            vec![some("P1"), some("P1"), some("P1"), some("P2")]
+        );
+        assert_eq!(
+            str_col(SEX_COL),
+            vec![some("FEMALE"), some("FEMALE"), some("FEMALE"), some("MALE")]
+        );
+        assert_eq!(str_col(DATE_OF_BIRTH_COL)[0], some("2000-01-01T00:00:00"));
+        assert_eq!(str_col(VITAL_STATUS_COL)[0], some("ALIVE"));
+        assert_eq!(
+            str_col(PHENOTYPE_ID_COL),
+            vec![some("HP:0041249"), some("HP:0001250"), None, None]
+        );
+        assert_eq!(
+            df.column(PHENOTYPE_OBSERVED_COL)
+                .unwrap()
+                .bool()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![Some(true), Some(false), None, None]
+        );
+        assert_eq!(
+            str_col(PHENOTYPE_ONSET_COL),
+            vec![some("P12Y"), None, None, None]
+        );
+        assert_eq!(
+            str_col(DISEASE_ID_COL),
+            vec![None, None, some("MONDO:0000359"), None]
+        );
+    }
+
+    #[rstest]
+    fn test_extract_ignores_non_json_files() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "not a phenopacket").unwrap();
+        FileSystemLoader::new(temp_dir.path().to_path_buf(), true)
+            .load(&[bare_phenopacket()])
+            .unwrap();
+
+        let df = PhenopacketDataSource::from_dir(temp_dir.path().to_path_buf())
+            .extract_to_df()
+            .unwrap();
+
+        assert_eq!(df.height(), 1);
+    }
+
+    #[rstest]
+    fn test_extract_invalid_phenopacket() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("broken.json"), "{\"id\": 1}").unwrap();
+
+        let result = PhenopacketDataSource::from_dir(temp_dir.path().to_path_buf()).extract_to_df();
+
+        assert!(matches!(
+            result,
+            Err(ExtractionError::InvalidPhenopacket { .. })
+        ));
+    }
+}
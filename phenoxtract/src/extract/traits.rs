@@ -1,9 +1,49 @@
 use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
 use crate::extract::error::ExtractionError;
+use validator::{Validate, ValidationErrors};
+
+/// The tables of a data source, which is extracted in chunks.
+pub type ExtractedChunks<'a> =
+    Box<dyn Iterator<Item = Result<ContextualizedDataFrame, ExtractionError>> + 'a>;
 
 /// A trait for types that can be extracted into one or more `ContextualizedDataFrame`s.
-pub trait Extractable: std::fmt::Debug {
+///
+/// Besides the built-in [`DataSource`](crate::extract::DataSource)s, library users can implement it for their own
+/// sources (e.g. a clinical database driver) and hand them to the [`Pipeline`](crate::Pipeline) as
+/// `Box<dyn Extractable>`, also mixed with built-in sources. The trait is object-safe.
+///
+/// `validate` is called before anything is extracted. The extracted tables are validated by
+/// [`ContextualizedDataFrame::new`], so they have to satisfy its contract:
+/// - Exactly one column has the data context `SubjectId`, and it has no nulls.
+/// - Every series context identifies at least one column, and no column is identified by more than one.
+///
+/// The data frames should be in the shape of the built-in sources: one row per patient, or per observation of a
+/// patient. The values are trimmed and cast by the pipeline, so they can be handed over as strings.
+pub trait Extractable: Validate + std::fmt::Debug {
     fn extract(&self) -> Result<Vec<ContextualizedDataFrame>, ExtractionError>;
+
+    /// Extracts the data in chunks of rows, so that only one chunk has to be in memory at a time.
+    ///
+    /// Returns `None`, if the data source is extracted at once.
+    fn extract_chunks(&self) -> Option<ExtractedChunks<'_>> {
+        None
+    }
+}
+
+impl Extractable for Box<dyn Extractable> {
+    fn extract(&self) -> Result<Vec<ContextualizedDataFrame>, ExtractionError> {
+        (**self).extract()
+    }
+
+    fn extract_chunks(&self) -> Option<ExtractedChunks<'_>> {
+        (**self).extract_chunks()
+    }
+}
+
+impl Validate for Box<dyn Extractable> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        (**self).validate()
+    }
 }
 
 pub trait HasSource {
@@ -1,7 +1,143 @@
+use crate::config::context::Context;
+use crate::config::table_context::{Identifier, TableContext};
+use crate::config::traits::SeriesContextBuilding;
+use flate2::read::MultiGzDecoder;
+use polars::prelude::{DataFrame, DataType, PolarsError, StringChunked};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek};
+use std::path::Path;
+
+const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC_BYTES: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
 pub(crate) fn generate_default_column_names(column_count: i64) -> Vec<String> {
     (0..column_count).map(|index| format!("{index}")).collect()
 }
 
+/// Reads and decompresses a gzip or zstd compressed file, e.g. a `cohort.csv.gz` export.
+///
+/// The compression is detected by the magic bytes at the start of the file, so the file extension doesn't matter.
+/// Returns `None` for uncompressed files, so that they can still be read from disk directly.
+pub(crate) fn read_decompressed(path: &Path) -> Result<Option<Vec<u8>>, std::io::Error> {
+    let mut file = File::open(path)?;
+    let magic_bytes = read_magic_bytes(&mut file)?;
+
+    let mut decompressed = vec![];
+    if magic_bytes.starts_with(&GZIP_MAGIC_BYTES) {
+        MultiGzDecoder::new(BufReader::new(file)).read_to_end(&mut decompressed)?;
+    } else if magic_bytes.starts_with(&ZSTD_MAGIC_BYTES) {
+        zstd::stream::read::Decoder::new(file)?.read_to_end(&mut decompressed)?;
+    } else {
+        return Ok(None);
+    }
+    Ok(Some(decompressed))
+}
+
+/// Opens a file, which might be gzip or zstd compressed, for reading.
+///
+/// Unlike [`read_decompressed`], the file is decompressed while it is read, so it never has to be in memory as a whole.
+pub(crate) fn open_decompressed(path: &Path) -> Result<Box<dyn BufRead>, std::io::Error> {
+    let mut file = File::open(path)?;
+    let magic_bytes = read_magic_bytes(&mut file)?;
+
+    let reader: Box<dyn BufRead> = if magic_bytes.starts_with(&GZIP_MAGIC_BYTES) {
+        Box::new(BufReader::new(MultiGzDecoder::new(BufReader::new(file))))
+    } else if magic_bytes.starts_with(&ZSTD_MAGIC_BYTES) {
+        Box::new(BufReader::new(zstd::stream::read::Decoder::new(file)?))
+    } else {
+        Box::new(BufReader::new(file))
+    };
+    Ok(reader)
+}
+
+/// Reads the first bytes of a file, which identify its compression, and rewinds it.
+fn read_magic_bytes(file: &mut File) -> Result<Vec<u8>, std::io::Error> {
+    let mut magic_bytes = Vec::with_capacity(ZSTD_MAGIC_BYTES.len());
+    file.by_ref()
+        .take(ZSTD_MAGIC_BYTES.len() as u64)
+        .read_to_end(&mut magic_bytes)?;
+    file.rewind()?;
+    Ok(magic_bytes)
+}
+
+/// Concatenates the columns of the subject id series context into a single subject id column,
+/// if the table context has a subject id separator.
+///
+/// The columns are joined in the order of a `Multi` identifier, a row with a null in any of them gets a null subject id.
+/// The merged column replaces the original columns and is named after them, e.g. "site_local_id" for the separator `_`.
+pub(crate) fn merge_subject_id_columns(
+    context: &TableContext,
+    mut data: DataFrame,
+) -> Result<(TableContext, DataFrame), PolarsError> {
+    let Some(separator) = context.subject_id_separator() else {
+        return Ok((context.clone(), data));
+    };
+    let Some(sc_idx) = context.context().iter().position(|sc| {
+        sc.get_header_context() == &Context::None && sc.get_data_context() == &Context::SubjectId
+    }) else {
+        return Ok((context.clone(), data));
+    };
+
+    let column_names: Vec<String> = {
+        let data_column_names: Vec<&str> = data
+            .get_column_names()
+            .into_iter()
+            .map(|name| name.as_str())
+            .collect();
+        match context.context()[sc_idx].get_identifier() {
+            Identifier::Multi(ids) => ids
+                .iter()
+                .filter(|id| data_column_names.contains(&id.as_str()))
+                .cloned()
+                .collect(),
+            identifier => identifier
+                .identify(&data_column_names)
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+        }
+    };
+    if column_names.len() < 2 {
+        return Ok((context.clone(), data));
+    }
+
+    let columns = column_names
+        .iter()
+        .map(|name| Ok(data.column(name)?.cast(&DataType::String)?.str()?.clone()))
+        .collect::<Result<Vec<StringChunked>, PolarsError>>()?;
+    let merged_column: StringChunked = (0..data.height())
+        .map(|row_idx| {
+            columns
+                .iter()
+                .map(|col| col.get(row_idx))
+                .collect::<Option<Vec<&str>>>()
+                .map(|parts| parts.join(separator))
+        })
+        .collect();
+
+    let merged_name = column_names.join(separator);
+    let position = column_names
+        .iter()
+        .filter_map(|name| data.get_column_index(name))
+        .min()
+        .unwrap_or_default();
+    for name in column_names.iter() {
+        data.drop_in_place(name)?;
+    }
+    data.insert_column(
+        position,
+        merged_column.with_name(merged_name.as_str().into()),
+    )?;
+
+    let mut context = context.clone();
+    let merged_sc = context.context()[sc_idx]
+        .clone()
+        .with_identifier(Identifier::Single(merged_name));
+    context.context_mut()[sc_idx] = merged_sc;
+
+    Ok((context, data))
+}
+
 pub(crate) fn fmt_vec<T: std::fmt::Display>(
     f: &mut std::fmt::Formatter<'_>,
     name: &str,
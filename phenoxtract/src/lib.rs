@@ -13,4 +13,4 @@ mod test_suite;
 pub mod phenoxtract;
 pub mod transform;
 pub(crate) mod utils;
-mod validation;
+pub mod validation;
@@ -0,0 +1,609 @@
+use crate::load::error::LoadError;
+use crate::load::traits::Loadable;
+use chrono::DateTime;
+use log::debug;
+use phenopackets::schema::v2::Phenopacket;
+use phenopackets::schema::v2::core::measurement::MeasurementValue;
+use phenopackets::schema::v2::core::time_element::Element;
+use phenopackets::schema::v2::core::value::Value as PhenopacketValue;
+use phenopackets::schema::v2::core::vital_status::Status;
+use phenopackets::schema::v2::core::{
+    Disease, Individual, Measurement, OntologyClass, PhenotypicFeature, Sex, TimeElement,
+};
+use serde_json::{Value, json};
+use std::fs;
+use std::fs::File;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// The canonical FHIR code system URIs of the terminologies, which aren't OBO ontologies.
+const FHIR_CODE_SYSTEMS: &[(&str, &str)] = &[
+    ("LOINC", "http://loinc.org"),
+    ("OMIM", "https://omim.org"),
+    ("ORPHA", "http://www.orpha.net"),
+];
+
+const OBO_PURL: &str = "http://purl.obolibrary.org/obo/";
+
+/// A loader that maps phenopackets onto minimal FHIR R4 bundles and saves them as JSON files.
+///
+/// Every phenopacket becomes a `collection` bundle with
+/// - one `Patient` for the subject,
+/// - one `Condition` per disease,
+/// - one `Observation` per phenotypic feature,
+/// - one `Observation` per measurement.
+///
+/// Codings carry the local id of the term (e.g. `0001250` for `HP:0001250`) and the canonical code system URI
+/// of its terminology, e.g. `http://purl.obolibrary.org/obo/hp.owl` or `http://loinc.org`. Observations without
+/// a phenotype or assay are coded as unknown, because FHIR requires `Observation.code`.
+///
+/// The following phenopacket fields are currently NOT mapped:
+/// interpretations, biosamples, medical actions, files, phenotypic feature modifiers/severity/evidence,
+/// disease stages/TNM findings/laterality and reference ranges of measurements.
+#[derive(Debug)]
+pub struct FhirLoader {
+    /// The path to the output directory where the bundles will be saved.
+    out_path: PathBuf,
+    /// If true will create the full out path
+    create_dir: bool,
+}
+
+impl FhirLoader {
+    pub fn new(out_path: PathBuf, create_dir: bool) -> Self {
+        Self {
+            out_path,
+            create_dir,
+        }
+    }
+
+    /// Converts a phenopacket into a FHIR `Bundle` of type `collection`.
+    pub fn phenopacket_to_bundle(phenopacket: &Phenopacket) -> Value {
+        let patient_id = Self::fhir_id(
+            phenopacket
+                .subject
+                .as_ref()
+                .map(|subject| subject.id.as_str())
+                .unwrap_or(&phenopacket.id),
+        );
+        let patient_ref = Self::full_url(&phenopacket.id, "Patient", &patient_id);
+
+        let mut resources = vec![Self::patient(phenopacket.subject.as_ref(), &patient_id)];
+
+        for (idx, disease) in phenopacket.diseases.iter().enumerate() {
+            resources.push(Self::condition(
+                phenopacket,
+                disease,
+                &Self::fhir_id(&format!("{}-condition-{}", phenopacket.id, idx + 1)),
+                &patient_ref,
+            ));
+        }
+
+        for (idx, feature) in phenopacket.phenotypic_features.iter().enumerate() {
+            resources.push(Self::phenotype_observation(
+                phenopacket,
+                feature,
+                &Self::fhir_id(&format!("{}-phenotype-{}", phenopacket.id, idx + 1)),
+                &patient_ref,
+            ));
+        }
+
+        for (idx, measurement) in phenopacket.measurements.iter().enumerate() {
+            resources.push(Self::measurement_observation(
+                phenopacket,
+                measurement,
+                &Self::fhir_id(&format!("{}-measurement-{}", phenopacket.id, idx + 1)),
+                &patient_ref,
+            ));
+        }
+
+        let entries: Vec<Value> = resources
+            .into_iter()
+            .map(|resource| {
+                json!({
+                    "fullUrl": Self::full_url(
+                        &phenopacket.id,
+                        resource["resourceType"].as_str().unwrap_or_default(),
+                        resource["id"].as_str().unwrap_or_default()
+                    ),
+                    "resource": resource,
+                })
+            })
+            .collect();
+
+        json!({
+            "resourceType": "Bundle",
+            "id": Self::fhir_id(&phenopacket.id),
+            "type": "collection",
+            "entry": entries,
+        })
+    }
+
+    /// Turns an arbitrary identifier into a valid FHIR id, i.e. one matching `[A-Za-z0-9\-\.]{1,64}`.
+    ///
+    /// Invalid characters are replaced by `-`. Identifiers, which are empty or longer than 64 characters, are replaced
+    /// by a UUID derived from them, so that the same identifier always results in the same id.
+    fn fhir_id(raw: &str) -> String {
+        let id: String = raw
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+
+        if id.is_empty() || id.len() > 64 {
+            Uuid::new_v5(&Uuid::NAMESPACE_OID, raw.as_bytes())
+                .simple()
+                .to_string()
+        } else {
+            id
+        }
+    }
+
+    /// The `urn:uuid:` full URL of a resource, derived from the phenopacket, the resource type and the resource id.
+    fn full_url(phenopacket_id: &str, resource_type: &str, id: &str) -> String {
+        let name = format!("{phenopacket_id}/{resource_type}/{id}");
+        format!("urn:uuid:{}", Uuid::new_v5(&Uuid::NAMESPACE_OID, name.as_bytes()))
+    }
+
+    fn patient(subject: Option<&Individual>, patient_id: &str) -> Value {
+        let mut patient = json!({
+            "resourceType": "Patient",
+            "id": patient_id,
+        });
+
+        if let Some(subject) = subject {
+            let gender = match subject.sex() {
+                Sex::Male => "male",
+                Sex::Female => "female",
+                Sex::OtherSex => "other",
+                Sex::UnknownSex => "unknown",
+            };
+            patient["gender"] = json!(gender);
+
+            if let Some(dob) = &subject.date_of_birth
+                && let Some(dob) = DateTime::from_timestamp(dob.seconds, dob.nanos as u32)
+            {
+                patient["birthDate"] = json!(dob.format("%Y-%m-%d").to_string());
+            }
+
+            if let Some(vital_status) = &subject.vital_status {
+                match vital_status.status() {
+                    Status::Deceased => patient["deceasedBoolean"] = json!(true),
+                    Status::Alive => patient["deceasedBoolean"] = json!(false),
+                    Status::UnknownStatus => {}
+                }
+            }
+        }
+
+        patient
+    }
+
+    fn condition(
+        phenopacket: &Phenopacket,
+        disease: &Disease,
+        id: &str,
+        patient_ref: &str,
+    ) -> Value {
+        let verification_status = if disease.excluded {
+            "refuted"
+        } else {
+            "confirmed"
+        };
+
+        let mut condition = json!({
+            "resourceType": "Condition",
+            "id": id,
+            "subject": { "reference": patient_ref },
+            "verificationStatus": {
+                "coding": [{
+                    "system": "http://terminology.hl7.org/CodeSystem/condition-ver-status",
+                    "code": verification_status,
+                }]
+            },
+        });
+
+        if let Some(term) = &disease.term {
+            condition["code"] = Self::codeable_concept(phenopacket, term);
+        }
+        if let Some(onset) = disease.onset.as_ref().and_then(Self::time_element_string) {
+            condition["onsetString"] = json!(onset);
+        }
+        if let Some(resolution) = disease
+            .resolution
+            .as_ref()
+            .and_then(Self::time_element_string)
+        {
+            condition["abatementString"] = json!(resolution);
+        }
+        if let Some(primary_site) = &disease.primary_site {
+            condition["bodySite"] = json!([Self::codeable_concept(phenopacket, primary_site)]);
+        }
+
+        condition
+    }
+
+    fn phenotype_observation(
+        phenopacket: &Phenopacket,
+        feature: &PhenotypicFeature,
+        id: &str,
+        patient_ref: &str,
+    ) -> Value {
+        let mut observation = json!({
+            "resourceType": "Observation",
+            "id": id,
+            "status": "final",
+            "code": Self::observation_code(phenopacket, feature.r#type.as_ref()),
+            "subject": { "reference": patient_ref },
+            "valueBoolean": !feature.excluded,
+        });
+
+        if !feature.description.is_empty() {
+            observation["note"] = json!([{ "text": feature.description }]);
+        }
+        if let Some(onset) = feature.onset.as_ref().and_then(Self::time_element_string) {
+            observation["effectiveString"] = json!(onset);
+        }
+
+        observation
+    }
+
+    fn measurement_observation(
+        phenopacket: &Phenopacket,
+        measurement: &Measurement,
+        id: &str,
+        patient_ref: &str,
+    ) -> Value {
+        let mut observation = json!({
+            "resourceType": "Observation",
+            "id": id,
+            "status": "final",
+            "code": Self::observation_code(phenopacket, measurement.assay.as_ref()),
+            "subject": { "reference": patient_ref },
+        });
+
+        if let Some(MeasurementValue::Value(value)) = &measurement.measurement_value {
+            match &value.value {
+                Some(PhenopacketValue::Quantity(quantity)) => {
+                    let mut fhir_quantity = json!({ "value": quantity.value });
+                    if let Some(unit) = &quantity.unit {
+                        fhir_quantity["unit"] = json!(unit.label);
+                        fhir_quantity["code"] = json!(Self::local_id(&unit.id));
+                        if let Some(system) = Self::coding_system(phenopacket, &unit.id) {
+                            fhir_quantity["system"] = json!(system);
+                        }
+                    }
+                    observation["valueQuantity"] = fhir_quantity;
+                }
+                Some(PhenopacketValue::OntologyClass(oc)) => {
+                    observation["valueCodeableConcept"] = Self::codeable_concept(phenopacket, oc);
+                }
+                None => {}
+            }
+        }
+
+        if let Some(time_observed) = measurement
+            .time_observed
+            .as_ref()
+            .and_then(Self::time_element_string)
+        {
+            observation["effectiveString"] = json!(time_observed);
+        }
+
+        observation
+    }
+
+    /// `Observation.code` is required, so observations without a term are coded as unknown.
+    fn observation_code(phenopacket: &Phenopacket, term: Option<&OntologyClass>) -> Value {
+        match term {
+            Some(term) => Self::codeable_concept(phenopacket, term),
+            None => json!({
+                "coding": [{
+                    "system": "http://terminology.hl7.org/CodeSystem/data-absent-reason",
+                    "code": "unknown",
+                    "display": "Unknown",
+                }],
+                "text": "Unknown",
+            }),
+        }
+    }
+
+    fn codeable_concept(phenopacket: &Phenopacket, term: &OntologyClass) -> Value {
+        let mut coding = json!({
+            "code": Self::local_id(&term.id),
+            "display": term.label,
+        });
+
+        if let Some(system) = Self::coding_system(phenopacket, &term.id) {
+            coding["system"] = json!(system);
+        }
+
+        json!({
+            "coding": [coding],
+            "text": term.label,
+        })
+    }
+
+    /// The id of a CURIE without its prefix, e.g. `0001250` for `HP:0001250`.
+    fn local_id(curie: &str) -> &str {
+        curie.split_once(':').map_or(curie, |(_, id)| id)
+    }
+
+    /// The canonical code system URI of the prefix of the CURIE. Terminologies, which aren't known, use the url of
+    /// the meta data resource, whose namespace prefix matches the prefix. OBO ontologies use their PURL,
+    /// e.g. `http://purl.obolibrary.org/obo/hp.owl`.
+    fn coding_system(phenopacket: &Phenopacket, curie: &str) -> Option<String> {
+        let (prefix, _) = curie.split_once(':')?;
+        if let Some((_, system)) = FHIR_CODE_SYSTEMS
+            .iter()
+            .find(|(known_prefix, _)| known_prefix.eq_ignore_ascii_case(prefix))
+        {
+            return Some(system.to_string());
+        }
+
+        let resource = phenopacket
+            .meta_data
+            .as_ref()?
+            .resources
+            .iter()
+            .find(|resource| resource.namespace_prefix.eq_ignore_ascii_case(prefix))?;
+        if resource.url.starts_with(OBO_PURL) {
+            Some(format!("{OBO_PURL}{}.owl", prefix.to_lowercase()))
+        } else {
+            Some(resource.url.clone())
+        }
+    }
+
+    fn time_element_string(time_element: &TimeElement) -> Option<String> {
+        match time_element.element.as_ref()? {
+            Element::Age(age) => Some(age.iso8601duration.clone()),
+            Element::Timestamp(ts) => DateTime::from_timestamp(ts.seconds, ts.nanos as u32)
+                .map(|dt| dt.format("%Y-%m-%dT%H:%M:%SZ").to_string()),
+            Element::OntologyClass(oc) => Some(oc.label.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl Loadable for FhirLoader {
+    /// Saves every phenopacket as a FHIR bundle into the directory specified in `out_path`.
+    ///
+    /// The filename is derived from the phenopacket's ID, followed by the `.fhir.json` extension
+    /// (e.g., `PMIT-00001.fhir.json`).
+    fn load(&self, phenopackets: &[Phenopacket]) -> Result<(), LoadError> {
+        if !phenopackets.is_empty() && self.create_dir {
+            fs::create_dir_all(self.out_path.as_path()).map_err(|err| LoadError::NoStorage {
+                reason: err.to_string(),
+            })?;
+        }
+
+        for pp in phenopackets.iter() {
+            let file = File::create(self.out_path.join(format!("{}.fhir.json", pp.id))).map_err(
+                |err| LoadError::CantStore {
+                    pp_id: pp.id.clone(),
+                    reason: err.to_string(),
+                },
+            )?;
+
+            debug!("Storing FHIR bundle to: {:?}", file);
+            serde_json::to_writer_pretty(file, &Self::phenopacket_to_bundle(pp)).map_err(
+                |err| LoadError::CantStore {
+                    pp_id: pp.id.clone(),
+                    reason: err.to_string(),
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_suite::cdf_generation::default_patient_id;
+    use crate::test_suite::phenopacket_component_generation::{
+        default_disease_oc, default_phenopacket_id, default_phenotype_oc,
+    };
+    use crate::test_suite::resource_references::{
+        hp_meta_data_resource, loinc_meta_data_resource, mondo_meta_data_resource,
+    };
+    use phenopackets::schema::v2::core::MetaData;
+    use pretty_assertions::assert_eq;
+    use rstest::*;
+    use tempfile::tempdir;
+
+    #[fixture]
+    fn phenopacket() -> Phenopacket {
+        Phenopacket {
+            id: default_phenopacket_id(),
+            subject: Some(Individual {
+                id: default_patient_id(),
+                sex: Sex::Female.into(),
+                ..Default::default()
+            }),
+            phenotypic_features: vec![PhenotypicFeature {
+                r#type: Some(default_phenotype_oc()),
+                ..Default::default()
+            }],
+            diseases: vec![Disease {
+                term: Some(default_disease_oc()),
+                ..Default::default()
+            }],
+            meta_data: Some(MetaData {
+                resources: vec![mondo_meta_data_resource(), hp_meta_data_resource()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn resources_of_type<'a>(bundle: &'a Value, resource_type: &str) -> Vec<&'a Value> {
+        bundle["entry"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| &entry["resource"])
+            .filter(|resource| resource["resourceType"] == resource_type)
+            .collect()
+    }
+
+    #[rstest]
+    fn test_phenopacket_to_bundle(phenopacket: Phenopacket) {
+        let bundle = FhirLoader::phenopacket_to_bundle(&phenopacket);
+
+        assert_eq!(bundle["resourceType"], "Bundle");
+        assert_eq!(bundle["type"], "collection");
+        assert_eq!(bundle["entry"].as_array().unwrap().len(), 3);
+
+        let patients = resources_of_type(&bundle, "Patient");
+        assert_eq!(patients.len(), 1);
+        assert_eq!(patients[0]["id"], default_patient_id());
+        assert_eq!(patients[0]["gender"], "female");
+
+        let patient_entry = bundle["entry"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|entry| entry["resource"]["resourceType"] == "Patient")
+            .unwrap();
+        let patient_ref = json!({ "reference": patient_entry["fullUrl"] });
+
+        let conditions = resources_of_type(&bundle, "Condition");
+        assert_eq!(conditions.len(), 1);
+        let disease = default_disease_oc();
+        assert_eq!(conditions[0]["subject"], patient_ref);
+        assert_eq!(
+            conditions[0]["code"]["coding"][0]["code"],
+            disease.id.trim_start_matches("MONDO:")
+        );
+        assert_eq!(conditions[0]["code"]["coding"][0]["display"], disease.label);
+        assert_eq!(
+            conditions[0]["code"]["coding"][0]["system"],
+            "http://purl.obolibrary.org/obo/mondo.owl"
+        );
+        assert_eq!(
+            conditions[0]["verificationStatus"]["coding"][0]["code"],
+            "confirmed"
+        );
+
+        let observations = resources_of_type(&bundle, "Observation");
+        assert_eq!(observations.len(), 1);
+        let phenotype = default_phenotype_oc();
+        assert_eq!(observations[0]["subject"], patient_ref);
+        assert_eq!(
+            observations[0]["code"]["coding"][0]["code"],
+            phenotype.id.trim_start_matches("HP:")
+        );
+        assert_eq!(
+            observations[0]["code"]["coding"][0]["system"],
+            "http://purl.obolibrary.org/obo/hp.owl"
+        );
+        assert_eq!(observations[0]["valueBoolean"], true);
+    }
+
+    #[rstest]
+    fn test_measurement_observation(mut phenopacket: Phenopacket) {
+        phenopacket.measurements = vec![
+            Measurement {
+                assay: Some(OntologyClass {
+                    id: "LOINC:718-7".to_string(),
+                    label: "Hemoglobin [Mass/volume] in Blood".to_string(),
+                }),
+                ..Default::default()
+            },
+            Measurement::default(),
+        ];
+        phenopacket
+            .meta_data
+            .as_mut()
+            .unwrap()
+            .resources
+            .push(loinc_meta_data_resource());
+
+        let bundle = FhirLoader::phenopacket_to_bundle(&phenopacket);
+
+        let observations = resources_of_type(&bundle, "Observation");
+        assert_eq!(observations.len(), 3);
+        let assay_coding = &observations[1]["code"]["coding"][0];
+        assert_eq!(assay_coding["code"], "718-7");
+        assert_eq!(assay_coding["system"], "http://loinc.org");
+        assert_eq!(observations[2]["code"]["coding"][0]["code"], "unknown");
+        assert_eq!(observations[2]["code"]["text"], "Unknown");
+    }
+
+    #[rstest]
+    #[case("HP:0001250", "0001250")]
+    #[case("LOINC:718-7", "718-7")]
+    #[case("unprefixed", "unprefixed")]
+    fn test_local_id(#[case] curie: &str, #[case] expected: &str) {
+        assert_eq!(FhirLoader::local_id(curie), expected);
+    }
+
+    #[rstest]
+    fn test_full_urls_are_uuids(phenopacket: Phenopacket) {
+        let bundle = FhirLoader::phenopacket_to_bundle(&phenopacket);
+
+        let full_urls: Vec<&str> = bundle["entry"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["fullUrl"].as_str().unwrap())
+            .collect();
+        assert_eq!(full_urls.len(), 3);
+        for full_url in &full_urls {
+            let uuid = full_url.strip_prefix("urn:uuid:").unwrap();
+            assert!(Uuid::parse_str(uuid).is_ok());
+        }
+        assert_eq!(
+            full_urls,
+            FhirLoader::phenopacket_to_bundle(&phenopacket)["entry"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|entry| entry["fullUrl"].as_str().unwrap())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case("P001", "P001")]
+    #[case("cohort-1.P001", "cohort-1.P001")]
+    #[case("cohort_1/P 001", "cohort-1-P-001")]
+    fn test_fhir_id(#[case] raw: &str, #[case] expected: &str) {
+        assert_eq!(FhirLoader::fhir_id(raw), expected);
+    }
+
+    #[rstest]
+    #[case(String::new())]
+    #[case("P".repeat(65))]
+    fn test_fhir_id_hashed(#[case] raw: String) {
+        let id = FhirLoader::fhir_id(&raw);
+
+        assert_eq!(id.len(), 32);
+        assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(id, FhirLoader::fhir_id(&raw));
+    }
+
+    #[rstest]
+    fn test_fhir_loader_writes_bundles(phenopacket: Phenopacket) {
+        let tmp_dir = tempdir().unwrap();
+        let loader = FhirLoader::new(tmp_dir.path().join("fhir"), true);
+
+        loader
+            .load(std::slice::from_ref(&phenopacket))
+            .expect("load should succeed");
+
+        let output_path = tmp_dir
+            .path()
+            .join("fhir")
+            .join(format!("{}.fhir.json", default_phenopacket_id()));
+        let bundle: Value =
+            serde_json::from_str(&fs::read_to_string(output_path).unwrap()).unwrap();
+
+        assert_eq!(bundle["id"], FhirLoader::fhir_id(&default_phenopacket_id()));
+    }
+}
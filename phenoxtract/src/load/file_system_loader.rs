@@ -1,14 +1,47 @@
 use crate::load::error::LoadError;
 use crate::load::traits::Loadable;
-use log::debug;
+use log::{debug, warn};
 use phenopackets::schema::v2::Phenopacket;
-use serde::Deserialize;
+use prost::Message;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashSet;
 use std::fs;
-use std::fs::File;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
 use std::path::PathBuf;
 
-/// A loader that saves phenopackets as individual JSON files to the local file system.
+/// The name of the file, which holds all phenopackets in the `protobuf_stream` output format.
+pub const PROTOBUF_STREAM_FILE_NAME: &str = "phenopackets.pb";
+
+/// The name of the checkpoint index of resumable runs. It lists the id of every written phenopacket, one per line.
+pub const CHECKPOINT_INDEX_FILE_NAME: &str = "checkpoint_index.txt";
+
+/// The file format, in which the phenopackets are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    #[default]
+    Json,
+    /// Holds the same fields in the same order as the JSON output.
+    Yaml,
+    /// The binary protobuf encoding, one file per phenopacket.
+    Protobuf,
+    /// The binary protobuf encoding of all phenopackets, each prefixed by its length, in a single file.
+    ProtobufStream,
+}
+
+impl OutputFormat {
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Yaml => "yaml",
+            OutputFormat::Protobuf | OutputFormat::ProtobufStream => "pb",
+        }
+    }
+}
+
+/// A loader that saves phenopackets as individual files to the local file system.
 ///
 /// This struct specifies an output directory where each `Phenopacket` will be
 /// serialized and saved.
@@ -18,6 +51,12 @@ pub struct FileSystemLoader {
     out_path: PathBuf,
     /// If true will create the full out path
     create_dir: bool,
+    #[serde(default)]
+    output_format: OutputFormat,
+    /// If true, every phenopacket is written as soon as it is built, and a restarted run skips the patients,
+    /// whose phenopacket was already written.
+    #[serde(default)]
+    resume: bool,
 }
 
 impl FileSystemLoader {
@@ -25,9 +64,101 @@ impl FileSystemLoader {
         Self {
             out_path,
             create_dir,
+            output_format: OutputFormat::default(),
+            resume: false,
+        }
+    }
+
+    /// Makes runs resumable. Not supported by the `protobuf_stream` format, which writes all phenopackets at once.
+    pub fn with_resume(mut self, resume: bool) -> Self {
+        self.resume = resume;
+        self
+    }
+
+    fn is_resumable(&self) -> bool {
+        if self.resume && self.output_format == OutputFormat::ProtobufStream {
+            warn!("Runs with the protobuf_stream output format can't be resumed.");
         }
+        self.resume && self.output_format != OutputFormat::ProtobufStream
     }
 
+    fn phenopacket_path(&self, pp_id: &str) -> PathBuf {
+        self.out_path
+            .join(format!("{}.{}", pp_id, self.output_format.file_extension()))
+    }
+
+    /// Appends the id of a written phenopacket to the checkpoint index.
+    fn record_checkpoint(&self, pp_id: &str) -> Result<(), LoadError> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.out_path.join(CHECKPOINT_INDEX_FILE_NAME))
+            .and_then(|mut index| writeln!(index, "{pp_id}"))
+            .map_err(|err| LoadError::CantStore {
+                pp_id: pp_id.to_string(),
+                reason: format!("could not update the checkpoint index: {err}"),
+            })
+    }
+
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
+    fn write_protobuf(mut file: File, pp: &Phenopacket) -> Result<(), LoadError> {
+        file.write_all(&pp.encode_to_vec())
+            .map_err(|err| LoadError::CantStore {
+                pp_id: pp.id.clone(),
+                reason: err.to_string(),
+            })
+    }
+
+    /// Writes a phenopacket as pretty-printed JSON or as YAML, depending on the output format.
+    fn write_serialized(&self, file: File, pp: &Phenopacket) -> Result<(), LoadError> {
+        let mut pp_value = serde_json::to_value(pp).map_err(|_| LoadError::ConversionError {
+            pp_id: pp.id.clone(),
+            format: "json".to_string(),
+        })?;
+
+        Self::remove_default_survival_time(&mut pp_value)?;
+        match self.output_format {
+            OutputFormat::Yaml => {
+                serde_norway::to_writer(file, &pp_value).map_err(|err| err.to_string())
+            }
+            _ => serde_json::to_writer_pretty(file, &pp_value).map_err(|err| err.to_string()),
+        }
+        .map_err(|reason| LoadError::CantStore {
+            pp_id: pp.id.clone(),
+            reason,
+        })
+    }
+
+    /// Writes all phenopackets length-delimited into a single file, in which they can be read back one after another.
+    fn write_protobuf_stream(&self, phenopackets: &[Phenopacket]) -> Result<(), LoadError> {
+        if phenopackets.is_empty() {
+            return Ok(());
+        }
+
+        let mut buffer = Vec::new();
+        for pp in phenopackets.iter() {
+            pp.encode_length_delimited(&mut buffer)
+                .map_err(|_| LoadError::ConversionError {
+                    pp_id: pp.id.clone(),
+                    format: "protobuf".to_string(),
+                })?;
+        }
+
+        let stream_path = self.out_path.join(PROTOBUF_STREAM_FILE_NAME);
+        debug!("Storing stream to: {:?}", stream_path);
+        fs::write(stream_path, buffer).map_err(|err| LoadError::NoStorage {
+            reason: err.to_string(),
+        })
+    }
+
+    /// Drops a survival time of 0 days from the JSON of a phenopacket.
+    ///
+    /// `survivalTimeInDays` is a proto3 scalar, so "no survival time known" is its default value of 0.
+    /// Like the proto3 JSON mapping, the loader omits the default instead of writing a fabricated survival time.
     pub fn remove_default_survival_time(phenopacket: &mut Value) -> Result<(), LoadError> {
         if let Some(vital_status) = phenopacket.pointer_mut("/subject/vitalStatus")
             && let Some(vital_status_obj) = vital_status.as_object_mut()
@@ -45,14 +176,33 @@ impl FileSystemLoader {
 
         Ok(())
     }
+
+    /// Deserializes the JSON of a phenopacket, e.g. one written by this loader.
+    ///
+    /// The serde implementation of the phenopackets crate requires `survivalTimeInDays`, although the loader omits it,
+    /// if the survival time is unknown. An omitted survival time is read as its proto3 default again.
+    pub fn phenopacket_from_json(content: &str) -> Result<Phenopacket, serde_json::Error> {
+        let mut pp_value: Value = serde_json::from_str(content)?;
+
+        if let Some(vital_status) = pp_value.pointer_mut("/subject/vitalStatus")
+            && let Some(vital_status_obj) = vital_status.as_object_mut()
+        {
+            vital_status_obj
+                .entry("survivalTimeInDays")
+                .or_insert(Value::from(0));
+        }
+
+        serde_json::from_value(pp_value)
+    }
 }
 
 impl Loadable for FileSystemLoader {
     /// Saves a slice of `Phenopacket`s to the directory specified in `out_path`.
     ///
-    /// Each `Phenopacket` is serialized into a pretty-printed JSON file. The filename
-    /// is derived from the phenopacket's ID, followed by the `.json` extension
-    /// (e.g., `PMIT-00001.json`).
+    /// Each `Phenopacket` is serialized into a pretty-printed JSON, a YAML or a binary protobuf file, depending on the output format.
+    /// The filename is derived from the phenopacket's ID, followed by the extension of the format
+    /// (e.g., `PMIT-00001.json` or `PMIT-00001.yaml`).
+    /// In the `protobuf_stream` format all phenopackets are written into a single `phenopackets.pb` file instead.
     ///
     /// If serialization for a specific phenopacket fails, a warning is logged,
     /// and the process continues with the next phenopacket.
@@ -71,31 +221,57 @@ impl Loadable for FileSystemLoader {
                 reason: err.to_string(),
             })?;
         }
+        if self.output_format == OutputFormat::ProtobufStream {
+            return self.write_protobuf_stream(phenopackets);
+        }
+
         for pp in phenopackets.iter() {
-            let file =
-                File::create(self.out_path.join(format!("{}.json", pp.id))).map_err(|err| {
-                    LoadError::CantStore {
-                        pp_id: pp.id.clone(),
-                        reason: err.to_string(),
-                    }
-                })?;
+            let file = File::create(self.phenopacket_path(&pp.id)).map_err(|err| {
+                LoadError::CantStore {
+                    pp_id: pp.id.clone(),
+                    reason: err.to_string(),
+                }
+            })?;
 
             debug!("Storing file to: {:?}", file);
-            let mut pp_value =
-                serde_json::to_value(pp).map_err(|_| LoadError::ConversionError {
-                    pp_id: pp.id.clone(),
-                    format: "json".to_string(),
-                })?;
+            if self.output_format == OutputFormat::Protobuf {
+                Self::write_protobuf(file, pp)?;
+            } else {
+                self.write_serialized(file, pp)?;
+            }
 
-            Self::remove_default_survival_time(&mut pp_value)?;
-            serde_json::to_writer_pretty(file, &pp_value).map_err(|err| LoadError::CantStore {
-                pp_id: pp.id.clone(),
-                reason: err.to_string(),
-            })?;
+            if self.is_resumable() {
+                self.record_checkpoint(&pp.id)?;
+            }
         }
 
         Ok(())
     }
+
+    /// The phenopackets in the checkpoint index, whose file still exists.
+    /// Phenopackets, whose file was deleted, are built again.
+    fn loaded_phenopacket_ids(&self) -> Result<Option<HashSet<String>>, LoadError> {
+        if !self.is_resumable() {
+            return Ok(None);
+        }
+
+        let index_path = self.out_path.join(CHECKPOINT_INDEX_FILE_NAME);
+        if !index_path.exists() {
+            return Ok(Some(HashSet::new()));
+        }
+
+        let index = fs::read_to_string(&index_path).map_err(|err| LoadError::NoStorage {
+            reason: format!("could not read the checkpoint index {index_path:?}: {err}"),
+        })?;
+        Ok(Some(
+            index
+                .lines()
+                .map(str::trim)
+                .filter(|pp_id| !pp_id.is_empty() && self.phenopacket_path(pp_id).exists())
+                .map(str::to_string)
+                .collect(),
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -103,7 +279,10 @@ mod tests {
     use super::*;
     use crate::test_suite::cdf_generation::default_patient_id;
     use crate::test_suite::phenopacket_component_generation::default_phenopacket_id;
-    use phenopackets::schema::v2::core::{Individual, VitalStatus};
+    use phenopackets::schema::v2::core::vital_status::Status;
+    use phenopackets::schema::v2::core::{
+        Individual, OntologyClass, PhenotypicFeature, Sex, VitalStatus,
+    };
     use rstest::*;
     use std::fs;
     use tempfile::tempdir;
@@ -114,6 +293,8 @@ mod tests {
         let loader = FileSystemLoader {
             out_path: tmp_dir.path().to_path_buf(),
             create_dir: true,
+            output_format: OutputFormat::Json,
+            resume: false,
         };
 
         let phenopacket = Phenopacket {
@@ -143,4 +324,132 @@ mod tests {
         assert_eq!(json.get("id").unwrap().as_str().unwrap(), phenopacket.id);
         assert!(!contents.contains("survivalTimeInDays"));
     }
+
+    #[rstest]
+    fn test_filesystem_loader_round_trips_unknown_survival_time() {
+        let tmp_dir = tempdir().unwrap();
+        let loader = FileSystemLoader::new(tmp_dir.path().to_path_buf(), true);
+
+        let phenopacket = Phenopacket {
+            id: default_phenopacket_id(),
+            subject: Some(Individual {
+                id: default_patient_id(),
+                vital_status: Some(VitalStatus {
+                    status: Status::Deceased as i32,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        loader
+            .load(std::slice::from_ref(&phenopacket))
+            .expect("load should succeed");
+        let contents = fs::read_to_string(
+            tmp_dir
+                .path()
+                .join(format!("{}.json", default_phenopacket_id())),
+        )
+        .unwrap();
+
+        assert!(contents.contains("DECEASED"));
+        assert!(!contents.contains("survivalTimeInDays"));
+        assert_eq!(
+            FileSystemLoader::phenopacket_from_json(&contents).unwrap(),
+            phenopacket
+        );
+    }
+
+    #[rstest]
+    fn test_filesystem_loader_writes_yaml_files() {
+        let tmp_dir = tempdir().unwrap();
+        let loader = FileSystemLoader::new(tmp_dir.path().to_path_buf(), true)
+            .with_output_format(OutputFormat::Yaml);
+
+        let phenopacket = Phenopacket {
+            id: default_phenopacket_id(),
+            subject: Some(Individual {
+                id: default_patient_id(),
+                sex: Sex::Female as i32,
+                ..Default::default()
+            }),
+            phenotypic_features: vec![PhenotypicFeature {
+                r#type: Some(OntologyClass {
+                    id: "HP:0001250".to_string(),
+                    label: "Seizure".to_string(),
+                }),
+                excluded: true,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        loader
+            .load(std::slice::from_ref(&phenopacket))
+            .expect("load should succeed");
+
+        let output_path = tmp_dir
+            .path()
+            .join(format!("{}.yaml", default_phenopacket_id()));
+        let contents = fs::read_to_string(&output_path).unwrap();
+
+        assert!(contents.contains("sex: FEMALE"));
+        let reparsed: Phenopacket = serde_norway::from_str(&contents).unwrap();
+        assert_eq!(reparsed, phenopacket);
+    }
+
+    #[rstest]
+    fn test_filesystem_loader_writes_protobuf_files() {
+        let tmp_dir = tempdir().unwrap();
+        let loader = FileSystemLoader::new(tmp_dir.path().to_path_buf(), true)
+            .with_output_format(OutputFormat::Protobuf);
+
+        let phenopacket = Phenopacket {
+            id: default_phenopacket_id(),
+            subject: Some(Individual {
+                id: default_patient_id(),
+                sex: Sex::Female as i32,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        loader
+            .load(std::slice::from_ref(&phenopacket))
+            .expect("load should succeed");
+
+        let bytes = fs::read(
+            tmp_dir
+                .path()
+                .join(format!("{}.pb", default_phenopacket_id())),
+        )
+        .unwrap();
+        assert_eq!(Phenopacket::decode(bytes.as_slice()).unwrap(), phenopacket);
+    }
+
+    #[rstest]
+    fn test_filesystem_loader_writes_protobuf_stream() {
+        let tmp_dir = tempdir().unwrap();
+        let loader = FileSystemLoader::new(tmp_dir.path().to_path_buf(), true)
+            .with_output_format(OutputFormat::ProtobufStream);
+
+        let phenopackets: Vec<Phenopacket> = ["P001", "P002"]
+            .into_iter()
+            .map(|id| Phenopacket {
+                id: id.to_string(),
+                ..Default::default()
+            })
+            .collect();
+
+        loader.load(&phenopackets).expect("load should succeed");
+
+        let bytes = fs::read(tmp_dir.path().join(PROTOBUF_STREAM_FILE_NAME)).unwrap();
+        let mut buffer = bytes.as_slice();
+        let mut decoded = vec![];
+        while !buffer.is_empty() {
+            decoded.push(Phenopacket::decode_length_delimited(&mut buffer).unwrap());
+        }
+        assert_eq!(decoded, phenopackets);
+    }
 }
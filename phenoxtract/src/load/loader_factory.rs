@@ -1,4 +1,6 @@
 use crate::config::loader_config::LoaderConfig;
+#[cfg(feature = "fhir")]
+use crate::load::FhirLoader;
 use crate::load::FileSystemLoader;
 use crate::load::traits::Loadable;
 use config::ConfigError;
@@ -11,7 +13,18 @@ impl LoaderFactory {
             LoaderConfig::FileSystem {
                 output_dir,
                 create_dir,
-            } => Ok(Box::new(FileSystemLoader::new(output_dir, create_dir))),
+                output_format,
+                resume,
+            } => Ok(Box::new(
+                FileSystemLoader::new(output_dir, create_dir)
+                    .with_output_format(output_format)
+                    .with_resume(resume),
+            )),
+            #[cfg(feature = "fhir")]
+            LoaderConfig::Fhir {
+                output_dir,
+                create_dir,
+            } => Ok(Box::new(FhirLoader::new(output_dir, create_dir))),
         }
     }
 }
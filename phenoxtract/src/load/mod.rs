@@ -1,6 +1,10 @@
 pub mod error;
+#[cfg(feature = "fhir")]
+pub mod fhir_loader;
+#[cfg(feature = "fhir")]
+pub use fhir_loader::FhirLoader;
 pub mod file_system_loader;
-pub use file_system_loader::FileSystemLoader;
+pub use file_system_loader::{FileSystemLoader, OutputFormat};
 pub mod loader_factory;
 pub mod loader_module;
 pub mod traits;
@@ -1,8 +1,16 @@
 use crate::load::error::LoadError;
 use phenopackets::schema::v2::Phenopacket;
+use std::collections::HashSet;
 use std::fmt::Debug;
 
 pub trait Loadable: Debug {
     /// A trait to implement saving Phenopackets to a file system.
     fn load(&self, phenopackets: &[Phenopacket]) -> Result<(), LoadError>;
+
+    /// The ids of the phenopackets, which an earlier, interrupted run already loaded.
+    ///
+    /// Returns `None`, if the loader does not resume runs. The phenopackets are then loaded all at once.
+    fn loaded_phenopacket_ids(&self) -> Result<Option<HashSet<String>>, LoadError> {
+        Ok(None)
+    }
 }
@@ -1,3 +1,4 @@
+use crate::ontology::concurrency_limiter::ConcurrencyLimiter;
 use crate::ontology::error::BiDictError;
 use crate::ontology::resource_references::ResourceRef;
 use crate::ontology::traits::BiDict;
@@ -11,6 +12,7 @@ use securiety::curie_parser::CurieParser;
 use securiety::traits::CurieParsing;
 use serde::Deserialize;
 use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
 
 impl fmt::Debug for BioPortalClient {
@@ -26,6 +28,7 @@ impl fmt::Debug for BioPortalClient {
             .field("cache_len", &cache_len)
             .field("api_key", &"<redacted>")
             .field("rate_limiter", &"<ratelimited>")
+            .field("request_limiter", &self.request_limiter)
             .field("resource_ref", &self.resource_ref)
             .finish()
     }
@@ -61,6 +64,7 @@ pub struct BioPortalClient {
 
     cache: Cache<String, String>,
     rate_limiter: Ratelimiter,
+    request_limiter: Arc<ConcurrencyLimiter>,
 
     resource_ref: ResourceRef,
 }
@@ -162,10 +166,23 @@ impl BioPortalClient {
             iri_prefix,
             cache: Cache::<String, String>::new(1500),
             rate_limiter,
+            request_limiter: Arc::new(ConcurrencyLimiter::default()),
             resource_ref,
         })
     }
 
+    /// Shares the limit of in-flight requests with other network backed clients. Cached lookups don't count.
+    pub fn with_request_limiter(mut self, request_limiter: Arc<ConcurrencyLimiter>) -> Self {
+        self.request_limiter = request_limiter;
+        self
+    }
+
+    /// Sends the requests to another BioPortal instance, e.g. a mirror or a mock server.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
     fn class_url(&self, local_id: &str) -> Result<Url, BiDictError> {
         // Build BioPortal "class" endpoint URL.
         let iri = format!("{}{}", self.iri_prefix, local_id);
@@ -218,6 +235,7 @@ impl BioPortalClient {
 
         self.wait_for_rate_limit();
 
+        let _permit = self.request_limiter.acquire();
         let resp = self
             .client
             .get(url)
@@ -239,6 +257,7 @@ impl BioPortalClient {
 
         self.wait_for_rate_limit();
 
+        let _permit = self.request_limiter.acquire();
         let resp = self
             .client
             .get(url)
@@ -380,6 +399,7 @@ mod tests {
             curie_parser,
             cache: Cache::new(1500),
             rate_limiter,
+            request_limiter: Arc::new(ConcurrencyLimiter::default()),
             resource_ref: ResourceRef::from("OMIM"),
         }
     }
@@ -416,6 +436,7 @@ mod tests {
             curie_parser,
             cache: Cache::new(1500),
             rate_limiter,
+            request_limiter: Arc::new(ConcurrencyLimiter::default()),
             resource_ref: ResourceRef::from(curie_prefix.as_str()),
         }
     }
@@ -0,0 +1,205 @@
+use pivotal::hgnc::{GeneDoc, GeneQuery, HGNCData, HGNCError};
+use pivotal::hgvs::{HGVSData, HGVSError, HgvsVariant};
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex};
+
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/// A counting semaphore, which caps the number of in-flight requests against network services.
+///
+/// A single limiter is meant to be shared (via `Arc`) between all network backed clients
+/// (HGNC, HGVS, BioPortal, LOINC), so that at most `max_in_flight` requests happen at the
+/// same time, regardless of how many threads are collecting patients. Only the requests
+/// themselves acquire a permit, lookups answered from a cache don't.
+pub struct ConcurrencyLimiter {
+    max_in_flight: usize,
+    in_flight: Mutex<usize>,
+    released: Condvar,
+}
+
+impl ConcurrencyLimiter {
+    /// Creates a new limiter.
+    ///
+    /// # Panics
+    ///
+    /// If `max_in_flight` is `0`, because every request would block forever.
+    pub fn new(max_in_flight: usize) -> Self {
+        assert!(
+            max_in_flight > 0,
+            "A ConcurrencyLimiter needs a limit of at least 1."
+        );
+        Self {
+            max_in_flight,
+            in_flight: Mutex::new(0),
+            released: Condvar::new(),
+        }
+    }
+
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight
+    }
+
+    /// Blocks until a slot is free. The slot is released, when the returned permit is dropped.
+    pub fn acquire(&self) -> ConcurrencyPermit<'_> {
+        let mut in_flight = self
+            .in_flight
+            .lock()
+            .expect("ConcurrencyLimiter mutex poisoned");
+
+        while *in_flight >= self.max_in_flight {
+            in_flight = self
+                .released
+                .wait(in_flight)
+                .expect("ConcurrencyLimiter mutex poisoned");
+        }
+        *in_flight += 1;
+
+        ConcurrencyPermit { limiter: self }
+    }
+
+    fn release(&self) {
+        let mut in_flight = self
+            .in_flight
+            .lock()
+            .expect("ConcurrencyLimiter mutex poisoned");
+        *in_flight -= 1;
+        self.released.notify_one();
+    }
+}
+
+impl Default for ConcurrencyLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_REQUESTS)
+    }
+}
+
+impl fmt::Debug for ConcurrencyLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConcurrencyLimiter")
+            .field("max_in_flight", &self.max_in_flight)
+            .finish()
+    }
+}
+
+impl PartialEq for ConcurrencyLimiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.max_in_flight == other.max_in_flight
+    }
+}
+
+#[must_use = "the slot is released as soon as the permit is dropped"]
+pub struct ConcurrencyPermit<'a> {
+    limiter: &'a ConcurrencyLimiter,
+}
+
+impl Drop for ConcurrencyPermit<'_> {
+    fn drop(&mut self) {
+        self.limiter.release();
+    }
+}
+
+/// Wraps an uncached HGNC client, so that every request has to acquire a permit from a shared `ConcurrencyLimiter`.
+#[derive(Debug)]
+pub struct ConcurrencyLimitedHgncClient<C> {
+    inner: C,
+    limiter: Arc<ConcurrencyLimiter>,
+}
+
+impl<C> ConcurrencyLimitedHgncClient<C> {
+    pub fn new(inner: C, limiter: Arc<ConcurrencyLimiter>) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+impl<C: HGNCData> HGNCData for ConcurrencyLimitedHgncClient<C> {
+    fn request_gene_data(&self, query: GeneQuery) -> Result<GeneDoc, HGNCError> {
+        let _permit = self.limiter.acquire();
+        self.inner.request_gene_data(query)
+    }
+}
+
+/// Wraps an uncached HGVS client, so that every request has to acquire a permit from a shared `ConcurrencyLimiter`.
+#[derive(Debug)]
+pub struct ConcurrencyLimitedHgvsClient<C> {
+    inner: C,
+    limiter: Arc<ConcurrencyLimiter>,
+}
+
+impl<C> ConcurrencyLimitedHgvsClient<C> {
+    pub fn new(inner: C, limiter: Arc<ConcurrencyLimiter>) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+impl<C: HGVSData> HGVSData for ConcurrencyLimitedHgvsClient<C> {
+    fn request_and_validate_hgvs(&self, unvalidated_hgvs: &str) -> Result<HgvsVariant, HGVSError> {
+        let _permit = self.limiter.acquire();
+        self.inner.request_and_validate_hgvs(unvalidated_hgvs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    #[derive(Debug)]
+    struct ConcurrencyCountingHgvsClient {
+        current: Arc<AtomicUsize>,
+        max_seen: Arc<AtomicUsize>,
+    }
+
+    impl HGVSData for ConcurrencyCountingHgvsClient {
+        fn request_and_validate_hgvs(
+            &self,
+            _unvalidated_hgvs: &str,
+        ) -> Result<HgvsVariant, HGVSError> {
+            let now = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_seen.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(10));
+            self.current.fetch_sub(1, Ordering::SeqCst);
+            Ok(HgvsVariant::default())
+        }
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(3)]
+    fn test_limited_client_respects_cap(#[case] cap: usize) {
+        let max_seen = Arc::new(AtomicUsize::new(0));
+        let limited = ConcurrencyLimitedHgvsClient::new(
+            ConcurrencyCountingHgvsClient {
+                current: Arc::new(AtomicUsize::new(0)),
+                max_seen: max_seen.clone(),
+            },
+            Arc::new(ConcurrencyLimiter::new(cap)),
+        );
+
+        thread::scope(|scope| {
+            for i in 0..16 {
+                let limited = &limited;
+                scope.spawn(move || {
+                    limited
+                        .request_and_validate_hgvs(&format!("NM_001173464.1:c.{i}A>G"))
+                        .unwrap()
+                });
+            }
+        });
+
+        let max_seen = max_seen.load(Ordering::SeqCst);
+        assert!(max_seen >= 1);
+        assert!(
+            max_seen <= cap,
+            "saw {max_seen} concurrent calls, cap {cap}"
+        );
+    }
+
+    #[rstest]
+    #[should_panic(expected = "at least 1")]
+    fn test_zero_limit_panics() {
+        ConcurrencyLimiter::new(0);
+    }
+}
@@ -18,6 +18,12 @@ pub enum RegistryError {
     UnableToResolveVersion(String, Option<String>),
     #[error("Client error: {0}")]
     Client(#[from] ClientError),
+    #[error(
+        "Ontology {ontology} in version {version} is not in the cache and can't be downloaded in offline mode"
+    )]
+    OfflineCacheMiss { ontology: String, version: String },
+    #[error("Term {term} is not part of version {version}")]
+    TermNotInVersion { term: String, version: String },
 }
 
 #[derive(Debug, Error)]
@@ -38,6 +44,8 @@ pub enum FactoryError {
     FastOboError(#[from] FastOboError),
     #[error(transparent)]
     OntologyRegistryError(#[from] OntologyRegistryError),
+    #[error(transparent)]
+    Registry(#[from] RegistryError),
 }
 
 #[derive(Debug, Error)]
@@ -48,6 +56,8 @@ pub enum BiDictError {
     Request(#[from] reqwest::Error),
     #[error("Invalid id format: {0}")]
     InvalidId(String),
+    #[error("The label '{label}' matches the terms {ids:?}, whose labels only differ by case")]
+    AmbiguousLabel { label: String, ids: Vec<String> },
     #[error("Cache error: {reason}")]
     Caching { reason: String },
 }
@@ -0,0 +1,113 @@
+use crate::ontology::error::ClientError;
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::fmt::Debug;
+
+/// Resolves deprecated and alias gene symbols to the symbol, which is currently approved by HGNC.
+pub trait GeneSymbolAliases: Debug + Send + Sync {
+    /// Looks up the current symbol of a previous or alias symbol.
+    ///
+    /// Returns `None`, if the symbol is neither a previous nor an alias symbol of exactly one gene.
+    fn current_symbol(&self, symbol: &str) -> Result<Option<String>, ClientError>;
+}
+
+#[derive(Debug, Deserialize)]
+struct HgncSearchResponse {
+    response: HgncSearchResult,
+}
+
+#[derive(Debug, Deserialize)]
+struct HgncSearchResult {
+    docs: Vec<HgncSearchDoc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HgncSearchDoc {
+    symbol: String,
+}
+
+/// Queries the previous symbol and alias symbol indices of the HGNC REST API.
+///
+/// Previous symbols take precedence, because they are unique to one gene, while aliases may be shared.
+#[derive(Clone, Debug)]
+pub struct HgncAliasClient {
+    api_url: String,
+    client: Client,
+}
+
+impl HgncAliasClient {
+    pub fn new(api_url: &str) -> Self {
+        let mut url = api_url.to_string();
+        if !url.ends_with("/") {
+            url += "/";
+        }
+        HgncAliasClient {
+            api_url: url,
+            client: Client::new(),
+        }
+    }
+
+    /// Searches one index (e.g. `prev_symbol`) for a symbol and returns the current symbols of all matching genes.
+    fn search(&self, index: &str, symbol: &str) -> Result<Vec<String>, ClientError> {
+        let url = format!("{}search/{index}/{symbol}", self.api_url);
+
+        let response: HgncSearchResponse = self
+            .client
+            .get(url)
+            .header("Accept", "application/json")
+            .header("User-Agent", "phenoxtractor")
+            .send()?
+            .error_for_status()?
+            .json()?;
+
+        Ok(response
+            .response
+            .docs
+            .into_iter()
+            .map(|doc| doc.symbol)
+            .collect())
+    }
+}
+
+impl GeneSymbolAliases for HgncAliasClient {
+    fn current_symbol(&self, symbol: &str) -> Result<Option<String>, ClientError> {
+        for index in ["prev_symbol", "alias_symbol"] {
+            let mut symbols = self.search(index, symbol)?;
+            match symbols.len() {
+                0 => continue,
+                1 => return Ok(symbols.pop()),
+                _ => return Ok(None),
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Default for HgncAliasClient {
+    fn default() -> Self {
+        HgncAliasClient::new("https://rest.genenames.org/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_current_symbol_of_previous_symbol() {
+        let client = HgncAliasClient::default();
+
+        assert_eq!(
+            client.current_symbol("MLL").unwrap(),
+            Some("KMT2A".to_string())
+        );
+    }
+
+    #[rstest]
+    fn test_current_symbol_unknown() {
+        let client = HgncAliasClient::default();
+
+        assert_eq!(client.current_symbol("NOT_A_GENE_XYZ").unwrap(), None);
+    }
+}
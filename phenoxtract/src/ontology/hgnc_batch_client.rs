@@ -0,0 +1,148 @@
+use crate::ontology::error::ClientError;
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+/// The number of genes, which are searched with one request, so that the URL stays short.
+const BATCH_SIZE: usize = 50;
+
+/// Resolves many genes to their symbol and HGNC id at once.
+pub trait GeneBatchLookup: Debug + Send + Sync {
+    /// Looks up the (symbol, HGNC id) pairs of genes, which are given by their symbol or HGNC id.
+    ///
+    /// The pairs are keyed by the gene, as it was given. Genes, which HGNC doesn't know, are left out.
+    fn request_gene_identifier_pairs(
+        &self,
+        genes: &[&str],
+    ) -> Result<HashMap<String, (String, String)>, ClientError>;
+}
+
+#[derive(Debug, Deserialize)]
+struct HgncSearchResponse {
+    response: HgncSearchResult,
+}
+
+#[derive(Debug, Deserialize)]
+struct HgncSearchResult {
+    docs: Vec<HgncSearchDoc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HgncSearchDoc {
+    hgnc_id: String,
+    symbol: String,
+}
+
+/// Searches the symbol and HGNC id indices of the HGNC REST API for up to 50 genes per request.
+#[derive(Clone, Debug)]
+pub struct HgncBatchClient {
+    api_url: String,
+    client: Client,
+}
+
+impl HgncBatchClient {
+    pub fn new(api_url: &str) -> Self {
+        let mut url = api_url.to_string();
+        if !url.ends_with("/") {
+            url += "/";
+        }
+        HgncBatchClient {
+            api_url: url,
+            client: Client::new(),
+        }
+    }
+
+    /// Searches for all genes with a single request, e.g. `search/symbol:CLOCK+OR+hgnc_id:1101`.
+    fn search(&self, genes: &[&str]) -> Result<Vec<HgncSearchDoc>, ClientError> {
+        let query = genes
+            .iter()
+            .map(|gene| Self::query_term(gene))
+            .collect::<Vec<_>>()
+            .join("+OR+");
+        let url = format!("{}search/{query}", self.api_url);
+
+        let response: HgncSearchResponse = self
+            .client
+            .get(url)
+            .header("Accept", "application/json")
+            .header("User-Agent", "phenoxtractor")
+            .send()?
+            .error_for_status()?
+            .json()?;
+
+        Ok(response.response.docs)
+    }
+
+    fn query_term(gene: &str) -> String {
+        match gene.split_once(':') {
+            Some((prefix, id)) if prefix.eq_ignore_ascii_case("HGNC") => format!("hgnc_id:{id}"),
+            _ => format!("symbol:{gene}"),
+        }
+    }
+}
+
+impl GeneBatchLookup for HgncBatchClient {
+    fn request_gene_identifier_pairs(
+        &self,
+        genes: &[&str],
+    ) -> Result<HashMap<String, (String, String)>, ClientError> {
+        let mut pairs = HashMap::new();
+
+        for batch in genes.chunks(BATCH_SIZE) {
+            let docs = self.search(batch)?;
+            for gene in batch {
+                let doc = docs.iter().find(|doc| {
+                    doc.symbol.eq_ignore_ascii_case(gene) || doc.hgnc_id.eq_ignore_ascii_case(gene)
+                });
+                if let Some(doc) = doc {
+                    pairs.insert(gene.to_string(), (doc.symbol.clone(), doc.hgnc_id.clone()));
+                }
+            }
+        }
+        Ok(pairs)
+    }
+}
+
+impl Default for HgncBatchClient {
+    fn default() -> Self {
+        HgncBatchClient::new("https://rest.genenames.org/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_request_gene_identifier_pairs() {
+        let client = HgncBatchClient::default();
+
+        let pairs = client
+            .request_gene_identifier_pairs(&["CLOCK", "HGNC:1101", "NOT_A_GENE_XYZ"])
+            .unwrap();
+
+        assert_eq!(
+            pairs,
+            HashMap::from([
+                (
+                    "CLOCK".to_string(),
+                    ("CLOCK".to_string(), "HGNC:2082".to_string())
+                ),
+                (
+                    "HGNC:1101".to_string(),
+                    ("BRCA2".to_string(), "HGNC:1101".to_string())
+                ),
+            ])
+        );
+    }
+
+    #[rstest]
+    #[case("CLOCK", "symbol:CLOCK")]
+    #[case("HGNC:2082", "hgnc_id:2082")]
+    #[case("hgnc:2082", "hgnc_id:2082")]
+    fn test_query_term(#[case] gene: &str, #[case] expected: &str) {
+        assert_eq!(HgncBatchClient::query_term(gene), expected);
+    }
+}
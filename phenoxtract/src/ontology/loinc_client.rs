@@ -1,15 +1,17 @@
 #![allow(unused)]
+use crate::ontology::concurrency_limiter::ConcurrencyLimiter;
 use crate::ontology::error::BiDictError;
 use crate::ontology::resource_references::{KnownResourcePrefixes, ResourceRef};
 use crate::ontology::traits::{BiDict, HasVersion};
 use moka::sync::Cache;
+use once_cell::sync::Lazy;
 use regex::Regex;
 use reqwest::blocking::Client;
 use securiety::{CurieParser, CurieParsing, CurieRegexValidator, CurieValidation};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::str::FromStr;
-use std::sync::{OnceLock, RwLock};
+use std::sync::{Arc, OnceLock, RwLock};
 use std::{env, fmt};
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -102,6 +104,7 @@ pub struct LoincResult {
 }
 
 static NOT_FOUND: &str = "NotFound";
+static LOINC_NUMBER: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\d{1,7}-\d$").unwrap());
 
 pub struct LoincClient {
     client: Client,
@@ -111,6 +114,7 @@ pub struct LoincClient {
     cache: Cache<String, String>,
     reference: OnceLock<ResourceRef>,
     curie_validator: CurieRegexValidator,
+    request_limiter: Arc<ConcurrencyLimiter>,
 }
 
 impl fmt::Debug for LoincClient {
@@ -122,6 +126,7 @@ impl fmt::Debug for LoincClient {
             .field("client", &"reqwest::Client")
             .field("cache_size", &self.cache.entry_count())
             .field("reference_initialized", &self.reference.get().is_some())
+            .field("request_limiter", &self.request_limiter)
             .finish()
     }
 }
@@ -141,13 +146,21 @@ impl LoincClient {
             cache: Cache::new(1500),
             reference: reference_lock,
             curie_validator: CurieRegexValidator::loinc(),
+            request_limiter: Arc::new(ConcurrencyLimiter::default()),
         }
     }
 
+    /// Shares the limit of in-flight requests with other network backed clients. Cached lookups don't count.
+    pub fn with_request_limiter(mut self, request_limiter: Arc<ConcurrencyLimiter>) -> Self {
+        self.request_limiter = request_limiter;
+        self
+    }
+
     fn query(&self, id_or_label: &str) -> Result<Vec<LoincResult>, BiDictError> {
         let url = format!("{}loincs", self.base_url);
         let params = [("query", id_or_label), ("rows", "10")];
 
+        let _permit = self.request_limiter.acquire();
         let loinc_response: LoincResponse = self
             .client
             .get(url)
@@ -162,6 +175,18 @@ impl LoincClient {
     fn format_loinc_curie(loinc_number: &str) -> String {
         format!("{}:{}", KnownResourcePrefixes::LOINC, loinc_number)
     }
+
+    /// Turns a bare LOINC number like `2160-0` into the CURIE `LOINC:2160-0`.
+    ///
+    /// Anything else, e.g. a CURIE or a label, is returned unchanged.
+    pub fn normalize_loinc_id(id_or_label: &str) -> String {
+        let trimmed = id_or_label.trim();
+        if LOINC_NUMBER.is_match(trimmed) {
+            Self::format_loinc_curie(trimmed)
+        } else {
+            id_or_label.to_string()
+        }
+    }
 }
 
 impl Default for LoincClient {
@@ -177,14 +202,17 @@ impl Default for LoincClient {
 
 impl BiDict for LoincClient {
     fn get(&self, id_or_label: &str) -> Result<String, BiDictError> {
-        if self.curie_validator.validate(id_or_label) {
-            self.get_label(id_or_label)
+        let id_or_label = Self::normalize_loinc_id(id_or_label);
+        if self.curie_validator.validate(&id_or_label) {
+            self.get_label(&id_or_label)
         } else {
-            self.get_id(id_or_label)
+            self.get_id(&id_or_label)
         }
     }
 
     fn get_label(&self, id: &str) -> Result<String, BiDictError> {
+        let id = Self::normalize_loinc_id(id);
+        let id = id.as_str();
         if !self.curie_validator.validate(id) {
             return Err(BiDictError::InvalidId(id.to_string()));
         }
@@ -213,6 +241,11 @@ impl BiDict for LoincClient {
     }
 
     fn get_id(&self, label: &str) -> Result<String, BiDictError> {
+        if LOINC_NUMBER.is_match(label.trim()) {
+            let loinc_id = Self::normalize_loinc_id(label);
+            return self.get_label(&loinc_id).map(|_| loinc_id);
+        }
+
         if let Some(loinc_number) = self.cache.get(label) {
             if loinc_number == NOT_FOUND {
                 return Err(BiDictError::NotFound(label.to_string()));
@@ -252,6 +285,7 @@ impl BiDict for LoincClient {
 
     fn reference(&self) -> &ResourceRef {
         self.reference.get_or_init(|| {
+            let _permit = self.request_limiter.acquire();
             let res: LoincRelease = self
                 .client
                 .get("https://loinc.regenstrief.org/api/v1/Loinc")
@@ -283,6 +317,33 @@ mod tests {
         LoincClient::default()
     }
 
+    #[rstest]
+    #[case("2160-0", "LOINC:2160-0")]
+    #[case(" 8302-2 ", "LOINC:8302-2")]
+    #[case("LOINC:8302-2", "LOINC:8302-2")]
+    #[case("Body height", "Body height")]
+    fn test_normalize_loinc_id(#[case] id_or_label: &str, #[case] expected: &str) {
+        assert_eq!(LoincClient::normalize_loinc_id(id_or_label), expected);
+    }
+
+    #[rstest]
+    fn test_get_label_bare_loinc_number(loinc_client: LoincClient) {
+        let loinc_number = default_quant_loinc().id.replace("LOINC:", "");
+
+        let res = loinc_client.get_label(&loinc_number);
+
+        assert_eq!(res.unwrap(), default_quant_loinc().label);
+    }
+
+    #[rstest]
+    fn test_get_id_bare_loinc_number(loinc_client: LoincClient) {
+        let loinc_number = default_quant_loinc().id.replace("LOINC:", "");
+
+        let res = loinc_client.get_id(&loinc_number);
+
+        assert_eq!(res.unwrap(), default_quant_loinc().id);
+    }
+
     #[rstest]
     fn test_get_label(loinc_client: LoincClient) {
         let res = loinc_client.get_label(default_quant_loinc().id.as_str());
@@ -1,13 +1,18 @@
 pub(crate) mod bio_registry_client;
 pub mod error;
+pub mod hgnc_alias_client;
+pub mod hgnc_batch_client;
 
 pub(crate) use bio_registry_client::BioRegistryClient;
+pub mod obo_ontology;
+pub mod obsolete_terms;
 pub(crate) mod ontology_bidict;
 
 pub mod ontology_factory;
 pub mod resource_references;
 pub use ontology_factory::CachedOntologyFactory;
 pub mod bioportal_client;
+pub mod concurrency_limiter;
 pub mod loinc_client;
 mod trait_implementations;
 pub mod traits;
@@ -0,0 +1,68 @@
+use crate::ontology::traits::{OntologyLike, OntologyTermLike};
+use fastobo::ast::{OboDoc, TermClause};
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+/// An OBO document, which indexes its `is_a` hierarchy, the first time the ancestors of a term are requested.
+#[derive(Debug)]
+pub struct OboOntology {
+    doc: OboDoc,
+    /// The ids of the direct `is_a` parents of each term.
+    parent_ids: OnceLock<HashMap<String, Vec<String>>>,
+}
+
+impl OboOntology {
+    pub fn new(doc: OboDoc) -> Self {
+        Self {
+            doc,
+            parent_ids: OnceLock::new(),
+        }
+    }
+
+    fn parent_ids(&self) -> &HashMap<String, Vec<String>> {
+        self.parent_ids.get_or_init(|| {
+            self.doc
+                .entities()
+                .iter()
+                .filter_map(|e| e.as_term())
+                .map(|t| {
+                    let parents = t
+                        .clauses()
+                        .iter()
+                        .filter_map(|line| match line.as_inner() {
+                            TermClause::IsA(parent) => Some(parent.to_string().trim().to_string()),
+                            _ => None,
+                        })
+                        .collect();
+                    (t.ontology_id(), parents)
+                })
+                .collect()
+        })
+    }
+}
+
+impl OntologyLike for OboOntology {
+    fn iter_ontology_terms<'a>(
+        &'a self,
+        ontology_prefix: String,
+    ) -> Box<dyn Iterator<Item = &'a dyn OntologyTermLike> + 'a> {
+        self.doc.iter_ontology_terms(ontology_prefix)
+    }
+
+    fn ancestor_ids(&self, term_id: &str) -> Option<Vec<String>> {
+        let parent_ids = self.parent_ids();
+
+        let mut ancestor_ids = vec![];
+        let mut visited = HashSet::new();
+        let mut to_visit = vec![term_id.to_string()];
+        while let Some(id) = to_visit.pop() {
+            for parent_id in parent_ids.get(&id).into_iter().flatten() {
+                if visited.insert(parent_id.clone()) {
+                    ancestor_ids.push(parent_id.clone());
+                    to_visit.push(parent_id.clone());
+                }
+            }
+        }
+        Some(ancestor_ids)
+    }
+}
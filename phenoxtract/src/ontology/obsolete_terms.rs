@@ -0,0 +1,159 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::Read;
+
+const OBO_IRI_PREFIX: &str = "http://purl.obolibrary.org/obo/";
+const REPLACED_BY_PRED: &str = "http://purl.obolibrary.org/obo/IAO_0100001";
+const CONSIDER_PRED: &str = "http://www.geneontology.org/formats/oboInOwl#consider";
+
+/// What an obsolete ontology term should be replaced with.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObsoleteReplacement {
+    /// The current term, which replaces the obsolete term.
+    ReplacedBy(String),
+    /// Current terms, which might replace the obsolete term. Choosing one of them requires a curator.
+    Consider(Vec<String>),
+}
+
+#[derive(Deserialize)]
+struct ObographsDocument {
+    #[serde(default)]
+    graphs: Vec<ObographsGraph>,
+}
+
+#[derive(Deserialize)]
+struct ObographsGraph {
+    #[serde(default)]
+    nodes: Vec<ObographsNode>,
+}
+
+#[derive(Deserialize)]
+struct ObographsNode {
+    id: String,
+    #[serde(default)]
+    meta: Option<ObographsMeta>,
+}
+
+#[derive(Deserialize)]
+struct ObographsMeta {
+    #[serde(default)]
+    deprecated: bool,
+    #[serde(default, rename = "basicPropertyValues")]
+    basic_property_values: Vec<ObographsPropertyValue>,
+}
+
+#[derive(Deserialize)]
+struct ObographsPropertyValue {
+    pred: String,
+    val: String,
+}
+
+/// Turns an OBO PURL like `http://purl.obolibrary.org/obo/HP_0000004` into the CURIE `HP:0000004`.
+///
+/// Values, which are not OBO PURLs, are assumed to be CURIEs already.
+fn to_curie(iri: &str) -> String {
+    match iri.strip_prefix(OBO_IRI_PREFIX) {
+        Some(local_id) => local_id.replacen('_', ":", 1),
+        None => iri.to_string(),
+    }
+}
+
+/// Parses the `replaced_by` and `consider` annotations of the deprecated terms of an obographs JSON.
+///
+/// Returns a map from the CURIEs of the obsolete terms to their replacements.
+/// Obsolete terms without either annotation are left out.
+pub(crate) fn parse_obographs_obsolete_terms(
+    reader: impl Read,
+) -> Result<HashMap<String, ObsoleteReplacement>, serde_json::Error> {
+    let document: ObographsDocument = serde_json::from_reader(reader)?;
+
+    let mut obsolete_terms = HashMap::new();
+    for node in document.graphs.iter().flat_map(|graph| graph.nodes.iter()) {
+        let Some(meta) = node.meta.as_ref().filter(|meta| meta.deprecated) else {
+            continue;
+        };
+
+        let mut replaced_by = None;
+        let mut consider = vec![];
+        for property_value in meta.basic_property_values.iter() {
+            match property_value.pred.as_str() {
+                REPLACED_BY_PRED => replaced_by = Some(to_curie(&property_value.val)),
+                CONSIDER_PRED => consider.push(to_curie(&property_value.val)),
+                _ => {}
+            }
+        }
+
+        let replacement = match replaced_by {
+            Some(replaced_by) => ObsoleteReplacement::ReplacedBy(replaced_by),
+            None if !consider.is_empty() => ObsoleteReplacement::Consider(consider),
+            None => continue,
+        };
+        obsolete_terms.insert(to_curie(&node.id), replacement);
+    }
+
+    Ok(obsolete_terms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_parse_obographs_obsolete_terms() {
+        let obographs = r#"{
+            "graphs": [{
+                "nodes": [
+                    {
+                        "id": "http://purl.obolibrary.org/obo/HP_0000004",
+                        "type": "CLASS",
+                        "meta": {
+                            "basicPropertyValues": [
+                                {"pred": "http://purl.obolibrary.org/obo/IAO_0100001", "val": "http://purl.obolibrary.org/obo/HP_0012823"}
+                            ],
+                            "deprecated": true
+                        }
+                    },
+                    {
+                        "id": "http://purl.obolibrary.org/obo/HP_0000005",
+                        "meta": {
+                            "basicPropertyValues": [
+                                {"pred": "http://www.geneontology.org/formats/oboInOwl#consider", "val": "HP:0000006"},
+                                {"pred": "http://www.geneontology.org/formats/oboInOwl#consider", "val": "HP:0000007"}
+                            ],
+                            "deprecated": true
+                        }
+                    },
+                    {
+                        "id": "http://purl.obolibrary.org/obo/HP_0000008",
+                        "meta": {"deprecated": true}
+                    },
+                    {
+                        "id": "http://purl.obolibrary.org/obo/HP_0012823",
+                        "lbl": "Clinical modifier"
+                    }
+                ]
+            }]
+        }"#;
+
+        let obsolete_terms = parse_obographs_obsolete_terms(obographs.as_bytes()).unwrap();
+
+        assert_eq!(
+            obsolete_terms,
+            HashMap::from([
+                (
+                    "HP:0000004".to_string(),
+                    ObsoleteReplacement::ReplacedBy("HP:0012823".to_string())
+                ),
+                (
+                    "HP:0000005".to_string(),
+                    ObsoleteReplacement::Consider(vec![
+                        "HP:0000006".to_string(),
+                        "HP:0000007".to_string()
+                    ])
+                ),
+            ])
+        );
+    }
+}
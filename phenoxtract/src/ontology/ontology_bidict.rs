@@ -1,6 +1,8 @@
-use crate::ontology::error::BiDictError;
+use crate::ontology::error::{BiDictError, RegistryError};
+use crate::ontology::obsolete_terms::ObsoleteReplacement;
 use crate::ontology::resource_references::ResourceRef;
-use crate::ontology::traits::{BiDict, HasPrefixId, OntologyLike};
+use crate::ontology::traits::{BiDict, HasPrefixId, HasVersion, OntologyLike};
+use log::warn;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -10,14 +12,17 @@ pub struct OntologyBiDict {
     label_to_id: HashMap<String, String>,
     synonym_to_id: HashMap<String, String>,
     id_to_label: HashMap<String, String>,
+    obsolete_terms: HashMap<String, ObsoleteReplacement>,
+    /// Labels, which only differ by case, keyed by their lowercase form. They can only be looked up with their exact case.
+    case_ambiguous_labels: HashMap<String, HashMap<String, String>>,
 }
 
 impl BiDict for OntologyBiDict {
     fn get(&self, id_or_label: &str) -> Result<String, BiDictError> {
         let normalized_key = Self::normalize_key(id_or_label);
 
-        if let Some(identifier) = self.label_to_id.get(&normalized_key) {
-            return Ok(identifier.to_string());
+        if let Some(identifier) = self.get_id_by_label(id_or_label, &normalized_key)? {
+            return Ok(identifier);
         }
         if let Some(identifier) = self.synonym_to_id.get(&normalized_key) {
             return Ok(identifier.to_string());
@@ -40,8 +45,8 @@ impl BiDict for OntologyBiDict {
     fn get_id(&self, term: &str) -> Result<String, BiDictError> {
         let normalized_key = Self::normalize_key(term);
 
-        if let Some(identifier) = self.label_to_id.get(&normalized_key) {
-            return Ok(identifier.to_string());
+        if let Some(identifier) = self.get_id_by_label(term, &normalized_key)? {
+            return Ok(identifier);
         }
         if let Some(identifier) = self.synonym_to_id.get(&normalized_key) {
             return Ok(identifier.to_string());
@@ -52,6 +57,25 @@ impl BiDict for OntologyBiDict {
     fn reference(&self) -> &ResourceRef {
         &self.ontology
     }
+
+    fn closest_labels(&self, query: &str, max_candidates: usize) -> Vec<(usize, String)> {
+        let normalized_query = Self::normalize_key(query);
+
+        let mut candidates: Vec<(usize, String)> = self
+            .id_to_label
+            .values()
+            .map(|label| {
+                (
+                    strsim::levenshtein(&normalized_query, &label.to_lowercase()),
+                    label.to_string(),
+                )
+            })
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+        candidates.truncate(max_candidates);
+        candidates
+    }
 }
 
 impl BiDict for Arc<OntologyBiDict> {
@@ -70,6 +94,10 @@ impl BiDict for Arc<OntologyBiDict> {
     fn reference(&self) -> &ResourceRef {
         self.as_ref().reference()
     }
+
+    fn closest_labels(&self, query: &str, max_candidates: usize) -> Vec<(usize, String)> {
+        self.as_ref().closest_labels(query, max_candidates)
+    }
 }
 
 impl OntologyBiDict {
@@ -99,6 +127,79 @@ impl OntologyBiDict {
             label_to_id: label_to_id_lower,
             synonym_to_id: synonym_to_id_lower,
             id_to_label: id_to_label_lower,
+            obsolete_terms: HashMap::new(),
+            case_ambiguous_labels: HashMap::new(),
+        }
+    }
+
+    /// Adds labels, which only differ by case, keyed by their lowercase form.
+    ///
+    /// These labels are removed from the case-insensitive lookup, so that a label, which only matches one of them
+    /// case-insensitively, is reported as ambiguous instead of resolving to an arbitrary term.
+    pub(crate) fn with_case_ambiguous_labels(
+        mut self,
+        case_ambiguous_labels: HashMap<String, HashMap<String, String>>,
+    ) -> Self {
+        for lowercase_label in case_ambiguous_labels.keys() {
+            self.label_to_id.remove(lowercase_label);
+        }
+        self.case_ambiguous_labels = case_ambiguous_labels;
+        self
+    }
+
+    /// Looks up the id of a label. Exact matches win over case-insensitive ones.
+    fn get_id_by_label(
+        &self,
+        label: &str,
+        normalized_key: &str,
+    ) -> Result<Option<String>, BiDictError> {
+        if let Some(candidates) = self.case_ambiguous_labels.get(normalized_key) {
+            return match candidates.get(label.trim()) {
+                Some(identifier) => Ok(Some(identifier.to_string())),
+                None => {
+                    let mut ids: Vec<String> = candidates.values().cloned().collect();
+                    ids.sort();
+                    Err(BiDictError::AmbiguousLabel {
+                        label: label.to_string(),
+                        ids,
+                    })
+                }
+            };
+        }
+
+        Ok(self.label_to_id.get(normalized_key).cloned())
+    }
+
+    /// Adds the replacements of obsolete terms, keyed by the ids of the obsolete terms.
+    pub(crate) fn with_obsolete_terms(
+        mut self,
+        obsolete_terms: HashMap<String, ObsoleteReplacement>,
+    ) -> Self {
+        self.obsolete_terms = obsolete_terms
+            .into_iter()
+            .map(|(obsolete_id, replacement)| (obsolete_id.to_lowercase(), replacement))
+            .collect();
+        self
+    }
+
+    /// Returns the replacement of an obsolete term, if the id belongs to an obsolete term.
+    pub fn get_obsolete_replacement(&self, obsolete_id: &str) -> Option<&ObsoleteReplacement> {
+        self.obsolete_terms.get(&Self::normalize_key(obsolete_id))
+    }
+
+    /// Checks, that all term ids are part of this version of the ontology.
+    ///
+    /// A sample of the ids in the data is enough to notice a pinned version, which doesn't fit the data.
+    pub fn verify_terms(&self, term_ids: &[&str]) -> Result<(), RegistryError> {
+        match term_ids
+            .iter()
+            .find(|term_id| !self.id_to_label.contains_key(&Self::normalize_key(term_id)))
+        {
+            Some(term_id) => Err(RegistryError::TermNotInVersion {
+                term: term_id.to_string(),
+                version: self.ontology.version().to_string(),
+            }),
+            None => Ok(()),
         }
     }
 
@@ -112,9 +213,19 @@ impl OntologyBiDict {
         let mut label_to_id: HashMap<String, String> = HashMap::with_capacity(map_size);
         let mut synonym_to_id: HashMap<String, String> = HashMap::with_capacity(map_size);
         let mut id_to_label: HashMap<String, String> = HashMap::with_capacity(map_size);
+        let mut case_ambiguous_labels: HashMap<String, HashMap<String, String>> = HashMap::new();
 
         for term in ontology.iter_ontology_terms(ontology_prefix) {
-            label_to_id.insert(term.label().to_lowercase(), term.ontology_id().to_string());
+            let lowercase_label = term.label().to_lowercase();
+            if let Some(previous_id) =
+                label_to_id.insert(lowercase_label.clone(), term.ontology_id().to_string())
+                && previous_id != term.ontology_id()
+                && let Some(previous_label) = id_to_label.get(&previous_id.to_lowercase())
+            {
+                let candidates = case_ambiguous_labels.entry(lowercase_label).or_default();
+                candidates.insert(previous_label.clone(), previous_id);
+                candidates.insert(term.label().to_string(), term.ontology_id().to_string());
+            }
             term.iter_synonyms().for_each(|syn| {
                 synonym_to_id.insert(
                     syn.syn_name().to_lowercase(),
@@ -127,21 +238,29 @@ impl OntologyBiDict {
             );
         }
 
+        for (label, candidates) in case_ambiguous_labels.iter() {
+            warn!(
+                "The labels of the terms {:?} in {} only differ by case. '{label}' can only be resolved with the exact case of a label.",
+                candidates.values().collect::<Vec<_>>(),
+                ontology_ref.prefix_id()
+            );
+        }
+
         OntologyBiDict::new(
             ontology_ref.clone(),
             label_to_id,
             synonym_to_id,
             id_to_label,
         )
+        .with_case_ambiguous_labels(case_ambiguous_labels)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ontology::traits::HasVersion;
     use crate::test_suite::mocks::MockOntologyRegistry;
-    use crate::test_suite::ontology_mocking::HPO;
+    use crate::test_suite::ontology_mocking::{HPO, HPO_BIDICT};
     use crate::test_suite::phenopacket_component_generation::default_unit_oc;
     use crate::test_suite::resource_references::{HPO_REF, UO_REF};
     use ontology_registry::{FileType, OntologyRegistration, RegistryKey, Version};
@@ -176,12 +295,70 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn test_hpo_bidict_get_obsolete_replacement() {
+        assert_eq!(
+            HPO_BIDICT.get_obsolete_replacement("HP:0000004"),
+            Some(&ObsoleteReplacement::ReplacedBy("HP:0012823".to_string()))
+        );
+        assert_eq!(HPO_BIDICT.get_obsolete_replacement("HP:0000639"), None);
+    }
+
+    #[rstest]
+    fn test_hpo_bidict_verify_terms() {
+        assert!(
+            HPO_BIDICT
+                .verify_terms(&["HP:0000639", "hp:4000120"])
+                .is_ok()
+        );
+
+        let result = HPO_BIDICT.verify_terms(&["HP:0000639", "HP:9999999"]);
+        assert!(matches!(
+            result,
+            Err(RegistryError::TermNotInVersion { term, version })
+                if term == "HP:9999999" && version == HPO_BIDICT.reference().version()
+        ));
+    }
+
     #[rstest]
     fn test_hpo_bidict_get_id_by_label() {
         let hpo_dict = OntologyBiDict::from_ontology(HPO.clone(), &HPO_REF);
         assert_eq!(hpo_dict.get("Nystagmus").unwrap(), "HP:0000639".to_string());
     }
 
+    #[rstest]
+    fn test_hpo_bidict_get_id_by_label_case_insensitive() {
+        let hpo_dict = OntologyBiDict::from_ontology(HPO.clone(), &HPO_REF);
+        assert_eq!(
+            hpo_dict.get_id("nYSTAGMUS").unwrap(),
+            "HP:0000639".to_string()
+        );
+    }
+
+    #[rstest]
+    fn test_bidict_case_ambiguous_labels() {
+        let bidict = OntologyBiDict::new(
+            HPO_REF.clone(),
+            HashMap::from([("abc".to_string(), "HP:0000002".to_string())]),
+            HashMap::new(),
+            HashMap::new(),
+        )
+        .with_case_ambiguous_labels(HashMap::from([(
+            "abc".to_string(),
+            HashMap::from([
+                ("ABC".to_string(), "HP:0000001".to_string()),
+                ("Abc".to_string(), "HP:0000002".to_string()),
+            ]),
+        )]));
+
+        assert_eq!(bidict.get_id("ABC").unwrap(), "HP:0000001".to_string());
+        assert_eq!(bidict.get(" Abc ").unwrap(), "HP:0000002".to_string());
+        assert!(matches!(
+            bidict.get_id("abc"),
+            Err(BiDictError::AmbiguousLabel { ids, .. }) if ids == vec!["HP:0000001", "HP:0000002"]
+        ));
+    }
+
     #[rstest]
     fn test_hpo_bidict_get_id_by_synonym() {
         let hpo_dict = OntologyBiDict::from_ontology(HPO.clone(), &HPO_REF);
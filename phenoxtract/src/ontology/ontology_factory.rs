@@ -1,4 +1,6 @@
-use crate::ontology::error::FactoryError;
+use crate::ontology::error::{FactoryError, RegistryError};
+use crate::ontology::obo_ontology::OboOntology;
+use crate::ontology::obsolete_terms::{ObsoleteReplacement, parse_obographs_obsolete_terms};
 use crate::ontology::ontology_bidict::OntologyBiDict;
 use crate::ontology::resource_references::{KnownResourcePrefixes, ResourceRef};
 use crate::ontology::traits::{HasPrefixId, HasVersion, OntologyLike};
@@ -35,6 +37,7 @@ impl CacheKey {
 #[derive(Debug)]
 struct CachedOntology {
     ontology: Arc<dyn OntologyLike>,
+    obsolete_terms: HashMap<String, ObsoleteReplacement>,
     bidict: OnceLock<Arc<OntologyBiDict>>,
 }
 
@@ -42,7 +45,9 @@ struct CachedOntology {
 pub struct CachedOntologyFactory<OR: OntologyRegistration> {
     cache: HashMap<CacheKey, CachedOntology>,
     registry: OR,
-    metadata_provider: BioRegistryMetadataProvider,
+    /// Only created once it is needed, because it talks to the BioRegistry API.
+    metadata_provider: OnceLock<BioRegistryMetadataProvider>,
+    offline: bool,
 }
 
 /// A factory for creating and caching ontology instances.
@@ -57,6 +62,12 @@ pub struct CachedOntologyFactory<OR: OntologyRegistration> {
 /// Once an ontology is built, subsequent requests for the same ontology will return the
 /// cached instance, avoiding expensive I/O and parsing operations.
 ///
+/// # Offline Mode
+///
+/// In offline mode only ontologies, which are already in the registry on disk, are used. Nothing is downloaded and
+/// a missing ontology results in `RegistryError::OfflineCacheMiss`. Requesting the latest version picks the newest
+/// version on disk.
+///
 /// # Thread Safety
 ///
 /// The ontology instances themselves are wrapped in `Arc`, making them safe to share
@@ -82,29 +93,83 @@ pub struct CachedOntologyFactory<OR: OntologyRegistration> {
 /// ```
 impl<OR: OntologyRegistration> CachedOntologyFactory<OR> {
     pub fn new(registry: OR) -> Self {
+        Self::new_with_offline(registry, false)
+    }
+
+    /// Creates a factory, which never downloads ontologies if `offline` is set. See the section on offline mode above.
+    pub fn new_with_offline(registry: OR, offline: bool) -> Self {
         Self {
             cache: HashMap::new(),
             registry,
-            metadata_provider: BioRegistryMetadataProvider::default(),
+            metadata_provider: OnceLock::new(),
+            offline,
         }
     }
 
+    fn metadata_provider(&self) -> &BioRegistryMetadataProvider {
+        self.metadata_provider.get_or_init(Default::default)
+    }
+
     fn register(
         &self,
         ontology_ref: &ResourceRef,
         file_type: FileType,
     ) -> Result<impl Read, FactoryError> {
-        let reg_key = RegistryKey::new(
-            ontology_ref.prefix_id().to_lowercase(),
-            ontology_ref.clone().as_version(),
-            file_type,
-        );
+        let reg_key = if self.offline {
+            self.find_offline_registry_key(ontology_ref, file_type)?
+        } else {
+            RegistryKey::new(
+                ontology_ref.prefix_id().to_lowercase(),
+                ontology_ref.clone().as_version(),
+                file_type,
+            )
+        };
 
         self.registry
             .register(reg_key)
             .map_err(|err| Self::cant_build_err_wrap(err, ontology_ref))
     }
 
+    /// Looks up the registry key of an ontology file on disk. For the latest version, the newest version on disk is used.
+    ///
+    /// Versions are compared by their numeric components, which orders both dated versions (e.g. `2025-07-25`) and
+    /// release numbers (e.g. `v1.10` after `v1.9`) correctly. Versions with equal components are compared as strings.
+    fn find_offline_registry_key(
+        &self,
+        ontology_ref: &ResourceRef,
+        file_type: FileType,
+    ) -> Result<RegistryKey, FactoryError> {
+        let is_latest = ontology_ref.version() == "latest";
+
+        self.registry
+            .list()?
+            .into_iter()
+            .filter(|key| {
+                key.ontology_id().to_lowercase() == ontology_ref.prefix_id().to_lowercase()
+                    && key.file_type().as_file_ending() == file_type.as_file_ending()
+                    && (is_latest || key.version().to_string() == ontology_ref.version())
+            })
+            .max_by_key(|key| {
+                let version = key.version().to_string();
+                (Self::version_components(&version), version)
+            })
+            .ok_or_else(|| {
+                RegistryError::OfflineCacheMiss {
+                    ontology: ontology_ref.prefix_id().to_string(),
+                    version: ontology_ref.version().to_string(),
+                }
+                .into()
+            })
+    }
+
+    /// The numbers within a version, e.g. `[2025, 7, 25]` for `2025-07-25`.
+    fn version_components(version: &str) -> Vec<u64> {
+        version
+            .split(|c: char| !c.is_ascii_digit())
+            .filter_map(|component| component.parse().ok())
+            .collect()
+    }
+
     /// Retrieve from the Cache an Arc<FullCsrOntology> ontology, or build and then cache if it is not already there.
     fn build_ontolius_ontology(
         &mut self,
@@ -116,15 +181,21 @@ impl<OR: OntologyRegistration> CachedOntologyFactory<OR> {
             return Ok(onto.ontology.clone());
         }
 
-        let ontology_path = self.register(ontology_ref, FileType::Json)?;
+        let mut ontology_bytes = vec![];
+        self.register(ontology_ref, FileType::Json)?
+            .read_to_end(&mut ontology_bytes)
+            .map_err(|err| Self::cant_build_err_wrap(err, ontology_ref))?;
 
-        let ontology_build = Self::init_ontolius(ontology_path)
+        let ontology_build = Self::init_ontolius(ontology_bytes.as_slice())
+            .map_err(|err| Self::cant_build_err_wrap(err, ontology_ref))?;
+        let obsolete_terms = parse_obographs_obsolete_terms(ontology_bytes.as_slice())
             .map_err(|err| Self::cant_build_err_wrap(err, ontology_ref))?;
 
         self.cache.insert(
             cache_key,
             CachedOntology {
                 ontology: ontology_build.clone(),
+                obsolete_terms,
                 bidict: OnceLock::new(),
             },
         );
@@ -132,7 +203,7 @@ impl<OR: OntologyRegistration> CachedOntologyFactory<OR> {
         Ok(ontology_build)
     }
 
-    /// Retrieve from the Cache an Arc<OboDoc> ontology, or build and then cache if it is not already there.
+    /// Retrieve from the Cache an Arc<OboOntology> ontology, or build and then cache if it is not already there.
     fn build_obodoc_ontology(
         &mut self,
         ontology_ref: &ResourceRef,
@@ -146,13 +217,14 @@ impl<OR: OntologyRegistration> CachedOntologyFactory<OR> {
         let doc = {
             let ontology_path = self.register(ontology_ref, FileType::Obo)?;
             let mut reader = BufReader::new(ontology_path);
-            Arc::new(fastobo::from_reader(&mut reader)?)
+            Arc::new(OboOntology::new(fastobo::from_reader(&mut reader)?))
         };
 
         self.cache.insert(
             cache_key,
             CachedOntology {
                 ontology: doc.clone(),
+                obsolete_terms: HashMap::new(),
                 bidict: OnceLock::new(),
             },
         );
@@ -215,8 +287,18 @@ impl<OR: OntologyRegistration> CachedOntologyFactory<OR> {
             }
         }
 
+        if self.offline {
+            // Only falls back to the OBO file, if there is no JSON file on disk. Broken JSON files are an error.
+            return match self.build_ontolius_ontology(ontology_ref) {
+                Err(FactoryError::Registry(RegistryError::OfflineCacheMiss { .. })) => {
+                    self.build_obodoc_ontology(ontology_ref)
+                }
+                result => result,
+            };
+        }
+
         let ontology_metadata = self
-            .metadata_provider
+            .metadata_provider()
             .provide_metadata(ontology_ref.prefix_id())?;
 
         if ontology_metadata.json_file_location.is_some()
@@ -272,15 +354,30 @@ impl<OR: OntologyRegistration> CachedOntologyFactory<OR> {
             .expect("Just inserted");
 
         let bidict = cached.bidict.get_or_init(|| {
-            Arc::new(OntologyBiDict::from_ontology(
-                cached.ontology.clone(),
-                ontology_ref,
-            ))
+            Arc::new(
+                OntologyBiDict::from_ontology(cached.ontology.clone(), ontology_ref)
+                    .with_obsolete_terms(cached.obsolete_terms.clone()),
+            )
         });
 
         Ok(bidict.clone())
     }
 
+    /// Builds or retrieves the bidict of an ontology and checks, that the given term ids are part of the ontology version.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RegistryError::TermNotInVersion` for the first term id, which isn't part of the version.
+    pub fn build_verified_bidict(
+        &mut self,
+        ontology_ref: &ResourceRef,
+        term_ids: &[&str],
+    ) -> Result<Arc<OntologyBiDict>, FactoryError> {
+        let bidict = self.build_bidict(ontology_ref)?;
+        bidict.verify_terms(term_ids)?;
+        Ok(bidict)
+    }
+
     /// Loads the Human Phenotype Ontology (HPO).
     ///
     /// Convenience method for loading the HPO ontology without needing to construct
@@ -401,6 +498,8 @@ mod tests {
     use crate::test_suite::mocks::MockOntologyRegistry;
     use crate::test_suite::resource_references::UO_REF;
     use rstest::rstest;
+    use std::fs;
+    use tempfile::tempdir;
 
     #[rstest]
     fn test_build_ontology_success() {
@@ -477,6 +576,86 @@ mod tests {
         }));
     }
 
+    #[rstest]
+    #[case(ResourceRef::new("geno", Some("2025-07-25")))]
+    #[case(ResourceRef::from("hp"))]
+    fn test_build_ontology_offline(#[case] ontology: ResourceRef) {
+        let mut factory =
+            CachedOntologyFactory::new_with_offline(MockOntologyRegistry::default(), true);
+
+        factory.build_bidict(&ontology).unwrap();
+
+        assert!(factory.metadata_provider.get().is_none());
+    }
+
+    #[rstest]
+    fn test_build_ontology_offline_cache_miss() {
+        let ontology = ResourceRef::new("geno", Some("1999-01-01"));
+
+        let mut factory =
+            CachedOntologyFactory::new_with_offline(MockOntologyRegistry::default(), true);
+        let result = factory.build_ontology(&ontology);
+
+        assert!(matches!(
+            result,
+            Err(FactoryError::Registry(RegistryError::OfflineCacheMiss { ontology, version }))
+                if ontology == "geno" && version == "1999-01-01"
+        ));
+        assert!(factory.metadata_provider.get().is_none());
+    }
+
+    #[rstest]
+    fn test_build_ontology_offline_broken_json() {
+        let tmp_dir = tempdir().unwrap();
+        fs::write(tmp_dir.path().join("geno@2025-07-25.json"), "not json").unwrap();
+
+        let registry = MockOntologyRegistry::new(tmp_dir.path().to_path_buf());
+        let mut factory = CachedOntologyFactory::new_with_offline(registry, true);
+        let result = factory.build_ontology(&ResourceRef::from("geno"));
+
+        assert!(matches!(result, Err(FactoryError::CantBuild { .. })));
+    }
+
+    #[rstest]
+    #[case(&["2024-04-26", "2025-01-03", "2025-01-10"], "2025-01-10")]
+    #[case(&["v1-9", "v1-10", "v1-2"], "v1-10")]
+    fn test_find_offline_registry_key(#[case] versions: &[&str], #[case] expected: &str) {
+        let tmp_dir = tempdir().unwrap();
+        for version in versions {
+            fs::write(tmp_dir.path().join(format!("hp@{version}.json")), "{}").unwrap();
+        }
+        fs::write(tmp_dir.path().join("hp@2099-01-01.obo"), "").unwrap();
+        fs::write(tmp_dir.path().join("mondo@2099-01-01.json"), "{}").unwrap();
+
+        let registry = MockOntologyRegistry::new(tmp_dir.path().to_path_buf());
+        let factory = CachedOntologyFactory::new_with_offline(registry, true);
+        let key = factory
+            .find_offline_registry_key(&ResourceRef::from("hp"), FileType::Json)
+            .unwrap();
+
+        assert_eq!(key.ontology_id(), "hp");
+        assert_eq!(key.version().to_string(), expected);
+    }
+
+    #[rstest]
+    fn test_build_verified_bidict() {
+        let ontology = ResourceRef::new("hp", Some("2025-09-01"));
+        let mut factory = CachedOntologyFactory::new(MockOntologyRegistry::default());
+
+        assert!(
+            factory
+                .build_verified_bidict(&ontology, &["HP:0000639"])
+                .is_ok()
+        );
+
+        let result = factory.build_verified_bidict(&ontology, &["HP:0000639", "HP:9999999"]);
+        assert!(matches!(
+            result,
+            Err(FactoryError::Registry(RegistryError::TermNotInVersion { term, version }))
+                if term == "HP:9999999" && version == "2025-09-01"
+        ));
+    }
+
     #[rstest]
     fn test_build_bidict_other() {
         let ontology = ResourceRef::from("ro").with_latest();
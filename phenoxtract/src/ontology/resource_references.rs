@@ -77,6 +77,9 @@ pub(crate) enum KnownResourcePrefixes {
     UBERON,
     MAXO,
     NCIT,
+    ECO,
+    GSSO,
+    NCBITaxon,
 }
 
 /// Auto implementation of convenience functions to construct `ResourceRef`s from `KnownResourcePrefixes`
@@ -103,6 +106,9 @@ impl_resource_constructors_no_dep!(
     UBERON => uberon,
     MAXO => maxo,
     NCIT => ncit,
+    ECO => eco,
+    GSSO => gsso,
+    NCBITaxon => ncbi_taxon,
 );
 
 impl Display for KnownResourcePrefixes {
@@ -118,6 +124,9 @@ impl Display for KnownResourcePrefixes {
             KnownResourcePrefixes::UBERON => "UBERON",
             KnownResourcePrefixes::MAXO => "MAXO",
             KnownResourcePrefixes::NCIT => "NCIT",
+            KnownResourcePrefixes::ECO => "ECO",
+            KnownResourcePrefixes::GSSO => "GSSO",
+            KnownResourcePrefixes::NCBITaxon => "NCBITaxon",
         };
         write!(f, "{}", as_str)
     }
@@ -1,8 +1,8 @@
 use crate::ontology::traits::{OntologyLike, OntologyTermLike, SynonymLike};
 use fastobo::ast::{Ident, OboDoc, Synonym as FastOboSynonym, TermClause, TermFrame};
-use ontolius::Identified;
-use ontolius::ontology::OntologyTerms;
 use ontolius::ontology::csr::FullCsrOntology;
+use ontolius::ontology::{HierarchyWalks, OntologyTerms};
+use ontolius::{Identified, TermId};
 use ontolius::term::simple::SimpleTerm;
 use ontolius::term::{MinimalTerm, Synonym as OntoliusSynonym, Synonymous};
 use std::sync::Arc;
@@ -18,6 +18,17 @@ impl OntologyLike for FullCsrOntology {
                 .filter(move |t| t.prefix().eq_ignore_ascii_case(&ontology_prefix)),
         )
     }
+
+    fn ancestor_ids(&self, term_id: &str) -> Option<Vec<String>> {
+        let Ok(term_id) = term_id.parse::<TermId>() else {
+            return Some(vec![]);
+        };
+        Some(
+            self.iter_ancestor_ids(&term_id)
+                .map(|ancestor_id| ancestor_id.to_string())
+                .collect(),
+        )
+    }
 }
 
 impl OntologyLike for Arc<FullCsrOntology> {
@@ -27,6 +38,10 @@ impl OntologyLike for Arc<FullCsrOntology> {
     ) -> Box<dyn Iterator<Item = &'a dyn OntologyTermLike> + 'a> {
         self.as_ref().iter_ontology_terms(ontology_prefix)
     }
+
+    fn ancestor_ids(&self, term_id: &str) -> Option<Vec<String>> {
+        self.as_ref().ancestor_ids(term_id)
+    }
 }
 
 impl OntologyTermLike for SimpleTerm {
@@ -125,12 +140,12 @@ impl SynonymLike for FastOboSynonym {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ontology::obo_ontology::OboOntology;
     use crate::ontology::traits::{HasPrefixId, HasVersion};
     use crate::test_suite::mocks::MockOntologyRegistry;
     use crate::test_suite::phenopacket_component_generation::default_unit_oc;
     use crate::test_suite::resource_references::UO_REF;
     use fastobo::ast::SynonymScope;
-    use ontolius::TermId;
     use ontolius::io::OntologyLoaderBuilder;
     use ontolius::term::Definition;
     use ontology_registry::{FileType, OntologyRegistration, RegistryKey, Version};
@@ -197,6 +212,43 @@ mod tests {
         assert_iter_ontology_terms(&uo_ontolius(), number_of_uo_terms());
     }
 
+    fn assert_ancestor_ids(ontology: &impl OntologyLike) {
+        let mut ancestor_ids = ontology.ancestor_ids(&centimeter_id()).unwrap();
+        ancestor_ids.sort();
+
+        assert_eq!(ancestor_ids, vec!["UO:0000000", "UO:0000001", "UO:1000008"]);
+        assert_eq!(
+            ontology.is_descendant_of(&centimeter_id(), "UO:0000001"),
+            Some(true)
+        );
+        assert_eq!(
+            ontology.is_descendant_of(&centimeter_id(), &centimeter_id()),
+            Some(false)
+        );
+        assert_eq!(
+            ontology.is_descendant_of("UO:0000001", &centimeter_id()),
+            Some(false)
+        );
+    }
+
+    #[rstest]
+    fn test_obodoc_ancestor_ids() {
+        assert_eq!(uo_obodoc().ancestor_ids(&centimeter_id()), None);
+    }
+
+    #[rstest]
+    fn test_obo_ontology_ancestor_ids() {
+        let obo_ontology = OboOntology::new(Arc::unwrap_or_clone(uo_obodoc()));
+
+        assert_ancestor_ids(&obo_ontology);
+        assert_ancestor_ids(&obo_ontology);
+    }
+
+    #[rstest]
+    fn test_ontolius_ancestor_ids() {
+        assert_ancestor_ids(&uo_ontolius());
+    }
+
     fn centimeter_id() -> String {
         default_unit_oc().id
     }
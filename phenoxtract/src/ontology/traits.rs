@@ -16,6 +16,13 @@ pub trait BiDict: Send + Sync + Debug {
     fn get_id(&self, term: &str) -> Result<String, BiDictError>;
 
     fn reference(&self) -> &ResourceRef;
+
+    /// Returns up to `max_candidates` labels closest to `query` by edit distance, together with their distance.
+    ///
+    /// BiDicts, which can't enumerate their labels, don't suggest anything.
+    fn closest_labels(&self, _query: &str, _max_candidates: usize) -> Vec<(usize, String)> {
+        vec![]
+    }
 }
 
 pub trait OntologyTermLike {
@@ -41,4 +48,19 @@ pub trait OntologyLike: Debug + Send + Sync {
         &'a self,
         ontology_prefix: String,
     ) -> Box<dyn Iterator<Item = &'a dyn OntologyTermLike> + 'a>;
+
+    /// The ids of all terms, which the term is transitively an `is_a` subclass of, excluding the term itself.
+    ///
+    /// Ontologies, which don't know their hierarchy, return `None`.
+    fn ancestor_ids(&self, _term_id: &str) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Whether `ancestor_id` is one of the ancestors of `term_id`. A term is not a descendant of itself.
+    ///
+    /// Returns `None`, if the ontology doesn't know its hierarchy.
+    fn is_descendant_of(&self, term_id: &str, ancestor_id: &str) -> Option<bool> {
+        self.ancestor_ids(term_id)
+            .map(|ancestor_ids| ancestor_ids.iter().any(|id| id == ancestor_id))
+    }
 }
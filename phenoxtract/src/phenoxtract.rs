@@ -1,18 +1,96 @@
 use crate::Pipeline;
 use crate::error::PipelineError;
 use crate::extract::DataSource;
+use crate::load::FileSystemLoader;
+use crate::load::traits::Loadable;
+use crate::transform::cohort_summary::CohortSummary;
+use crate::validation::lint_report::LintReport;
+use phenopackets::schema::v2::Phenopacket;
+use std::path::PathBuf;
 
 #[derive(PartialEq, Debug)]
 pub struct Phenoxtract {
     pub(crate) pipeline: Pipeline,
     pub(crate) data_sources: Vec<DataSource>,
+    /// Further cohorts, each with their own pipeline and data sources. They are run after the main cohort.
+    pub(crate) cohorts: Vec<Phenoxtract>,
+}
+
+/// The phenopackets of all cohorts of a run, together with the lint report and the summary of every cohort.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunOutput {
+    pub phenopackets: Vec<Phenopacket>,
+    /// The lint report of the main cohort, followed by the reports of the further cohorts.
+    pub lint_reports: Vec<LintReport>,
+    /// The summary of the main cohort, followed by the summaries of the further cohorts.
+    pub cohort_summaries: Vec<CohortSummary>,
+}
+
+impl RunOutput {
+    /// Whether the lint report of any cohort has an error.
+    pub fn has_lint_errors(&self) -> bool {
+        self.lint_reports.iter().any(LintReport::has_errors)
+    }
+
+    fn extend(&mut self, other: RunOutput) {
+        self.phenopackets.extend(other.phenopackets);
+        self.lint_reports.extend(other.lint_reports);
+        self.cohort_summaries.extend(other.cohort_summaries);
+    }
 }
 
 impl Phenoxtract {
+    /// Runs the main cohort and all further cohorts and loads their phenopackets with the configured loaders.
+    ///
+    /// The lint reports of the cohorts are available via [`Phenoxtract::lint_reports`] afterwards, also if a lint
+    /// error aborted the run.
     pub fn run(&mut self) -> Result<(), PipelineError> {
         self.pipeline.run(self.data_sources.as_mut_slice())?;
+
+        for cohort in self.cohorts.iter_mut() {
+            cohort.run()?;
+        }
         Ok(())
     }
+
+    /// Runs the main cohort and all further cohorts and returns their phenopackets, lint reports and summaries.
+    ///
+    /// The configured loaders are not used, so nothing is written. This is the entry point for embedding
+    /// PhenoXtract into other programs.
+    pub fn run_in_memory(&mut self) -> Result<RunOutput, PipelineError> {
+        let phenopackets = self
+            .pipeline
+            .extract_and_transform(self.data_sources.as_mut_slice())?;
+        let mut output = RunOutput {
+            lint_reports: vec![self.pipeline.lint_report().clone()],
+            cohort_summaries: vec![self.pipeline.cohort_summary(&phenopackets)],
+            phenopackets,
+        };
+
+        for cohort in self.cohorts.iter_mut() {
+            output.extend(cohort.run_in_memory()?);
+        }
+        Ok(output)
+    }
+
+    /// Like [`Phenoxtract::run_in_memory`], but also writes the phenopackets of all cohorts as JSON files into
+    /// `output_dir`, instead of using the configured loaders. The directory is created, if it doesn't exist.
+    pub fn run_to_dir(
+        &mut self,
+        output_dir: impl Into<PathBuf>,
+    ) -> Result<RunOutput, PipelineError> {
+        let output = self.run_in_memory()?;
+        FileSystemLoader::new(output_dir.into(), true).load(&output.phenopackets)?;
+        Ok(output)
+    }
+
+    /// The lint reports of the last run of the main cohort and the further cohorts, in this order. Cohorts, which
+    /// weren't run yet, have an empty report.
+    pub fn lint_reports(&self) -> Vec<&LintReport> {
+        std::iter::once(self.pipeline.lint_report())
+            .chain(self.cohorts.iter().flat_map(|cohort| cohort.lint_reports()))
+            .collect()
+    }
 }
 
 impl Phenoxtract {
@@ -20,6 +98,145 @@ impl Phenoxtract {
         Phenoxtract {
             pipeline,
             data_sources,
+            cohorts: vec![],
         }
     }
+
+    pub fn add_cohort(&mut self, pipeline: Pipeline, data_sources: Vec<DataSource>) {
+        self.cohorts.push(Phenoxtract::new(pipeline, data_sources));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::context::Context;
+    use crate::config::table_context::{SeriesContext, TableContext};
+    use crate::config::traits::SeriesContextBuilding;
+    use crate::extract::CsvDataSource;
+    use crate::extract::extraction_config::ExtractionConfig;
+    use crate::load::FileSystemLoader;
+    use crate::transform::collecting::cdf_collector_broker::CdfCollectorBroker;
+    use crate::transform::phenopacket_builder::BuilderMetaData;
+    use crate::transform::transform_context::TransformContext;
+    use crate::transform::{PhenopacketBuilder, TransformerModule};
+    use pivotal::hgnc::MockHGNCClient;
+    use pivotal::hgvs::MockHGVSClient;
+    use rstest::rstest;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    fn build_cohort(
+        cohort_name: &str,
+        dir: &Path,
+        patient_ids: &[&str],
+    ) -> (Pipeline, Vec<DataSource>, PathBuf) {
+        let csv_path = dir.join(format!("{cohort_name}.csv"));
+        let rows: Vec<String> = patient_ids.iter().map(|id| format!("{id},MALE")).collect();
+        fs::write(&csv_path, format!("patient_id,sex\n{}\n", rows.join("\n"))).unwrap();
+
+        let data_source = DataSource::Csv(CsvDataSource::new(
+            csv_path,
+            None,
+            TableContext::new(
+                cohort_name.to_string(),
+                vec![
+                    SeriesContext::from_identifier("patient_id")
+                        .with_data_context(Context::SubjectId),
+                    SeriesContext::from_identifier("sex").with_data_context(Context::SubjectSex),
+                ],
+            ),
+            ExtractionConfig::new(cohort_name.to_string(), true, true),
+        ));
+
+        let ctx = TransformContext::builder(
+            BuilderMetaData::new(cohort_name, "PhenoXtract Test Suite", "Someone"),
+            Arc::new(MockHGNCClient::default()),
+            Arc::new(MockHGVSClient::default()),
+        )
+        .build();
+        let tf_module = TransformerModule::new(
+            vec![],
+            CdfCollectorBroker::with_default_collectors(PhenopacketBuilder::new(ctx)),
+        );
+
+        let out_dir = dir.join(format!("{cohort_name}_out"));
+        let loader = Box::new(FileSystemLoader::new(out_dir.clone(), true));
+
+        (Pipeline::new(tf_module, loader), vec![data_source], out_dir)
+    }
+
+    fn output_file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[rstest]
+    fn test_run_multiple_cohorts_with_isolated_outputs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        let (pipeline_a, data_sources_a, out_dir_a) =
+            build_cohort("cohort_a", temp_dir.path(), &["P1", "P2"]);
+        let (pipeline_b, data_sources_b, out_dir_b) =
+            build_cohort("cohort_b", temp_dir.path(), &["P1"]);
+
+        let mut phenoxtract = Phenoxtract::new(pipeline_a, data_sources_a);
+        phenoxtract.add_cohort(pipeline_b, data_sources_b);
+        phenoxtract.run().unwrap();
+
+        assert_eq!(
+            output_file_names(&out_dir_a),
+            vec!["cohort_a-P1.json", "cohort_a-P2.json"]
+        );
+        assert_eq!(output_file_names(&out_dir_b), vec!["cohort_b-P1.json"]);
+        assert_eq!(phenoxtract.lint_reports().len(), 2);
+    }
+
+    #[rstest]
+    fn test_run_in_memory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        let (pipeline_a, data_sources_a, out_dir_a) =
+            build_cohort("cohort_a", temp_dir.path(), &["P1", "P2"]);
+        let (pipeline_b, data_sources_b, out_dir_b) =
+            build_cohort("cohort_b", temp_dir.path(), &["P1"]);
+
+        let mut phenoxtract = Phenoxtract::new(pipeline_a, data_sources_a);
+        phenoxtract.add_cohort(pipeline_b, data_sources_b);
+        let output = phenoxtract.run_in_memory().unwrap();
+
+        let mut ids: Vec<&str> = output.phenopackets.iter().map(|pp| pp.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["cohort_a-P1", "cohort_a-P2", "cohort_b-P1"]);
+        assert_eq!(output.lint_reports.len(), 2);
+        assert_eq!(
+            output
+                .cohort_summaries
+                .iter()
+                .map(|summary| summary.n_phenopackets)
+                .collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert!(!out_dir_a.exists());
+        assert!(!out_dir_b.exists());
+    }
+
+    #[rstest]
+    fn test_run_to_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (pipeline, data_sources, out_dir) = build_cohort("cohort_a", temp_dir.path(), &["P1"]);
+        let custom_dir = temp_dir.path().join("custom_out");
+
+        let mut phenoxtract = Phenoxtract::new(pipeline, data_sources);
+        let output = phenoxtract.run_to_dir(&custom_dir).unwrap();
+
+        assert_eq!(output.phenopackets.len(), 1);
+        assert_eq!(output_file_names(&custom_dir), vec!["cohort_a-P1.json"]);
+        assert!(!out_dir.exists());
+    }
 }
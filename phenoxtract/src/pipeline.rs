@@ -1,18 +1,32 @@
+use crate::config::TableContext;
 use crate::error::PipelineError;
 use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
 use crate::extract::traits::Extractable;
 use crate::load::traits::Loadable;
+use crate::ontology::traits::OntologyLike;
 
+use crate::transform::clock::{Clock, FixedClock};
+use crate::transform::cohort_summary::CohortSummary;
 use crate::transform::strategies::traits::Strategy;
 use crate::transform::transform_module::TransformerModule;
-use log::info;
+use crate::validation::lint_report::{LintFinding, LintReport, Severity};
+use crate::validation::linter::{LintLevel, Linter, PlausibleRange};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
 use phenopackets::schema::v2::Phenopacket;
+use polars::prelude::DataFrame;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use validator::Validate;
 
 #[derive(Debug)]
 pub struct Pipeline {
     pub(crate) transformer_module: TransformerModule,
     pub(crate) loader_module: Box<dyn Loadable>,
+    /// Checks the built phenopackets.
+    pub(crate) linter: Linter,
+    /// The findings of the checks of the phenopackets, which were built in the last run.
+    pub(crate) lint_report: LintReport,
 }
 
 impl Pipeline {
@@ -23,6 +37,8 @@ impl Pipeline {
         Pipeline {
             transformer_module,
             loader_module,
+            linter: Linter::default(),
+            lint_report: LintReport::default(),
         }
     }
 
@@ -33,19 +49,199 @@ impl Pipeline {
         self.transformer_module.insert_strategy(idx, strategy);
     }
 
+    /// Sets the clock, which is used for time based fields like `MetaData.created`.
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.transformer_module
+            .broker
+            .phenopacket_builder_mut()
+            .ctx_mut()
+            .set_clock(clock);
+    }
+
+    /// Makes the output of this pipeline reproducible: `MetaData.created` is fixed to `created`.
+    /// All other fields, including the variation descriptor ids, already only depend on the input,
+    /// so two runs over the same input produce identical phenopackets.
+    pub fn set_deterministic(&mut self, created: DateTime<Utc>) {
+        self.set_clock(Arc::new(FixedClock::new(created)));
+    }
+
+    /// Leaves `MetaData.created` empty, e.g. to compare the output of different runs byte for byte.
+    pub fn set_omit_created(&mut self, omit_created: bool) {
+        self.transformer_module
+            .broker
+            .phenopacket_builder_mut()
+            .ctx_mut()
+            .set_omit_created(omit_created);
+    }
+
+    /// Makes phenopackets, which reference different versions of the same resource, an error instead of a warning.
+    pub fn set_strict_resource_versions(&mut self, strict_resource_versions: bool) {
+        self.linter.set_strict_resource_versions(strict_resource_versions);
+    }
+
+    /// Checks the onset ontology classes of the built phenopackets against the hierarchy of the HPO.
+    pub fn set_hpo(&mut self, hpo: Arc<dyn OntologyLike>) {
+        self.linter.set_hpo(hpo);
+    }
+
+    /// Overrides the severity of single lint rules (e.g. `"missing_resource"`), or turns them off.
+    pub fn set_lint_rules(&mut self, lint_rules: HashMap<String, LintLevel>) {
+        self.linter.set_rule_levels(lint_rules);
+    }
+
+    /// Aborts the run on the first lint finding, which is at least as severe as `fail_on`. Errors by default.
+    pub fn set_fail_on(&mut self, fail_on: Severity) {
+        self.linter.set_fail_on(fail_on);
+    }
+
+    /// Reports quantitative measurements, whose value is outside of the plausible range of their assay
+    /// (e.g. `"LOINC:718-7"`), as warnings.
+    pub fn set_plausible_measurement_ranges(
+        &mut self,
+        plausible_measurement_ranges: HashMap<String, PlausibleRange>,
+    ) {
+        self.linter.set_plausible_measurement_ranges(plausible_measurement_ranges);
+    }
+
+    /// Extracts, transforms and loads the data.
+    ///
+    /// If the loader resumes runs, every phenopacket is loaded as soon as it is built,
+    /// and patients, whose phenopacket an earlier run already loaded, are skipped.
+    /// Resumed runs read every data source at once, so they can't be combined with data sources, which are read in chunks.
     pub fn run(
         &mut self,
-        extractables: &mut [impl Extractable + Validate],
+        extractables: &mut [impl Extractable],
     ) -> Result<(), PipelineError> {
-        let data = self.extract(extractables)?;
-        let phenopackets = self.transform(data)?;
+        if let Some(loaded_ids) = self.loader_module.loaded_phenopacket_ids()? {
+            if extractables
+                .iter()
+                .any(|extractable| extractable.extract_chunks().is_some())
+            {
+                return Err(PipelineError::ChunkedResume);
+            }
+            let data = self.extract(extractables)?;
+            return self.transform_and_load_incrementally(data, &loaded_ids);
+        }
+
+        let phenopackets = self.extract_and_transform(extractables)?;
         self.load(phenopackets.as_slice())?;
         Ok(())
     }
 
+    fn transform_and_load_incrementally(
+        &mut self,
+        data: Vec<ContextualizedDataFrame>,
+        loaded_ids: &HashSet<String>,
+    ) -> Result<(), PipelineError> {
+        info!(
+            "Starting Transformation. Skipping {} already loaded Phenopackets",
+            loaded_ids.len()
+        );
+        data.iter().try_for_each(|t| t.validate())?;
+
+        let loader = &self.loader_module;
+        let linter = &self.linter;
+        let mut lint_report = LintReport::default();
+        let mut known_resources = vec![];
+        let mut n_loaded = 0;
+        let result = self
+            .transformer_module
+            .run_incrementally(data, loaded_ids, |phenopacket| {
+                let mut findings = linter.lint_phenopacket(&phenopacket);
+                findings.extend(linter.lint_resource_versions(&phenopacket, &mut known_resources));
+                Self::record_findings(linter, &mut lint_report, findings)?;
+                loader.load(std::slice::from_ref(&phenopacket))?;
+                n_loaded += 1;
+                Ok::<(), PipelineError>(())
+            });
+        let dropped_field_result =
+            Self::record_findings(&self.linter, &mut lint_report, self.dropped_field_findings());
+        self.lint_report = lint_report;
+        result?;
+        dropped_field_result?;
+
+        info!("Concluded Transformation and Loading of {n_loaded} Phenopackets");
+        Ok(())
+    }
+
+    /// Extracts and transforms the data into phenopackets, without handing them to the loader.
+    ///
+    /// Data sources, which are extracted in chunks, are transformed chunk by chunk, so that only one of their chunks
+    /// has to be in memory at a time. Strategies, which read other tables, see every chunk together with the tables,
+    /// which are read at once. The phenopackets are built and checked against the cohort size guard once all chunks
+    /// are collected.
+    pub fn extract_and_transform(
+        &mut self,
+        extractables: &mut [impl Extractable],
+    ) -> Result<Vec<Phenopacket>, PipelineError> {
+        if extractables
+            .iter()
+            .any(|extractable| extractable.extract_chunks().is_some())
+        {
+            return self.extract_and_transform_in_chunks(extractables);
+        }
+
+        let data = self.extract(extractables)?;
+        self.transform(data)
+    }
+
+    fn extract_and_transform_in_chunks(
+        &mut self,
+        extractables: &mut [impl Extractable],
+    ) -> Result<Vec<Phenopacket>, PipelineError> {
+        info!("Starting chunked extraction and transformation");
+        extractables.validate()?;
+
+        let mut tables = vec![];
+        let mut chunked_sources = vec![];
+        for extractable in extractables.iter() {
+            match extractable.extract_chunks() {
+                Some(chunks) => chunked_sources.push(chunks),
+                None => tables.extend(extractable.extract()?),
+            }
+        }
+
+        tables.iter().try_for_each(|t| t.validate())?;
+        let chunk_context = self.transformer_module.collect_unchunked(tables)?;
+
+        let mut n_chunks = 0;
+        for chunks in chunked_sources {
+            for chunk in chunks {
+                let chunk = chunk?;
+                chunk.validate()?;
+                self.transformer_module
+                    .collect_chunk(chunk, &chunk_context)?;
+                n_chunks += 1;
+            }
+        }
+
+        let phenopackets = self.transformer_module.build()?;
+        self.lint(&phenopackets)?;
+        info!(
+            "Concluded Transformation of {n_chunks} chunks. Found {:?} Phenopackets",
+            phenopackets.len()
+        );
+        Ok(phenopackets)
+    }
+
+    /// Transforms tables, which are already in memory (e.g. the result of a database query), into phenopackets.
+    ///
+    /// No data source is read and the phenopackets are not handed to the loader. The data frames are expected
+    /// to be in the shape extraction produces: one column per series context and, usually, one row per patient.
+    pub fn transform_data_frames(
+        &mut self,
+        tables: Vec<(TableContext, DataFrame)>,
+    ) -> Result<Vec<Phenopacket>, PipelineError> {
+        let data = tables
+            .into_iter()
+            .map(|(context, data)| ContextualizedDataFrame::new(context, data))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.transform(data)
+    }
+
     pub fn extract(
         &self,
-        extractables: &mut [impl Extractable + Validate],
+        extractables: &mut [impl Extractable],
     ) -> Result<Vec<ContextualizedDataFrame>, PipelineError> {
         info!("Starting extract");
         extractables.validate()?;
@@ -70,6 +266,7 @@ impl Pipeline {
         data.iter().try_for_each(|t| t.validate())?;
 
         let phenopackets = self.transformer_module.run(data)?;
+        self.lint(&phenopackets)?;
         info!(
             "Concluded Transformation. Found {:?} Phenopackets",
             phenopackets.len()
@@ -77,6 +274,62 @@ impl Pipeline {
         Ok(phenopackets)
     }
 
+    /// The findings of the checks of the phenopackets, which were built in the last run, e.g. missing resources,
+    /// invalid onsets or unresolved terms.
+    pub fn lint_report(&self) -> &LintReport {
+        &self.lint_report
+    }
+
+    /// Checks the phenopackets and replaces the lint report with the findings. The first finding, which is at least
+    /// as severe as the `fail_on` threshold, aborts the run.
+    fn lint(&mut self, phenopackets: &[Phenopacket]) -> Result<(), PipelineError> {
+        let mut findings = self.linter.lint_cohort(phenopackets);
+        findings.extend(self.dropped_field_findings());
+
+        let mut lint_report = LintReport::default();
+        let result = Self::record_findings(&self.linter, &mut lint_report, findings);
+        self.lint_report = lint_report;
+        result
+    }
+
+    /// The values, which were dropped, because the data is faulty, e.g. unresolved terms in lenient mode.
+    fn dropped_field_findings(&self) -> Vec<LintFinding> {
+        let dropped_fields = self
+            .transformer_module
+            .broker
+            .phenopacket_builder()
+            .dropped_fields();
+        self.linter.lint_dropped_fields(dropped_fields)
+    }
+
+    /// Logs the findings and adds them to the lint report. The first finding, which fails the run, is returned.
+    fn record_findings(
+        linter: &Linter,
+        lint_report: &mut LintReport,
+        findings: Vec<LintFinding>,
+    ) -> Result<(), PipelineError> {
+        findings.iter().for_each(|finding| warn!("{finding}"));
+        let first_failure = linter.first_failure(&findings).cloned();
+        lint_report.extend(findings);
+
+        match first_failure {
+            Some(first_failure) => Err(PipelineError::Lint(first_failure)),
+            None => Ok(()),
+        }
+    }
+
+    /// Summarises the phenopackets, which this pipeline built, for a quick QC, e.g. the number of patients per sex
+    /// and the number of terms, which couldn't be resolved.
+    pub fn cohort_summary(&self, phenopackets: &[Phenopacket]) -> CohortSummary {
+        CohortSummary::new(
+            phenopackets,
+            self.transformer_module
+                .broker
+                .phenopacket_builder()
+                .dropped_fields(),
+        )
+    }
+
     pub fn load(&self, phenopackets: &[Phenopacket]) -> Result<(), PipelineError> {
         self.loader_module.load(phenopackets)?;
 
@@ -89,5 +342,631 @@ impl PartialEq for Pipeline {
     fn eq(&self, other: &Self) -> bool {
         self.transformer_module == other.transformer_module
             && format!("{:?}", self.loader_module) == format!("{:?}", other.loader_module)
+            && self.linter == other.linter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::context::{Context, TimeElementType};
+    use crate::config::table_context::SeriesContext;
+    use crate::config::traits::SeriesContextBuilding;
+    use crate::extract::csv_data_source::CsvDataSource;
+    use crate::extract::data_source::DataSource;
+    use crate::extract::error::ExtractionError;
+    use crate::extract::extraction_config::ExtractionConfig;
+    use crate::load::FileSystemLoader;
+    use crate::test_suite::component_building::default_builder_context;
+    use crate::test_suite::phenopacket_component_generation::default_cohort_id;
+    use crate::test_suite::phenopacket_component_generation::default_phenotype_oc;
+    use crate::test_suite::resource_references::hp_meta_data_resource;
+    use crate::transform::collecting::cdf_collector_broker::CdfCollectorBroker;
+    use crate::transform::error::TransformError;
+    use crate::transform::strategies::date_to_age::DateToAgeStrategy;
+    use crate::transform::strategies::deduplicate_rows::{DeduplicateRows, DeduplicateRowsStrategy};
+    use crate::transform::{PhenopacketBuilder, TransformerModule};
+    use phenopackets::schema::v2::core::{Individual, MetaData, PhenotypicFeature, Resource, Sex};
+    use polars::prelude::Column;
+    use rstest::rstest;
+    use std::fs;
+    use std::path::PathBuf;
+    use validator::ValidationErrors;
+
+    #[rstest]
+    fn test_transform_data_frames() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let out_dir = temp_dir.path().join("out");
+        let mut pipeline = Pipeline::new(
+            TransformerModule::new(
+                vec![],
+                CdfCollectorBroker::with_default_collectors(PhenopacketBuilder::new(
+                    default_builder_context(),
+                )),
+            ),
+            Box::new(FileSystemLoader::new(out_dir.clone(), true)),
+        );
+
+        let hpo_id = default_phenotype_oc().id;
+        let tc = TableContext::new(
+            "in_memory",
+            vec![
+                SeriesContext::from_identifier("patient_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("sex").with_data_context(Context::SubjectSex),
+                SeriesContext::from_identifier("phenotype").with_data_context(Context::Hpo),
+            ],
+        );
+        let df = DataFrame::new(
+            2,
+            vec![
+                Column::new("patient_id".into(), ["P1", "P2"]),
+                Column::new("sex".into(), ["MALE", "FEMALE"]),
+                Column::new("phenotype".into(), [hpo_id.as_str(), hpo_id.as_str()]),
+            ],
+        )
+        .unwrap();
+
+        let mut phenopackets = pipeline.transform_data_frames(vec![(tc, df)]).unwrap();
+        phenopackets.sort_by(|a, b| a.id.cmp(&b.id));
+
+        assert_eq!(phenopackets.len(), 2);
+        assert_eq!(
+            phenopackets[1].subject.as_ref().unwrap().sex,
+            Sex::Female as i32
+        );
+        assert_eq!(
+            phenopackets[0].phenotypic_features[0].r#type,
+            Some(default_phenotype_oc())
+        );
+        assert!(!out_dir.exists());
+    }
+
+    #[rstest]
+    fn test_cohort_summary() {
+        let mut pipeline = in_memory_pipeline();
+        let hpo_id = default_phenotype_oc().id;
+        let tc = TableContext::new(
+            "in_memory",
+            vec![
+                SeriesContext::from_identifier("patient_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("sex").with_data_context(Context::SubjectSex),
+                SeriesContext::from_identifier("phenotype").with_data_context(Context::Hpo),
+            ],
+        );
+        let df = DataFrame::new(
+            3,
+            vec![
+                Column::new("patient_id".into(), ["P1", "P2", "P3"]),
+                Column::new("sex".into(), ["MALE", "FEMALE", "MALE"]),
+                Column::new(
+                    "phenotype".into(),
+                    [Some(hpo_id.as_str()), Some(hpo_id.as_str()), None],
+                ),
+            ],
+        )
+        .unwrap();
+
+        let phenopackets = pipeline.transform_data_frames(vec![(tc, df)]).unwrap();
+        let summary = pipeline.cohort_summary(&phenopackets);
+
+        assert_eq!(summary.n_phenopackets, 3);
+        assert_eq!(summary.n_patients_per_sex["MALE"], 2);
+        assert_eq!(summary.n_patients_per_sex["FEMALE"], 1);
+        assert_eq!(summary.n_patients_with_interpretation, 0);
+        assert_eq!(summary.most_frequent_hpo_terms[0].id, hpo_id);
+        assert_eq!(summary.most_frequent_hpo_terms[0].n_patients, 2);
+        assert_eq!(summary.n_unresolved_terms, 0);
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn test_check_resource_versions(#[case] strict_resource_versions: bool) {
+        let mut pipeline = in_memory_pipeline();
+        pipeline.set_strict_resource_versions(strict_resource_versions);
+        let phenopacket_with_hpo_version = |id: &str, version: &str| Phenopacket {
+            id: id.to_string(),
+            meta_data: Some(MetaData {
+                resources: vec![Resource {
+                    version: version.to_string(),
+                    ..hp_meta_data_resource()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let phenopackets = vec![
+            phenopacket_with_hpo_version("P1", "2024-04-26"),
+            phenopacket_with_hpo_version("P2", "2025-05-06"),
+        ];
+
+        let result = pipeline.lint(&phenopackets);
+
+        assert_eq!(result.is_err(), strict_resource_versions);
+        let findings = pipeline.lint_report().findings();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, "inconsistent_resource_versions");
+        assert_eq!(findings[0].patient_id, None);
+        assert_eq!(
+            findings[0].severity,
+            if strict_resource_versions {
+                Severity::Error
+            } else {
+                Severity::Warning
+            }
+        );
+    }
+
+    fn phenopacket_with_conflicting_statuses() -> Phenopacket {
+        let phenotype = |excluded: bool| PhenotypicFeature {
+            r#type: Some(default_phenotype_oc()),
+            excluded,
+            ..Default::default()
+        };
+        Phenopacket {
+            id: "cohort-P1".to_string(),
+            subject: Some(Individual {
+                id: "P1".to_string(),
+                ..Default::default()
+            }),
+            phenotypic_features: vec![phenotype(false), phenotype(true)],
+            meta_data: Some(MetaData {
+                resources: vec![hp_meta_data_resource()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    fn test_lint_report() {
+        let mut pipeline = in_memory_pipeline();
+
+        let result = pipeline.lint(&[phenopacket_with_conflicting_statuses()]);
+
+        assert!(matches!(result, Err(PipelineError::Lint(_))));
+        let lint_report = pipeline.lint_report();
+        assert_eq!(lint_report.findings().len(), 1);
+        let finding = &lint_report.findings()[0];
+        assert_eq!(finding.rule, "conflicting_observation_status");
+        assert_eq!(finding.severity, Severity::Error);
+        assert_eq!(finding.patient_id.as_deref(), Some("P1"));
+    }
+
+    #[rstest]
+    #[case(LintLevel::Warn, vec![Severity::Warning])]
+    #[case(LintLevel::Off, vec![])]
+    fn test_lint_rules_downgrade(
+        #[case] level: LintLevel,
+        #[case] expected_severities: Vec<Severity>,
+    ) {
+        let mut pipeline = in_memory_pipeline();
+        pipeline.set_lint_rules(HashMap::from([(
+            "conflicting_observation_status".to_string(),
+            level,
+        )]));
+
+        let result = pipeline.lint(&[phenopacket_with_conflicting_statuses()]);
+
+        assert!(result.is_ok());
+        let severities: Vec<Severity> = pipeline
+            .lint_report()
+            .findings()
+            .iter()
+            .map(|finding| finding.severity)
+            .collect();
+        assert_eq!(severities, expected_severities);
+    }
+
+    #[rstest]
+    #[case(Severity::Error, false)]
+    #[case(Severity::Warning, true)]
+    fn test_fail_on(#[case] fail_on: Severity, #[case] aborts: bool) {
+        let mut pipeline = in_memory_pipeline();
+        pipeline.set_fail_on(fail_on);
+        let phenopacket_without_resources = Phenopacket {
+            id: "cohort-P1".to_string(),
+            phenotypic_features: vec![PhenotypicFeature {
+                r#type: Some(default_phenotype_oc()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let result = pipeline.lint(&[phenopacket_without_resources]);
+
+        match result {
+            Err(PipelineError::Lint(finding)) => {
+                assert!(aborts);
+                assert_eq!(finding.rule, "missing_resource");
+                assert_eq!(finding.severity, Severity::Warning);
+            }
+            Err(err) => panic!("Unexpected error: {err}"),
+            Ok(()) => assert!(!aborts),
+        }
+        assert_eq!(pipeline.lint_report().findings().len(), 1);
+    }
+
+    #[rstest]
+    fn test_null_values_of_series_context() {
+        let tc = TableContext::new(
+            "in_memory",
+            vec![
+                SeriesContext::from_identifier("patient_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("vital_status")
+                    .with_data_context(Context::VitalStatus)
+                    .with_null_values(vec!["unknown".to_string()]),
+            ],
+        );
+        let df = DataFrame::new(
+            2,
+            vec![
+                Column::new("patient_id".into(), ["P1", "P2"]),
+                Column::new("vital_status".into(), ["ALIVE", "unknown"]),
+            ],
+        )
+        .unwrap();
+
+        let mut phenopackets = in_memory_pipeline()
+            .transform_data_frames(vec![(tc, df)])
+            .unwrap();
+        phenopackets.sort_by(|a, b| a.id.cmp(&b.id));
+
+        assert!(
+            phenopackets[0]
+                .subject
+                .as_ref()
+                .unwrap()
+                .vital_status
+                .is_some()
+        );
+        assert!(
+            phenopackets
+                .get(1)
+                .and_then(|pp| pp.subject.as_ref())
+                .is_none_or(|subject| subject.vital_status.is_none())
+        );
+    }
+
+    fn in_memory_pipeline() -> Pipeline {
+        let mut pipeline = Pipeline::new(
+            TransformerModule::new(
+                vec![],
+                CdfCollectorBroker::with_default_collectors(PhenopacketBuilder::new(
+                    default_builder_context(),
+                )),
+            ),
+            Box::new(FileSystemLoader::new(PathBuf::from("unused"), false)),
+        );
+        pipeline.set_omit_created(true);
+        pipeline
+    }
+
+    #[rstest]
+    #[case(7)]
+    #[case(2)]
+    #[case(1000)]
+    fn test_chunked_csv_matches_eager_extraction(#[case] chunk_size: usize) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let csv_path = temp_dir.path().join("patients.csv");
+        let hpo_id = default_phenotype_oc().id;
+        let mut csv = "patient_id,sex,phenotype\n".to_string();
+        for patient_idx in 0..60 {
+            let sex = if patient_idx % 2 == 0 {
+                "MALE"
+            } else {
+                "FEMALE"
+            };
+            for _ in 0..3 {
+                csv.push_str(&format!("P{patient_idx:03},{sex},{hpo_id}\n"));
+            }
+        }
+        fs::write(&csv_path, csv).unwrap();
+
+        let csv_source = CsvDataSource::new(
+            csv_path,
+            None,
+            TableContext::new(
+                "patients",
+                vec![
+                    SeriesContext::from_identifier("patient_id")
+                        .with_data_context(Context::SubjectId),
+                    SeriesContext::from_identifier("sex").with_data_context(Context::SubjectSex),
+                    SeriesContext::from_identifier("phenotype").with_data_context(Context::Hpo),
+                ],
+            ),
+            ExtractionConfig::new("patients".to_string(), true, true),
+        );
+
+        let mut eager = in_memory_pipeline()
+            .extract_and_transform(&mut [DataSource::Csv(csv_source.clone())])
+            .unwrap();
+        let mut chunked = in_memory_pipeline()
+            .extract_and_transform(&mut [DataSource::Csv(
+                csv_source.with_chunk_size(Some(chunk_size)),
+            )])
+            .unwrap();
+        eager.sort_by(|a, b| a.id.cmp(&b.id));
+        chunked.sort_by(|a, b| a.id.cmp(&b.id));
+
+        assert_eq!(eager.len(), 60);
+        assert_eq!(chunked, eager);
+    }
+
+    fn csv_source(
+        path: PathBuf,
+        table_name: &str,
+        series_contexts: Vec<SeriesContext>,
+    ) -> CsvDataSource {
+        CsvDataSource::new(
+            path,
+            None,
+            TableContext::new(table_name, series_contexts),
+            ExtractionConfig::new(table_name.to_string(), true, true),
+        )
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(4)]
+    #[case(1000)]
+    fn test_chunked_dates_match_eager_extraction(#[case] chunk_size: usize) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dob_path = temp_dir.path().join("dob.csv");
+        let dates_path = temp_dir.path().join("dates.csv");
+        let hpo_id = default_phenotype_oc().id;
+        let mut dob_csv = "patient_id,dob\n".to_string();
+        let mut dates_csv = "patient_id,phenotype,onset\n".to_string();
+        for patient_idx in 0..10 {
+            dob_csv.push_str(&format!("P{patient_idx:03},2000-01-01\n"));
+            let onset_year = 2001 + patient_idx;
+            dates_csv.push_str(&format!("P{patient_idx:03},{hpo_id},{onset_year}-06-01\n"));
+        }
+        fs::write(&dob_path, dob_csv).unwrap();
+        fs::write(&dates_path, dates_csv).unwrap();
+
+        let dob_source = DataSource::Csv(csv_source(
+            dob_path,
+            "dob",
+            vec![
+                SeriesContext::from_identifier("patient_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("dob").with_data_context(Context::DateOfBirth),
+            ],
+        ));
+        let dates_source = csv_source(
+            dates_path,
+            "dates",
+            vec![
+                SeriesContext::from_identifier("patient_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("phenotype")
+                    .with_data_context(Context::Hpo)
+                    .with_building_block_id("phenotype"),
+                SeriesContext::from_identifier("onset")
+                    .with_data_context(Context::Onset(TimeElementType::Date))
+                    .with_building_block_id("phenotype"),
+            ],
+        );
+        let pipeline_with_date_to_age = || {
+            let mut pipeline = in_memory_pipeline();
+            pipeline.add_strategy(Box::new(DateToAgeStrategy::new(true)));
+            pipeline
+        };
+
+        let mut eager = pipeline_with_date_to_age()
+            .extract_and_transform(&mut [
+                dob_source.clone(),
+                DataSource::Csv(dates_source.clone()),
+            ])
+            .unwrap();
+        let mut chunked = pipeline_with_date_to_age()
+            .extract_and_transform(&mut [
+                dob_source,
+                DataSource::Csv(dates_source.with_chunk_size(Some(chunk_size))),
+            ])
+            .unwrap();
+        eager.sort_by(|a, b| a.id.cmp(&b.id));
+        chunked.sort_by(|a, b| a.id.cmp(&b.id));
+
+        assert_eq!(eager.len(), 10);
+        assert!(
+            eager
+                .iter()
+                .all(|pp| pp.phenotypic_features[0].onset.is_some())
+        );
+        assert_eq!(chunked, eager);
+    }
+
+    #[rstest]
+    fn test_chunked_table_rejects_deduplication() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let csv_path = temp_dir.path().join("patients.csv");
+        fs::write(&csv_path, "patient_id,sex\nP1,FEMALE\nP1,FEMALE\n").unwrap();
+        let csv_source = csv_source(
+            csv_path,
+            "patients",
+            vec![
+                SeriesContext::from_identifier("patient_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("sex").with_data_context(Context::SubjectSex),
+            ],
+        )
+        .with_chunk_size(Some(1));
+        let mut pipeline = in_memory_pipeline();
+        pipeline.add_strategy(Box::new(DeduplicateRowsStrategy::new(&DeduplicateRows {
+            table_name: "patients".to_string(),
+            columns: vec![],
+        })));
+
+        let result = pipeline.extract_and_transform(&mut [DataSource::Csv(csv_source)]);
+
+        assert!(matches!(
+            result,
+            Err(PipelineError::Transform(TransformError::UnchunkableStrategy { table_name, .. }))
+                if table_name == "patients"
+        ));
+    }
+
+    /// A custom source, like a library user would implement it for a format without a built-in data source.
+    #[derive(Debug)]
+    struct InMemorySource {
+        patient_ids: Vec<&'static str>,
+    }
+
+    impl Validate for InMemorySource {
+        fn validate(&self) -> Result<(), ValidationErrors> {
+            Ok(())
+        }
+    }
+
+    impl Extractable for InMemorySource {
+        fn extract(&self) -> Result<Vec<ContextualizedDataFrame>, ExtractionError> {
+            Ok(sex_table(&self.patient_ids))
+        }
+    }
+
+    #[rstest]
+    fn test_custom_extractable_alongside_data_source() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let csv_path = temp_dir.path().join("patients.csv");
+        fs::write(&csv_path, "patient_id,sex\nP003,MALE\n").unwrap();
+        let csv_source = DataSource::Csv(CsvDataSource::new(
+            csv_path,
+            None,
+            TableContext::new(
+                "csv_patients",
+                vec![
+                    SeriesContext::from_identifier("patient_id")
+                        .with_data_context(Context::SubjectId),
+                    SeriesContext::from_identifier("sex").with_data_context(Context::SubjectSex),
+                ],
+            ),
+            ExtractionConfig::new("csv_patients".to_string(), true, true),
+        ));
+
+        let mut sources: Vec<Box<dyn Extractable>> = vec![
+            Box::new(InMemorySource {
+                patient_ids: vec!["P001", "P002"],
+            }),
+            Box::new(csv_source),
+        ];
+        let mut phenopackets = in_memory_pipeline()
+            .extract_and_transform(sources.as_mut_slice())
+            .unwrap();
+        phenopackets.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let sexes: Vec<i32> = phenopackets
+            .iter()
+            .map(|pp| pp.subject.as_ref().unwrap().sex)
+            .collect();
+        assert_eq!(
+            sexes,
+            vec![Sex::Female as i32, Sex::Female as i32, Sex::Male as i32]
+        );
+    }
+
+    fn resumable_pipeline(out_dir: PathBuf) -> Pipeline {
+        let mut pipeline = Pipeline::new(
+            TransformerModule::new(
+                vec![],
+                CdfCollectorBroker::with_default_collectors(PhenopacketBuilder::new(
+                    default_builder_context(),
+                )),
+            ),
+            Box::new(FileSystemLoader::new(out_dir, true).with_resume(true)),
+        );
+        pipeline.set_omit_created(true);
+        pipeline
+    }
+
+    fn sex_table(patient_ids: &[&str]) -> Vec<ContextualizedDataFrame> {
+        let tc = TableContext::new(
+            "patients",
+            vec![
+                SeriesContext::from_identifier("patient_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("sex").with_data_context(Context::SubjectSex),
+            ],
+        );
+        let df = DataFrame::new(
+            patient_ids.len(),
+            vec![
+                Column::new("patient_id".into(), patient_ids),
+                Column::new("sex".into(), vec!["FEMALE"; patient_ids.len()]),
+            ],
+        )
+        .unwrap();
+        vec![ContextualizedDataFrame::new(tc, df).unwrap()]
+    }
+
+    #[rstest]
+    fn test_resume_rejects_chunked_sources() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let out_dir = temp_dir.path().join("out");
+        let csv_path = temp_dir.path().join("patients.csv");
+        fs::write(&csv_path, "patient_id,sex\nP1,FEMALE\nP2,MALE\n").unwrap();
+        let csv_source = CsvDataSource::new(
+            csv_path,
+            None,
+            TableContext::new(
+                "patients",
+                vec![
+                    SeriesContext::from_identifier("patient_id")
+                        .with_data_context(Context::SubjectId),
+                    SeriesContext::from_identifier("sex").with_data_context(Context::SubjectSex),
+                ],
+            ),
+            ExtractionConfig::new("patients".to_string(), true, true),
+        )
+        .with_chunk_size(Some(1));
+
+        let mut pipeline = resumable_pipeline(out_dir.clone());
+
+        let result = pipeline.run(&mut [DataSource::Csv(csv_source)]);
+
+        assert!(matches!(result, Err(PipelineError::ChunkedResume)));
+        assert!(!out_dir.exists());
+    }
+
+    #[rstest]
+    fn test_resume_only_regenerates_missing_phenopackets() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let out_dir = temp_dir.path().join("out");
+        let patient_ids = ["P1", "P2", "P3", "P4"];
+        let loader = FileSystemLoader::new(out_dir.clone(), true).with_resume(true);
+
+        let mut first_run = resumable_pipeline(out_dir.clone());
+        let loaded_ids = first_run.loader_module.loaded_phenopacket_ids().unwrap();
+        first_run
+            .transform_and_load_incrementally(sex_table(&patient_ids), &loaded_ids.unwrap())
+            .unwrap();
+
+        let written: Vec<PathBuf> = patient_ids
+            .iter()
+            .map(|patient_id| out_dir.join(format!("{}-{patient_id}.json", default_cohort_id())))
+            .collect();
+        assert!(written.iter().all(|path| path.exists()));
+
+        // Simulates a crash after the first half: the kept outputs are marked to detect rewrites.
+        for path in &written[..2] {
+            fs::write(path, "kept").unwrap();
+        }
+        for path in &written[2..] {
+            fs::remove_file(path).unwrap();
+        }
+        assert_eq!(loader.loaded_phenopacket_ids().unwrap().unwrap().len(), 2);
+
+        let mut second_run = resumable_pipeline(out_dir.clone());
+        let loaded_ids = second_run.loader_module.loaded_phenopacket_ids().unwrap();
+        second_run
+            .transform_and_load_incrementally(sex_table(&patient_ids), &loaded_ids.unwrap())
+            .unwrap();
+
+        for path in &written[..2] {
+            assert_eq!(fs::read_to_string(path).unwrap(), "kept");
+        }
+        for path in &written[2..] {
+            let pp = FileSystemLoader::phenopacket_from_json(&fs::read_to_string(path).unwrap())
+                .unwrap();
+            assert_eq!(pp.subject.unwrap().sex, Sex::Female as i32);
+        }
+        assert_eq!(loader.loaded_phenopacket_ids().unwrap().unwrap().len(), 4);
     }
 }
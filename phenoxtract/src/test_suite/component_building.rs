@@ -1,6 +1,7 @@
 use crate::ontology::loinc_client::LoincClient;
 use crate::test_suite::ontology_mocking::{
-    HPO_BIDICT, MAXO_BIDICT, MONDO_BIDICT, NCIT_BIDICT, PATO_BIDICT, UBERON_BIDICT, UO_BIDICT,
+    ECO_BIDICT, GSSO_BIDICT, HPO_BIDICT, MAXO_BIDICT, MONDO_BIDICT, NCIT_BIDICT, PATO_BIDICT,
+    UBERON_BIDICT, UO_BIDICT,
 };
 use crate::test_suite::phenopacket_component_generation::default_meta_data;
 use crate::transform::PhenopacketBuilder;
@@ -35,6 +36,9 @@ pub(crate) fn default_builder_context() -> TransformContext {
     builder.add_qualitative_measurement_bidict(Box::new(PATO_BIDICT.clone()));
     builder.add_procedure_bidict(Box::new(MAXO_BIDICT.clone()));
     builder.add_drug_bidict(Box::new(NCIT_BIDICT.clone()));
+    builder.add_evidence_bidict(Box::new(ECO_BIDICT.clone()));
+    builder.add_gender_bidict(Box::new(GSSO_BIDICT.clone()));
+    builder.add_disease_attributes_bidict(Box::new(NCIT_BIDICT.clone()));
 
     builder.build()
 }
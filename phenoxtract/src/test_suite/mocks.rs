@@ -3,9 +3,12 @@ use crate::ontology::CachedOntologyFactory;
 use crate::test_suite::utils::test_ontology_path;
 use crate::transform::collecting::medical_actions::medical_treatment_data::DoseIntervalRow;
 use crate::transform::collecting::traits::Collect;
+use crate::transform::dropped_fields::DroppedFieldKind;
 use crate::transform::error::CollectorError;
 use crate::transform::error::PhenopacketBuilderError;
-use crate::transform::pathogenic_gene_variant_info::PathogenicGeneVariantData;
+use crate::transform::pathogenic_gene_variant_info::{
+    PathogenicGeneVariantData, VariantAnnotations,
+};
 use crate::transform::traits::PhenopacketBuilding;
 use mockall::mock;
 use mockall::predicate::*;
@@ -46,6 +49,15 @@ mock! {
     impl PhenopacketBuilding for PhenopacketBuilding {
         fn build(&self) -> Vec<Phenopacket>;
 
+        fn build_phenopacket<'a>(&mut self, patient_id: &'a str) -> Option<Phenopacket>;
+
+        fn record_dropped_field<'a>(
+            &mut self,
+            kind: DroppedFieldKind,
+            patient_id: &'a str,
+            value: &'a str,
+        );
+
         fn upsert_individual<'a>(
             &mut self,
             patient_id: &'a str,
@@ -78,6 +90,7 @@ mock! {
             onset: Option<&'a str>,
             resolution: Option<&'a str>,
             evidence: Option<&'a str>,
+            reference: Option<&'a str>,
         ) -> Result<(), PhenopacketBuilderError>;
 
         fn insert_phenotypic_feature<'a>(
@@ -91,6 +104,7 @@ mock! {
             onset: Option<&'a str>,
             resolution: Option<&'a str>,
             evidence: Option<&'a str>,
+            reference: Option<&'a str>,
         ) -> Result<(), PhenopacketBuilderError>;
 
         fn upsert_interpretation<'a>(
@@ -98,6 +112,7 @@ mock! {
             patient_id: &'a str,
             disease: &'a str,
             gene_variant_data: &'a PathogenicGeneVariantData,
+            variant_annotations: &'a [VariantAnnotations],
             subject_sex: Option<&'a str>,
         ) -> Result<(), PhenopacketBuilderError>;
 
@@ -124,6 +139,19 @@ mock! {
             reference_range: Option<(f64, f64)>,
         ) -> Result<(), PhenopacketBuilderError>;
 
+        fn insert_ratio_measurement<'a>(
+            &mut self,
+            patient_id: &'a str,
+            time_observed: Option<&'a str>,
+            assay_id: &'a str,
+            numerator: f64,
+            numerator_type_id: &'a str,
+            numerator_unit_id: &'a str,
+            denominator: f64,
+            denominator_type_id: &'a str,
+            denominator_unit_id: &'a str,
+        ) -> Result<(), PhenopacketBuilderError>;
+
         fn insert_qualitative_measurement<'a>(
             &mut self,
             patient_id: &'a str,
@@ -132,6 +160,13 @@ mock! {
             assay_id: &'a str,
         ) -> Result<(), PhenopacketBuilderError>;
 
+        fn upsert_biosample<'a>(
+            &mut self,
+            patient_id: &'a str,
+            biosample_id: &'a str,
+            sampled_tissue: Option<&'a str>,
+        ) -> Result<(), PhenopacketBuilderError>;
+
         fn insert_medical_procedure<'a>(
             &mut self,
             patient_id: &'a str,
@@ -177,6 +212,12 @@ pub(crate) struct MockOntologyRegistry {
     registry_path: PathBuf,
 }
 
+impl MockOntologyRegistry {
+    pub(crate) fn new(registry_path: PathBuf) -> Self {
+        Self { registry_path }
+    }
+}
+
 impl Default for MockOntologyRegistry {
     fn default() -> Self {
         Self {
@@ -7,7 +7,7 @@ use crate::test_suite::phenopacket_component_generation::{
     default_treatment_response, default_treatment_termination_reason,
 };
 use crate::test_suite::resource_references::{
-    HPO_REF, MAXO_REF, MONDO_REF, NCIT_REF, PATO_REF, UBERON_REF, UO_REF,
+    ECO_REF, GSSO_REF, HPO_REF, MAXO_REF, MONDO_REF, NCIT_REF, PATO_REF, UBERON_REF, UO_REF,
 };
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
@@ -122,6 +122,11 @@ pub(crate) static NCIT_BIDICT: Lazy<Arc<OntologyBiDict>> = Lazy::new(|| {
             default_route_of_administration_oc().label,
             default_route_of_administration_oc().id,
         ),
+        ("T2 Stage Finding".to_string(), "NCIT:C48724".to_string()),
+        ("N1 Stage Finding".to_string(), "NCIT:C48706".to_string()),
+        ("M0 Stage Finding".to_string(), "NCIT:C48699".to_string()),
+        ("Stage III".to_string(), "NCIT:C27970".to_string()),
+        ("Stage IV".to_string(), "NCIT:C27971".to_string()),
     ]);
 
     let mock_ncit_id_to_label: HashMap<String, String> = mock_ncit_label_to_id
@@ -137,6 +142,63 @@ pub(crate) static NCIT_BIDICT: Lazy<Arc<OntologyBiDict>> = Lazy::new(|| {
     ))
 });
 
+pub(crate) static ECO_BIDICT: Lazy<Arc<OntologyBiDict>> = Lazy::new(|| {
+    let mock_eco_label_to_id: HashMap<String, String> = HashMap::from_iter([
+        (
+            "sequence similarity evidence".to_string(),
+            "ECO:0000044".to_string(),
+        ),
+        (
+            "author statement supported by traceable reference".to_string(),
+            "ECO:0000033".to_string(),
+        ),
+    ]);
+
+    let mock_eco_id_to_label: HashMap<String, String> = mock_eco_label_to_id
+        .iter()
+        .map(|(label, id)| (id.to_string(), label.to_string()))
+        .collect();
+
+    Arc::new(OntologyBiDict::new(
+        ECO_REF.clone(),
+        mock_eco_label_to_id,
+        HashMap::new(),
+        mock_eco_id_to_label,
+    ))
+});
+
+pub(crate) static GSSO_BIDICT: Lazy<Arc<OntologyBiDict>> = Lazy::new(|| {
+    let mock_gsso_label_to_id: HashMap<String, String> = HashMap::from_iter([
+        (
+            "female gender identity".to_string(),
+            "GSSO:000369".to_string(),
+        ),
+        (
+            "male gender identity".to_string(),
+            "GSSO:000371".to_string(),
+        ),
+        ("non-binary gender".to_string(), "GSSO:000132".to_string()),
+    ]);
+
+    let mock_gsso_synonym_to_id: HashMap<String, String> = HashMap::from_iter([
+        ("woman".to_string(), "GSSO:000369".to_string()),
+        ("man".to_string(), "GSSO:000371".to_string()),
+        ("nonbinary".to_string(), "GSSO:000132".to_string()),
+    ]);
+
+    let mock_gsso_id_to_label: HashMap<String, String> = mock_gsso_label_to_id
+        .iter()
+        .map(|(label, id)| (id.to_string(), label.to_string()))
+        .collect();
+
+    Arc::new(OntologyBiDict::new(
+        GSSO_REF.clone(),
+        mock_gsso_label_to_id,
+        mock_gsso_synonym_to_id,
+        mock_gsso_id_to_label,
+    ))
+});
+
 pub(crate) static HPO: Lazy<Arc<dyn OntologyLike>> = Lazy::new(|| {
     let result = ONTOLOGY_FACTORY.lock().unwrap().build_ontology(&HPO_REF);
 
@@ -17,6 +17,10 @@ pub(crate) static UO_REF: Lazy<ResourceRef> =
     Lazy::new(|| ResourceRef::uo().with_version("2026-01-16"));
 pub(crate) static PATO_REF: Lazy<ResourceRef> =
     Lazy::new(|| ResourceRef::pato().with_version("2025-05-14"));
+pub(crate) static ECO_REF: Lazy<ResourceRef> =
+    Lazy::new(|| ResourceRef::eco().with_version("2025-06-24"));
+pub(crate) static GSSO_REF: Lazy<ResourceRef> =
+    Lazy::new(|| ResourceRef::gsso().with_version("2.0.10"));
 pub(crate) static LOINC_REF: Lazy<ResourceRef> = Lazy::new(ResourceRef::loinc);
 
 pub(crate) fn mondo_meta_data_resource() -> Resource {
@@ -1,5 +1,5 @@
 use crate::ontology::resource_references::ResourceRef;
-use crate::ontology::traits::BiDict;
+use crate::ontology::traits::{BiDict, HasPrefixId};
 use phenopackets::schema::v2::core::OntologyClass;
 use securiety::{CurieRegexValidator, CurieValidation};
 
@@ -8,14 +8,19 @@ pub struct BiDictLibrary {
     name: String,
     bidicts: Vec<Box<dyn BiDict>>,
     curie_validator: CurieRegexValidator,
+    label_suggestions: bool,
 }
 
+/// The maximal number of close labels suggested for a label, which could not be resolved.
+const MAX_LABEL_SUGGESTIONS: usize = 3;
+
 impl BiDictLibrary {
     pub fn new(name: &str, bidicts: Vec<Box<dyn BiDict>>) -> Self {
         BiDictLibrary {
             name: name.to_string(),
             bidicts,
             curie_validator: CurieRegexValidator::general(),
+            label_suggestions: false,
         }
     }
 
@@ -24,9 +29,30 @@ impl BiDictLibrary {
             name: name.to_string(),
             bidicts: vec![],
             curie_validator: CurieRegexValidator::general(),
+            label_suggestions: false,
         }
     }
 
+    /// If enabled, labels, which can't be resolved, are reported together with the closest known labels.
+    ///
+    /// Disabled by default, because every label of every bidict has to be compared to the unresolved one.
+    pub fn with_label_suggestions(mut self, label_suggestions: bool) -> Self {
+        self.label_suggestions = label_suggestions;
+        self
+    }
+
+    /// Reorders the bidicts, so that terms are looked up in the bidicts with the given prefixes first,
+    /// in the given order (case-insensitive). The remaining bidicts keep their order and are asked last.
+    pub fn with_priority(mut self, prefixes: &[String]) -> Self {
+        self.bidicts.sort_by_key(|bidict| {
+            prefixes
+                .iter()
+                .position(|prefix| prefix.eq_ignore_ascii_case(bidict.reference().prefix_id()))
+                .unwrap_or(prefixes.len())
+        });
+        self
+    }
+
     pub fn add_bidict(&mut self, bidict: Box<dyn BiDict>) {
         self.bidicts.push(bidict);
     }
@@ -43,6 +69,8 @@ impl BiDictLibrary {
         self.bidicts.is_empty()
     }
 
+    /// Looks up a label or CURIE in the bidicts, in order, and returns the term together with the resource
+    /// of the first bidict, which knows it.
     pub(crate) fn lookup(&self, query: &str) -> Option<(OntologyClass, ResourceRef)> {
         for bidict in self.bidicts.iter() {
             if self.curie_validator.validate(query) {
@@ -68,6 +96,56 @@ impl BiDictLibrary {
 
         None
     }
+
+    /// Returns up to three labels of this library, which are closest to `query` by edit distance.
+    ///
+    /// Returns nothing, if label suggestions are disabled or `query` is a CURIE.
+    pub(crate) fn suggest_labels(&self, query: &str) -> Vec<String> {
+        if !self.label_suggestions || self.curie_validator.validate(query) {
+            return vec![];
+        }
+
+        let mut candidates: Vec<(usize, String)> = self
+            .bidicts
+            .iter()
+            .flat_map(|bidict| bidict.closest_labels(query, MAX_LABEL_SUGGESTIONS))
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+
+        candidates
+            .into_iter()
+            .take(MAX_LABEL_SUGGESTIONS)
+            .map(|(_, label)| label)
+            .collect()
+    }
+
+    /// Resolves a single label, synonym or CURIE against the bidicts of this library, whose prefix matches
+    /// `ontology_prefix` (case-insensitive), e.g. to validate or autocomplete terms in a config UI.
+    ///
+    /// Synonyms are resolved to the primary label of the term. Returns `None`, if no matching bidict knows the query.
+    pub fn resolve_term(&self, ontology_prefix: &str, query: &str) -> Option<OntologyClass> {
+        let query = query.trim();
+        let is_curie = self.curie_validator.validate(query);
+
+        self.bidicts
+            .iter()
+            .filter(|bidict| {
+                bidict
+                    .reference()
+                    .prefix_id()
+                    .eq_ignore_ascii_case(ontology_prefix)
+            })
+            .find_map(|bidict| {
+                let id = if is_curie {
+                    query.to_string()
+                } else {
+                    bidict.get_id(query).ok()?
+                };
+                let label = bidict.get_label(&id).ok()?;
+                Some(OntologyClass { id, label })
+            })
+    }
 }
 
 impl PartialEq for BiDictLibrary {
@@ -90,6 +168,7 @@ impl PartialEq for BiDictLibrary {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ontology::error::BiDictError;
     use crate::ontology::loinc_client::LoincClient;
     use crate::test_suite::component_building::{
         build_test_hpo_bidict_library, build_test_mondo_bidict_library,
@@ -101,6 +180,47 @@ mod tests {
     use pretty_assertions::assert_eq;
     use rstest::*;
 
+    /// A bidict, which only knows one term.
+    #[derive(Debug)]
+    struct SingleTermBiDict {
+        reference: ResourceRef,
+        id: String,
+        label: String,
+    }
+
+    impl SingleTermBiDict {
+        fn new(prefix: &str, id: &str, label: &str) -> Self {
+            Self {
+                reference: ResourceRef::from(prefix),
+                id: id.to_string(),
+                label: label.to_string(),
+            }
+        }
+    }
+
+    impl BiDict for SingleTermBiDict {
+        fn get(&self, id_or_label: &str) -> Result<String, BiDictError> {
+            self.get_label(id_or_label)
+                .or_else(|_| self.get_id(id_or_label))
+        }
+
+        fn get_label(&self, id: &str) -> Result<String, BiDictError> {
+            (id == self.id)
+                .then(|| self.label.clone())
+                .ok_or_else(|| BiDictError::NotFound(id.to_string()))
+        }
+
+        fn get_id(&self, term: &str) -> Result<String, BiDictError> {
+            (term == self.label)
+                .then(|| self.id.clone())
+                .ok_or_else(|| BiDictError::NotFound(term.to_string()))
+        }
+
+        fn reference(&self) -> &ResourceRef {
+            &self.reference
+        }
+    }
+
     #[rstest]
     fn test_lookup_bidicts_with_valid_label() {
         let phenotype = default_phenotype_oc();
@@ -155,6 +275,78 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[rstest]
+    #[case(vec![], "ORPHA:2322")]
+    #[case(vec!["MONDO".to_string(), "OMIM".to_string()], "MONDO:0007848")]
+    #[case(vec!["omim".to_string()], "OMIM:147920")]
+    fn test_lookup_with_priority(#[case] priority: Vec<String>, #[case] expected_id: &str) {
+        let library = BiDictLibrary::new(
+            "DISEASE",
+            vec![
+                Box::new(SingleTermBiDict::new(
+                    "ORPHA",
+                    "ORPHA:2322",
+                    "Kabuki syndrome",
+                )),
+                Box::new(SingleTermBiDict::new(
+                    "MONDO",
+                    "MONDO:0007848",
+                    "Kabuki syndrome",
+                )),
+                Box::new(SingleTermBiDict::new(
+                    "OMIM",
+                    "OMIM:147920",
+                    "Kabuki syndrome",
+                )),
+            ],
+        )
+        .with_priority(&priority);
+
+        let (term, resource_ref) = library.lookup("Kabuki syndrome").unwrap();
+
+        assert_eq!(term.id, expected_id);
+        assert!(expected_id.starts_with(resource_ref.prefix_id()));
+    }
+
+    #[rstest]
+    #[case("HP", default_phenotype_oc().label)]
+    #[case("hp", default_phenotype_oc().id)]
+    fn test_resolve_term(#[case] prefix: &str, #[case] query: String) {
+        let result = build_test_hpo_bidict_library()
+            .resolve_term(prefix, &query)
+            .unwrap();
+
+        assert_eq!(result, default_phenotype_oc());
+    }
+
+    #[rstest]
+    #[case("HP", "NonexistentTerm")]
+    #[case("MONDO", "Fractured nose")]
+    fn test_resolve_term_miss(#[case] prefix: &str, #[case] query: &str) {
+        let result = build_test_hpo_bidict_library().resolve_term(prefix, query);
+
+        assert!(result.is_none());
+    }
+
+    #[rstest]
+    fn test_suggest_labels() {
+        let library = build_test_hpo_bidict_library().with_label_suggestions(true);
+
+        let suggestions = library.suggest_labels("Seizur");
+
+        assert_eq!(suggestions.len(), 3);
+        assert_eq!(suggestions[0], "Seizure");
+    }
+
+    #[rstest]
+    #[case(false, "Seizur")]
+    #[case(true, "HP:0001250")]
+    fn test_suggest_labels_none(#[case] label_suggestions: bool, #[case] query: &str) {
+        let library = build_test_hpo_bidict_library().with_label_suggestions(label_suggestions);
+
+        assert!(library.suggest_labels(query).is_empty());
+    }
+
     #[rstest]
     fn test_lookup_bidicts_returns_correct_resource_ref() {
         let phenotype = default_phenotype_oc();
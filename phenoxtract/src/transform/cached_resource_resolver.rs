@@ -1,27 +1,65 @@
 use crate::ontology::BioRegistryClient;
+use crate::ontology::bio_registry_client::BioRegistryResource;
 use crate::ontology::traits::{HasPrefixId, HasVersion};
+use crate::transform::error::PhenopacketBuilderError;
 use log::{debug, warn};
 use phenopackets::schema::v2::core::Resource;
 use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
 
 /// A cached resolver for biological ontology resources.
 ///
 /// This resolver fetches resource metadata from the BioRegistry API and caches
 /// the results to avoid repeated network requests. It maintains a cache of resolved
 /// resources and allows specifying known versions for resources before resolution.
-#[derive(Clone, Default, Debug, PartialEq)]
+///
+/// Resources are cached per prefix and requested version, so that two versions of the same ontology
+/// resolve to two different resources.
+///
+/// The resolver is shared by the builders, which collect patients in parallel. Its cache is locked while a resource
+/// is resolved, so that every resource is only requested once.
+///
+/// # Snapshot
+///
+/// If the resolver has a snapshot file, every BioRegistry record it fetches is written to it. In offline mode,
+/// BioRegistry is never asked and the resources are resolved from the snapshot of an earlier online run.
+#[derive(Default, Debug)]
 pub struct CachedResourceResolver {
-    cache: HashMap<String, Resource>,
+    cache: Mutex<HashMap<(String, String), Resource>>,
     bio_reg_client: BioRegistryClient,
+    /// The BioRegistry records, which were fetched so far, by prefix.
+    snapshot: Mutex<HashMap<String, BioRegistryResource>>,
+    snapshot_path: Option<PathBuf>,
+    offline: bool,
 }
 
 impl CachedResourceResolver {
+    /// Creates a resolver backed by a JSON snapshot file. A missing or unreadable file results in an empty snapshot.
+    pub fn with_snapshot(path: impl Into<PathBuf>, offline: bool) -> Self {
+        let path = path.into();
+        let snapshot = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| {
+                serde_json::from_str::<HashMap<String, BioRegistryResource>>(&content).ok()
+            })
+            .unwrap_or_default();
+
+        Self {
+            snapshot: Mutex::new(snapshot),
+            snapshot_path: Some(path),
+            offline,
+            ..Default::default()
+        }
+    }
+
     /// Resolves a resource by its ID, returning cached data if available or fetching
     /// from BioRegistry if not.
     ///
     /// The resolution process:
     /// 1. Checks the cache for an existing resource
-    /// 2. If not cached, fetches from BioRegistry API
+    /// 2. If not cached, fetches from BioRegistry API, or takes it from the snapshot in offline mode
     /// 3. Prioritizes known versions over BioRegistry versions
     /// 4. Selects the first available download format (JSON, OWL, OBO, RDF) or homepage
     /// 5. Caches successful resolutions for future use
@@ -30,52 +68,114 @@ impl CachedResourceResolver {
     ///
     /// * `id` - The resource identifier (case-insensitive)
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// * `Some(Resource)` if the resource was successfully resolved with all required fields
-    /// * `None` if the resource couldn't be found or is missing required fields
-    pub fn resolve(&mut self, resource_ref: &(impl HasPrefixId + HasVersion)) -> Option<Resource> {
+    /// Returns `PhenopacketBuilderError::UnresolvedResource`, if the BioRegistry record can't be fetched,
+    /// or isn't in the snapshot in offline mode, or if it is missing required fields.
+    pub fn resolve(
+        &self,
+        resource_ref: &(impl HasPrefixId + HasVersion),
+    ) -> Result<Resource, PhenopacketBuilderError> {
         let prefix_id = resource_ref.prefix_id().to_lowercase();
+        let cache_key = (prefix_id.clone(), resource_ref.version().to_string());
         debug!("Resolve id: {}", prefix_id);
-        self.cache.get(&prefix_id).cloned().or_else(|| {
-            debug!("Cache not hit");
-            let response = self.bio_reg_client.get_resource(&prefix_id);
-
-            response.ok().and_then(|bio_reg_resource| {
-                let resolved_version: Option<String> = match resource_ref.version() {
-                    "latest" => bio_reg_resource.version.unwrap_or("-".to_string()).into(),
-                    version => version.to_string().into(),
-                };
-                let resolved_url = bio_reg_resource
-                    .download_json
-                    .or(bio_reg_resource.download_owl)
-                    .or(bio_reg_resource.download_obo)
-                    .or(bio_reg_resource.download_rdf)
-                    .or(bio_reg_resource.homepage);
-
-                CachedResourceResolver::log_missing_fields(
-                    &prefix_id,
-                    &resolved_version,
-                    &resolved_url,
-                    &bio_reg_resource.name,
-                    &bio_reg_resource.preferred_prefix,
-                    &bio_reg_resource.uri_format,
-                );
-
-                let resource = Resource {
-                    id: bio_reg_resource.prefix,
-                    name: bio_reg_resource.name?,
-                    url: resolved_url?,
-                    version: resolved_version?,
-                    namespace_prefix: bio_reg_resource.preferred_prefix?,
-                    iri_prefix: bio_reg_resource.uri_format?,
-                };
-                debug!("Cached resource: {}", prefix_id);
-                self.cache.insert(prefix_id, resource.clone());
-
-                Some(resource)
+        let mut cache = self.cache.lock().unwrap();
+        if let Some(resource) = cache.get(&cache_key) {
+            return Ok(resource.clone());
+        }
+
+        debug!("Cache not hit");
+        let bio_reg_resource = self.bio_registry_record(&prefix_id)?;
+        let resolved_version: Option<String> = match resource_ref.version() {
+            "latest" => bio_reg_resource.version.unwrap_or("-".to_string()).into(),
+            version => version.to_string().into(),
+        };
+        let resolved_url = bio_reg_resource
+            .download_json
+            .or(bio_reg_resource.download_owl)
+            .or(bio_reg_resource.download_obo)
+            .or(bio_reg_resource.download_rdf)
+            .or(bio_reg_resource.homepage);
+
+        CachedResourceResolver::log_missing_fields(
+            &prefix_id,
+            &resolved_version,
+            &resolved_url,
+            &bio_reg_resource.name,
+            &bio_reg_resource.preferred_prefix,
+            &bio_reg_resource.uri_format,
+        );
+
+        let resource = (|| {
+            Some(Resource {
+                id: bio_reg_resource.prefix,
+                name: bio_reg_resource.name?,
+                url: resolved_url?,
+                version: resolved_version?,
+                namespace_prefix: bio_reg_resource.preferred_prefix?,
+                iri_prefix: bio_reg_resource.uri_format?,
             })
-        })
+        })()
+        .ok_or_else(|| PhenopacketBuilderError::UnresolvedResource {
+            resource: prefix_id.clone(),
+            reason: "its BioRegistry record is missing required fields".to_string(),
+        })?;
+        debug!("Cached resource: {}", prefix_id);
+        cache.insert(cache_key, resource.clone());
+
+        Ok(resource)
+    }
+
+    /// The BioRegistry record of a prefix. Online, it is fetched and added to the snapshot. Offline, or if the request
+    /// fails, it is taken from the snapshot.
+    fn bio_registry_record(
+        &self,
+        prefix_id: &str,
+    ) -> Result<BioRegistryResource, PhenopacketBuilderError> {
+        let mut snapshot = self.snapshot.lock().unwrap();
+        let unresolved = |reason: String| PhenopacketBuilderError::UnresolvedResource {
+            resource: prefix_id.to_string(),
+            reason,
+        };
+
+        if self.offline {
+            return snapshot.get(prefix_id).cloned().ok_or_else(|| {
+                unresolved(
+                    "it is not in the resource snapshot and BioRegistry is not asked in offline mode"
+                        .to_string(),
+                )
+            });
+        }
+
+        match self.bio_reg_client.get_resource(prefix_id) {
+            Ok(record) => {
+                snapshot.insert(prefix_id.to_string(), record.clone());
+                self.write_snapshot(&snapshot);
+                Ok(record)
+            }
+            Err(err) => match snapshot.get(prefix_id) {
+                Some(record) => {
+                    warn!(
+                        "Could not fetch resource {prefix_id} from BioRegistry, using the snapshot: {err}"
+                    );
+                    Ok(record.clone())
+                }
+                None => Err(unresolved(err.to_string())),
+            },
+        }
+    }
+
+    fn write_snapshot(&self, snapshot: &HashMap<String, BioRegistryResource>) {
+        let Some(path) = &self.snapshot_path else {
+            return;
+        };
+
+        let written = serde_json::to_string(snapshot)
+            .map_err(|err| err.to_string())
+            .and_then(|content| fs::write(path, content).map_err(|err| err.to_string()));
+        if let Err(err) = written {
+            warn!("Could not write resource snapshot to {path:?}: {err}");
+        }
     }
 
     fn log_missing_fields(
@@ -115,14 +215,34 @@ impl CachedResourceResolver {
 
 #[cfg(test)]
 mod tests {
+    use crate::ontology::BioRegistryClient;
+    use crate::ontology::bio_registry_client::BioRegistryResource;
     use crate::ontology::resource_references::{KnownResourcePrefixes, ResourceRef};
     use crate::transform::cached_resource_resolver::CachedResourceResolver;
     use rstest::rstest;
+    use std::collections::HashMap;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn hp_record() -> BioRegistryResource {
+        BioRegistryResource {
+            prefix: "hp".to_string(),
+            name: Some("Human Phenotype Ontology".to_string()),
+            uri_format: Some("http://purl.obolibrary.org/obo/HP_$1".to_string()),
+            homepage: None,
+            version: Some("2025-05-06".to_string()),
+            download_owl: None,
+            download_obo: None,
+            download_json: Some("http://purl.obolibrary.org/obo/hp.json".to_string()),
+            download_rdf: None,
+            preferred_prefix: Some("HP".to_string()),
+        }
+    }
 
     #[rstest]
     fn test_resolve() {
         let resource_id = ResourceRef::from(KnownResourcePrefixes::HP).with_latest();
-        let mut resolver = CachedResourceResolver::default();
+        let resolver = CachedResourceResolver::default();
         let hpo_metadata = resolver.resolve(&resource_id).unwrap();
 
         assert_eq!(
@@ -143,7 +263,7 @@ mod tests {
 
     #[rstest]
     fn test_resolve_versionless_resource() {
-        let mut resolver = CachedResourceResolver::default();
+        let resolver = CachedResourceResolver::default();
         let resource_id = ResourceRef::from(KnownResourcePrefixes::HGNC).with_latest();
         let hgnc_metadata = resolver.resolve(&resource_id).unwrap();
 
@@ -153,4 +273,85 @@ mod tests {
         );
         assert_eq!(hgnc_metadata.version, "-");
     }
+
+    #[rstest]
+    fn test_resolve_different_versions() {
+        let resolver = CachedResourceResolver::default();
+
+        let hpo_2024 = resolver
+            .resolve(&ResourceRef::from(KnownResourcePrefixes::HP).with_version("2024-04-26"))
+            .unwrap();
+        let hpo_2025 = resolver
+            .resolve(&ResourceRef::from(KnownResourcePrefixes::HP).with_version("2025-05-06"))
+            .unwrap();
+
+        assert_eq!(hpo_2024.version, "2024-04-26");
+        assert_eq!(hpo_2025.version, "2025-05-06");
+    }
+
+    #[rstest]
+    fn test_resolve_offline_from_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("bioregistry_resources.json");
+        let snapshot = HashMap::from([("hp".to_string(), hp_record())]);
+        fs::write(&path, serde_json::to_string(&snapshot).unwrap()).unwrap();
+        let mut server = mockito::Server::new();
+        let mock = server.mock("GET", mockito::Matcher::Any).expect(0).create();
+        let resolver = CachedResourceResolver {
+            bio_reg_client: BioRegistryClient::new(&server.url()),
+            ..CachedResourceResolver::with_snapshot(&path, true)
+        };
+
+        let hpo_metadata = resolver
+            .resolve(&ResourceRef::from(KnownResourcePrefixes::HP).with_latest())
+            .unwrap();
+
+        mock.assert();
+        assert_eq!(hpo_metadata.version, "2025-05-06");
+        assert_eq!(hpo_metadata.url, "http://purl.obolibrary.org/obo/hp.json");
+    }
+
+    #[rstest]
+    fn test_resolve_offline_without_snapshot_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut server = mockito::Server::new();
+        let mock = server.mock("GET", mockito::Matcher::Any).expect(0).create();
+        let resolver = CachedResourceResolver {
+            bio_reg_client: BioRegistryClient::new(&server.url()),
+            ..CachedResourceResolver::with_snapshot(temp_dir.path().join("missing.json"), true)
+        };
+
+        let hp = ResourceRef::from(KnownResourcePrefixes::HP).with_latest();
+        let result = resolver.resolve(&hp);
+
+        mock.assert();
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_resolve_writes_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("bioregistry_resources.json");
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", "/registry/hp")
+            .with_body(serde_json::to_string(&hp_record()).unwrap())
+            .create();
+        let resolver = CachedResourceResolver {
+            bio_reg_client: BioRegistryClient::new(&server.url()),
+            ..CachedResourceResolver::with_snapshot(&path, false)
+        };
+
+        resolver
+            .resolve(&ResourceRef::from(KnownResourcePrefixes::HP).with_latest())
+            .unwrap();
+
+        mock.assert();
+        let offline = CachedResourceResolver::with_snapshot(&path, true);
+        assert!(
+            offline
+                .resolve(&ResourceRef::from(KnownResourcePrefixes::HP).with_latest())
+                .is_ok()
+        );
+    }
 }
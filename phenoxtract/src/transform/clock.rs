@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use std::fmt::Debug;
+
+/// The source of time based fields, like `MetaData.created`.
+///
+/// A `FixedClock` can be injected to make the output of runs over identical input byte-identical.
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock, which always returns the same point in time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedClock {
+    now: DateTime<Utc>,
+}
+
+impl FixedClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self { now }
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.now
+    }
+}
+
+/// A 64-bit FNV-1a hash. Unlike `DefaultHasher`, its output is stable across Rust versions and platforms,
+/// which makes it suitable for ids that end up in golden files.
+pub(crate) fn stable_content_hash(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(b"", 0xcbf29ce484222325)]
+    #[case(b"a", 0xaf63dc4c8601ec8c)]
+    #[case(b"foobar", 0x85944171f73967e8)]
+    fn test_stable_content_hash(#[case] bytes: &[u8], #[case] expected: u64) {
+        assert_eq!(stable_content_hash(bytes), expected);
+    }
+
+    #[rstest]
+    fn test_fixed_clock() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let clock = FixedClock::new(now);
+
+        assert_eq!(clock.now(), now);
+        assert_eq!(clock.now(), clock.now());
+    }
+}
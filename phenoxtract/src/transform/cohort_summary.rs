@@ -0,0 +1,220 @@
+use crate::transform::dropped_fields::{DroppedField, DroppedFieldKind};
+use phenopackets::schema::v2::Phenopacket;
+use phenopackets::schema::v2::core::Sex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// The number of HPO terms listed in [`CohortSummary::most_frequent_hpo_terms`].
+const N_MOST_FREQUENT_HPO_TERMS: usize = 10;
+
+/// An HPO term together with the number of patients, in whom it was observed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HpoTermCount {
+    pub id: String,
+    pub label: String,
+    pub n_patients: usize,
+}
+
+/// A quick QC summary of a built cohort.
+///
+/// It is only aggregated from the phenopackets and the dropped fields of the builder, so it can be computed
+/// after every run without changing the output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CohortSummary {
+    pub n_phenopackets: usize,
+    /// The number of patients per sex, e.g. `"MALE"` or `"UNKNOWN_SEX"`. Patients without a subject are counted
+    /// as `"UNKNOWN_SEX"`.
+    pub n_patients_per_sex: BTreeMap<String, usize>,
+    pub n_patients_with_interpretation: usize,
+    /// The HPO terms, which were observed in the most patients. Excluded phenotypes are not counted.
+    /// Ties are ordered by the term id.
+    pub most_frequent_hpo_terms: Vec<HpoTermCount>,
+    /// The number of phenotype and disease terms, which couldn't be resolved in lenient mode.
+    pub n_unresolved_terms: usize,
+}
+
+impl CohortSummary {
+    pub fn new(phenopackets: &[Phenopacket], dropped_fields: &[DroppedField]) -> Self {
+        let mut n_patients_per_sex: BTreeMap<String, usize> = BTreeMap::new();
+        for phenopacket in phenopackets {
+            let sex = phenopacket
+                .subject
+                .as_ref()
+                .and_then(|subject| Sex::try_from(subject.sex).ok())
+                .unwrap_or(Sex::UnknownSex);
+            *n_patients_per_sex.entry(sex.as_str_name().to_string()).or_default() += 1;
+        }
+
+        let n_patients_with_interpretation = phenopackets
+            .iter()
+            .filter(|phenopacket| !phenopacket.interpretations.is_empty())
+            .count();
+
+        let n_unresolved_terms = dropped_fields
+            .iter()
+            .filter(|dropped_field| {
+                matches!(
+                    dropped_field.kind,
+                    DroppedFieldKind::UnresolvedPhenotype | DroppedFieldKind::UnresolvedDisease
+                )
+            })
+            .count();
+
+        CohortSummary {
+            n_phenopackets: phenopackets.len(),
+            n_patients_per_sex,
+            n_patients_with_interpretation,
+            most_frequent_hpo_terms: Self::most_frequent_hpo_terms(phenopackets),
+            n_unresolved_terms,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    fn most_frequent_hpo_terms(phenopackets: &[Phenopacket]) -> Vec<HpoTermCount> {
+        let mut counts: HashMap<&str, HpoTermCount> = HashMap::new();
+        for phenopacket in phenopackets {
+            let observed_terms: HashSet<(&str, &str)> = phenopacket
+                .phenotypic_features
+                .iter()
+                .filter(|feature| !feature.excluded)
+                .filter_map(|feature| feature.r#type.as_ref())
+                .map(|term| (term.id.as_str(), term.label.as_str()))
+                .collect();
+
+            for (id, label) in observed_terms {
+                counts
+                    .entry(id)
+                    .or_insert_with(|| HpoTermCount {
+                        id: id.to_string(),
+                        label: label.to_string(),
+                        n_patients: 0,
+                    })
+                    .n_patients += 1;
+            }
+        }
+
+        let mut counts: Vec<HpoTermCount> = counts.into_values().collect();
+        counts.sort_by(|a, b| b.n_patients.cmp(&a.n_patients).then(a.id.cmp(&b.id)));
+        counts.truncate(N_MOST_FREQUENT_HPO_TERMS);
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use phenopackets::schema::v2::core::{
+        Individual, Interpretation, OntologyClass, PhenotypicFeature,
+    };
+    use rstest::rstest;
+
+    fn phenopacket(
+        id: &str,
+        sex: Option<Sex>,
+        hpo_terms: &[(&str, bool)],
+        has_interpretation: bool,
+    ) -> Phenopacket {
+        Phenopacket {
+            id: id.to_string(),
+            subject: sex.map(|sex| Individual {
+                id: id.to_string(),
+                sex: sex as i32,
+                ..Default::default()
+            }),
+            phenotypic_features: hpo_terms
+                .iter()
+                .map(|(hpo_id, excluded)| PhenotypicFeature {
+                    r#type: Some(OntologyClass {
+                        id: hpo_id.to_string(),
+                        label: format!("label of {hpo_id}"),
+                    }),
+                    excluded: *excluded,
+                    ..Default::default()
+                })
+                .collect(),
+            interpretations: if has_interpretation {
+                vec![Interpretation::default()]
+            } else {
+                vec![]
+            },
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    fn test_cohort_summary() {
+        let phenopackets = vec![
+            phenopacket(
+                "P1",
+                Some(Sex::Male),
+                &[("HP:0000002", false), ("HP:0000001", false)],
+                true,
+            ),
+            phenopacket(
+                "P2",
+                Some(Sex::Female),
+                &[("HP:0000001", false), ("HP:0000001", false)],
+                false,
+            ),
+            phenopacket("P3", Some(Sex::Male), &[("HP:0000002", true)], true),
+            phenopacket("P4", None, &[("HP:0000003", false)], false),
+        ];
+        let dropped_fields = vec![
+            DroppedField {
+                kind: DroppedFieldKind::UnresolvedPhenotype,
+                patient_id: "P1".to_string(),
+                value: "Unknown phenotype".to_string(),
+            },
+            DroppedField {
+                kind: DroppedFieldKind::KaryotypicSex,
+                patient_id: "P2".to_string(),
+                value: "XX".to_string(),
+            },
+            DroppedField {
+                kind: DroppedFieldKind::UnresolvedDisease,
+                patient_id: "P3".to_string(),
+                value: "Unknown disease".to_string(),
+            },
+        ];
+
+        let summary = CohortSummary::new(&phenopackets, &dropped_fields);
+
+        assert_eq!(summary.n_phenopackets, 4);
+        assert_eq!(
+            summary.n_patients_per_sex,
+            BTreeMap::from([
+                ("FEMALE".to_string(), 1),
+                ("MALE".to_string(), 2),
+                ("UNKNOWN_SEX".to_string(), 1),
+            ])
+        );
+        assert_eq!(summary.n_patients_with_interpretation, 2);
+        assert_eq!(
+            summary
+                .most_frequent_hpo_terms
+                .iter()
+                .map(|term| (term.id.as_str(), term.n_patients))
+                .collect::<Vec<_>>(),
+            vec![("HP:0000001", 2), ("HP:0000002", 1), ("HP:0000003", 1)]
+        );
+        assert_eq!(summary.n_unresolved_terms, 2);
+    }
+
+    #[rstest]
+    fn test_cohort_summary_to_json() {
+        let summary = CohortSummary::new(
+            &[phenopacket("P1", Some(Sex::Male), &[("HP:0000001", false)], false)],
+            &[],
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&summary.to_json().unwrap()).unwrap();
+
+        assert_eq!(json["n_phenopackets"], 1);
+        assert_eq!(json["n_patients_per_sex"]["MALE"], 1);
+        assert_eq!(json["most_frequent_hpo_terms"][0]["id"], "HP:0000001");
+        assert_eq!(json["n_unresolved_terms"], 0);
+    }
+}
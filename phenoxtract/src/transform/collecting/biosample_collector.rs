@@ -0,0 +1,138 @@
+use crate::config::context::Context;
+use crate::extract::ContextualizedDataFrame;
+use crate::extract::enums::Filter;
+use crate::transform::collecting::traits::Collect;
+use crate::transform::error::CollectorError;
+use crate::transform::traits::PhenopacketBuilding;
+use polars::datatypes::DataType;
+use std::any::Any;
+
+/// Collects the biosamples of a patient from the columns with the `BiosampleId` data context.
+///
+/// The sampled tissue is taken from a column with the `SampledTissue` data context, which shares the building block.
+#[derive(Debug)]
+pub struct BiosampleCollector;
+
+impl Collect for BiosampleCollector {
+    fn collect(
+        &self,
+        builder: &mut dyn PhenopacketBuilding,
+        patient_cdfs: &[ContextualizedDataFrame],
+        patient_id: &str,
+    ) -> Result<(), CollectorError> {
+        for patient_cdf in patient_cdfs {
+            let biosample_id_scs = patient_cdf
+                .filter_series_context()
+                .where_header_context(Filter::Is(&Context::None))
+                .where_data_context(Filter::Is(&Context::BiosampleId))
+                .collect();
+
+            for biosample_id_sc in biosample_id_scs {
+                let sampled_tissue_col = patient_cdf.get_single_linked_column_as_str(
+                    biosample_id_sc.get_building_block_id(),
+                    &[Context::SampledTissue],
+                )?;
+
+                for biosample_id_col in
+                    patient_cdf.identify_columns(biosample_id_sc.get_identifier())
+                {
+                    let stringified_biosample_id_col = biosample_id_col.cast(&DataType::String)?;
+                    let stringified_biosample_id_col = stringified_biosample_id_col.str()?;
+
+                    for row_idx in 0..stringified_biosample_id_col.len() {
+                        let Some(biosample_id) = stringified_biosample_id_col.get(row_idx) else {
+                            continue;
+                        };
+                        let sampled_tissue = sampled_tissue_col
+                            .as_ref()
+                            .and_then(|sampled_tissue_col| sampled_tissue_col.get(row_idx));
+
+                        builder.upsert_biosample(patient_id, biosample_id, sampled_tissue)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TableContext;
+    use crate::config::table_context::SeriesContext;
+    use crate::config::traits::SeriesContextBuilding;
+    use crate::test_suite::cdf_generation::default_patient_id;
+    use crate::test_suite::component_building::build_test_phenopacket_builder;
+    use crate::test_suite::phenopacket_component_generation::default_anatomy_region;
+    use phenopackets::schema::v2::core::Biosample;
+    use polars::frame::DataFrame;
+    use polars::prelude::Column;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_collect_biosamples() {
+        let patient_id = default_patient_id();
+        let tc = TableContext::new(
+            "biosamples",
+            vec![
+                SeriesContext::from_identifier("subject_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("biosample_id")
+                    .with_data_context(Context::BiosampleId)
+                    .with_building_block_id("sample"),
+                SeriesContext::from_identifier("tissue")
+                    .with_data_context(Context::SampledTissue)
+                    .with_building_block_id("sample"),
+            ],
+        );
+        let df = DataFrame::new(
+            3,
+            vec![
+                Column::new(
+                    "subject_id".into(),
+                    [patient_id.clone(), patient_id.clone(), patient_id.clone()],
+                ),
+                Column::new("biosample_id".into(), [Some("BS-1"), Some("BS-2"), None]),
+                Column::new(
+                    "tissue".into(),
+                    [
+                        Some(default_anatomy_region().id),
+                        None,
+                        Some(default_anatomy_region().id),
+                    ],
+                ),
+            ],
+        )
+        .unwrap();
+        let cdf = ContextualizedDataFrame::new(tc, df).unwrap();
+
+        let mut builder = build_test_phenopacket_builder();
+        BiosampleCollector
+            .collect(&mut builder, &[cdf], &patient_id)
+            .unwrap();
+
+        let phenopackets = builder.build();
+        assert_eq!(
+            phenopackets[0].biosamples,
+            vec![
+                Biosample {
+                    id: "BS-1".to_string(),
+                    individual_id: patient_id.clone(),
+                    sampled_tissue: Some(default_anatomy_region()),
+                    ..Default::default()
+                },
+                Biosample {
+                    id: "BS-2".to_string(),
+                    individual_id: patient_id,
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+}
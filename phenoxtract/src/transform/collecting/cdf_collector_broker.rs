@@ -1,5 +1,6 @@
 use crate::extract::ContextualizedDataFrame;
 use crate::transform::PhenopacketBuilder;
+use crate::transform::collecting::biosample_collector::BiosampleCollector;
 use crate::transform::collecting::disease_collector::DiseaseCollector;
 use crate::transform::collecting::hpo_in_cells_collector::HpoInCellsCollector;
 use crate::transform::collecting::hpo_in_header_collector::HpoInHeaderCollector;
@@ -12,13 +13,81 @@ use crate::transform::collecting::quantitative_measurement_collector::Quantitati
 use crate::transform::collecting::traits::Collect;
 use crate::transform::error::CollectorError;
 use crate::transform::traits::PhenopacketBuilding;
+use log::warn;
 use phenopackets::schema::v2::Phenopacket;
-use std::collections::HashMap;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// A row, which had a subject id, but no other data that could have been collected.
+///
+/// Such rows do not change the output, but often hint at structural issues, like a mis-split table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmptyRowDiagnostic {
+    pub table_name: String,
+    pub row_idx: usize,
+    pub patient_id: String,
+}
+
+/// An opt-in guard against misconfigured subject id columns.
+///
+/// A column, which is mistakenly used as the subject id (e.g. a continuous measurement), splits the data into
+/// lots of one-row "patients". The guard turns this into an error instead of thousands of phenopackets.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CohortSizeGuard {
+    /// The maximum number of distinct subjects.
+    #[serde(default)]
+    pub max_subjects: Option<usize>,
+    /// The minimum average number of rows per subject, over all tables.
+    #[serde(default)]
+    pub min_rows_per_subject: Option<f64>,
+}
+
+impl CohortSizeGuard {
+    /// Only checks the number of subjects, which can only grow while more data is collected.
+    fn check_max_subjects(&self, n_subjects: usize) -> Result<(), CollectorError> {
+        if let Some(max_subjects) = self.max_subjects
+            && n_subjects > max_subjects
+        {
+            return Err(CollectorError::TooManySubjects {
+                n_subjects,
+                max_subjects,
+            });
+        }
+        Ok(())
+    }
+
+    fn check(&self, n_subjects: usize, n_rows: usize) -> Result<(), CollectorError> {
+        self.check_max_subjects(n_subjects)?;
+
+        if let Some(min_rows_per_subject) = self.min_rows_per_subject
+            && n_subjects > 0
+        {
+            let rows_per_subject = n_rows as f64 / n_subjects as f64;
+            if rows_per_subject < min_rows_per_subject {
+                return Err(CollectorError::TooFewRowsPerSubject {
+                    rows_per_subject,
+                    min_rows_per_subject,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
 
 #[derive(Debug)]
 pub struct CdfCollectorBroker {
     phenopacket_builder: PhenopacketBuilder,
     collectors: Vec<Box<dyn Collect>>,
+    empty_rows: Vec<EmptyRowDiagnostic>,
+    /// The subjects, which were collected in the current run.
+    collected_subjects: HashSet<String>,
+    /// The number of rows of each table, which were collected in the current run.
+    n_collected_rows: HashMap<String, usize>,
+    cohort_size_guard: Option<CohortSizeGuard>,
+    parallel_collection: bool,
 }
 
 impl CdfCollectorBroker {
@@ -26,16 +95,174 @@ impl CdfCollectorBroker {
         CdfCollectorBroker {
             phenopacket_builder,
             collectors,
+            empty_rows: vec![],
+            collected_subjects: HashSet::new(),
+            n_collected_rows: HashMap::new(),
+            cohort_size_guard: None,
+            parallel_collection: true,
         }
     }
 
+    /// Patients are collected in parallel by default. Turning it off collects them one after another on the current thread.
+    pub fn with_parallel_collection(mut self, parallel_collection: bool) -> Self {
+        self.parallel_collection = parallel_collection;
+        self
+    }
+
+    pub fn with_cohort_size_guard(mut self, cohort_size_guard: CohortSizeGuard) -> Self {
+        self.cohort_size_guard = Some(cohort_size_guard);
+        self
+    }
+
+    pub(crate) fn phenopacket_builder(&self) -> &PhenopacketBuilder {
+        &self.phenopacket_builder
+    }
+
+    pub(crate) fn phenopacket_builder_mut(&mut self) -> &mut PhenopacketBuilder {
+        &mut self.phenopacket_builder
+    }
+
+    /// Rows of the CDFs of the current run, which only contained a subject id.
+    pub fn empty_rows(&self) -> &[EmptyRowDiagnostic] {
+        &self.empty_rows
+    }
+
+    /// Forgets the empty rows and the subjects and rows, which were counted for the cohort size guard.
+    ///
+    /// [`CdfCollectorBroker::process`] and [`CdfCollectorBroker::process_incrementally`] start a run themselves.
+    /// Callers of [`CdfCollectorBroker::collect`] start it before the first part of the data.
+    pub fn start_run(&mut self) {
+        self.empty_rows.clear();
+        self.collected_subjects.clear();
+        self.n_collected_rows.clear();
+    }
+
     pub fn process(
         &mut self,
         cdfs: Vec<ContextualizedDataFrame>,
     ) -> Result<Vec<Phenopacket>, CollectorError> {
+        self.start_run();
+        self.collect(cdfs)?;
+        self.check_cohort_size()?;
+        Ok(self.build())
+    }
+
+    /// Collects the data of the CDFs into the phenopacket builder, without building the phenopackets yet.
+    ///
+    /// Data, which is collected for the same patient in several calls, ends up in the same phenopacket.
+    /// The empty rows and the cohort size are accumulated over all calls of the current run.
+    pub fn collect(&mut self, cdfs: Vec<ContextualizedDataFrame>) -> Result<(), CollectorError> {
+        let patient_id_to_dfs = self.split_by_patient(cdfs)?;
+        if let Some(guard) = &self.cohort_size_guard {
+            guard.check_max_subjects(self.collected_subjects.len())?;
+        }
+
+        if self.parallel_collection {
+            self.collect_parallel(patient_id_to_dfs)?;
+        } else {
+            for (patient_id, patient_cdfs) in patient_id_to_dfs {
+                Self::collect_patient(
+                    &self.collectors,
+                    &mut self.phenopacket_builder,
+                    &patient_cdfs,
+                    &patient_id,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Builds the phenopackets of all patients collected so far.
+    pub fn build(&self) -> Vec<Phenopacket> {
+        self.warn_about_dropped_fields();
+        self.phenopacket_builder.build()
+    }
+
+    /// Collects the patients one after another and hands every phenopacket to `on_phenopacket` as soon as it is built,
+    /// e.g. to write it before the next patient is collected.
+    ///
+    /// Patients, whose phenopacket id is in `skipped_phenopacket_ids`, are not collected at all.
+    pub fn process_incrementally<E: From<CollectorError>>(
+        &mut self,
+        cdfs: Vec<ContextualizedDataFrame>,
+        skipped_phenopacket_ids: &HashSet<String>,
+        mut on_phenopacket: impl FnMut(Phenopacket) -> Result<(), E>,
+    ) -> Result<(), E> {
+        self.start_run();
+        let patient_id_to_dfs = self.split_by_patient(cdfs)?;
+        self.check_cohort_size()?;
+        let mut patient_ids: Vec<&String> = patient_id_to_dfs.keys().collect();
+        patient_ids.sort();
+
+        for patient_id in patient_ids {
+            if skipped_phenopacket_ids
+                .contains(&self.phenopacket_builder.generate_phenopacket_id(patient_id))
+            {
+                continue;
+            }
+
+            Self::collect_patient(
+                &self.collectors,
+                &mut self.phenopacket_builder,
+                &patient_id_to_dfs[patient_id],
+                patient_id,
+            )?;
+            if let Some(phenopacket) = self.phenopacket_builder.build_phenopacket(patient_id) {
+                on_phenopacket(phenopacket)?;
+            }
+        }
+
+        self.warn_about_dropped_fields();
+        Ok(())
+    }
+
+    /// Checks the subjects and rows, which were collected in the current run, against the cohort size guard.
+    pub fn check_cohort_size(&self) -> Result<(), CollectorError> {
+        match &self.cohort_size_guard {
+            Some(guard) => guard.check(
+                self.collected_subjects.len(),
+                self.n_collected_rows.values().sum(),
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Splits the CDFs into the CDFs of each patient and counts their subjects, rows and empty rows.
+    fn split_by_patient(
+        &mut self,
+        cdfs: Vec<ContextualizedDataFrame>,
+    ) -> Result<HashMap<String, Vec<ContextualizedDataFrame>>, CollectorError> {
         let mut patient_id_to_dfs: HashMap<String, Vec<ContextualizedDataFrame>> = HashMap::new();
 
+        self.resolve_genes_up_front(&cdfs)?;
+
         for cdf in cdfs {
+            // The rows of a table, which is collected in parts, are numbered across all of its parts.
+            let n_table_rows = self
+                .n_collected_rows
+                .entry(cdf.context().name().to_string())
+                .or_default();
+            let first_row_idx = *n_table_rows;
+            *n_table_rows += cdf.data().height();
+
+            let mut empty_rows = Self::find_empty_rows(&cdf)?;
+            empty_rows
+                .iter_mut()
+                .for_each(|row| row.row_idx += first_row_idx);
+            if !empty_rows.is_empty() {
+                warn!(
+                    "Table '{}' has {} row(s) with a subject id, but no other data: {:?}",
+                    cdf.context().name(),
+                    empty_rows.len(),
+                    empty_rows
+                        .iter()
+                        .map(|row| format!("row {} ({})", row.row_idx, row.patient_id))
+                        .collect::<Vec<_>>()
+                );
+                self.empty_rows.extend(empty_rows);
+            }
+
             let subject_id_col = cdf.get_subject_id_col();
 
             let patient_dfs = cdf
@@ -60,32 +287,148 @@ impl CdfCollectorBroker {
             }
         }
 
-        for (patient_id, patient_cdfs) in patient_id_to_dfs {
-            for collector in &mut self.collectors {
-                collector.collect(
-                    &mut self.phenopacket_builder,
-                    &patient_cdfs,
-                    patient_id.as_str(),
-                )?;
+        self.collected_subjects
+            .extend(patient_id_to_dfs.keys().cloned());
+
+        Ok(patient_id_to_dfs)
+    }
+
+    fn warn_about_dropped_fields(&self) {
+        for (kind, n_patients) in self.phenopacket_builder.dropped_field_summary() {
+            warn!(
+                "{kind} was supplied for {n_patients} patient(s), but not emitted, because {}.",
+                kind.reason()
+            );
+        }
+    }
+
+    /// Splits the patients into one chunk per thread. Every chunk is collected into a builder of its own,
+    /// which are merged back afterward. The patients of different chunks never share a phenopacket.
+    fn collect_parallel(
+        &mut self,
+        patient_id_to_dfs: HashMap<String, Vec<ContextualizedDataFrame>>,
+    ) -> Result<(), CollectorError> {
+        let patients: Vec<(String, Vec<ContextualizedDataFrame>)> =
+            patient_id_to_dfs.into_iter().collect();
+        let chunk_size = patients.len().div_ceil(rayon::current_num_threads()).max(1);
+
+        let chunks: Vec<(
+            PhenopacketBuilder,
+            &[(String, Vec<ContextualizedDataFrame>)],
+        )> = patients
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let builder = self
+                    .phenopacket_builder
+                    .split_off(chunk.iter().map(|(patient_id, _)| patient_id.as_str()));
+                (builder, chunk)
+            })
+            .collect();
+
+        let collectors = &self.collectors;
+        let builders = chunks
+            .into_par_iter()
+            .map(|(mut builder, chunk)| {
+                for (patient_id, patient_cdfs) in chunk {
+                    Self::collect_patient(collectors, &mut builder, patient_cdfs, patient_id)?;
+                }
+                Ok(builder)
+            })
+            .collect::<Result<Vec<PhenopacketBuilder>, CollectorError>>()?;
+
+        for builder in builders {
+            self.phenopacket_builder.merge(builder);
+        }
+        Ok(())
+    }
+
+    /// Resolves the genes of all tables in one batch, before they are split by patient.
+    /// Afterward, the collection of each patient finds its genes in the cache.
+    fn resolve_genes_up_front(
+        &self,
+        cdfs: &[ContextualizedDataFrame],
+    ) -> Result<(), CollectorError> {
+        let collects_interpretations = self
+            .collectors
+            .iter()
+            .any(|collector| collector.as_any().is::<InterpretationCollector>());
+        if !collects_interpretations {
+            return Ok(());
+        }
+
+        let mut genes = HashSet::new();
+        for cdf in cdfs {
+            genes.extend(InterpretationCollector::causative_genes(cdf)?);
+        }
+        let unresolved_genes = self
+            .phenopacket_builder
+            .resolve_genes(genes.iter().map(String::as_str));
+        if !unresolved_genes.is_empty() {
+            warn!("Could not resolve the genes {unresolved_genes:?} via HGNC.");
+        }
+
+        Ok(())
+    }
+
+    fn collect_patient(
+        collectors: &[Box<dyn Collect>],
+        builder: &mut PhenopacketBuilder,
+        patient_cdfs: &[ContextualizedDataFrame],
+        patient_id: &str,
+    ) -> Result<(), CollectorError> {
+        for collector in collectors {
+            collector.collect(builder, patient_cdfs, patient_id)?;
+        }
+        Ok(())
+    }
+
+    fn find_empty_rows(
+        cdf: &ContextualizedDataFrame,
+    ) -> Result<Vec<EmptyRowDiagnostic>, CollectorError> {
+        let subject_id_col = cdf.get_subject_id_col();
+
+        let all_null = cdf
+            .data()
+            .columns()
+            .iter()
+            .filter(|col| col.name() != subject_id_col.name())
+            .map(|col| col.is_null())
+            .reduce(|acc, is_null| &acc & &is_null);
+
+        let Some(all_null) = all_null else {
+            return Ok(vec![]);
+        };
+
+        let mut empty_rows = vec![];
+        for (row_idx, is_empty) in all_null.iter().enumerate() {
+            if is_empty == Some(true) {
+                empty_rows.push(EmptyRowDiagnostic {
+                    table_name: cdf.context().name().to_string(),
+                    row_idx,
+                    patient_id: subject_id_col.get(row_idx)?.str_value().to_string(),
+                });
             }
         }
 
-        Ok(self.phenopacket_builder.build())
+        Ok(empty_rows)
     }
 
+    /// The [`HpoInHeaderCollector`] takes its observed threshold from the context of the builder.
     pub fn with_default_collectors(phenopacket_builder: PhenopacketBuilder) -> Self {
+        let hpo_observed_threshold = phenopacket_builder.ctx().hpo_observed_threshold();
         CdfCollectorBroker::new(
             phenopacket_builder,
             vec![
                 Box::new(IndividualCollector),
                 Box::new(HpoInCellsCollector::default()),
-                Box::new(HpoInHeaderCollector),
+                Box::new(HpoInHeaderCollector::new(hpo_observed_threshold)),
                 Box::new(InterpretationCollector),
                 Box::new(DiseaseCollector),
                 Box::new(QuantitativeMeasurementCollector),
                 Box::new(QualitativeMeasurementCollector),
                 Box::new(MedicalProcedureCollector),
                 Box::new(MedicalTreatmentCollector),
+                Box::new(BiosampleCollector),
             ],
         )
     }
@@ -93,7 +436,10 @@ impl CdfCollectorBroker {
 
 impl PartialEq for CdfCollectorBroker {
     fn eq(&self, other: &Self) -> bool {
-        if self.phenopacket_builder != other.phenopacket_builder {
+        if self.phenopacket_builder != other.phenopacket_builder
+            || self.cohort_size_guard != other.cohort_size_guard
+            || self.parallel_collection != other.parallel_collection
+        {
             return false;
         }
 
@@ -120,11 +466,21 @@ impl PartialEq for CdfCollectorBroker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::TableContext;
     use crate::config::context::Context;
+    use crate::config::table_context::SeriesContext;
+    use crate::config::traits::SeriesContextBuilding;
     use crate::extract::enums::Filter;
-    use crate::test_suite::cdf_generation::generate_minimal_cdf;
+    use crate::extract::utils::merge_subject_id_columns;
+    use crate::test_suite::cdf_generation::{
+        generate_minimal_cdf, generate_minimal_cdf_components, generate_patient_id,
+    };
     use crate::test_suite::component_building::build_test_phenopacket_builder;
     use crate::test_suite::mocks::MockCollector;
+    use crate::test_suite::phenopacket_component_generation::default_cohort_id;
+    use crate::validation::lint_report::Severity;
+    use crate::validation::linter::Linter;
+    use polars::prelude::{AnyValue, Column, DataFrame};
     use rstest::rstest;
 
     fn build_test_cdf_broker() -> CdfCollectorBroker {
@@ -166,6 +522,282 @@ mod tests {
         CdfCollectorBroker::new(builder, vec![Box::new(mock1), Box::new(mock2)])
     }
 
+    /// The second of the two rows only has a subject id.
+    fn cdf_with_empty_row() -> ContextualizedDataFrame {
+        let (subject_col, subject_sc) = generate_minimal_cdf_components(2, 1);
+        let df = DataFrame::new(
+            subject_col.len(),
+            vec![
+                subject_col,
+                Column::new("sex".into(), &[AnyValue::String("MALE"), AnyValue::Null]),
+            ],
+        )
+        .unwrap();
+        let tc = TableContext::new(
+            "sex_table",
+            vec![
+                subject_sc,
+                SeriesContext::from_identifier("sex").with_data_context(Context::SubjectSex),
+            ],
+        );
+        ContextualizedDataFrame::new(tc, df).unwrap()
+    }
+
+    #[rstest]
+    fn test_process_flags_rows_with_only_subject_id() {
+        let mut broker = CdfCollectorBroker::new(build_test_phenopacket_builder(), vec![]);
+        broker.process(vec![cdf_with_empty_row()]).unwrap();
+
+        assert_eq!(
+            broker.empty_rows(),
+            &[EmptyRowDiagnostic {
+                table_name: "sex_table".to_string(),
+                row_idx: 1,
+                patient_id: generate_patient_id(1),
+            }]
+        );
+    }
+
+    #[rstest]
+    fn test_cohort_size_guard_trips_on_mis_mapped_subject_id() {
+        let mut broker = CdfCollectorBroker::new(build_test_phenopacket_builder(), vec![])
+            .with_cohort_size_guard(CohortSizeGuard {
+                max_subjects: Some(10),
+                min_rows_per_subject: None,
+            });
+
+        // e.g. a measurement column mapped to SubjectId: every row becomes its own "patient"
+        let result = broker.process(vec![generate_minimal_cdf(50, 1)]);
+
+        assert!(matches!(
+            result,
+            Err(CollectorError::TooManySubjects {
+                n_subjects: 50,
+                max_subjects: 10
+            })
+        ));
+    }
+
+    #[rstest]
+    #[case(CohortSizeGuard { max_subjects: None, min_rows_per_subject: Some(2.0) }, 10, 1, true)]
+    #[case(CohortSizeGuard { max_subjects: None, min_rows_per_subject: Some(2.0) }, 10, 3, false)]
+    #[case(CohortSizeGuard { max_subjects: Some(10), min_rows_per_subject: None }, 10, 1, false)]
+    #[case(CohortSizeGuard::default(), 100, 1, false)]
+    fn test_cohort_size_guard(
+        #[case] guard: CohortSizeGuard,
+        #[case] n_patients: i64,
+        #[case] n_rows_per_patient: i64,
+        #[case] expect_err: bool,
+    ) {
+        let mut broker = CdfCollectorBroker::new(build_test_phenopacket_builder(), vec![])
+            .with_cohort_size_guard(guard);
+
+        let result = broker.process(vec![generate_minimal_cdf(n_patients, n_rows_per_patient)]);
+
+        assert_eq!(result.is_err(), expect_err);
+    }
+
+    #[rstest]
+    fn test_collect_accumulates_over_parts() {
+        let mut broker = CdfCollectorBroker::new(build_test_phenopacket_builder(), vec![]);
+
+        broker.start_run();
+        broker.collect(vec![cdf_with_empty_row()]).unwrap();
+        broker.collect(vec![cdf_with_empty_row()]).unwrap();
+
+        let row_idxs: Vec<usize> = broker.empty_rows().iter().map(|row| row.row_idx).collect();
+        assert_eq!(row_idxs, vec![1, 3]);
+    }
+
+    #[rstest]
+    #[case(CohortSizeGuard { max_subjects: None, min_rows_per_subject: Some(2.0) }, false)]
+    #[case(CohortSizeGuard { max_subjects: None, min_rows_per_subject: Some(3.0) }, true)]
+    #[case(CohortSizeGuard { max_subjects: Some(10), min_rows_per_subject: None }, false)]
+    fn test_cohort_size_guard_over_parts(#[case] guard: CohortSizeGuard, #[case] expect_err: bool) {
+        let mut broker = CdfCollectorBroker::new(build_test_phenopacket_builder(), vec![])
+            .with_cohort_size_guard(guard);
+
+        // every part on its own only has one row for each of the same 10 subjects
+        broker.start_run();
+        broker.collect(vec![generate_minimal_cdf(10, 1)]).unwrap();
+        broker.collect(vec![generate_minimal_cdf(10, 1)]).unwrap();
+
+        assert_eq!(broker.check_cohort_size().is_err(), expect_err);
+    }
+
+    fn sex_table_cdf(n_patients: i64) -> ContextualizedDataFrame {
+        let (subject_col, subject_sc) = generate_minimal_cdf_components(n_patients, 5);
+        let sexes: Vec<&str> = (0..subject_col.len())
+            .map(|row_idx| {
+                if (row_idx / 5) % 2 == 0 {
+                    "MALE"
+                } else {
+                    "FEMALE"
+                }
+            })
+            .collect();
+        let df = DataFrame::new(
+            subject_col.len(),
+            vec![subject_col, Column::new("sex".into(), sexes)],
+        )
+        .unwrap();
+        let tc = TableContext::new(
+            "sex_table",
+            vec![
+                subject_sc,
+                SeriesContext::from_identifier("sex").with_data_context(Context::SubjectSex),
+            ],
+        );
+        ContextualizedDataFrame::new(tc, df).unwrap()
+    }
+
+    fn process_sex_table(parallel_collection: bool) -> Vec<Phenopacket> {
+        let mut broker =
+            CdfCollectorBroker::with_default_collectors(build_test_phenopacket_builder())
+                .with_parallel_collection(parallel_collection);
+        let mut phenopackets = broker.process(vec![sex_table_cdf(200)]).unwrap();
+
+        // The creation timestamps differ between the runs
+        for pp in phenopackets.iter_mut() {
+            pp.meta_data.as_mut().unwrap().created = None;
+        }
+        phenopackets
+    }
+
+    #[rstest]
+    fn test_process_incrementally() {
+        let cohort_id = default_cohort_id();
+        let skipped = HashSet::from([format!("{cohort_id}-P1"), format!("{cohort_id}-P3")]);
+
+        let mut broker =
+            CdfCollectorBroker::with_default_collectors(build_test_phenopacket_builder());
+        let mut built_ids = vec![];
+        broker
+            .process_incrementally(vec![sex_table_cdf(4)], &skipped, |pp| {
+                built_ids.push(pp.id);
+                Ok::<(), CollectorError>(())
+            })
+            .unwrap();
+
+        assert_eq!(
+            built_ids,
+            vec![format!("{cohort_id}-P0"), format!("{cohort_id}-P2")]
+        );
+        assert!(broker.phenopacket_builder.build().is_empty());
+    }
+
+    #[rstest]
+    fn test_with_default_collectors_hpo_observed_threshold() {
+        let mut phenopacket_builder = build_test_phenopacket_builder();
+        phenopacket_builder.ctx_mut().set_hpo_observed_threshold(0.5);
+        let broker = CdfCollectorBroker::with_default_collectors(phenopacket_builder);
+
+        let thresholds: Vec<f64> = broker
+            .collectors
+            .iter()
+            .filter_map(|collector| collector.as_any().downcast_ref::<HpoInHeaderCollector>())
+            .map(HpoInHeaderCollector::observed_threshold)
+            .collect();
+
+        assert_eq!(thresholds, vec![0.5]);
+        assert_eq!(broker.collectors.len(), 10);
+    }
+
+    #[rstest]
+    fn test_process_flags_conflicting_observation_statuses() {
+        let (subject_col, subject_sc) = generate_minimal_cdf_components(1, 1);
+        let observed_col = "HP:0041249#(block foo)";
+        let excluded_col = "HP:0041249#(block bar)";
+        let df = DataFrame::new(
+            subject_col.len(),
+            vec![
+                subject_col,
+                Column::new(observed_col.into(), [true]),
+                Column::new(excluded_col.into(), [false]),
+            ],
+        )
+        .unwrap();
+        let tc = TableContext::new(
+            "hpo_table",
+            vec![
+                subject_sc,
+                SeriesContext::from_identifier(observed_col)
+                    .with_header_context(Context::Hpo)
+                    .with_data_context(Context::ObservationStatus)
+                    .with_building_block_id("foo"),
+                SeriesContext::from_identifier(excluded_col)
+                    .with_header_context(Context::Hpo)
+                    .with_data_context(Context::ObservationStatus)
+                    .with_building_block_id("bar"),
+            ],
+        );
+        let cdf = ContextualizedDataFrame::new(tc, df).unwrap();
+
+        let mut broker =
+            CdfCollectorBroker::with_default_collectors(build_test_phenopacket_builder());
+        let phenopackets = broker.process(vec![cdf]).unwrap();
+        let findings =
+            Linter::default().lint_dropped_fields(broker.phenopacket_builder().dropped_fields());
+
+        assert!(!phenopackets[0].phenotypic_features[0].excluded);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, "conflicting_observation_status");
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[0].patient_id, Some(generate_patient_id(0)));
+    }
+
+    #[rstest]
+    fn test_process_with_composite_subject_id() {
+        let df = DataFrame::new(
+            3,
+            vec![
+                Column::new("site".into(), ["SITE1", "SITE1", "SITE2"]),
+                Column::new("local_id".into(), ["P001", "P002", "P001"]),
+                Column::new("sex".into(), ["MALE", "FEMALE", "MALE"]),
+            ],
+        )
+        .unwrap();
+        let tc = TableContext::new(
+            "sex_table",
+            vec![
+                SeriesContext::from_identifier(vec!["site", "local_id"])
+                    .with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("sex").with_data_context(Context::SubjectSex),
+            ],
+        )
+        .with_subject_id_separator("_");
+        let (tc, df) = merge_subject_id_columns(&tc, df).unwrap();
+        let cdf = ContextualizedDataFrame::new(tc, df).unwrap();
+
+        let mut broker =
+            CdfCollectorBroker::with_default_collectors(build_test_phenopacket_builder());
+        let mut pp_ids: Vec<String> = broker
+            .process(vec![cdf])
+            .unwrap()
+            .into_iter()
+            .map(|pp| pp.id)
+            .collect();
+        pp_ids.sort();
+
+        let cohort_id = default_cohort_id();
+        assert_eq!(
+            pp_ids,
+            vec![
+                format!("{cohort_id}-SITE1_P001"),
+                format!("{cohort_id}-SITE1_P002"),
+                format!("{cohort_id}-SITE2_P001"),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_parallel_collection_matches_sequential() {
+        let sequential = process_sex_table(false);
+        let parallel = process_sex_table(true);
+
+        assert_eq!(parallel, sequential);
+    }
+
     #[rstest]
     fn test_process() {
         let mut broker = build_test_cdf_broker();
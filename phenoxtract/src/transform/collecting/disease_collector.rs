@@ -1,8 +1,9 @@
 use crate::config::context::Context;
 use crate::extract::ContextualizedDataFrame;
+use crate::extract::column_filter::ColumnFilterConfig;
 use crate::extract::enums::Filter;
 use crate::transform::collecting::traits::Collect;
-use crate::transform::collecting::utils::get_str_at_index;
+use crate::transform::collecting::utils::{get_single_multiplicity_element, get_str_at_index};
 use crate::transform::error::CollectorError;
 use crate::transform::traits::PhenopacketBuilding;
 use std::any::Any;
@@ -33,38 +34,90 @@ impl Collect for DiseaseCollector {
                 let onset_col =
                     patient_cdf.get_single_linked_column_as_str(bb_id, Context::ONSET_VARIANTS)?;
 
-                let resolution_col = patient_cdf.get_single_linked_column_as_str(
-                    disease_sc.get_building_block_id(),
+                let spread_onset = Self::get_spread_building_block_element(
+                    patient_cdfs,
+                    onset_col.is_some(),
+                    bb_id,
+                    Context::ONSET_VARIANTS,
+                )?;
+
+                let resolution_col = patient_cdf
+                    .get_single_linked_column_as_str(bb_id, Context::TIME_OF_RESOLUTION_VARIANTS)?;
+
+                let spread_resolution = Self::get_spread_building_block_element(
+                    patient_cdfs,
+                    resolution_col.is_some(),
+                    bb_id,
                     Context::TIME_OF_RESOLUTION_VARIANTS,
                 )?;
 
                 let primary_site_col =
                     patient_cdf.get_single_linked_column_as_str(bb_id, &[Context::PrimarySite])?;
 
-                for row_idx in 0..patient_cdf.data().height() {
+                let laterality_col =
+                    patient_cdf.get_single_linked_column_as_str(bb_id, &[Context::Laterality])?;
+
+                let observation_status_col = patient_cdf
+                    .get_single_linked_bool_column(bb_id, &[Context::ObservationStatus])?;
+
+                let stage_cols =
+                    patient_cdf.get_stringified_cols(patient_cdf.get_linked_cols_with_context(
+                        bb_id,
+                        &Context::DiseaseStage,
+                        &Context::None,
+                    ))?;
+
+                let tnm_finding_cols =
+                    patient_cdf.get_stringified_cols(patient_cdf.get_linked_cols_with_context(
+                        bb_id,
+                        &Context::ClinicalTnmFinding,
+                        &Context::None,
+                    ))?;
+
+                for row_idx in patient_cdf.building_block_rows(bb_id) {
                     for disease_col in disease_cols.iter() {
                         let stringified_disease_col = disease_col.str()?;
 
                         let disease = stringified_disease_col.get(row_idx);
                         if let Some(disease) = disease {
-                            let disease_onset = get_str_at_index(onset_col.as_ref(), row_idx);
+                            let disease_onset = get_str_at_index(onset_col.as_ref(), row_idx)
+                                .or(spread_onset.as_deref());
 
                             let disease_resolution =
-                                get_str_at_index(resolution_col.as_ref(), row_idx);
+                                get_str_at_index(resolution_col.as_ref(), row_idx)
+                                    .or(spread_resolution.as_deref());
 
                             let disease_primary_site =
                                 get_str_at_index(primary_site_col.as_ref(), row_idx);
 
+                            let excluded = observation_status_col
+                                .as_ref()
+                                .and_then(|col| col.get(row_idx))
+                                .map(|observed| !observed);
+
+                            let disease_laterality =
+                                get_str_at_index(laterality_col.as_ref(), row_idx);
+
+                            let stages: Vec<&str> = stage_cols
+                                .iter()
+                                .filter_map(|stage_col| stage_col.get(row_idx))
+                                .collect();
+
+                            let tnm_findings: Vec<&str> = tnm_finding_cols
+                                .iter()
+                                .filter_map(|tnm_finding_col| tnm_finding_col.get(row_idx))
+                                .collect();
+
                             builder.insert_disease(
                                 patient_id,
                                 disease,
-                                None,
+                                excluded,
                                 disease_onset,
                                 disease_resolution,
-                                None,
-                                None,
+                                (!stages.is_empty()).then_some(stages.as_slice()),
+                                (!tnm_findings.is_empty()).then_some(tnm_findings.as_slice()),
                                 disease_primary_site,
-                                None,
+                                disease_laterality,
                             )?;
                         }
                     }
@@ -80,13 +133,38 @@ impl Collect for DiseaseCollector {
     }
 }
 
+impl DiseaseCollector {
+    /// The elements of a building block, which is spread over several tables, can be in another table.
+    /// The interpretation collector links such blocks, so these elements are looked up the same way.
+    fn get_spread_building_block_element(
+        patient_cdfs: &[ContextualizedDataFrame],
+        has_linked_col: bool,
+        bb_id: Option<&str>,
+        data_contexts: &[Context],
+    ) -> Result<Option<String>, CollectorError> {
+        match (has_linked_col, bb_id) {
+            (false, Some(bb_id)) => get_single_multiplicity_element(
+                patient_cdfs,
+                ColumnFilterConfig::default()
+                    .where_building_block(Filter::Is(bb_id))
+                    .where_header_context(Filter::Is(&Context::None))
+                    .where_data_contexts_are(data_contexts),
+            ),
+            _ => Ok(None),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::TableContext;
     use crate::config::context::TimeElementType;
     use crate::config::table_context::SeriesContext;
     use crate::config::traits::SeriesContextBuilding;
-    use crate::test_suite::cdf_generation::{default_patient_id, generate_minimal_cdf};
+    use crate::test_suite::cdf_generation::{
+        default_patient_id, generate_minimal_cdf, generate_minimal_cdf_components,
+    };
     use crate::test_suite::component_building::build_test_phenopacket_builder;
     use crate::test_suite::phenopacket_component_generation::{
         default_anatomy_region, default_meta_data,
@@ -100,7 +178,9 @@ mod tests {
     use crate::test_suite::utils::assert_phenopackets;
     use crate::utils::phenopacket_schema_version;
     use phenopackets::schema::v2::Phenopacket;
-    use phenopackets::schema::v2::core::MetaData;
+    use phenopackets::schema::v2::core::time_element::Element;
+    use phenopackets::schema::v2::core::{Age, MetaData, TimeElement};
+    use polars::frame::DataFrame;
     use polars::prelude::{AnyValue, Column};
     use rstest::rstest;
 
@@ -184,7 +264,7 @@ mod tests {
             diseases,
             meta_data: Some(MetaData {
                 phenopacket_schema_version: phenopacket_schema_version(),
-                resources: vec![uberon_meta_data_resource(), mondo_meta_data_resource()],
+                resources: vec![mondo_meta_data_resource(), uberon_meta_data_resource()],
                 created_by: default_meta_data().created_by.clone(),
                 submitted_by: default_meta_data().submitted_by.clone(),
                 ..Default::default()
@@ -195,4 +275,268 @@ mod tests {
         pretty_assertions::assert_eq!(phenopackets.len(), 1);
         assert_phenopackets(&mut phenopackets[0], &mut expected_phenopacket);
     }
+
+    #[rstest]
+    fn test_collect_disease_clinical_tnm_findings() {
+        let mut builder = build_test_phenopacket_builder();
+        let patient_id = default_patient_id();
+
+        let mut cdf = generate_minimal_cdf(1, 2);
+        let disease_col = Column::new("disease".into(), ["MONDO:0008258", "MONDO:0000252"]);
+        let t_col = Column::new("t_stage".into(), [Some("NCIT:C48724"), None]);
+        let n_col = Column::new("n_stage".into(), [Some("NCIT:C48706"), Some("NCIT:C48706")]);
+
+        cdf.builder()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("disease")
+                    .with_data_context(Context::Disease)
+                    .with_building_block_id("tumor"),
+                vec![disease_col].as_ref(),
+            )
+            .unwrap()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("t_stage")
+                    .with_data_context(Context::ClinicalTnmFinding)
+                    .with_building_block_id("tumor"),
+                vec![t_col].as_ref(),
+            )
+            .unwrap()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("n_stage")
+                    .with_data_context(Context::ClinicalTnmFinding)
+                    .with_building_block_id("tumor"),
+                vec![n_col].as_ref(),
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        DiseaseCollector
+            .collect(&mut builder, &[cdf], &patient_id)
+            .unwrap();
+
+        let phenopackets = builder.build();
+        let tnm_findings: Vec<Vec<String>> = phenopackets[0]
+            .diseases
+            .iter()
+            .map(|disease| {
+                disease
+                    .clinical_tnm_finding
+                    .iter()
+                    .map(|tnm_finding| tnm_finding.id.clone())
+                    .collect()
+            })
+            .collect();
+
+        pretty_assertions::assert_eq!(
+            tnm_findings,
+            vec![
+                vec!["NCIT:C48724".to_string(), "NCIT:C48706".to_string()],
+                vec!["NCIT:C48706".to_string()],
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_collect_disease_stages() {
+        let mut builder = build_test_phenopacket_builder();
+        let patient_id = default_patient_id();
+
+        let mut cdf = generate_minimal_cdf(1, 1);
+        let disease_col = Column::new("disease".into(), ["MONDO:0008258"]);
+        let initial_stage_col = Column::new("initial_stage".into(), ["NCIT:C27970"]);
+        let current_stage_col = Column::new("current_stage".into(), ["Stage IV"]);
+
+        cdf.builder()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("disease")
+                    .with_data_context(Context::Disease)
+                    .with_building_block_id("tumor"),
+                vec![disease_col].as_ref(),
+            )
+            .unwrap()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("initial_stage")
+                    .with_data_context(Context::DiseaseStage)
+                    .with_building_block_id("tumor"),
+                vec![initial_stage_col].as_ref(),
+            )
+            .unwrap()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("current_stage")
+                    .with_data_context(Context::DiseaseStage)
+                    .with_building_block_id("tumor"),
+                vec![current_stage_col].as_ref(),
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        DiseaseCollector
+            .collect(&mut builder, &[cdf], &patient_id)
+            .unwrap();
+
+        let phenopackets = builder.build();
+        let stages: Vec<String> = phenopackets[0].diseases[0]
+            .disease_stage
+            .iter()
+            .map(|stage| stage.id.clone())
+            .collect();
+
+        pretty_assertions::assert_eq!(stages, vec!["NCIT:C27970", "NCIT:C27971"]);
+    }
+
+    #[rstest]
+    fn test_collect_disease_laterality() {
+        let mut builder = build_test_phenopacket_builder();
+        let patient_id = default_patient_id();
+
+        let mut cdf = generate_minimal_cdf(1, 2);
+        let disease_col = Column::new("disease".into(), ["MONDO:0008258", "MONDO:0000252"]);
+        let laterality_col = Column::new("side".into(), [Some("Left"), None]);
+
+        cdf.builder()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("disease")
+                    .with_data_context(Context::Disease)
+                    .with_building_block_id("disease_1"),
+                vec![disease_col].as_ref(),
+            )
+            .unwrap()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("side")
+                    .with_data_context(Context::Laterality)
+                    .with_building_block_id("disease_1"),
+                vec![laterality_col].as_ref(),
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        DiseaseCollector
+            .collect(&mut builder, &[cdf], &patient_id)
+            .unwrap();
+
+        let phenopackets = builder.build();
+        let lateralities: Vec<Option<String>> = phenopackets[0]
+            .diseases
+            .iter()
+            .map(|disease| disease.laterality.as_ref().map(|side| side.id.clone()))
+            .collect();
+
+        pretty_assertions::assert_eq!(lateralities, vec![Some("HP:0012835".to_string()), None]);
+    }
+
+    #[rstest]
+    fn test_collect_disease_resolution_multi_sheet() {
+        let (patient_col, patient_sc) = generate_minimal_cdf_components(1, 1);
+
+        let disease_cdf = ContextualizedDataFrame::new(
+            TableContext::new(
+                "disease_table",
+                vec![
+                    patient_sc.clone(),
+                    SeriesContext::from_identifier("disease")
+                        .with_data_context(Context::Disease)
+                        .with_building_block_id("D"),
+                ],
+            ),
+            DataFrame::new(
+                1,
+                vec![
+                    patient_col.clone(),
+                    Column::new("disease".into(), ["MONDO:0008258"]),
+                ],
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let course_cdf = ContextualizedDataFrame::new(
+            TableContext::new(
+                "course_table",
+                vec![
+                    patient_sc,
+                    SeriesContext::from_identifier("resolved_at")
+                        .with_data_context(Context::TimeOfResolution(TimeElementType::Age))
+                        .with_building_block_id("D"),
+                ],
+            ),
+            DataFrame::new(
+                1,
+                vec![patient_col, Column::new("resolved_at".into(), ["P5Y"])],
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mut builder = build_test_phenopacket_builder();
+        DiseaseCollector
+            .collect(
+                &mut builder,
+                &[disease_cdf, course_cdf],
+                &default_patient_id(),
+            )
+            .unwrap();
+
+        let phenopackets = builder.build();
+        pretty_assertions::assert_eq!(
+            phenopackets[0].diseases[0].resolution,
+            Some(TimeElement {
+                element: Some(Element::Age(Age {
+                    iso8601duration: "P5Y".to_string(),
+                })),
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_collect_excluded_diseases() {
+        let mut builder = build_test_phenopacket_builder();
+        let patient_id = default_patient_id();
+
+        let mut cdf = generate_minimal_cdf(1, 2);
+        let disease_col = Column::new("disease".into(), ["MONDO:0008258", "MONDO:0000252"]);
+        let observation_status_col = Column::new(
+            "observation_status".into(),
+            [AnyValue::Boolean(false), AnyValue::Boolean(true)],
+        );
+
+        cdf.builder()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("disease")
+                    .with_data_context(Context::Disease)
+                    .with_building_block_id("disease_1"),
+                vec![disease_col].as_ref(),
+            )
+            .unwrap()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("observation_status")
+                    .with_data_context(Context::ObservationStatus)
+                    .with_building_block_id("disease_1"),
+                vec![observation_status_col].as_ref(),
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        DiseaseCollector
+            .collect(&mut builder, &[cdf], &patient_id)
+            .unwrap();
+
+        let phenopackets = builder.build();
+        let excluded: Vec<(String, bool)> = phenopackets[0]
+            .diseases
+            .iter()
+            .map(|disease| (disease.term.clone().unwrap().id, disease.excluded))
+            .collect();
+
+        pretty_assertions::assert_eq!(
+            excluded,
+            vec![
+                ("MONDO:0008258".to_string(), true),
+                ("MONDO:0000252".to_string(), false),
+            ]
+        );
+    }
 }
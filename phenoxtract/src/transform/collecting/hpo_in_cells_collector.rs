@@ -45,6 +45,16 @@ impl Collect for HpoInCellsCollector {
                     &[Context::Severity],
                 )?;
 
+                let evidence_column = patient_cdf.get_single_linked_column_as_str(
+                    hpo_sc.get_building_block_id(),
+                    &[Context::EvidenceCode],
+                )?;
+
+                let reference_column = patient_cdf.get_single_linked_column_as_str(
+                    hpo_sc.get_building_block_id(),
+                    &[Context::Reference],
+                )?;
+
                 let observation_status_column = patient_cdf.get_single_linked_bool_column(
                     hpo_sc.get_building_block_id(),
                     &[Context::ObservationStatus],
@@ -52,10 +62,19 @@ impl Collect for HpoInCellsCollector {
 
                 for hpo_col in hpo_cols {
                     let stringified_hpo_col = hpo_col.str()?;
+                    let raw_value_col = patient_cdf
+                        .get_raw_value_column(hpo_col.name())
+                        .map(|col| col.str())
+                        .transpose()?;
 
                     for row_idx in 0..stringified_hpo_col.len() {
                         let hpo = stringified_hpo_col.get(row_idx);
                         if let Some(hpo) = hpo {
+                            let hpo_description = raw_value_col
+                                .and_then(|col| col.get(row_idx))
+                                .filter(|raw_value| *raw_value != hpo)
+                                .map(|raw_value| format!("Original value: {raw_value}"));
+
                             let hpo_onset = get_str_at_index(onset_column.as_ref(), row_idx);
 
                             let hpo_resolution =
@@ -63,6 +82,11 @@ impl Collect for HpoInCellsCollector {
 
                             let hpo_severity = get_str_at_index(severity_column.as_ref(), row_idx);
 
+                            let hpo_evidence = get_str_at_index(evidence_column.as_ref(), row_idx);
+
+                            let hpo_reference =
+                                get_str_at_index(reference_column.as_ref(), row_idx);
+
                             let excluded = observation_status_column
                                 .as_ref()
                                 .and_then(|col| col.get(row_idx))
@@ -72,25 +96,27 @@ impl Collect for HpoInCellsCollector {
                                 builder.insert_phenotypic_feature(
                                     patient_id,
                                     hpo,
-                                    None,
+                                    hpo_description.as_deref(),
                                     excluded,
                                     hpo_severity,
                                     None,
                                     hpo_onset,
                                     hpo_resolution,
-                                    None,
+                                    hpo_evidence,
+                                    hpo_reference,
                                 )?;
                             } else {
                                 builder.upsert_phenotypic_feature(
                                     patient_id,
                                     hpo,
-                                    None,
+                                    hpo_description.as_deref(),
                                     excluded,
                                     hpo_severity,
                                     None,
                                     hpo_onset,
                                     hpo_resolution,
-                                    None,
+                                    hpo_evidence,
+                                    hpo_reference,
                                 )?;
                             }
                         }
@@ -119,6 +145,7 @@ mod tests {
     };
     use crate::test_suite::resource_references::hp_meta_data_resource;
     use crate::test_suite::utils::assert_phenopackets;
+    use crate::transform::data_processing::preprocessor::CdfPreprocessor;
     use phenopackets::schema::v2::Phenopacket;
 
     use crate::config::context::TimeElementType;
@@ -203,4 +230,211 @@ mod tests {
         pretty_assertions::assert_eq!(phenopackets.len(), 1);
         assert_phenopackets(&mut phenopackets[0], &mut expected_phenopacket);
     }
+
+    fn phenotype_with_resolution_cdf(resolution: &str) -> ContextualizedDataFrame {
+        let mut patient_cdf = generate_minimal_cdf(1, 1);
+        let phenotypes = Series::new(
+            "phenotypes".into(),
+            &[default_phenotype().r#type.unwrap().label],
+        );
+        let resolution = Series::new("resolution_age".into(), &[resolution]);
+
+        patient_cdf
+            .builder()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("phenotypes")
+                    .with_data_context(Context::Hpo)
+                    .with_building_block_id("phenotype_1"),
+                vec![phenotypes.into_column()].as_ref(),
+            )
+            .unwrap()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("resolution_age")
+                    .with_data_context(Context::TimeOfResolution(TimeElementType::Age))
+                    .with_building_block_id("phenotype_1"),
+                vec![resolution.into_column()].as_ref(),
+            )
+            .unwrap()
+            .build()
+            .unwrap()
+            .clone()
+    }
+
+    #[rstest]
+    fn test_collect_phenotypic_feature_resolution() {
+        let mut builder = build_test_phenopacket_builder();
+        let patient_id = default_patient_id();
+
+        HpoInCellsCollector::default()
+            .collect(
+                &mut builder,
+                &[phenotype_with_resolution_cdf(&default_iso_age())],
+                &patient_id,
+            )
+            .unwrap();
+
+        let phenopackets = builder.build();
+
+        pretty_assertions::assert_eq!(
+            phenopackets[0].phenotypic_features[0].resolution,
+            Some(default_age_element())
+        );
+    }
+
+    #[rstest]
+    fn test_collect_phenotypic_feature_unparseable_resolution() {
+        let mut builder = build_test_phenopacket_builder();
+        let patient_id = default_patient_id();
+
+        let result = HpoInCellsCollector::default().collect(
+            &mut builder,
+            &[phenotype_with_resolution_cdf("when the rash cleared")],
+            &patient_id,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_collect_phenotypic_feature_evidence() {
+        let mut patient_cdf = generate_minimal_cdf(1, 1);
+        let phenotypes = Series::new(
+            "phenotypes".into(),
+            &[default_phenotype().r#type.unwrap().label],
+        );
+        let evidence = Series::new("evidence".into(), &["ECO:0000033"]);
+
+        patient_cdf
+            .builder()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("phenotypes")
+                    .with_data_context(Context::Hpo)
+                    .with_building_block_id("phenotype_1"),
+                vec![phenotypes.into_column()].as_ref(),
+            )
+            .unwrap()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("evidence")
+                    .with_data_context(Context::EvidenceCode)
+                    .with_building_block_id("phenotype_1"),
+                vec![evidence.into_column()].as_ref(),
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut builder = build_test_phenopacket_builder();
+        HpoInCellsCollector::default()
+            .collect(&mut builder, &[patient_cdf], &default_patient_id())
+            .unwrap();
+
+        let phenopackets = builder.build();
+        let evidence = &phenopackets[0].phenotypic_features[0].evidence;
+
+        pretty_assertions::assert_eq!(evidence.len(), 1);
+        pretty_assertions::assert_eq!(
+            evidence[0].evidence_code.as_ref().unwrap().id,
+            "ECO:0000033"
+        );
+    }
+
+    #[rstest]
+    fn test_collect_phenotypic_feature_evidence_reference() {
+        let mut patient_cdf = generate_minimal_cdf(1, 1);
+        let phenotypes = Series::new(
+            "phenotypes".into(),
+            &[default_phenotype().r#type.unwrap().label],
+        );
+        let evidence = Series::new("evidence".into(), &["ECO:0000033"]);
+        let reference = Series::new("reference".into(), &["PMID: 12345678"]);
+
+        patient_cdf
+            .builder()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("phenotypes")
+                    .with_data_context(Context::Hpo)
+                    .with_building_block_id("phenotype_1"),
+                vec![phenotypes.into_column()].as_ref(),
+            )
+            .unwrap()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("evidence")
+                    .with_data_context(Context::EvidenceCode)
+                    .with_building_block_id("phenotype_1"),
+                vec![evidence.into_column()].as_ref(),
+            )
+            .unwrap()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("reference")
+                    .with_data_context(Context::Reference)
+                    .with_building_block_id("phenotype_1"),
+                vec![reference.into_column()].as_ref(),
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut builder = build_test_phenopacket_builder();
+        HpoInCellsCollector::default()
+            .collect(&mut builder, &[patient_cdf], &default_patient_id())
+            .unwrap();
+
+        let phenopackets = builder.build();
+        let evidence = &phenopackets[0].phenotypic_features[0].evidence;
+
+        pretty_assertions::assert_eq!(evidence.len(), 1);
+        let reference = evidence[0].reference.as_ref().unwrap();
+        pretty_assertions::assert_eq!(reference.id, "PMID:12345678");
+        pretty_assertions::assert_eq!(
+            reference.reference,
+            "https://pubmed.ncbi.nlm.nih.gov/12345678"
+        );
+    }
+
+    #[rstest]
+    fn test_collect_phenotypic_feature_raw_value() {
+        let mut patient_cdf = generate_minimal_cdf(1, 2);
+        let phenotypes = Series::new("phenotypes".into(), &["fits", "Spasmus nutans"]);
+
+        patient_cdf
+            .builder()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("phenotypes").with_data_context(Context::Hpo),
+                vec![phenotypes.into_column()].as_ref(),
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        CdfPreprocessor::preserve_raw_values(&mut patient_cdf).unwrap();
+
+        // Stands in for a strategy, which normalises the cell values.
+        let normalised_phenotypes = Series::new(
+            "phenotypes".into(),
+            &[
+                default_phenotype().r#type.unwrap().label,
+                "Spasmus nutans".to_string(),
+            ],
+        );
+        patient_cdf
+            .builder()
+            .replace_col("phenotypes", normalised_phenotypes)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut builder = build_test_phenopacket_builder();
+        HpoInCellsCollector::default()
+            .collect(&mut builder, &[patient_cdf], &default_patient_id())
+            .unwrap();
+
+        let phenopackets = builder.build();
+        let phenotypic_features = &phenopackets[0].phenotypic_features;
+
+        pretty_assertions::assert_eq!(phenotypic_features.len(), 2);
+        pretty_assertions::assert_eq!(
+            phenotypic_features[0].description,
+            "Original value: fits"
+        );
+        pretty_assertions::assert_eq!(phenotypic_features[1].description, "");
+    }
 }
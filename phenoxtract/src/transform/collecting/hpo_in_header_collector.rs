@@ -2,15 +2,46 @@ use crate::config::context::Context;
 use crate::extract::ContextualizedDataFrame;
 use crate::extract::enums::Filter;
 use crate::transform::collecting::traits::Collect;
+use crate::transform::collecting::utils::get_str_at_index;
 use crate::transform::error::CollectorError;
 use crate::transform::traits::PhenopacketBuilding;
 use crate::transform::utils::HpoColMaker;
 use log::warn;
+use polars::prelude::{BooleanChunked, Column, DataType};
 use std::any::Any;
 use std::collections::HashSet;
 
-#[derive(Debug)]
-pub struct HpoInHeaderCollector;
+/// Collects phenotypes from columns, which have an HPO term in their header and the observation status in their cells.
+///
+/// The cells are either booleans or numeric scores/counts. A score above `observed_threshold` means the
+/// phenotype was observed, otherwise it was excluded.
+#[derive(Debug, Default)]
+pub struct HpoInHeaderCollector {
+    observed_threshold: f64,
+}
+
+impl HpoInHeaderCollector {
+    pub fn new(observed_threshold: f64) -> Self {
+        Self { observed_threshold }
+    }
+
+    pub fn observed_threshold(&self) -> f64 {
+        self.observed_threshold
+    }
+
+    fn observation_statuses(&self, hpo_col: &Column) -> Result<BooleanChunked, CollectorError> {
+        if hpo_col.dtype().is_primitive_numeric() {
+            let scores = hpo_col.cast(&DataType::Float64)?;
+            Ok(scores
+                .f64()?
+                .into_iter()
+                .map(|score| score.map(|score| score > self.observed_threshold))
+                .collect())
+        } else {
+            Ok(hpo_col.bool()?.clone())
+        }
+    }
+}
 
 impl Collect for HpoInHeaderCollector {
     fn collect(
@@ -35,34 +66,41 @@ impl Collect for HpoInHeaderCollector {
                     Context::ONSET_VARIANTS,
                 )?;
 
+                let stringified_linked_resolution_col = patient_cdf
+                    .get_single_linked_column_as_str(
+                        hpo_sc.get_building_block_id(),
+                        Context::TIME_OF_RESOLUTION_VARIANTS,
+                    )?;
+
                 for hpo_col in hpo_cols {
                     let hpo_id = HpoColMaker::new().decode_column_header(hpo_col).0;
 
-                    let boolified_hpo_col = hpo_col.bool()?;
+                    let boolified_hpo_col = self.observation_statuses(hpo_col)?;
 
                     let mut seen_pairs = HashSet::new();
 
                     for row_idx in 0..boolified_hpo_col.len() {
                         let obs_status = boolified_hpo_col.get(row_idx);
-                        let onset = if let Some(onset_col) = &stringified_linked_onset_col {
-                            onset_col.get(row_idx)
-                        } else {
-                            None
-                        };
-                        seen_pairs.insert((obs_status, onset));
+                        let onset =
+                            get_str_at_index(stringified_linked_onset_col.as_ref(), row_idx);
+                        let resolution =
+                            get_str_at_index(stringified_linked_resolution_col.as_ref(), row_idx);
+                        seen_pairs.insert((obs_status, onset, resolution));
                     }
 
-                    seen_pairs.remove(&(None, None));
+                    seen_pairs.remove(&(None, None, None));
 
                     if seen_pairs.len() == 1 {
-                        let (obs_status, onset) = seen_pairs.into_iter().next().unwrap();
+                        let (obs_status, onset, resolution) =
+                            seen_pairs.into_iter().next().unwrap();
                         //if the observation_status is None, no phenotype is upserted
                         //if the observation_status is true, the phenotype is upserted with excluded = None
                         //if the observation_status is false, the phenotype is upserted with excluded = true
                         if let Some(obs_status) = obs_status {
                             let excluded = if obs_status { None } else { Some(true) };
                             builder.upsert_phenotypic_feature(
-                                patient_id, hpo_id, None, excluded, None, None, onset, None, None,
+                                patient_id, hpo_id, None, excluded, None, None, onset, resolution,
+                                None, None,
                             )?;
                         } else if let Some(onset) = onset {
                             warn!(
@@ -157,10 +195,58 @@ mod tests {
             .clone()
     }
 
+    #[rstest]
+    fn test_collect_hpo_in_header_col_with_resolution() {
+        let mut builder = build_test_phenopacket_builder();
+        let patient_id = default_patient_id();
+
+        let (patient_col, sc) = generate_minimal_cdf_components(1, 1);
+
+        let phenotype_col_name = default_phenotype().r#type.unwrap().id;
+        let phenotype_col =
+            Column::new(phenotype_col_name.clone().into(), [AnyValue::Boolean(true)]);
+        let resolution_col = Column::new(
+            "resolution_age".into(),
+            [AnyValue::String(&default_iso_age())],
+        );
+
+        let context = vec![
+            sc,
+            SeriesContext::from_identifier(phenotype_col_name)
+                .with_data_context(Context::ObservationStatus)
+                .with_building_block_id("bb1")
+                .with_header_context(Context::Hpo),
+            SeriesContext::from_identifier("resolution_age")
+                .with_data_context(Context::TimeOfResolution(TimeElementType::Age))
+                .with_building_block_id("bb1"),
+        ];
+
+        let cdf = ContextualizedDataFrame::new(
+            TableContext::new("TestTable", context),
+            DataFrame::new(
+                patient_col.len(),
+                vec![patient_col, phenotype_col, resolution_col],
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        HpoInHeaderCollector::default()
+            .collect(&mut builder, &[cdf], &patient_id)
+            .unwrap();
+
+        let phenopackets = builder.build();
+
+        pretty_assertions::assert_eq!(
+            phenopackets[0].phenotypic_features[0].resolution,
+            Some(default_age_element())
+        );
+    }
+
     #[rstest]
     fn test_collect_hpo_in_header_col() {
         let mut builder = build_test_phenopacket_builder();
-        let collector = HpoInHeaderCollector;
+        let collector = HpoInHeaderCollector::default();
 
         let patient_id = default_patient_id();
 
@@ -225,4 +311,63 @@ mod tests {
         pretty_assertions::assert_eq!(phenopackets.len(), 1);
         assert_phenopackets(&mut phenopackets[0], &mut expected_phenopacket);
     }
+
+    #[rstest]
+    #[case(0.0, [Some(false), Some(true), Some(true), None])]
+    #[case(1.0, [Some(false), Some(false), Some(true), None])]
+    fn test_observation_statuses_from_scores(
+        #[case] observed_threshold: f64,
+        #[case] expected: [Option<bool>; 4],
+    ) {
+        let collector = HpoInHeaderCollector::new(observed_threshold);
+        let score_col = Column::new(
+            "HP:0041249".into(),
+            [Some(0i64), Some(1i64), Some(2i64), None],
+        );
+
+        let obs_statuses = collector.observation_statuses(&score_col).unwrap();
+
+        assert_eq!(obs_statuses.into_iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[rstest]
+    #[case(0, true)]
+    #[case(1, false)]
+    #[case(2, false)]
+    fn test_collect_hpo_in_header_col_with_scores(
+        #[case] score: i64,
+        #[case] expected_excluded: bool,
+    ) {
+        let mut builder = build_test_phenopacket_builder();
+        let collector = HpoInHeaderCollector::default();
+        let patient_id = default_patient_id();
+
+        let (patient_col, sc) = generate_minimal_cdf_components(1, 1);
+        let phenotype = default_phenotype();
+        let phenotype_col_name = phenotype.r#type.clone().unwrap().id;
+        let score_col = Column::new(phenotype_col_name.clone().into(), [score]);
+
+        let cdf = ContextualizedDataFrame::new(
+            TableContext::new(
+                "TestTable",
+                vec![
+                    sc,
+                    SeriesContext::from_identifier(phenotype_col_name)
+                        .with_data_context(Context::ObservationStatus)
+                        .with_header_context(Context::Hpo),
+                ],
+            ),
+            DataFrame::new(patient_col.len(), vec![patient_col, score_col]).unwrap(),
+        )
+        .unwrap();
+
+        collector
+            .collect(&mut builder, &[cdf], &patient_id)
+            .unwrap();
+
+        let phenopackets = builder.build();
+        let features = &phenopackets[0].phenotypic_features;
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0].excluded, expected_excluded);
+    }
 }
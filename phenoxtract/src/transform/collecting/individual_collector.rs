@@ -1,13 +1,22 @@
 use crate::config::context::Context;
+use crate::constants::HUMAN_TAXON;
 use crate::extract::ContextualizedDataFrame;
 use crate::extract::column_filter::ColumnFilterConfig;
 use crate::extract::enums::Filter;
 use crate::transform::collecting::traits::Collect;
-use crate::transform::collecting::utils::get_single_multiplicity_element;
+use crate::transform::collecting::utils::{
+    get_multiplicity_elements, get_single_multiplicity_element,
+};
+use crate::transform::dropped_fields::DroppedFieldKind;
 use crate::transform::error::CollectorError;
+use crate::transform::strategies::DateToAgeStrategy;
 use crate::transform::traits::PhenopacketBuilding;
+use log::warn;
 use std::any::Any;
 
+/// Survival times above 150 years are considered to be a data error.
+const MAX_SURVIVAL_TIME_DAYS: f64 = 150.0 * 365.0;
+
 #[derive(Debug)]
 pub struct IndividualCollector;
 
@@ -28,20 +37,40 @@ impl Collect for IndividualCollector {
             ColumnFilterConfig::default().where_data_context(Filter::Is(&Context::SubjectSex)),
         )?;
 
-        let time_at_last_encounter = get_single_multiplicity_element(
+        let gender = get_single_multiplicity_element(
             patient_cdfs,
-            ColumnFilterConfig::default().where_data_contexts_are(Context::LAST_ENCOUNTER_VARIANTS),
+            ColumnFilterConfig::default().where_data_context(Filter::Is(&Context::Gender)),
         )?;
 
+        let alternate_ids = get_multiplicity_elements(
+            patient_cdfs,
+            ColumnFilterConfig::default().where_data_context(Filter::Is(&Context::AlternateId)),
+        )?;
+        let alternate_ids: Vec<&str> = alternate_ids.iter().map(String::as_str).collect();
+
+        let taxonomy = Self::collect_taxonomy(patient_cdfs)?;
+
+        let time_at_last_encounter = match get_single_multiplicity_element(
+            patient_cdfs,
+            ColumnFilterConfig::default().where_data_contexts_are(Context::LAST_ENCOUNTER_VARIANTS),
+        )? {
+            Some(time_at_last_encounter) => Some(time_at_last_encounter),
+            None => Self::collect_age_at_last_encounter(
+                patient_cdfs,
+                patient_id,
+                date_of_birth.as_deref(),
+            )?,
+        };
+
         builder.upsert_individual(
             patient_id,
-            None,
+            (!alternate_ids.is_empty()).then_some(alternate_ids.as_slice()),
             date_of_birth.as_deref(),
             time_at_last_encounter.as_deref(),
             subject_sex.as_deref(),
             None,
-            None,
-            None,
+            gender.as_deref(),
+            taxonomy.as_deref(),
         )?;
 
         Self::collect_vitality_status(builder, patient_cdfs, patient_id)?;
@@ -54,6 +83,60 @@ impl Collect for IndividualCollector {
 }
 
 impl IndividualCollector {
+    /// Collects the taxonomy of the individual. If the data has a taxonomy column, but the patient has no value in it,
+    /// the individual is assumed to be human.
+    fn collect_taxonomy(
+        patient_cdfs: &[ContextualizedDataFrame],
+    ) -> Result<Option<String>, CollectorError> {
+        let taxonomy = get_single_multiplicity_element(
+            patient_cdfs,
+            ColumnFilterConfig::default().where_data_context(Filter::Is(&Context::Taxonomy)),
+        )?;
+
+        let has_taxonomy_column = patient_cdfs.iter().any(|patient_cdf| {
+            !patient_cdf
+                .filter_columns()
+                .where_data_context(Filter::Is(&Context::Taxonomy))
+                .collect()
+                .is_empty()
+        });
+
+        Ok(taxonomy.or_else(|| has_taxonomy_column.then(|| HUMAN_TAXON.0.to_string())))
+    }
+
+    /// Derives the age at the last encounter from the encounter date and the date of birth of the individual.
+    ///
+    /// Like the other individual data, this shouldn't abort the whole run, so encounter dates without a date of birth
+    /// or with an unparsable or negative age are skipped with a warning.
+    fn collect_age_at_last_encounter(
+        patient_cdfs: &[ContextualizedDataFrame],
+        patient_id: &str,
+        date_of_birth: Option<&str>,
+    ) -> Result<Option<String>, CollectorError> {
+        let Some(encounter_date) = get_single_multiplicity_element(
+            patient_cdfs,
+            ColumnFilterConfig::default().where_data_context(Filter::Is(&Context::EncounterDate)),
+        )?
+        else {
+            return Ok(None);
+        };
+
+        let Some(date_of_birth) = date_of_birth else {
+            warn!(
+                "Skipped the encounter date of patient '{patient_id}': there was no date of birth to derive the time at last encounter from."
+            );
+            return Ok(None);
+        };
+
+        match DateToAgeStrategy::date_and_dob_to_age(date_of_birth.to_string(), &encounter_date) {
+            Ok(age) => Ok(Some(age)),
+            Err(problem) => {
+                warn!("Skipped the encounter date of patient '{patient_id}': {problem}");
+                Ok(None)
+            }
+        }
+    }
+
     fn collect_vitality_status(
         builder: &mut dyn PhenopacketBuilding,
         patient_cdfs: &[ContextualizedDataFrame],
@@ -83,9 +166,9 @@ impl IndividualCollector {
                     .where_data_context(Filter::Is(&Context::SurvivalTimeDays)),
             )?;
 
-            let survival_time_days = survival_time_days
-                .map(|str| str.parse::<u32>())
-                .transpose()?;
+            let survival_time_days = survival_time_days.and_then(|survival_time_days| {
+                Self::parse_survival_time_days(builder, patient_id, &survival_time_days)
+            });
 
             builder.upsert_vital_status(
                 patient_id,
@@ -97,6 +180,29 @@ impl IndividualCollector {
         }
         Ok(())
     }
+
+    /// Parses the survival time of a patient in days.
+    ///
+    /// A single malformed cell shouldn't abort the whole run, so values, which are not a number of days
+    /// between 0 and [`MAX_SURVIVAL_TIME_DAYS`], are skipped and recorded as dropped, which puts them into
+    /// the lint report of the pipeline.
+    fn parse_survival_time_days(
+        builder: &mut dyn PhenopacketBuilding,
+        patient_id: &str,
+        survival_time_days: &str,
+    ) -> Option<u32> {
+        match survival_time_days.trim().parse::<f64>() {
+            Ok(days) if (0.0..=MAX_SURVIVAL_TIME_DAYS).contains(&days) => Some(days as u32),
+            _ => {
+                builder.record_dropped_field(
+                    DroppedFieldKind::MalformedSurvivalTime,
+                    patient_id,
+                    survival_time_days,
+                );
+                None
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -120,7 +226,7 @@ mod tests {
     use phenopackets::schema::v2::core::time_element::Element;
     use phenopackets::schema::v2::core::vital_status::Status;
     use phenopackets::schema::v2::core::{
-        Age, Individual, MetaData, Sex, TimeElement, VitalStatus,
+        Age, Individual, MetaData, OntologyClass, Sex, TimeElement, VitalStatus,
     };
     use polars::datatypes::AnyValue;
     use polars::frame::DataFrame;
@@ -267,4 +373,230 @@ mod tests {
         pretty_assertions::assert_eq!(phenopackets.len(), 1);
         assert_phenopackets(&mut phenopackets[0], &mut expected_phenopacket);
     }
+
+    #[rstest]
+    #[case("155", 155)]
+    #[case("155.0", 155)]
+    #[case("abc", 0)]
+    #[case("~300", 0)]
+    #[case("-5", 0)]
+    #[case("1000000", 0)]
+    fn test_collect_individual_survival_time(
+        patient_id: String,
+        #[case] survival_time: &str,
+        #[case] expected_days: u32,
+    ) {
+        let tc = TableContext::new(
+            "patient_data".to_string(),
+            vec![
+                SeriesContext::from_identifier("subject_id".to_string())
+                    .with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("vital_status".to_string())
+                    .with_data_context(Context::VitalStatus),
+                SeriesContext::from_identifier("survival_time".to_string())
+                    .with_data_context(Context::SurvivalTimeDays),
+            ],
+        );
+        let id_col = Column::new("subject_id".into(), [patient_id.clone()]);
+        let vital_status_col = Column::new("vital_status".into(), ["DECEASED"]);
+        let survival_time_col = Column::new("survival_time".into(), [survival_time]);
+        let df = DataFrame::new(
+            id_col.len(),
+            vec![id_col, vital_status_col, survival_time_col],
+        )
+        .unwrap();
+        let cdf = ContextualizedDataFrame::new(tc, df).unwrap();
+
+        let mut builder = build_test_phenopacket_builder();
+        IndividualCollector
+            .collect(&mut builder, &[cdf], &patient_id)
+            .unwrap();
+
+        let phenopackets = builder.build();
+        let vital_status = phenopackets[0]
+            .subject
+            .as_ref()
+            .unwrap()
+            .vital_status
+            .as_ref()
+            .unwrap();
+        pretty_assertions::assert_eq!(vital_status.status, Status::Deceased as i32);
+        pretty_assertions::assert_eq!(vital_status.survival_time_in_days, expected_days);
+        pretty_assertions::assert_eq!(
+            builder
+                .dropped_fields()
+                .iter()
+                .map(|dropped_field| dropped_field.kind)
+                .collect::<Vec<_>>(),
+            if expected_days == 0 {
+                vec![DroppedFieldKind::MalformedSurvivalTime]
+            } else {
+                vec![]
+            }
+        );
+    }
+
+    #[rstest]
+    #[case(Some("1960-02-05"), "2020-02-05", Some("P60Y"))]
+    #[case(Some("1960-02-05"), "2020-05-20", Some("P60Y3M15D"))]
+    #[case(None, "2020-02-05", None)]
+    #[case(Some("2021-01-01"), "2020-02-05", None)]
+    fn test_collect_individual_age_at_last_encounter(
+        patient_id: String,
+        #[case] date_of_birth: Option<&str>,
+        #[case] encounter_date: &str,
+        #[case] expected_age: Option<&str>,
+    ) {
+        let tc = TableContext::new(
+            "patient_data".to_string(),
+            vec![
+                SeriesContext::from_identifier("subject_id".to_string())
+                    .with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("dob".to_string())
+                    .with_data_context(Context::DateOfBirth),
+                SeriesContext::from_identifier("encounter_date".to_string())
+                    .with_data_context(Context::EncounterDate),
+            ],
+        );
+        let id_col = Column::new("subject_id".into(), [patient_id.clone()]);
+        let dob_col = Column::new("dob".into(), [date_of_birth]);
+        let encounter_date_col = Column::new("encounter_date".into(), [encounter_date]);
+        let df = DataFrame::new(id_col.len(), vec![id_col, dob_col, encounter_date_col]).unwrap();
+        let cdf = ContextualizedDataFrame::new(tc, df).unwrap();
+
+        let mut builder = build_test_phenopacket_builder();
+        IndividualCollector
+            .collect(&mut builder, &[cdf], &patient_id)
+            .unwrap();
+
+        let phenopackets = builder.build();
+        let time_at_last_encounter = phenopackets[0]
+            .subject
+            .as_ref()
+            .unwrap()
+            .time_at_last_encounter
+            .clone();
+        pretty_assertions::assert_eq!(
+            time_at_last_encounter,
+            expected_age.map(|age| TimeElement {
+                element: Some(Element::Age(Age {
+                    iso8601duration: age.to_string(),
+                })),
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_collect_individual_alternate_ids(patient_id: String) {
+        let tc = TableContext::new(
+            "patient_data".to_string(),
+            vec![
+                SeriesContext::from_identifier("subject_id".to_string())
+                    .with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("lab_id".to_string())
+                    .with_data_context(Context::AlternateId),
+                SeriesContext::from_identifier("biobank_id".to_string())
+                    .with_data_context(Context::AlternateId),
+            ],
+        );
+        let id_col = Column::new(
+            "subject_id".into(),
+            [patient_id.clone(), patient_id.clone()],
+        );
+        let lab_id_col = Column::new("lab_id".into(), [Some("LAB-17"), None]);
+        let biobank_id_col = Column::new("biobank_id".into(), ["BB-9", "BB-9"]);
+        let df = DataFrame::new(id_col.len(), vec![id_col, lab_id_col, biobank_id_col]).unwrap();
+        let cdf = ContextualizedDataFrame::new(tc, df).unwrap();
+
+        let mut builder = build_test_phenopacket_builder();
+        IndividualCollector
+            .collect(&mut builder, &[cdf], &patient_id)
+            .unwrap();
+
+        let phenopackets = builder.build();
+        pretty_assertions::assert_eq!(
+            phenopackets[0].subject.as_ref().unwrap().alternate_ids,
+            vec!["LAB-17", "BB-9"]
+        );
+    }
+
+    #[rstest]
+    fn test_collect_individual_taxonomy() {
+        let species_cdf = |patient_id: &str, species: Option<&str>| {
+            let tc = TableContext::new(
+                "patient_data".to_string(),
+                vec![
+                    SeriesContext::from_identifier("subject_id".to_string())
+                        .with_data_context(Context::SubjectId),
+                    SeriesContext::from_identifier("species".to_string())
+                        .with_data_context(Context::Taxonomy),
+                ],
+            );
+            let id_col = Column::new("subject_id".into(), [patient_id]);
+            let species_col = Column::new("species".into(), [species]);
+            let df = DataFrame::new(id_col.len(), vec![id_col, species_col]).unwrap();
+            ContextualizedDataFrame::new(tc, df).unwrap()
+        };
+
+        let mut builder = build_test_phenopacket_builder();
+        for (patient_id, species) in [("P001", Some("mouse")), ("P002", None)] {
+            IndividualCollector
+                .collect(
+                    &mut builder,
+                    &[species_cdf(patient_id, species)],
+                    patient_id,
+                )
+                .unwrap();
+        }
+
+        let taxonomies: Vec<Option<OntologyClass>> = builder
+            .build()
+            .into_iter()
+            .map(|pp| pp.subject.unwrap().taxonomy)
+            .collect();
+        pretty_assertions::assert_eq!(
+            taxonomies,
+            vec![
+                Some(OntologyClass {
+                    id: "NCBITaxon:10090".to_string(),
+                    label: "Mus musculus".to_string(),
+                }),
+                Some(OntologyClass {
+                    id: "NCBITaxon:9606".to_string(),
+                    label: "Homo sapiens".to_string(),
+                }),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_collect_individual_gender(patient_id: String) {
+        let tc = TableContext::new(
+            "patient_data".to_string(),
+            vec![
+                SeriesContext::from_identifier("subject_id".to_string())
+                    .with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("gender".to_string())
+                    .with_data_context(Context::Gender),
+            ],
+        );
+        let id_col = Column::new("subject_id".into(), [patient_id.clone()]);
+        let gender_col = Column::new("gender".into(), [AnyValue::String("woman")]);
+        let df = DataFrame::new(id_col.len(), vec![id_col, gender_col]).unwrap();
+        let cdf = ContextualizedDataFrame::new(tc, df).unwrap();
+
+        let mut builder = build_test_phenopacket_builder();
+        IndividualCollector
+            .collect(&mut builder, &[cdf], &patient_id)
+            .unwrap();
+
+        let phenopackets = builder.build();
+        pretty_assertions::assert_eq!(
+            phenopackets[0].subject.as_ref().unwrap().gender,
+            Some(OntologyClass {
+                id: "GSSO:000369".to_string(),
+                label: "female gender identity".to_string(),
+            })
+        );
+    }
 }
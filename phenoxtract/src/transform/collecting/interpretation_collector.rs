@@ -5,10 +5,14 @@ use crate::extract::enums::Filter;
 use crate::transform::collecting::traits::Collect;
 use crate::transform::collecting::utils::get_single_multiplicity_element;
 use crate::transform::error::CollectorError;
-use crate::transform::pathogenic_gene_variant_info::PathogenicGeneVariantData;
+use crate::transform::pathogenic_gene_variant_info::{
+    PathogenicGeneVariantData, VariantAnnotations, VariantOrigin,
+};
 use crate::transform::traits::PhenopacketBuilding;
 use polars::prelude::StringChunked;
 use std::any::Any;
+use std::collections::HashSet;
+use std::str::FromStr;
 
 #[derive(Debug)]
 pub struct InterpretationCollector;
@@ -67,6 +71,54 @@ impl Collect for InterpretationCollector {
 }
 
 impl InterpretationCollector {
+    /// The distinct genes of all rows, which have a single gene and no variant.
+    ///
+    /// Only for these rows the HGNC client is asked, so resolving them up front means every gene is requested once.
+    pub(crate) fn causative_genes(
+        cdf: &ContextualizedDataFrame,
+    ) -> Result<HashSet<String>, CollectorError> {
+        let gene_bbs: HashSet<String> = cdf
+            .filter_series_context()
+            .where_data_context(Filter::Is(&Context::Hgnc))
+            .where_building_block(Filter::IsSome)
+            .collect()
+            .into_iter()
+            .filter_map(|sc| sc.get_building_block_id().map(|s| s.to_string()))
+            .collect();
+
+        let mut genes = HashSet::new();
+        for bb_id in gene_bbs.iter() {
+            let linked_hgnc_cols = cdf.get_stringified_cols(cdf.get_linked_cols_with_context(
+                Some(bb_id.as_str()),
+                &Context::Hgnc,
+                &Context::None,
+            ))?;
+            let linked_hgvs_cols = cdf.get_stringified_cols(cdf.get_linked_cols_with_context(
+                Some(bb_id.as_str()),
+                &Context::Hgvs,
+                &Context::None,
+            ))?;
+
+            for row_idx in cdf.building_block_rows(Some(bb_id.as_str())) {
+                let row_genes: Vec<&str> = linked_hgnc_cols
+                    .iter()
+                    .filter_map(|col| col.get(row_idx))
+                    .collect();
+                let has_variant = linked_hgvs_cols
+                    .iter()
+                    .any(|col| col.get(row_idx).is_some());
+
+                if let [gene] = row_genes.as_slice()
+                    && !has_variant
+                {
+                    genes.insert(gene.to_string());
+                }
+            }
+        }
+
+        Ok(genes)
+    }
+
     fn is_spread(patient_cdfs: &[ContextualizedDataFrame], disease_bb: &str) -> bool {
         patient_cdfs
             .iter()
@@ -163,20 +215,75 @@ impl InterpretationCollector {
             patient_cdf.get_linked_cols_with_context(Some(bb_id), &Context::Hgvs, &Context::None),
         )?;
 
-        for row_idx in 0..patient_cdf.data().height() {
+        // The n-th origin, ACMG and actionability column hold the annotations of the variants in the n-th HGVS column.
+        let linked_origin_cols =
+            patient_cdf.get_stringified_cols(patient_cdf.get_linked_cols_with_context(
+                Some(bb_id),
+                &Context::VariantOrigin,
+                &Context::None,
+            ))?;
+        let linked_acmg_cols =
+            patient_cdf.get_stringified_cols(patient_cdf.get_linked_cols_with_context(
+                Some(bb_id),
+                &Context::AcmgClassification,
+                &Context::None,
+            ))?;
+        let linked_actionability_cols =
+            patient_cdf.get_stringified_cols(patient_cdf.get_linked_cols_with_context(
+                Some(bb_id),
+                &Context::TherapeuticActionability,
+                &Context::None,
+            ))?;
+        let linked_assembly_cols =
+            patient_cdf.get_stringified_cols(patient_cdf.get_linked_cols_with_context(
+                Some(bb_id),
+                &Context::GenomeAssembly,
+                &Context::None,
+            ))?;
+
+        for row_idx in patient_cdf.building_block_rows(Some(bb_id)) {
             let genes = linked_hgnc_cols
                 .iter()
                 .filter_map(|col| col.get(row_idx))
                 .collect::<Vec<&str>>();
 
-            let variants = linked_hgvs_cols
+            // The genome assembly is declared per row and applies to all of its variants.
+            let genome_assembly = linked_assembly_cols
                 .iter()
-                .filter_map(|col| col.get(row_idx))
-                .collect::<Vec<&str>>();
+                .find_map(|assembly_col| assembly_col.get(row_idx))
+                .map(str::to_string);
+
+            let mut variants = vec![];
+            let mut variant_origins = vec![];
+            let mut variant_annotations = vec![];
+            for (col_idx, hgvs_col) in linked_hgvs_cols.iter().enumerate() {
+                let Some(variant) = hgvs_col.get(row_idx) else {
+                    continue;
+                };
+                let origin = linked_origin_cols
+                    .get(col_idx)
+                    .and_then(|origin_col| origin_col.get(row_idx))
+                    .map(Self::parse_variant_origin)
+                    .transpose()?;
+                variants.push(variant);
+                variant_origins.push(origin);
+                variant_annotations.push(VariantAnnotations {
+                    acmg_classification: linked_acmg_cols
+                        .get(col_idx)
+                        .and_then(|acmg_col| acmg_col.get(row_idx))
+                        .map(str::to_string),
+                    therapeutic_actionability: linked_actionability_cols
+                        .get(col_idx)
+                        .and_then(|actionability_col| actionability_col.get(row_idx))
+                        .map(str::to_string),
+                    genome_assembly: genome_assembly.clone(),
+                });
+            }
 
             let gene_variant_data =
                 PathogenicGeneVariantData::from_genes_and_variants(genes, variants)
-                    .map_err(CollectorError::GeneVariantData)?;
+                    .map_err(CollectorError::GeneVariantData)?
+                    .with_variant_origins(&variant_origins);
 
             if matches!(gene_variant_data, PathogenicGeneVariantData::None) {
                 continue;
@@ -187,6 +294,7 @@ impl InterpretationCollector {
                     patient_id,
                     disease,
                     &gene_variant_data,
+                    &variant_annotations,
                     subject_sex,
                 )?;
             }
@@ -194,16 +302,26 @@ impl InterpretationCollector {
 
         Ok(())
     }
+
+    fn parse_variant_origin(origin: &str) -> Result<VariantOrigin, CollectorError> {
+        VariantOrigin::from_str(origin.trim()).map_err(|_| {
+            CollectorError::GeneVariantData(format!(
+                "Could not interpret '{origin}' as a variant origin. Expected 'maternal' or 'paternal'."
+            ))
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::TableContext;
+    use crate::config::context::TimeElementType;
     use crate::config::table_context::SeriesContext;
     use crate::config::traits::SeriesContextBuilding;
     use crate::test_suite::cdf_generation::{default_patient_id, generate_minimal_cdf_components};
     use crate::test_suite::component_building::build_test_phenopacket_builder;
+    use crate::test_suite::mocks::MockPhenopacketBuilding;
     use crate::test_suite::phenopacket_component_generation::default_meta_data;
     use crate::test_suite::phenopacket_component_generation::{
         default_cohort_id, default_disease_oc, default_phenopacket_id,
@@ -212,6 +330,7 @@ mod tests {
         geno_meta_data_resource, hgnc_meta_data_resource, mondo_meta_data_resource,
     };
     use crate::test_suite::utils::assert_phenopackets;
+    use crate::transform::collecting::disease_collector::DiseaseCollector;
     use crate::utils::phenopacket_schema_version;
     use phenopackets::ga4gh::vrsatile::v1::{Expression, GeneDescriptor, VcfRecord};
     use phenopackets::ga4gh::vrsatile::v1::{MoleculeContext, VariationDescriptor};
@@ -219,8 +338,9 @@ mod tests {
     use phenopackets::schema::v2::core::AcmgPathogenicityClassification;
     use phenopackets::schema::v2::core::TherapeuticActionability;
     use phenopackets::schema::v2::core::genomic_interpretation::Call;
+    use phenopackets::schema::v2::core::time_element::Element;
     use phenopackets::schema::v2::core::{
-        Diagnosis, GenomicInterpretation, Interpretation, MetaData, OntologyClass,
+        Age, Diagnosis, GenomicInterpretation, Interpretation, MetaData, OntologyClass,
         VariantInterpretation,
     };
     use polars::datatypes::AnyValue;
@@ -358,9 +478,9 @@ mod tests {
             meta_data: Some(MetaData {
                 phenopacket_schema_version: phenopacket_schema_version(),
                 resources: vec![
-                    mondo_meta_data_resource(),
-                    hgnc_meta_data_resource(),
                     geno_meta_data_resource(),
+                    hgnc_meta_data_resource(),
+                    mondo_meta_data_resource(),
                 ],
                 created_by: default_meta_data().created_by,
                 submitted_by: default_meta_data().submitted_by,
@@ -407,6 +527,68 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn test_collect_interpretations_variant_annotations(
+        disease_col: Column,
+        disease_sc: SeriesContext,
+    ) {
+        let (patient_col, patient_sc) = generate_minimal_cdf_components(1, 1);
+        let mut cols = vec![patient_col, disease_col];
+        let mut scs = vec![patient_sc, disease_sc];
+        for (idx, (variant, origin, acmg_classification)) in [
+            ("NR_002196.1:n.601G>T", "Maternal", "LIKELY_BENIGN"),
+            ("NR_002196.1:n.602C>T", "paternal", "PATHOGENIC"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            cols.push(Column::new(format!("hgvs{idx}").into(), [variant]));
+            cols.push(Column::new(format!("origin{idx}").into(), [origin]));
+            scs.push(
+                SeriesContext::from_identifier(format!("hgvs{idx}"))
+                    .with_data_context(Context::Hgvs)
+                    .with_building_block_id("D"),
+            );
+            scs.push(
+                SeriesContext::from_identifier(format!("origin{idx}"))
+                    .with_data_context(Context::VariantOrigin)
+                    .with_building_block_id("D"),
+            );
+            cols.push(Column::new(
+                format!("acmg{idx}").into(),
+                [acmg_classification],
+            ));
+            scs.push(
+                SeriesContext::from_identifier(format!("acmg{idx}"))
+                    .with_data_context(Context::AcmgClassification)
+                    .with_building_block_id("D"),
+            );
+        }
+        let patient_cdf = ContextualizedDataFrame::new(
+            TableContext::new("disease_table", scs),
+            DataFrame::new(1, cols).unwrap(),
+        )
+        .unwrap();
+
+        let mut builder = MockPhenopacketBuilding::new();
+        builder
+            .expect_upsert_interpretation()
+            .withf(|_, _, gene_variant_data, variant_annotations, _| {
+                gene_variant_data.get_var_origins()
+                    == vec![Some(VariantOrigin::Maternal), Some(VariantOrigin::Paternal)]
+                    && variant_annotations
+                        .iter()
+                        .map(|annotations| annotations.acmg_classification.as_deref())
+                        .eq([Some("LIKELY_BENIGN"), Some("PATHOGENIC")])
+            })
+            .times(1)
+            .returning(|_, _, _, _, _| Ok(()));
+
+        InterpretationCollector
+            .collect(&mut builder, &[patient_cdf], &default_patient_id())
+            .unwrap();
+    }
+
     #[rstest]
     fn test_collect_interpretations_multi_sheet(
         phenopacket_with_interpretation: Phenopacket,
@@ -454,6 +636,98 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn test_disease_onset_consistent_with_interpretation_multi_sheet(
+        disease_col: Column,
+        genetics_cols: Vec<Column>,
+        disease_sc: SeriesContext,
+        genetics_scs: Vec<SeriesContext>,
+    ) {
+        let (patient_col, patient_sc) = generate_minimal_cdf_components(1, 1);
+
+        let disease_cdf = ContextualizedDataFrame::new(
+            TableContext::new("disease_table", vec![patient_sc.clone(), disease_sc]),
+            DataFrame::new(1, vec![patient_col.clone(), disease_col]).unwrap(),
+        )
+        .unwrap();
+
+        let onset_sc = SeriesContext::from_identifier("onset".to_string())
+            .with_data_context(Context::Onset(TimeElementType::Age))
+            .with_building_block_id("D");
+        let onset_col = Column::new("onset".into(), [AnyValue::String("P3Y")]);
+
+        let mut genetics_cdf_cols = vec![patient_col, onset_col];
+        let mut genetics_cdf_scs = vec![patient_sc, onset_sc];
+        genetics_cdf_cols.extend(genetics_cols);
+        genetics_cdf_scs.extend(genetics_scs);
+
+        let genetics_cdf = ContextualizedDataFrame::new(
+            TableContext::new("genetics_table", genetics_cdf_scs),
+            DataFrame::new(1, genetics_cdf_cols).unwrap(),
+        )
+        .unwrap();
+
+        let patient_cdfs = [disease_cdf, genetics_cdf];
+        let mut builder = build_test_phenopacket_builder();
+        let patient_id = default_patient_id();
+
+        DiseaseCollector
+            .collect(&mut builder, &patient_cdfs, &patient_id)
+            .unwrap();
+        InterpretationCollector
+            .collect(&mut builder, &patient_cdfs, &patient_id)
+            .unwrap();
+
+        let phenopackets = builder.build();
+        pretty_assertions::assert_eq!(phenopackets.len(), 1);
+
+        let phenopacket = &phenopackets[0];
+        let diagnosed_disease = phenopacket.interpretations[0]
+            .diagnosis
+            .as_ref()
+            .unwrap()
+            .disease
+            .clone();
+
+        pretty_assertions::assert_eq!(phenopacket.diseases.len(), 1);
+        let disease = &phenopacket.diseases[0];
+        pretty_assertions::assert_eq!(disease.term, diagnosed_disease);
+        pretty_assertions::assert_eq!(
+            disease.onset.as_ref().unwrap().element,
+            Some(Element::Age(Age {
+                iso8601duration: "P3Y".to_string()
+            }))
+        );
+    }
+
+    #[rstest]
+    fn test_causative_genes() {
+        let (patient_col, patient_sc) = generate_minimal_cdf_components(1, 3);
+
+        let gene_col = Column::new("gene".into(), ["CLOCK", "KIF21A", "CLOCK"]);
+        let hgvs_col = Column::new(
+            "hgvs".into(),
+            [None, Some("NM_001173464.1:c.2860C>T"), None],
+        );
+
+        let gene_sc = SeriesContext::from_identifier("gene".to_string())
+            .with_data_context(Context::Hgnc)
+            .with_building_block_id("D");
+        let hgvs_sc = SeriesContext::from_identifier("hgvs".to_string())
+            .with_data_context(Context::Hgvs)
+            .with_building_block_id("D");
+
+        let cdf = ContextualizedDataFrame::new(
+            TableContext::new("gene_table", vec![patient_sc, gene_sc, hgvs_sc]),
+            DataFrame::new(3, vec![patient_col, gene_col, hgvs_col]).unwrap(),
+        )
+        .unwrap();
+
+        let genes = InterpretationCollector::causative_genes(&cdf).unwrap();
+
+        pretty_assertions::assert_eq!(genes, HashSet::from(["CLOCK".to_string()]));
+    }
+
     #[rstest]
     fn test_collect_interpretations_invalid_format_err(disease_sc: SeriesContext) {
         let (patient_col, patient_sc) = generate_minimal_cdf_components(1, 2);
@@ -171,7 +171,9 @@ mod tests {
     };
 
     use crate::config::traits::SeriesContextBuilding;
+    use crate::test_suite::component_building::build_test_phenopacket_builder;
     use crate::test_suite::mocks::MockPhenopacketBuilding;
+    use phenopackets::schema::v2::core::medical_action::Action;
     use polars::datatypes::AnyValue;
     use polars::prelude::{IntoColumn, NamedFrom, Series};
     use rstest::{fixture, rstest};
@@ -354,4 +356,55 @@ mod tests {
             .collect(&mut builder, &[medical_treatment_cdf], &patient_id)
             .unwrap();
     }
+
+    #[rstest]
+    fn test_collect_treatment_resolves_agent() {
+        let mut patient_cdf = generate_minimal_cdf(1, 1);
+        let building_block = "treatment_1";
+        patient_cdf
+            .builder()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("agent")
+                    .with_data_context(Context::TreatmentAgent)
+                    .with_building_block_id(building_block),
+                vec![
+                    Series::new("agent".into(), &[default_treatment_agent_oc().label])
+                        .into_column(),
+                ]
+                .as_ref(),
+            )
+            .unwrap()
+            .insert_sc_alongside_cols(
+                SeriesContext::from_identifier("route_of_administration")
+                    .with_data_context(Context::RouteOfAdministration)
+                    .with_building_block_id(building_block),
+                vec![
+                    Series::new(
+                        "route_of_administration".into(),
+                        &[default_route_of_administration_oc().id],
+                    )
+                    .into_column(),
+                ]
+                .as_ref(),
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut builder = build_test_phenopacket_builder();
+        MedicalTreatmentCollector
+            .collect(&mut builder, &[patient_cdf], &default_patient_id())
+            .unwrap();
+
+        let phenopackets = builder.build();
+        assert_eq!(phenopackets[0].medical_actions.len(), 1);
+        let Some(Action::Treatment(treatment)) = &phenopackets[0].medical_actions[0].action else {
+            panic!("Expected the medical action to be a treatment.");
+        };
+        assert_eq!(treatment.agent, Some(default_treatment_agent_oc()));
+        assert_eq!(
+            treatment.route_of_administration,
+            Some(default_route_of_administration_oc())
+        );
+    }
 }
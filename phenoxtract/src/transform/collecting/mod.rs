@@ -1,3 +1,4 @@
+pub mod biosample_collector;
 pub mod cdf_collector_broker;
 pub mod disease_collector;
 pub mod hpo_in_cells_collector;
@@ -52,7 +52,9 @@ impl Collect for QualitativeMeasurementCollector {
 
                     let stringified_qual_measurement_col = casted_qual_col.str()?;
 
-                    for row_idx in 0..stringified_qual_measurement_col.len() {
+                    for row_idx in
+                        patient_cdf.building_block_rows(qual_measurement_sc.get_building_block_id())
+                    {
                         let qual_measurement = stringified_qual_measurement_col.get(row_idx);
                         if let Some(qual_measurement) = qual_measurement {
                             let time_observed = if let Some(time_observed_col) = &time_observed_col
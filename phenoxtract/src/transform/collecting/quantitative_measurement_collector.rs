@@ -6,8 +6,11 @@ use crate::transform::collecting::traits::Collect;
 use crate::transform::error::CollectorError;
 use crate::transform::traits::PhenopacketBuilding;
 use crate::transform::utils::cow_cast;
+use log::warn;
 use polars::datatypes::DataType;
+use polars::prelude::Column;
 use std::any::Any;
+use std::borrow::Cow;
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -51,18 +54,13 @@ impl Collect for QuantitativeMeasurementCollector {
                 )?;
 
                 for quant_measurement_col in quant_measurement_cols {
-                    let allowed_datatypes = {
-                        let mut v = vec![DataType::String, DataType::Null];
-                        v.extend_from_slice(PolarsNumericTypes::all());
-                        v
-                    };
-
-                    let casted_quant_col =
-                        cow_cast(quant_measurement_col, DataType::Float64, allowed_datatypes)?;
+                    let casted_quant_col = Self::floatify(quant_measurement_col)?;
 
                     let floatified_quant_measurement_col = casted_quant_col.f64()?;
 
-                    for row_idx in 0..floatified_quant_measurement_col.len() {
+                    for row_idx in patient_cdf
+                        .building_block_rows(quant_measurement_sc.get_building_block_id())
+                    {
                         let quant_measurement = floatified_quant_measurement_col.get(row_idx);
                         if let Some(quant_measurement) = quant_measurement {
                             let time_observed = if let Some(time_observed_col) = &time_observed_col
@@ -81,6 +79,16 @@ impl Collect for QuantitativeMeasurementCollector {
                             } else {
                                 None
                             };
+                            let reference_range = match (ref_low, ref_high) {
+                                (Some(low), Some(high)) => Some((low, high)),
+                                (None, None) => None,
+                                _ => {
+                                    warn!(
+                                        "Skipping the reference range of the '{assay_id}' measurement of patient '{patient_id}', because only one of its bounds is given."
+                                    );
+                                    None
+                                }
+                            };
 
                             builder.insert_quantitative_measurement(
                                 patient_id,
@@ -88,12 +96,14 @@ impl Collect for QuantitativeMeasurementCollector {
                                 time_observed,
                                 assay_id,
                                 unit_ontology_id,
-                                ref_low.zip(ref_high),
+                                reference_range,
                             )?;
                         }
                     }
                 }
             }
+
+            Self::collect_ratio_measurements(builder, patient_cdf, patient_id)?;
         }
 
         Ok(())
@@ -103,6 +113,108 @@ impl Collect for QuantitativeMeasurementCollector {
     }
 }
 
+impl QuantitativeMeasurementCollector {
+    /// Collects the ratio measurements of a table.
+    /// Every `MeasurementNumerator` column needs exactly one `MeasurementDenominator` column in its building block.
+    fn collect_ratio_measurements(
+        builder: &mut dyn PhenopacketBuilding,
+        patient_cdf: &ContextualizedDataFrame,
+        patient_id: &str,
+    ) -> Result<(), CollectorError> {
+        let numerator_scs = patient_cdf
+            .filter_series_context()
+            .where_data_context_kind(Filter::Is(&ContextKind::MeasurementNumerator))
+            .collect();
+
+        for numerator_sc in numerator_scs {
+            let Context::MeasurementNumerator {
+                assay_id,
+                type_id: numerator_type_id,
+                unit_ontology_id: numerator_unit_id,
+            } = numerator_sc.get_data_context()
+            else {
+                continue;
+            };
+
+            let bb_id = numerator_sc.get_building_block_id().ok_or_else(|| {
+                CollectorError::ContextError(format!(
+                    "The ratio measurement '{assay_id}' needs a building block to link its numerator and denominator."
+                ))
+            })?;
+
+            let denominator_scs = patient_cdf
+                .filter_series_context()
+                .where_building_block(Filter::Is(bb_id))
+                .where_data_context_kind(Filter::Is(&ContextKind::MeasurementDenominator))
+                .collect();
+            let [denominator_sc] = denominator_scs.as_slice() else {
+                return Err(CollectorError::ContextError(format!(
+                    "Expected exactly one measurement denominator in building block '{bb_id}', but found {}.",
+                    denominator_scs.len()
+                )));
+            };
+            let Context::MeasurementDenominator {
+                type_id: denominator_type_id,
+                unit_ontology_id: denominator_unit_id,
+            } = denominator_sc.get_data_context()
+            else {
+                continue;
+            };
+
+            let time_observed_col = patient_cdf.get_single_linked_column_as_str(
+                Some(bb_id),
+                Context::TIME_OF_MEASUREMENT_VARIANTS,
+            )?;
+
+            let numerator_cols = patient_cdf.identify_columns(numerator_sc.get_identifier());
+            let denominator_cols = patient_cdf.identify_columns(denominator_sc.get_identifier());
+
+            for (numerator_col, denominator_col) in numerator_cols.into_iter().zip(denominator_cols)
+            {
+                let numerator_col = Self::floatify(numerator_col)?;
+                let numerators = numerator_col.f64()?;
+                let denominator_col = Self::floatify(denominator_col)?;
+                let denominators = denominator_col.f64()?;
+
+                for row_idx in patient_cdf.building_block_rows(Some(bb_id)) {
+                    let (Some(numerator), Some(denominator)) =
+                        (numerators.get(row_idx), denominators.get(row_idx))
+                    else {
+                        continue;
+                    };
+                    let time_observed = time_observed_col
+                        .as_ref()
+                        .and_then(|time_observed_col| time_observed_col.get(row_idx));
+
+                    builder.insert_ratio_measurement(
+                        patient_id,
+                        time_observed,
+                        assay_id,
+                        numerator,
+                        numerator_type_id,
+                        numerator_unit_id,
+                        denominator,
+                        denominator_type_id,
+                        denominator_unit_id,
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn floatify(col: &Column) -> Result<Cow<'_, Column>, CollectorError> {
+        let allowed_datatypes = {
+            let mut v = vec![DataType::String, DataType::Null];
+            v.extend_from_slice(PolarsNumericTypes::all());
+            v
+        };
+
+        cow_cast(col, DataType::Float64, allowed_datatypes)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,10 +223,12 @@ mod tests {
     use crate::config::traits::SeriesContextBuilding;
     use crate::test_suite::cdf_generation::{default_patient_id, generate_minimal_cdf};
     use crate::test_suite::component_building::build_test_phenopacket_builder;
+    use crate::test_suite::mocks::MockPhenopacketBuilding;
     use crate::test_suite::phenopacket_component_generation::default_meta_data;
     use crate::test_suite::phenopacket_component_generation::{
-        default_iso_age, default_phenopacket_id, default_quant_loinc, default_quant_measurement,
-        default_quant_value, default_reference_range, default_unit_oc, generate_quant_measurement,
+        default_iso_age, default_phenopacket_id, default_qual_loinc, default_quant_loinc,
+        default_quant_measurement, default_quant_value, default_reference_range, default_unit_oc,
+        generate_quant_measurement,
     };
     use crate::test_suite::resource_references::{loinc_meta_data_resource, uo_meta_data_resource};
     use crate::test_suite::utils::assert_phenopackets;
@@ -223,7 +337,7 @@ mod tests {
             measurements: vec![measurement1, measurement2],
             meta_data: Some(MetaData {
                 phenopacket_schema_version: phenopacket_schema_version(),
-                resources: vec![uo_meta_data_resource(), loinc_meta_data_resource()],
+                resources: vec![loinc_meta_data_resource(), uo_meta_data_resource()],
                 submitted_by: default_meta_data().submitted_by,
                 created_by: default_meta_data().created_by,
                 ..Default::default()
@@ -234,4 +348,119 @@ mod tests {
         pretty_assertions::assert_eq!(phenopackets.len(), 1);
         assert_phenopackets(&mut phenopackets[0], &mut expected_phenopacket);
     }
+
+    #[rstest]
+    fn test_collect_quantitative_measurement_single_reference_bound() {
+        let mut patient_cdf = generate_minimal_cdf(1, 1);
+        let measurement = Series::new("height".into(), [default_quant_value()]);
+        let ref_low = Series::new("ref_low".into(), [default_reference_range().0]);
+
+        patient_cdf
+            .builder()
+            .insert_sc_alongside_cols(
+                SeriesContext::default()
+                    .with_identifier("height")
+                    .with_data_context(Context::QuantitativeMeasurement {
+                        assay_id: default_quant_loinc().id,
+                        unit_ontology_id: default_unit_oc().id,
+                    })
+                    .with_building_block_id("height_measurement"),
+                vec![measurement.into_column()].as_ref(),
+            )
+            .unwrap()
+            .insert_sc_alongside_cols(
+                SeriesContext::default()
+                    .with_identifier("ref_low")
+                    .with_data_context(Context::ReferenceRange(Boundary::Start))
+                    .with_building_block_id("height_measurement"),
+                vec![ref_low.into_column()].as_ref(),
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut builder = build_test_phenopacket_builder();
+        QuantitativeMeasurementCollector
+            .collect(&mut builder, &[patient_cdf], &default_patient_id())
+            .unwrap();
+
+        let phenopackets = builder.build();
+
+        pretty_assertions::assert_eq!(
+            phenopackets[0].measurements,
+            vec![generate_quant_measurement(
+                default_quant_loinc(),
+                default_quant_value(),
+                None,
+                default_unit_oc().id.as_str(),
+                None,
+            )]
+        );
+    }
+
+    #[rstest]
+    fn test_collect_ratio_measurement() {
+        let mut patient_cdf = generate_minimal_cdf(1, 2);
+        let numerators = Series::new("albumin".into(), [Some(30.0), None]);
+        let denominators = Series::new("creatinine".into(), [Some(2.0), Some(1.0)]);
+
+        patient_cdf
+            .builder()
+            .insert_sc_alongside_cols(
+                SeriesContext::default()
+                    .with_identifier("albumin")
+                    .with_data_context(Context::MeasurementNumerator {
+                        assay_id: default_quant_loinc().id,
+                        type_id: default_quant_loinc().id,
+                        unit_ontology_id: default_unit_oc().id,
+                    })
+                    .with_building_block_id("ratio_measurement"),
+                vec![numerators.into_column()].as_ref(),
+            )
+            .unwrap()
+            .insert_sc_alongside_cols(
+                SeriesContext::default()
+                    .with_identifier("creatinine")
+                    .with_data_context(Context::MeasurementDenominator {
+                        type_id: default_qual_loinc().id,
+                        unit_ontology_id: default_unit_oc().id,
+                    })
+                    .with_building_block_id("ratio_measurement"),
+                vec![denominators.into_column()].as_ref(),
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut builder = MockPhenopacketBuilding::new();
+        builder
+            .expect_insert_ratio_measurement()
+            .withf(
+                |id,
+                 time_observed,
+                 assay_id,
+                 numerator,
+                 numerator_type_id,
+                 numerator_unit_id,
+                 denominator,
+                 denominator_type_id,
+                 denominator_unit_id| {
+                    id == default_patient_id()
+                        && time_observed.is_none()
+                        && *assay_id == default_quant_loinc().id
+                        && *numerator == 30.0
+                        && *numerator_type_id == default_quant_loinc().id
+                        && *numerator_unit_id == default_unit_oc().id
+                        && *denominator == 2.0
+                        && *denominator_type_id == default_qual_loinc().id
+                        && *denominator_unit_id == default_unit_oc().id
+                },
+            )
+            .times(1)
+            .returning(|_, _, _, _, _, _, _, _, _| Ok(()));
+
+        QuantitativeMeasurementCollector
+            .collect(&mut builder, &[patient_cdf], &default_patient_id())
+            .unwrap();
+    }
 }
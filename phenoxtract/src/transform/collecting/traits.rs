@@ -5,7 +5,7 @@ use crate::transform::traits::PhenopacketBuilding;
 use std::any::Any;
 use std::fmt::Debug;
 
-pub trait Collect: Debug {
+pub trait Collect: Debug + Send + Sync {
     fn collect(
         &self,
         builder: &mut dyn PhenopacketBuilding,
@@ -70,6 +70,31 @@ pub(crate) fn get_single_multiplicity_element(
     }
 }
 
+/// Collects all distinct values from matching contexts given a collection of CDFs.
+///
+/// Unlike [`get_single_multiplicity_element`] any number of values is allowed. The values are
+/// returned in the order of their first occurrence, nulls are skipped.
+pub(crate) fn get_multiplicity_elements(
+    patient_cdfs: &[ContextualizedDataFrame],
+    column_filters: ColumnFilterConfig,
+) -> Result<Vec<String>, CollectorError> {
+    let mut elements: Vec<String> = vec![];
+
+    for patient_cdf in patient_cdfs {
+        let filter = ColumnFilter::from_config(patient_cdf, column_filters.clone());
+        for col in filter.collect() {
+            let cast_col = col.cast(&DataType::String)?;
+            for value in cast_col.str()?.into_iter().flatten() {
+                if !elements.iter().any(|element| element == value) {
+                    elements.push(value.to_string());
+                }
+            }
+        }
+    }
+
+    Ok(elements)
+}
+
 pub(crate) fn validate_no_unexpected_contexts<T>(
     col_to_context: Vec<(bool, Vec<Context>)>,
     expected_contexts: Vec<Context>,
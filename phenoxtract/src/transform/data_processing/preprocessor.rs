@@ -4,9 +4,11 @@ use crate::extract::enums::Filter;
 use crate::transform::data_processing::casting::{is_ints, polars_column_cast_ambivalent};
 use crate::transform::error::DataProcessingError;
 use polars::datatypes::DataType;
+use polars::prelude::Column;
 use polars::prelude::ChunkApply;
 use polars::series::IntoSeries;
 use std::borrow::Cow;
+use std::collections::HashSet;
 
 pub(crate) struct CdfPreprocessor;
 
@@ -14,10 +16,35 @@ impl CdfPreprocessor {
     pub(crate) fn process(cdf: &mut ContextualizedDataFrame) -> Result<(), DataProcessingError> {
         Self::trim_strings(cdf)?;
         Self::ensure_ints(cdf)?;
+        Self::apply_null_values(cdf)?;
         Self::cast_cdf(cdf)?;
         Ok(())
     }
 
+    /// Copies the phenotype columns into raw value columns, so that the original cell values (e.g. "fits") are still
+    /// known after the strategies normalised them (e.g. to "Seizure").
+    pub(crate) fn preserve_raw_values(
+        cdf: &mut ContextualizedDataFrame,
+    ) -> Result<(), DataProcessingError> {
+        let raw_value_cols = cdf
+            .filter_columns()
+            .where_header_context(Filter::Is(&Context::None))
+            .where_data_context(Filter::Is(&Context::Hpo))
+            .collect()
+            .into_iter()
+            .map(|col| {
+                let raw_value_col_name = ContextualizedDataFrame::raw_value_column_name(col.name());
+                col.cast(&DataType::String)
+                    .map(|raw_value_col| raw_value_col.with_name(raw_value_col_name.into()))
+            })
+            .collect::<Result<Vec<Column>, _>>()?;
+
+        if !raw_value_cols.is_empty() {
+            cdf.builder().insert_cols(&raw_value_cols)?.build()?;
+        }
+        Ok(())
+    }
+
     /// Trims whitespace from all string columns and converts empty strings to null.
     ///
     /// This method:
@@ -25,11 +52,23 @@ impl CdfPreprocessor {
     /// - Applies `.trim()` to each string value
     /// - Converts empty strings (after trimming) to `None`
     /// - Leaves existing null values unchanged
+    /// - Skips columns, whose series context opts out via `keep_blank_cells`
     fn trim_strings(cdf: &mut ContextualizedDataFrame) -> Result<(), DataProcessingError> {
+        let kept_col_names: HashSet<String> = cdf
+            .series_contexts()
+            .iter()
+            .filter(|sc| sc.keeps_blank_cells())
+            .flat_map(|sc| cdf.identify_columns(sc.get_identifier()))
+            .map(|col| col.name().to_string())
+            .collect();
+
         let string_col_names: Vec<String> = cdf
             .filter_columns()
             .where_data_type(Filter::Is(&DataType::String))
-            .collect_owned_names();
+            .collect_owned_names()
+            .into_iter()
+            .filter(|col_name| !kept_col_names.contains(col_name))
+            .collect();
 
         for col_name in string_col_names {
             let column = cdf.data().column(&col_name)?;
@@ -86,6 +125,35 @@ impl CdfPreprocessor {
         Ok(())
     }
 
+    /// Converts the cells, which match one of the `null_values` of their series context (e.g. "unknown"), to null.
+    ///
+    /// The cells are compared as strings, so that numeric sentinels like `9999` match as well. Affected columns
+    /// are left as strings and get their type back in `cast_cdf`.
+    fn apply_null_values(cdf: &mut ContextualizedDataFrame) -> Result<(), DataProcessingError> {
+        let null_values_by_col: Vec<(String, Vec<String>)> = cdf
+            .series_contexts()
+            .iter()
+            .filter(|sc| !sc.get_null_values().is_empty())
+            .flat_map(|sc| {
+                cdf.identify_columns(sc.get_identifier())
+                    .into_iter()
+                    .map(|col| (col.name().to_string(), sc.get_null_values().to_vec()))
+            })
+            .collect();
+
+        for (col_name, null_values) in null_values_by_col {
+            let column = cdf.data().column(&col_name)?.cast(&DataType::String)?;
+            let nulled_col = column.str()?.apply(|s| match s {
+                Some(s) if null_values.iter().any(|null_value| null_value == s.trim()) => None,
+                s => s.map(Cow::Borrowed),
+            });
+            cdf.builder()
+                .replace_col(&col_name, nulled_col.into_series())?
+                .build()?;
+        }
+        Ok(())
+    }
+
     /// Applies context-aware type casting to all columns in the dataframe.
     ///
     /// This method:
@@ -333,4 +401,127 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[rstest]
+    fn test_trim_strings_whitespace_only_sex_cell_becomes_null() {
+        let df = df![
+            "subject_id" => ["P001", "P002", "P003"],
+            "sex" => ["MALE", "   ", ""],
+        ]
+        .unwrap();
+        let mut cdf = ContextualizedDataFrame::new(
+            TableContext::new(
+                "table".to_string(),
+                vec![
+                    SeriesContext::from_identifier("subject_id")
+                        .with_data_context(Context::SubjectId),
+                    SeriesContext::from_identifier("sex").with_data_context(Context::SubjectSex),
+                ],
+            ),
+            df,
+        )
+        .unwrap();
+
+        CdfPreprocessor::trim_strings(&mut cdf).unwrap();
+
+        let sex_col = cdf.data().column("sex").unwrap().str().unwrap();
+        assert_eq!(sex_col.get(0), Some("MALE"));
+        assert_eq!(sex_col.get(1), None);
+        assert_eq!(sex_col.get(2), None);
+    }
+
+    #[rstest]
+    fn test_apply_null_values() {
+        let df = df![
+            "subject_id" => ["P001", "P002", "P003"],
+            "vital_status" => ["ALIVE", "unknown", "-"],
+            "age" => [42, 9999, 7],
+            "notes" => ["unknown", "NA", "fine"],
+        ]
+        .unwrap();
+        let mut cdf = ContextualizedDataFrame::new(
+            TableContext::new(
+                "table".to_string(),
+                vec![
+                    SeriesContext::from_identifier("subject_id")
+                        .with_data_context(Context::SubjectId),
+                    SeriesContext::from_identifier("vital_status")
+                        .with_null_values(vec!["unknown".to_string(), "-".to_string()]),
+                    SeriesContext::from_identifier("age").with_null_values(vec!["9999".to_string()]),
+                    SeriesContext::from_identifier("notes"),
+                ],
+            ),
+            df,
+        )
+        .unwrap();
+
+        CdfPreprocessor::process(&mut cdf).unwrap();
+
+        let vital_status_col = cdf.data().column("vital_status").unwrap();
+        assert_eq!(vital_status_col.str().unwrap().get(0), Some("ALIVE"));
+        assert_eq!(vital_status_col.null_count(), 2);
+        let age_col = cdf.data().column("age").unwrap();
+        assert_eq!(age_col.dtype(), &DataType::Int64);
+        assert_eq!(age_col.i64().unwrap().get(1), None);
+        assert_eq!(age_col.i64().unwrap().get(2), Some(7));
+        assert_eq!(cdf.data().column("notes").unwrap().null_count(), 0);
+    }
+
+    #[rstest]
+    fn test_trim_strings_keep_blank_cells() {
+        let df = df![
+            "subject_id" => ["P001", "P002"],
+            "notes" => [" note ", "  "],
+        ]
+        .unwrap();
+        let mut cdf = ContextualizedDataFrame::new(
+            TableContext::new(
+                "table".to_string(),
+                vec![
+                    SeriesContext::from_identifier("subject_id")
+                        .with_data_context(Context::SubjectId),
+                    SeriesContext::from_identifier("notes").with_keep_blank_cells(true),
+                ],
+            ),
+            df,
+        )
+        .unwrap();
+
+        CdfPreprocessor::trim_strings(&mut cdf).unwrap();
+
+        let notes_col = cdf.data().column("notes").unwrap().str().unwrap();
+        assert_eq!(notes_col.get(0), Some(" note "));
+        assert_eq!(notes_col.get(1), Some("  "));
+    }
+
+    #[rstest]
+    fn test_preserve_raw_values() {
+        let df = df![
+            "subject_id" => ["P001", "P002"],
+            "phenotypes" => ["fits", "Seizure"],
+            "sex" => ["M", "F"],
+        ]
+        .unwrap();
+        let hpo_identifier = Identifier::regex_from_str("phenotypes|sex").unwrap();
+        let mut cdf = ContextualizedDataFrame::new(
+            TableContext::new(
+                "table".to_string(),
+                vec![
+                    SeriesContext::from_identifier("subject_id")
+                        .with_data_context(Context::SubjectId),
+                    SeriesContext::from_identifier(hpo_identifier.clone())
+                        .with_data_context(Context::Hpo),
+                ],
+            ),
+            df,
+        )
+        .unwrap();
+
+        CdfPreprocessor::preserve_raw_values(&mut cdf).unwrap();
+
+        let raw_value_col = cdf.get_raw_value_column("phenotypes").unwrap();
+        assert_eq!(raw_value_col.str().unwrap().get(0), Some("fits"));
+        assert!(cdf.get_raw_value_column("subject_id").is_none());
+        assert_eq!(cdf.identify_columns(&hpo_identifier).len(), 2);
+    }
 }
@@ -0,0 +1,62 @@
+use serde::Serialize;
+use strum_macros::Display;
+
+/// The fields, which can be supplied to the builder, but are not emitted into the phenopackets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Display, Serialize)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DroppedFieldKind {
+    KaryotypicSex,
+    PhenotypicFeatureModifiers,
+    /// A phenotype, whose term couldn't be resolved in lenient mode.
+    UnresolvedPhenotype,
+    /// A disease, whose term couldn't be resolved in lenient mode.
+    UnresolvedDisease,
+    /// A survival time, which is not a plausible number of days.
+    MalformedSurvivalTime,
+    /// A phenotype, which is excluded in one row, but observed in another row of the same patient.
+    ConflictingObservationStatus,
+    /// An onset of a phenotype, which can't be compared with the onset, which was collected before, e.g. an age and a
+    /// timestamp.
+    IncomparableOnset,
+}
+
+impl DroppedFieldKind {
+    /// Why values of this kind are not emitted.
+    pub fn reason(&self) -> &'static str {
+        match self {
+            DroppedFieldKind::KaryotypicSex | DroppedFieldKind::PhenotypicFeatureModifiers => {
+                "it is not supported yet"
+            }
+            DroppedFieldKind::UnresolvedPhenotype | DroppedFieldKind::UnresolvedDisease => {
+                "its term could not be resolved"
+            }
+            DroppedFieldKind::MalformedSurvivalTime => {
+                "it is not a number of days between 0 and 150 years"
+            }
+            DroppedFieldKind::ConflictingObservationStatus => {
+                "the phenotype is observed in another row, which wins"
+            }
+            DroppedFieldKind::IncomparableOnset => {
+                "it can't be compared with the onset, which was collected first"
+            }
+        }
+    }
+
+    /// Whether values of this kind are dropped, because the data is faulty, rather than unsupported.
+    pub fn is_data_issue(&self) -> bool {
+        !matches!(
+            self,
+            DroppedFieldKind::KaryotypicSex | DroppedFieldKind::PhenotypicFeatureModifiers
+        )
+    }
+}
+
+/// A value, which was supplied for a patient, but dropped, because its field is not supported yet or its term
+/// couldn't be resolved.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DroppedField {
+    pub kind: DroppedFieldKind,
+    pub patient_id: String,
+    pub value: String,
+}
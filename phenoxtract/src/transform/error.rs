@@ -166,6 +166,13 @@ pub enum TransformError {
     DataProcessingError(#[from] Box<DataProcessingError>),
     #[error(transparent)]
     ValidationError(#[from] ValidationErrors),
+    #[error(
+        "The strategy {strategy} can't be applied to table '{table_name}', because the table is read in chunks. Remove its chunk_size or the strategy."
+    )]
+    UnchunkableStrategy {
+        strategy: String,
+        table_name: String,
+    },
 }
 
 impl From<CollectorError> for TransformError {
@@ -371,6 +378,20 @@ pub enum CollectorError {
     ValidationError(#[from] ValidationErrors),
     #[error(transparent)]
     GetterError(#[from] GetterError),
+    #[error(
+        "Found {n_subjects} distinct subjects, which exceeds the configured maximum of {max_subjects}. Is the subject id column mapped correctly?"
+    )]
+    TooManySubjects {
+        n_subjects: usize,
+        max_subjects: usize,
+    },
+    #[error(
+        "Found only {rows_per_subject:.2} rows per subject on average, which is below the configured minimum of {min_rows_per_subject}. Is the subject id column mapped correctly?"
+    )]
+    TooFewRowsPerSubject {
+        rows_per_subject: f64,
+        min_rows_per_subject: f64,
+    },
 }
 
 impl From<DataProcessingError> for CollectorError {
@@ -382,6 +403,12 @@ impl From<DataProcessingError> for CollectorError {
 pub enum PhenopacketBuilderError {
     #[error("Could not parse {what} from value {value}.")]
     ParsingError { what: String, value: String },
+    #[error("Could not parse {what} from value {value}. Did you mean one of {suggestions:?}?")]
+    UnknownTerm {
+        what: String,
+        value: String,
+        suggestions: Vec<String>,
+    },
     #[error("Value {value_name} was missing, when building {struct_name}.")]
     MissingValueError {
         value_name: String,
@@ -394,6 +421,22 @@ pub enum PhenopacketBuilderError {
         missing: String,
         required_for: String,
     },
+    #[error("Impossible genotype for patient '{patient_id}': {reason}")]
+    ImpossibleZygosity { patient_id: String, reason: String },
+    #[error("Invalid HGVS variant '{variant}': {reason}")]
+    InvalidHgvs { variant: String, reason: String },
+    #[error(
+        "Variant '{variant}' was declared on genome assembly {declared}, but its coordinates are on '{reported}'."
+    )]
+    GenomeAssemblyMismatch {
+        variant: String,
+        declared: String,
+        reported: String,
+    },
+    #[error("Lookup of '{query}' failed recently and is not retried yet: {reason}")]
+    RecentlyFailedLookup { query: String, reason: String },
+    #[error("Could not resolve the resource '{resource}': {reason}")]
+    UnresolvedResource { resource: String, reason: String },
     #[error(transparent)]
     HgvsError(#[from] HGVSError),
     #[error(transparent)]
@@ -0,0 +1,180 @@
+use chrono::{DateTime, Duration, Utc};
+use log::warn;
+use moka::sync::Cache;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// A (symbol, HGNC id) pair and when it was fetched from HGNC.
+#[derive(Debug, Clone, PartialEq)]
+struct FetchedGene {
+    pair: (String, String),
+    fetched_at: DateTime<Utc>,
+}
+
+/// The file representation of a [`FetchedGene`].
+#[derive(Serialize, Deserialize)]
+struct StoredFetchedGene {
+    symbol: String,
+    hgnc_id: String,
+    fetched_at: i64,
+}
+
+/// The (symbol, HGNC id) pairs of the genes, which were already resolved, keyed by the gene query.
+///
+/// Pairs, which are older than the time to live, are treated as missing, so that renamed genes are fetched again.
+/// Without a time to live, pairs never expire. Clones of the cache share its pairs.
+///
+/// If the cache has a file, it is loaded on creation and written after every new pair, together with the time the
+/// pair was fetched at, so that the time to live also applies across runs.
+#[derive(Debug, Clone)]
+pub struct HgncGeneCache {
+    ttl: Option<Duration>,
+    path: Option<PathBuf>,
+    genes: Cache<String, FetchedGene>,
+    /// Serialises the writes of the file.
+    file_lock: Arc<Mutex<()>>,
+}
+
+impl Default for HgncGeneCache {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl HgncGeneCache {
+    pub fn new(ttl: Option<Duration>) -> Self {
+        Self {
+            ttl,
+            path: None,
+            genes: Cache::new(10_000),
+            file_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Creates a cache backed by a JSON file. A missing or unreadable file results in an empty cache.
+    pub fn with_file(ttl: Option<Duration>, path: impl Into<PathBuf>) -> Self {
+        let cache = Self {
+            path: Some(path.into()),
+            ..Self::new(ttl)
+        };
+
+        let stored = cache
+            .path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| {
+                serde_json::from_str::<HashMap<String, StoredFetchedGene>>(&content).ok()
+            })
+            .unwrap_or_default();
+        for (gene, stored) in stored {
+            if let Some(fetched_at) = DateTime::from_timestamp(stored.fetched_at, 0) {
+                cache.genes.insert(
+                    gene,
+                    FetchedGene {
+                        pair: (stored.symbol, stored.hgnc_id),
+                        fetched_at,
+                    },
+                );
+            }
+        }
+        cache
+    }
+
+    /// Returns the pair of a gene, if it was fetched within the time to live.
+    pub fn get(&self, gene: &str, now: DateTime<Utc>) -> Option<(String, String)> {
+        self.genes
+            .get(gene)
+            .filter(|fetched| !self.is_expired(fetched, now))
+            .map(|fetched| fetched.pair)
+    }
+
+    pub fn insert(&self, gene: impl Into<String>, pair: (String, String), now: DateTime<Utc>) {
+        self.genes.insert(
+            gene.into(),
+            FetchedGene {
+                pair,
+                fetched_at: now,
+            },
+        );
+        self.write();
+    }
+
+    fn write(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        let _guard = self.file_lock.lock().unwrap();
+        let stored: HashMap<String, StoredFetchedGene> = self
+            .genes
+            .iter()
+            .map(|(gene, fetched)| {
+                let (symbol, hgnc_id) = fetched.pair;
+                (
+                    gene.as_ref().clone(),
+                    StoredFetchedGene {
+                        symbol,
+                        hgnc_id,
+                        fetched_at: fetched.fetched_at.timestamp(),
+                    },
+                )
+            })
+            .collect();
+        let written = serde_json::to_string(&stored)
+            .map_err(|err| err.to_string())
+            .and_then(|content| fs::write(path, content).map_err(|err| err.to_string()));
+        if let Err(err) = written {
+            warn!("Could not write HGNC gene cache to {path:?}: {err}");
+        }
+    }
+
+    fn is_expired(&self, fetched: &FetchedGene, now: DateTime<Utc>) -> bool {
+        self.ttl.is_some_and(|ttl| now - fetched.fetched_at >= ttl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use tempfile::TempDir;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+    }
+
+    fn stale_pair() -> (String, String) {
+        ("OLD_SYMBOL".to_string(), "HGNC:0".to_string())
+    }
+
+    #[rstest]
+    #[case(None, true)]
+    #[case(Some(Duration::hours(2)), true)]
+    #[case(Some(Duration::hours(1)), false)]
+    fn test_get(#[case] ttl: Option<Duration>, #[case] is_cached: bool) {
+        let cache = HgncGeneCache::new(ttl);
+        cache.insert("CLOCK", stale_pair(), now());
+
+        let pair = cache.get("CLOCK", now() + Duration::hours(1));
+
+        assert_eq!(pair.is_some(), is_cached);
+    }
+
+    #[rstest]
+    fn test_with_file_keeps_fetched_at() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("hgnc_genes.json");
+        HgncGeneCache::with_file(None, &path).insert("CLOCK", stale_pair(), now());
+
+        let reloaded = HgncGeneCache::with_file(Some(Duration::hours(1)), &path);
+
+        assert_eq!(
+            reloaded.get("CLOCK", now() + Duration::minutes(59)),
+            Some(stale_pair())
+        );
+        assert!(reloaded.get("CLOCK", now() + Duration::hours(1)).is_none());
+    }
+}
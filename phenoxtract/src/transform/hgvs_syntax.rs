@@ -0,0 +1,143 @@
+use regex::Regex;
+use std::sync::LazyLock;
+use strum_macros::Display;
+
+/// An HGVS expression: its reference sequence, the variant type and the change. The reference is a versioned RefSeq
+/// accession (e.g. `NM_001173464.1`), a versioned Ensembl accession (e.g. `ENST00000357033.9`) or an LRG, either
+/// a gene region (e.g. `LRG_199`) or one of its transcripts (e.g. `LRG_199t1`).
+static HGVS_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([A-Z]{2}_\d+\.\d+|ENS[A-Z]*\d+\.\d+|LRG_\d+(?:t\d+)?):([a-z])\.(\S+)$")
+        .expect("valid regex")
+});
+
+/// The HGVS syntaxes, which are accepted as input variants.
+///
+/// - `c.`: coding variants on a transcript, e.g. `NM_001173464.1:c.2860C>T`, `ENST00000357033.9:c.100del` or
+///   `LRG_199t1:c.100del`
+/// - `n.`: variants on a non-coding transcript, e.g. `NR_002196.2:n.601G>A`
+/// - `g.`: genomic variants on a chromosome or gene region, e.g. `NC_000012.12:g.39332405G>A` or `LRG_199:g.5000A>T`
+///
+/// Ensembl gene accessions (`ENSG`) are rejected, because a variant has to be described on a transcript
+/// or a RefSeq genomic reference to be validated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum HgvsSyntax {
+    #[strum(serialize = "hgvs.c")]
+    Coding,
+    #[strum(serialize = "hgvs.n")]
+    NonCoding,
+    #[strum(serialize = "hgvs.g")]
+    Genomic,
+}
+
+impl HgvsSyntax {
+    /// The RefSeq and Ensembl accession prefixes, which are valid references for the syntax.
+    pub fn reference_prefixes(&self) -> &'static [&'static str] {
+        match self {
+            HgvsSyntax::Coding => &["NM_", "XM_", "ENST"],
+            HgvsSyntax::NonCoding => &["NR_", "XR_", "NM_", "ENST"],
+            HgvsSyntax::Genomic => &["NC_", "NG_"],
+        }
+    }
+
+    /// Whether the reference sequence fits the syntax. LRG transcripts (e.g. `LRG_199t1`) fit the transcript
+    /// syntaxes and LRG gene regions (e.g. `LRG_199`) the genomic syntax.
+    fn fits(&self, reference: &str) -> bool {
+        if let Some(lrg) = reference.strip_prefix("LRG_") {
+            return lrg.contains('t') != (*self == HgvsSyntax::Genomic);
+        }
+        self.reference_prefixes()
+            .iter()
+            .any(|prefix| reference.starts_with(prefix))
+    }
+
+    /// Determines the syntax of an HGVS expression and checks, that its reference sequence fits.
+    ///
+    /// Fails with a human-readable reason, if the expression is malformed, ambiguous or of an unsupported type.
+    pub fn try_from_hgvs(hgvs: &str) -> Result<Self, String> {
+        let hgvs = hgvs.trim();
+
+        let Some(captures) = HGVS_REGEX.captures(hgvs) else {
+            return Err(match hgvs.matches(':').count() {
+                0 => "it has no versioned reference sequence (e.g. 'NC_000012.12:g.39332405G>A')"
+                    .to_string(),
+                1 => "it is not of the form '<reference sequence>:<type>.<change>'".to_string(),
+                _ => "it contains more than one reference sequence, which is ambiguous".to_string(),
+            });
+        };
+
+        let reference = &captures[1];
+        let syntax = match &captures[2] {
+            "c" => HgvsSyntax::Coding,
+            "n" => HgvsSyntax::NonCoding,
+            "g" => HgvsSyntax::Genomic,
+            other => {
+                return Err(format!(
+                    "variants of type '{other}.' are not supported, use one of hgvs.c, hgvs.n or hgvs.g"
+                ));
+            }
+        };
+
+        if reference.starts_with("ENSG") {
+            return Err(format!(
+                "reference sequence '{reference}' is an Ensembl gene, use a transcript (ENST) instead"
+            ));
+        }
+        if !syntax.fits(reference) {
+            return Err(format!(
+                "reference sequence '{reference}' does not fit {syntax}, expected one of {:?} or an LRG",
+                syntax.reference_prefixes()
+            ));
+        }
+
+        Ok(syntax)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("NM_001173464.1:c.2860C>T", HgvsSyntax::Coding)]
+    #[case("XM_005245080.4:c.100del", HgvsSyntax::Coding)]
+    #[case("NR_002196.2:n.601G>A", HgvsSyntax::NonCoding)]
+    #[case("NC_000012.12:g.39332405G>A", HgvsSyntax::Genomic)]
+    #[case("NG_012232.1:g.12345_12346insA", HgvsSyntax::Genomic)]
+    #[case("  NC_000023.11:g.153296777C>T ", HgvsSyntax::Genomic)]
+    #[case("ENST00000357033.9:c.100del", HgvsSyntax::Coding)]
+    #[case("ENST00000456328.2:n.601G>A", HgvsSyntax::NonCoding)]
+    #[case("LRG_199t1:c.100del", HgvsSyntax::Coding)]
+    #[case("LRG_199:g.5000A>T", HgvsSyntax::Genomic)]
+    fn test_try_from_hgvs(#[case] hgvs: &str, #[case] expected: HgvsSyntax) {
+        assert_eq!(HgvsSyntax::try_from_hgvs(hgvs).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("c.2860C>T")]
+    #[case("NM_001173464.1c.2860C>T")]
+    #[case("NM_001173464:c.2860C>T")]
+    #[case("NM_001173464.1:2860C>T")]
+    #[case("NC_000012.12:NM_001173464.1:c.2860C>T")]
+    #[case("NP_001166935.1:p.Arg954Trp")]
+    #[case("NM_001173464.1:r.2860c>u")]
+    #[case("NC_000012.12:c.2860C>T")]
+    #[case("NM_001173464.1:g.39332405G>A")]
+    #[case("NC_000012.12:n.601G>A")]
+    #[case("ENSG00000198947.15:g.5000A>T")]
+    #[case("ENSG00000198947.15:c.100del")]
+    #[case("ENST00000357033:c.100del")]
+    #[case("LRG_199:c.100del")]
+    #[case("LRG_199t1:g.5000A>T")]
+    #[case("")]
+    fn test_try_from_hgvs_invalid(#[case] hgvs: &str) {
+        assert!(HgvsSyntax::try_from_hgvs(hgvs).is_err());
+    }
+
+    #[rstest]
+    fn test_display() {
+        assert_eq!(HgvsSyntax::Coding.to_string(), "hgvs.c");
+        assert_eq!(HgvsSyntax::NonCoding.to_string(), "hgvs.n");
+        assert_eq!(HgvsSyntax::Genomic.to_string(), "hgvs.g");
+    }
+}
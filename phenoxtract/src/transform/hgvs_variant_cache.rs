@@ -0,0 +1,105 @@
+use log::warn;
+use moka::sync::Cache;
+use pivotal::hgvs::HgvsVariant;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// The HGVS variants, which were already validated, keyed by the unvalidated HGVS string.
+///
+/// Variants in the cache are served without a request to the HGVS service. Clones of the cache share its variants.
+///
+/// If the cache has a file, it is loaded on creation and written after every new variant, so that variants are only
+/// validated once across runs.
+#[derive(Debug, Clone)]
+pub struct HgvsVariantCache {
+    path: Option<PathBuf>,
+    variants: Cache<String, HgvsVariant>,
+    /// Serialises the writes of the file.
+    file_lock: Arc<Mutex<()>>,
+}
+
+impl Default for HgvsVariantCache {
+    fn default() -> Self {
+        Self {
+            path: None,
+            variants: Cache::new(10_000),
+            file_lock: Arc::new(Mutex::new(())),
+        }
+    }
+}
+
+impl HgvsVariantCache {
+    /// Creates a cache backed by a JSON file. A missing or unreadable file results in an empty cache.
+    pub fn with_file(path: impl Into<PathBuf>) -> Self {
+        let cache = Self {
+            path: Some(path.into()),
+            ..Self::default()
+        };
+
+        let stored = cache
+            .path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| {
+                serde_json::from_str::<HashMap<String, HgvsVariant>>(&content).ok()
+            })
+            .unwrap_or_default();
+        for (hgvs, variant) in stored {
+            cache.variants.insert(hgvs, variant);
+        }
+        cache
+    }
+
+    pub fn get(&self, hgvs: &str) -> Option<HgvsVariant> {
+        self.variants.get(hgvs)
+    }
+
+    pub fn insert(&self, hgvs: impl Into<String>, variant: HgvsVariant) {
+        self.variants.insert(hgvs.into(), variant);
+        self.write();
+    }
+
+    fn write(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        let _guard = self.file_lock.lock().unwrap();
+        let stored: HashMap<String, HgvsVariant> = self
+            .variants
+            .iter()
+            .map(|(hgvs, variant)| (hgvs.as_ref().clone(), variant))
+            .collect();
+        let written = serde_json::to_string(&stored)
+            .map_err(|err| err.to_string())
+            .and_then(|content| fs::write(path, content).map_err(|err| err.to_string()));
+        if let Err(err) = written {
+            warn!("Could not write HGVS variant cache to {path:?}: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use tempfile::TempDir;
+
+    #[rstest]
+    fn test_with_file_reloads_variants() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("hgvs_variants.json");
+        HgvsVariantCache::with_file(&path)
+            .insert("NM_001173464.1:c.2860C>T", HgvsVariant::default());
+
+        let reloaded = HgvsVariantCache::with_file(&path);
+
+        assert_eq!(
+            reloaded.get("NM_001173464.1:c.2860C>T"),
+            Some(HgvsVariant::default())
+        );
+        assert!(reloaded.get("NM_001173464.1:c.2861C>T").is_none());
+    }
+}
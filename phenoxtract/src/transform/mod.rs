@@ -4,8 +4,15 @@ pub use phenopacket_builder::PhenopacketBuilder;
 pub(crate) mod cached_resource_resolver;
 
 pub mod bidict_library;
+pub mod clock;
+pub mod cohort_summary;
 pub mod collecting;
 pub(crate) mod data_processing;
+pub mod dropped_fields;
+pub mod hgnc_gene_cache;
+pub mod hgvs_syntax;
+pub mod hgvs_variant_cache;
+pub mod negative_lookup_cache;
 
 pub mod pathogenic_gene_variant_info;
 mod phenopacket_access;
@@ -13,6 +20,6 @@ pub mod strategies;
 pub mod traits;
 pub mod transform_context;
 pub mod transform_module;
-mod utils;
+pub(crate) mod utils;
 
 pub use transform_module::TransformerModule;
@@ -0,0 +1,219 @@
+use chrono::{DateTime, Duration, Utc};
+use log::warn;
+use pivotal::hgnc::HGNCError;
+use pivotal::hgvs::HGVSError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// A lookup, which failed, because the queried gene or variant doesn't exist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailedLookup {
+    pub reason: String,
+    pub failed_at: DateTime<Utc>,
+}
+
+/// The file representation of a [`FailedLookup`].
+#[derive(Serialize, Deserialize)]
+struct StoredFailedLookup {
+    reason: String,
+    failed_at: i64,
+}
+
+/// Remembers HGNC and HGVS lookups, which failed because the query can't be resolved,
+/// so that repeated lookups fail fast instead of querying the service again.
+///
+/// Failures are forgotten once they are older than the time to live.
+/// If the cache has a file, it is loaded on creation and written after every new failure, so it outlives the run.
+#[derive(Debug)]
+pub struct NegativeLookupCache {
+    ttl: Duration,
+    path: Option<PathBuf>,
+    failed_lookups: Mutex<HashMap<String, FailedLookup>>,
+}
+
+impl NegativeLookupCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            path: None,
+            failed_lookups: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Creates a cache backed by a JSON file. A missing or unreadable file results in an empty cache.
+    pub fn with_file(ttl: Duration, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let failed_lookups = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| {
+                serde_json::from_str::<HashMap<String, StoredFailedLookup>>(&content).ok()
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(query, stored)| {
+                let failed_at = DateTime::from_timestamp(stored.failed_at, 0)?;
+                Some((
+                    query,
+                    FailedLookup {
+                        reason: stored.reason,
+                        failed_at,
+                    },
+                ))
+            })
+            .collect();
+
+        Self {
+            ttl,
+            path: Some(path),
+            failed_lookups: Mutex::new(failed_lookups),
+        }
+    }
+
+    /// Returns the failure of a query, if it failed within the time to live.
+    pub fn get(&self, query: &str, now: DateTime<Utc>) -> Option<FailedLookup> {
+        self.failed_lookups
+            .lock()
+            .unwrap()
+            .get(query)
+            .filter(|failed| now - failed.failed_at < self.ttl)
+            .cloned()
+    }
+
+    /// Remembers that a query failed, if the query can't be resolved.
+    /// Any other error, like a timeout or an unavailable service, is not remembered.
+    pub fn insert(&self, query: &str, err: &(dyn Error + 'static), now: DateTime<Utc>) {
+        if !is_not_found(err) {
+            return;
+        }
+
+        let mut failed_lookups = self.failed_lookups.lock().unwrap();
+        failed_lookups.insert(
+            query.to_string(),
+            FailedLookup {
+                reason: err.to_string(),
+                failed_at: now,
+            },
+        );
+
+        if let Some(path) = &self.path {
+            let stored: HashMap<&String, StoredFailedLookup> = failed_lookups
+                .iter()
+                .map(|(query, failed)| {
+                    (
+                        query,
+                        StoredFailedLookup {
+                            reason: failed.reason.clone(),
+                            failed_at: failed.failed_at.timestamp(),
+                        },
+                    )
+                })
+                .collect();
+            let written = serde_json::to_string(&stored)
+                .map_err(|err| err.to_string())
+                .and_then(|content| fs::write(path, content).map_err(|err| err.to_string()));
+            if let Err(err) = written {
+                warn!("Could not write negative lookup cache to {path:?}: {err}");
+            }
+        }
+    }
+}
+
+/// A lookup failed because the query can't be resolved, if HGNC found no gene for it
+/// or VariantValidator rejected the variant. All other errors may be transient.
+fn is_not_found(err: &(dyn Error + 'static)) -> bool {
+    if let Some(err) = err.downcast_ref::<HGNCError>() {
+        return matches!(
+            err,
+            HGNCError::UnexpectedNumberOfDocuments { n_found: 0, .. }
+        );
+    }
+    if let Some(err) = err.downcast_ref::<HGVSError>() {
+        return matches!(
+            err,
+            HGVSError::InvalidHgvs { .. } | HGVSError::HgvsFormatNotAccepted { .. }
+        );
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use tempfile::TempDir;
+
+    fn not_found() -> HGNCError {
+        HGNCError::UnexpectedNumberOfDocuments {
+            identifier: "FAKE".to_string(),
+            n_found: 0,
+            n_expected: 1,
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+    }
+
+    #[rstest]
+    fn test_insert_and_get() {
+        let cache = NegativeLookupCache::new(Duration::hours(1));
+
+        cache.insert("FAKE", &not_found(), now());
+
+        assert_eq!(
+            cache.get("FAKE", now() + Duration::minutes(59)).unwrap(),
+            FailedLookup {
+                reason: not_found().to_string(),
+                failed_at: now(),
+            }
+        );
+        assert!(cache.get("FAKE", now() + Duration::hours(1)).is_none());
+        assert!(cache.get("OTHER", now()).is_none());
+    }
+
+    #[rstest]
+    #[case(HGNCError::HgncAPI { gene: "FAKE".to_string(), attempts: 3 })]
+    #[case(HGNCError::UnexpectedNumberOfDocuments {
+        identifier: "FAKE".to_string(),
+        n_found: 2,
+        n_expected: 1,
+    })]
+    #[case(HGNCError::MutexError("poisoned".to_string()))]
+    fn test_transient_hgnc_errors_are_not_cached(#[case] err: HGNCError) {
+        let cache = NegativeLookupCache::new(Duration::hours(1));
+
+        cache.insert("FAKE", &err, now());
+
+        assert!(cache.get("FAKE", now()).is_none());
+    }
+
+    #[rstest]
+    #[case(HGVSError::InvalidHgvs { hgvs: "FAKE".to_string(), problems: vec![] }, true)]
+    #[case(HGVSError::VariantValidatorAPI { hgvs: "FAKE".to_string(), attempts: 3 }, false)]
+    fn test_insert_hgvs_errors(#[case] err: HGVSError, #[case] is_cached: bool) {
+        let cache = NegativeLookupCache::new(Duration::hours(1));
+
+        cache.insert("FAKE", &err, now());
+
+        assert_eq!(cache.get("FAKE", now()).is_some(), is_cached);
+    }
+
+    #[rstest]
+    fn test_with_file_outlives_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("negative_lookups.json");
+
+        NegativeLookupCache::with_file(Duration::hours(1), &path).insert(
+            "FAKE",
+            &not_found(),
+            now(),
+        );
+        let reloaded = NegativeLookupCache::with_file(Duration::hours(1), &path);
+
+        assert!(reloaded.get("FAKE", now()).is_some());
+    }
+}
@@ -1,3 +1,42 @@
+use pivotal::hgvs::ChromosomalSex;
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+/// The parent, from whom a variant was inherited.
+///
+/// Tagging the variants of a compound heterozygous pair with different parents records, that they are in trans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString)]
+#[strum(serialize_all = "snake_case", ascii_case_insensitive)]
+pub enum VariantOrigin {
+    Maternal,
+    Paternal,
+}
+
+/// The reference genome, whose coordinates the VCF records of the variants refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, Deserialize, Serialize)]
+#[strum(ascii_case_insensitive)]
+pub enum GenomeAssembly {
+    #[strum(to_string = "hg19", serialize = "grch37")]
+    #[serde(rename = "hg19", alias = "GRCh37")]
+    Hg19,
+    #[strum(to_string = "hg38", serialize = "grch38")]
+    #[serde(rename = "hg38", alias = "GRCh38")]
+    Hg38,
+}
+
+/// The annotations of a single variant, which are given in the columns next to its HGVS column.
+/// Missing annotations fall back to the defaults of the phenopacket builder.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VariantAnnotations {
+    /// An ACMG pathogenicity classification, e.g. "LIKELY_PATHOGENIC" or "VUS".
+    pub acmg_classification: Option<String>,
+    /// A therapeutic actionability, e.g. "ACTIONABLE" or "NOT_ACTIONABLE".
+    pub therapeutic_actionability: Option<String>,
+    /// The genome assembly (e.g. "hg19" or "GRCh38") declared in the row of the variant.
+    /// It takes precedence over the assembly of the transform context.
+    pub genome_assembly: Option<String>,
+}
+
 /// An enum which carries gene and variant information.
 /// The collector should collect gene and variant data which can be interpreted
 /// as one of the enum's branches.
@@ -17,6 +56,8 @@ pub enum PathogenicGeneVariantData {
         gene: Option<String>,
         var1: String,
         var2: String,
+        /// The parental origins of `var1` and `var2`, if they are known.
+        origins: [Option<VariantOrigin>; 2],
     },
 }
 
@@ -61,6 +102,7 @@ impl PathogenicGeneVariantData {
                         gene: genes.first().map(|s| s.to_string()),
                         var1: variants[0].to_string(),
                         var2: variants[1].to_string(),
+                        origins: [None, None],
                     })
                 }
             }
@@ -72,6 +114,67 @@ impl PathogenicGeneVariantData {
         }
     }
 
+    /// Tags the variants of a compound heterozygous pair with their parental origins, which are given in the same order
+    /// as the variants. Origins of all other kinds of data are ignored, because they are only recorded for pairs.
+    pub fn with_variant_origins(mut self, variant_origins: &[Option<VariantOrigin>]) -> Self {
+        if let PathogenicGeneVariantData::CompoundHeterozygousVariantPair { origins, .. } =
+            &mut self
+        {
+            origins[0] = variant_origins.first().copied().flatten();
+            origins[1] = variant_origins.get(1).copied().flatten();
+        }
+        self
+    }
+
+    /// The parental origins of the variants, in the same order as [`PathogenicGeneVariantData::get_vars`].
+    pub fn get_var_origins(&self) -> Vec<Option<VariantOrigin>> {
+        match self {
+            PathogenicGeneVariantData::CompoundHeterozygousVariantPair { origins, .. } => {
+                origins.to_vec()
+            }
+            _ => vec![None; self.get_vars().len()],
+        }
+    }
+
+    /// Checks, that the zygosity of the variants is possible on `chromosome` (e.g. "chrX" or "12")
+    /// for a subject with `chromosomal_sex`.
+    ///
+    /// A subject with one X chromosome can't be homozygous or compound heterozygous for an X-linked variant,
+    /// and a subject without a Y chromosome can't carry a Y-linked variant at all. Subjects of unknown
+    /// chromosomal sex are never rejected.
+    pub fn validate_zygosity(
+        &self,
+        chromosome: &str,
+        chromosomal_sex: &ChromosomalSex,
+    ) -> Result<(), String> {
+        let chromosome = chromosome.trim();
+        let chromosome = chromosome
+            .strip_prefix("chr")
+            .or_else(|| chromosome.strip_prefix("CHR"))
+            .unwrap_or(chromosome)
+            .to_uppercase();
+
+        let is_pair = matches!(
+            self,
+            PathogenicGeneVariantData::HomozygousVariant { .. }
+                | PathogenicGeneVariantData::CompoundHeterozygousVariantPair { .. }
+        );
+
+        match chromosome.as_str() {
+            "X" | "Y" if is_pair && matches!(chromosomal_sex, ChromosomalSex::XY) => Err(format!(
+                "A subject with chromosomal sex {chromosomal_sex:?} has a single copy of chromosome {chromosome}, \
+                so the variants {:?} can't be homozygous or compound heterozygous.",
+                self.get_vars()
+            )),
+            "Y" if matches!(chromosomal_sex, ChromosomalSex::XX) => Err(format!(
+                "A subject with chromosomal sex {chromosomal_sex:?} has no Y chromosome, \
+                so it can't carry the variants {:?}.",
+                self.get_vars()
+            )),
+            _ => Ok(()),
+        }
+    }
+
     pub fn get_allelic_count(&self) -> usize {
         match self {
             PathogenicGeneVariantData::None => 0,
@@ -108,8 +211,71 @@ impl PathogenicGeneVariantData {
 
 #[cfg(test)]
 mod tests {
-    use crate::transform::pathogenic_gene_variant_info::PathogenicGeneVariantData;
+    use crate::transform::pathogenic_gene_variant_info::{
+        GenomeAssembly, PathogenicGeneVariantData, VariantOrigin,
+    };
     use rstest::rstest;
+    use std::str::FromStr;
+
+    #[rstest]
+    #[case("hg19", GenomeAssembly::Hg19)]
+    #[case("GRCh37", GenomeAssembly::Hg19)]
+    #[case("HG38", GenomeAssembly::Hg38)]
+    #[case("grch38", GenomeAssembly::Hg38)]
+    fn test_genome_assembly_from_str(#[case] assembly: &str, #[case] expected: GenomeAssembly) {
+        assert_eq!(GenomeAssembly::from_str(assembly).unwrap(), expected);
+    }
+
+    #[rstest]
+    fn test_genome_assembly_invalid() {
+        assert!(GenomeAssembly::from_str("hg18").is_err());
+    }
+
+    #[rstest]
+    #[case(vec!["NM_000000.1:c.1A>G", "NM_000000.1:c.1A>G"], "chrX", ChromosomalSex::XY, false)]
+    #[case(vec!["NM_000000.1:c.1A>G", "NM_000000.1:c.2A>G"], "X", ChromosomalSex::XY, false)]
+    #[case(vec!["NM_000000.1:c.1A>G"], "chrY", ChromosomalSex::XX, false)]
+    #[case(vec!["NM_000000.1:c.1A>G"], "chrX", ChromosomalSex::XY, true)]
+    #[case(vec!["NM_000000.1:c.1A>G", "NM_000000.1:c.1A>G"], "chrX", ChromosomalSex::XX, true)]
+    #[case(vec!["NM_000000.1:c.1A>G", "NM_000000.1:c.1A>G"], "chr12", ChromosomalSex::XY, true)]
+    #[case(vec!["NM_000000.1:c.1A>G", "NM_000000.1:c.1A>G"], "chrX", ChromosomalSex::Unknown, true)]
+    fn test_validate_zygosity(
+        #[case] variants: Vec<&str>,
+        #[case] chromosome: &str,
+        #[case] chromosomal_sex: ChromosomalSex,
+        #[case] is_valid: bool,
+    ) {
+        let data = PathogenicGeneVariantData::from_genes_and_variants(vec![], variants).unwrap();
+
+        assert_eq!(
+            data.validate_zygosity(chromosome, &chromosomal_sex).is_ok(),
+            is_valid
+        );
+    }
+
+    #[rstest]
+    fn test_with_variant_origins() {
+        let pair = PathogenicGeneVariantData::from_genes_and_variants(
+            vec![],
+            vec!["NR_002196.1:n.601G>T", "NR_002196.1:n.602C>T"],
+        )
+        .unwrap()
+        .with_variant_origins(&[Some(VariantOrigin::from_str("Maternal").unwrap()), None]);
+
+        assert_eq!(
+            pair.get_var_origins(),
+            vec![Some(VariantOrigin::Maternal), None]
+        );
+
+        let single = PathogenicGeneVariantData::from_genes_and_variants(
+            vec![],
+            vec!["NR_002196.1:n.601G>T"],
+        )
+        .unwrap()
+        .with_variant_origins(&[Some(VariantOrigin::Paternal)]);
+
+        assert_eq!(single.get_var_origins(), vec![None]);
+    }
 
     #[rstest]
     fn test_from_genes_and_variants() {
@@ -1,7 +1,8 @@
 use crate::transform::traits::PhenopacketAccessors;
 use phenopackets::schema::v2::Phenopacket;
 use phenopackets::schema::v2::core::{
-    Disease, Individual, Interpretation, Measurement, MedicalAction, PhenotypicFeature, Resource,
+    Biosample, Disease, Individual, Interpretation, Measurement, MedicalAction, PhenotypicFeature,
+    Resource,
 };
 
 impl PhenopacketAccessors for Phenopacket {
@@ -67,9 +68,66 @@ impl PhenopacketAccessors for Phenopacket {
     fn push_disease(&mut self, disease: Disease) {
         self.diseases.push(disease)
     }
+
+    fn merge_disease(&mut self, disease: Disease) {
+        match self
+            .diseases
+            .iter_mut()
+            .find(|existing| can_merge_diseases(existing, &disease))
+        {
+            Some(existing) => merge_diseases(existing, disease),
+            None => self.diseases.push(disease),
+        }
+    }
     fn push_medical_action(&mut self, medical_action: MedicalAction) {
         self.medical_actions.push(medical_action)
     }
+
+    fn find_biosample_mut(&mut self, id: &str) -> Option<&mut Biosample> {
+        self.biosamples
+            .iter_mut()
+            .find(|biosample| biosample.id == id)
+    }
+
+    fn push_biosample(&mut self, biosample: Biosample) {
+        self.biosamples.push(biosample)
+    }
+}
+
+/// Two diseases can be merged, if they have the same term and every other field is either equal or missing in one of them.
+fn can_merge_diseases(existing: &Disease, disease: &Disease) -> bool {
+    fn compatible<T: PartialEq>(a: &Option<T>, b: &Option<T>) -> bool {
+        a.is_none() || b.is_none() || a == b
+    }
+    fn compatible_lists<T: PartialEq>(a: &[T], b: &[T]) -> bool {
+        a.is_empty() || b.is_empty() || a == b
+    }
+
+    existing.term == disease.term
+        && existing.excluded == disease.excluded
+        && compatible(&existing.onset, &disease.onset)
+        && compatible(&existing.resolution, &disease.resolution)
+        && compatible(&existing.primary_site, &disease.primary_site)
+        && compatible(&existing.laterality, &disease.laterality)
+        && compatible_lists(&existing.disease_stage, &disease.disease_stage)
+        && compatible_lists(
+            &existing.clinical_tnm_finding,
+            &disease.clinical_tnm_finding,
+        )
+}
+
+/// Fills the missing fields of `existing` with the fields of `disease`.
+fn merge_diseases(existing: &mut Disease, disease: Disease) {
+    existing.onset = existing.onset.take().or(disease.onset);
+    existing.resolution = existing.resolution.take().or(disease.resolution);
+    existing.primary_site = existing.primary_site.take().or(disease.primary_site);
+    existing.laterality = existing.laterality.take().or(disease.laterality);
+    if existing.disease_stage.is_empty() {
+        existing.disease_stage = disease.disease_stage;
+    }
+    if existing.clinical_tnm_finding.is_empty() {
+        existing.clinical_tnm_finding = disease.clinical_tnm_finding;
+    }
 }
 
 #[cfg(test)]
@@ -79,6 +137,8 @@ mod tests {
         default_disease_oc, default_phenotype,
     };
     use crate::test_suite::resource_references::hp_meta_data_resource;
+    use phenopackets::schema::v2::core::time_element::Element;
+    use phenopackets::schema::v2::core::{Age, TimeElement};
 
     #[test]
     fn test_get_or_create_individual_mut() {
@@ -158,4 +218,33 @@ mod tests {
         assert_eq!(pp.diseases.len(), 1);
         assert_eq!(pp.measurements.len(), 1);
     }
+
+    #[test]
+    fn test_merge_disease() {
+        let mut pp = Phenopacket::default();
+        let onset = |iso8601duration: &str| TimeElement {
+            element: Some(Element::Age(Age {
+                iso8601duration: iso8601duration.to_string(),
+            })),
+        };
+
+        pp.merge_disease(Disease {
+            term: Some(default_disease_oc()),
+            ..Default::default()
+        });
+        pp.merge_disease(Disease {
+            term: Some(default_disease_oc()),
+            onset: Some(onset("P3Y")),
+            ..Default::default()
+        });
+        pp.merge_disease(Disease {
+            term: Some(default_disease_oc()),
+            onset: Some(onset("P5Y")),
+            ..Default::default()
+        });
+
+        assert_eq!(pp.diseases.len(), 2);
+        assert_eq!(pp.diseases[0].onset, Some(onset("P3Y")));
+        assert_eq!(pp.diseases[1].onset, Some(onset("P5Y")));
+    }
 }
@@ -1,19 +1,27 @@
 #![allow(clippy::too_many_arguments)]
+use crate::constants::{HPO_SEVERITY_TERMS, NCBI_TAXA};
+use crate::ontology::loinc_client::LoincClient;
 use crate::ontology::resource_references::{KnownResourcePrefixes, ResourceRef};
 use crate::ontology::traits::{HasPrefixId, HasVersion};
 use crate::transform::bidict_library::BiDictLibrary;
-use crate::transform::cached_resource_resolver::CachedResourceResolver;
+use crate::transform::clock::stable_content_hash;
 use crate::transform::collecting::medical_actions::medical_treatment_data::DoseIntervalRow;
+use crate::transform::dropped_fields::{DroppedField, DroppedFieldKind};
 use crate::transform::error::PhenopacketBuilderError;
-use crate::transform::pathogenic_gene_variant_info::PathogenicGeneVariantData;
+use crate::transform::hgvs_syntax::HgvsSyntax;
+use crate::transform::pathogenic_gene_variant_info::{
+    GenomeAssembly, PathogenicGeneVariantData, VariantAnnotations, VariantOrigin,
+};
 use crate::transform::traits::{PhenopacketAccessors, PhenopacketBuilding};
+use crate::transform::transform_context::UnknownSexPolicy;
 pub use crate::transform::transform_context::{BuilderMetaData, TransformContext};
 use crate::transform::utils::chromosomal_sex_from_str;
-use crate::transform::utils::{try_parse_time_element, try_parse_timestamp};
+use crate::transform::utils::{
+    compare_time_elements, try_parse_onset, try_parse_time_element, try_parse_timestamp,
+};
 use crate::utils::phenopacket_schema_version;
-use chrono::Utc;
-use log::warn;
-use phenopackets::ga4gh::vrsatile::v1::GeneDescriptor;
+use log::{debug, info, warn};
+use phenopackets::ga4gh::vrsatile::v1::{Extension, GeneDescriptor, VariationDescriptor};
 use phenopackets::schema::v2::Phenopacket;
 use phenopackets::schema::v2::core::DrugType as PhenopacketDrugType;
 use phenopackets::schema::v2::core::genomic_interpretation::Call;
@@ -23,43 +31,64 @@ use phenopackets::schema::v2::core::medical_action::Action;
 use phenopackets::schema::v2::core::value::Value;
 use phenopackets::schema::v2::core::vital_status::Status;
 use phenopackets::schema::v2::core::{
-    Diagnosis, Disease, GenomicInterpretation, Interpretation, Measurement, MedicalAction,
-    OntologyClass, PhenotypicFeature, Procedure, Quantity, ReferenceRange, Sex, Treatment,
-    Value as ValueStruct, VitalStatus,
+    AcmgPathogenicityClassification, Biosample, ComplexValue, Diagnosis, Disease, Evidence,
+    ExternalReference, GenomicInterpretation, Interpretation, Measurement, MedicalAction,
+    OntologyClass, PhenotypicFeature, Procedure, Quantity, ReferenceRange, Resource, Sex,
+    TherapeuticActionability, TimeElement, Treatment, TypedQuantity, Value as ValueStruct,
+    VitalStatus,
 };
 use pivotal::hgnc::GeneQuery;
-use pivotal::hgvs::AlleleCount;
-use std::cmp::PartialEq;
-use std::collections::HashMap;
+use pivotal::hgvs::{AlleleCount, HgvsVariant};
+use prost::Message;
+use prost_types::Any;
+use std::cmp::{Ordering, PartialEq};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 use std::sync::Arc;
 
 #[derive(Debug, PartialEq)]
 pub struct PhenopacketBuilder {
     subject_to_phenopacket: HashMap<String, Phenopacket>,
     ctx: TransformContext,
-    resource_resolver: CachedResourceResolver,
+    /// The supplied values, which were not emitted, because their fields are not supported yet.
+    dropped_fields: Vec<DroppedField>,
 }
 
 impl PhenopacketBuilding for PhenopacketBuilder {
+    /// Builds the phenopackets sorted by their id. The resources within each phenopacket are sorted by their id as well,
+    /// because otherwise their order depends on which collector happened to register them first.
     fn build(&self) -> Vec<Phenopacket> {
         let mut phenopackets: Vec<Phenopacket> =
             self.subject_to_phenopacket.values().cloned().collect();
-        let now = Utc::now().to_string();
+        phenopackets.sort_by(|a, b| a.id.cmp(&b.id));
+        let now = self.ctx.clock().now().to_string();
 
-        phenopackets.iter_mut().for_each(|pp| {
-            let metadata = pp.meta_data.get_or_insert(Default::default());
-            metadata.created = Some(
-                try_parse_timestamp(&now)
-                    .expect("Failed to parse current timestamp for phenopacket metadata"),
-            );
-            metadata.created_by = self.ctx.meta_data().created_by().to_string();
-            metadata.submitted_by = self.ctx.meta_data().submitted_by().to_string();
-            metadata.phenopacket_schema_version = phenopacket_schema_version();
-        });
+        phenopackets
+            .iter_mut()
+            .for_each(|pp| self.finish_meta_data(pp, &now));
 
         phenopackets
     }
 
+    fn build_phenopacket(&mut self, patient_id: &str) -> Option<Phenopacket> {
+        let phenopacket_id = self.generate_phenopacket_id(patient_id);
+        let mut phenopacket = self.subject_to_phenopacket.remove(&phenopacket_id)?;
+        self.finish_meta_data(&mut phenopacket, &self.ctx.clock().now().to_string());
+        Some(phenopacket)
+    }
+
+    fn record_dropped_field(&mut self, kind: DroppedFieldKind, patient_id: &str, value: &str) {
+        debug!(
+            "Dropped {kind} '{value}' of patient '{patient_id}', because {}.",
+            kind.reason()
+        );
+        self.dropped_fields.push(DroppedField {
+            kind,
+            patient_id: patient_id.to_string(),
+            value: value.to_string(),
+        });
+    }
+
     fn upsert_individual(
         &mut self,
         patient_id: &str,
@@ -71,24 +100,42 @@ impl PhenopacketBuilding for PhenopacketBuilder {
         gender: Option<&str>,
         taxonomy: Option<&str>,
     ) -> Result<(), PhenopacketBuilderError> {
-        if alternate_ids.is_some() {
-            warn!("alternate_ids - not implemented for individual yet");
-        }
-        if karyotypic_sex.is_some() {
-            warn!("karyotypic_sex - not implemented for individual yet");
-        }
-        if gender.is_some() {
-            warn!("gender - not implemented for individual yet");
-        }
-        if taxonomy.is_some() {
-            warn!("taxonomy - not implemented for individual yet");
+        if let Some(karyotypic_sex) = karyotypic_sex {
+            self.record_dropped_field(DroppedFieldKind::KaryotypicSex, patient_id, karyotypic_sex);
         }
 
+        let sex = sex.map(|sex| self.parse_sex(sex)).transpose()?;
+
+        let gender = match gender {
+            Some(gender) => {
+                let (gender_term, gender_ref) =
+                    Self::resolve_term(self.ctx.gender_bi_dict_lib(), gender)?;
+                self.ensure_resource(patient_id, &gender_ref)?;
+                Some(gender_term)
+            }
+            None => None,
+        };
+
+        let taxonomy = match taxonomy {
+            Some(taxonomy) => {
+                let taxonomy_term = Self::resolve_taxonomy(taxonomy)?;
+                self.ensure_resource(patient_id, &ResourceRef::ncbi_taxon())?;
+                Some(taxonomy_term)
+            }
+            None => None,
+        };
+
         let phenopacket = self.get_or_create_phenopacket(patient_id);
 
         let individual = phenopacket.get_or_create_individual_mut();
         individual.id = patient_id.to_string();
 
+        for alternate_id in alternate_ids.unwrap_or_default() {
+            if !individual.alternate_ids.iter().any(|id| id == alternate_id) {
+                individual.alternate_ids.push(alternate_id.to_string());
+            }
+        }
+
         if let Some(date_of_birth) = date_of_birth {
             individual.date_of_birth =
                 Some(try_parse_timestamp(date_of_birth).ok_or_else(|| {
@@ -100,12 +147,15 @@ impl PhenopacketBuilding for PhenopacketBuilder {
         }
 
         if let Some(sex) = sex {
-            individual.sex = Sex::from_str_name(sex)
-                .ok_or_else(|| PhenopacketBuilderError::ParsingError {
-                    what: "Sex".to_string(),
-                    value: sex.to_string(),
-                })?
-                .into();
+            individual.sex = sex.into();
+        }
+
+        if let Some(gender) = gender {
+            individual.gender = Some(gender);
+        }
+
+        if let Some(taxonomy) = taxonomy {
+            individual.taxonomy = Some(taxonomy);
         }
 
         if let Some(time_str) = time_at_last_encounter {
@@ -121,6 +171,7 @@ impl PhenopacketBuilding for PhenopacketBuilder {
         Ok(())
     }
 
+    /// An unknown survival time is left at its proto3 default of 0 days, which the `FileSystemLoader` omits.
     fn upsert_vital_status(
         &mut self,
         patient_id: &str,
@@ -148,7 +199,7 @@ impl PhenopacketBuilding for PhenopacketBuilder {
             Some(cause_of_death) => {
                 let (disease_term, disease_ref) =
                     Self::resolve_term(self.ctx.disease_bidict_lib(), cause_of_death)?;
-                self.ensure_resource(patient_id, &disease_ref);
+                self.ensure_resource(patient_id, &disease_ref)?;
                 Some(disease_term)
             }
             None => None,
@@ -172,10 +223,10 @@ impl PhenopacketBuilding for PhenopacketBuilder {
 
     /// Upserts a phenotypic feature within a specific phenopacket.
     ///
-    /// This function adds or overwrites a `PhenotypicFeature` for a given phenopacket,
+    /// This function adds or updates a `PhenotypicFeature` for a given phenopacket,
     /// identified by `phenopacket_id`. If the phenopacket does not exist, it will be
     /// created. If a feature with the same `phenotype` ID already exists within the
-    /// phenopacket, this function will overwrite it (upsert).
+    /// phenopacket, the new data is merged into it (see `merge_phenotypic_feature`).
     ///
     /// # Arguments
     ///
@@ -188,12 +239,13 @@ impl PhenopacketBuilding for PhenopacketBuilder {
     /// * `modifiers` - An optional `Vec<String>` of terms that modify the phenotype.
     /// * `on_set` - An optional `TimeElement` representing the onset time of the feature.
     /// * `resolution` - An optional `TimeElement` indicating when the feature resolved.
-    /// * `evidence` - An optional `Evidence` struct providing support for the feature.
+    /// * `evidence` - An optional evidence code (e.g. `"ECO:0000033"`), which is resolved against the evidence `BiDict`s.
+    /// * `reference` - An optional external reference (e.g. `"PMID:12345678"`), which supports the evidence code.
     ///
     /// # Errors
     ///
     /// This function will return an `Err` if the provided `phenotype` term cannot be
-    /// resolved into a valid `HpoTerm`.
+    /// resolved into a valid `HpoTerm`, or if a `reference` is given without an `evidence` code.
     ///
     /// # Returns
     ///
@@ -209,7 +261,7 @@ impl PhenopacketBuilding for PhenopacketBuilder {
     /// match handler.upsert_phenotypic_feature(
     ///     phenopacket_id,
     ///     phenotype_term,
-    ///     None, None, None, None, None, None, None
+    ///     None, None, None, None, None, None, None, None
     /// ) {
     ///     Ok(()) => println!("Successfully upserted the phenotypic feature."),
     ///     Err(e) => eprintln!("Error upserting feature: {}", e)
@@ -226,27 +278,65 @@ impl PhenopacketBuilding for PhenopacketBuilder {
         onset: Option<&str>,
         resolution: Option<&str>,
         evidence: Option<&str>,
+        reference: Option<&str>,
     ) -> Result<(), PhenopacketBuilderError> {
-        let (built, hpo_term, hpo_ref) = Self::parse_phenotypic_feature(
-            self.ctx.hpo_bidict_lib(),
+        let Some((hpo_term, hpo_ref)) = self.resolve_or_skip_term(
+            self.ctx.hpo_bidict_lib().clone(),
+            DroppedFieldKind::UnresolvedPhenotype,
+            patient_id,
             phenotype,
+        )?
+        else {
+            return Ok(());
+        };
+
+        if let Some(modifiers) = modifiers {
+            self.record_dropped_field(
+                DroppedFieldKind::PhenotypicFeatureModifiers,
+                patient_id,
+                &modifiers.join(", "),
+            );
+        }
+
+        let (built, hpo_term, resource_refs) = Self::parse_phenotypic_feature(
+            self.ctx.hpo_bidict_lib(),
+            self.ctx.evidence_bi_dict_lib(),
+            hpo_term,
+            hpo_ref,
             description,
             excluded,
             severity,
-            modifiers,
             onset,
             resolution,
             evidence,
+            reference,
         )?;
 
+        let is_new = self
+            .get_or_create_phenopacket(patient_id)
+            .phenotypes_with_id(&hpo_term.id)
+            .is_empty();
+        let hpo_id = hpo_term.id.clone();
         let feature = self.get_or_create_phenotypic_feature(patient_id, hpo_term);
-        feature.severity = built.severity;
-        feature.description = built.description;
-        feature.excluded = built.excluded;
-        feature.onset = built.onset;
-        feature.resolution = built.resolution;
+        if is_new {
+            *feature = built;
+        } else {
+            for conflict in Self::merge_phenotypic_feature(feature, built) {
+                let value = match conflict {
+                    DroppedFieldKind::IncomparableOnset => format!("onset of {hpo_id}"),
+                    _ => format!("exclusion of {hpo_id}"),
+                };
+                warn!(
+                    "Dropped the {value} of patient '{patient_id}', because {}.",
+                    conflict.reason()
+                );
+                self.record_dropped_field(conflict, patient_id, &value);
+            }
+        }
 
-        self.ensure_resource(patient_id, &hpo_ref);
+        for resource_ref in resource_refs.iter() {
+            self.ensure_resource(patient_id, resource_ref)?;
+        }
         Ok(())
     }
 
@@ -261,22 +351,45 @@ impl PhenopacketBuilding for PhenopacketBuilder {
         onset: Option<&str>,
         resolution: Option<&str>,
         evidence: Option<&str>,
+        reference: Option<&str>,
     ) -> Result<(), PhenopacketBuilderError> {
-        let (feature, _, hpo_ref) = Self::parse_phenotypic_feature(
-            self.ctx.hpo_bidict_lib(),
+        let Some((hpo_term, hpo_ref)) = self.resolve_or_skip_term(
+            self.ctx.hpo_bidict_lib().clone(),
+            DroppedFieldKind::UnresolvedPhenotype,
+            patient_id,
             phenotype,
+        )?
+        else {
+            return Ok(());
+        };
+
+        if let Some(modifiers) = modifiers {
+            self.record_dropped_field(
+                DroppedFieldKind::PhenotypicFeatureModifiers,
+                patient_id,
+                &modifiers.join(", "),
+            );
+        }
+
+        let (feature, _, resource_refs) = Self::parse_phenotypic_feature(
+            self.ctx.hpo_bidict_lib(),
+            self.ctx.evidence_bi_dict_lib(),
+            hpo_term,
+            hpo_ref,
             description,
             excluded,
             severity,
-            modifiers,
             onset,
             resolution,
             evidence,
+            reference,
         )?;
 
         let phenopacket = self.get_or_create_phenopacket(patient_id);
         phenopacket.push_phenotype(feature);
-        self.ensure_resource(patient_id, &hpo_ref);
+        for resource_ref in resource_refs.iter() {
+            self.ensure_resource(patient_id, resource_ref)?;
+        }
         Ok(())
     }
 
@@ -285,21 +398,26 @@ impl PhenopacketBuilding for PhenopacketBuilder {
         patient_id: &str,
         disease: &str,
         gene_variant_data: &PathogenicGeneVariantData,
+        variant_annotations: &[VariantAnnotations],
         subject_sex: Option<&str>,
     ) -> Result<(), PhenopacketBuilderError> {
+        let Some((disease_term, res_ref)) = self.resolve_or_skip_term(
+            self.ctx.disease_bidict_lib().clone(),
+            DroppedFieldKind::UnresolvedDisease,
+            patient_id,
+            disease,
+        )?
+        else {
+            return Ok(());
+        };
         let mut genomic_interpretations: Vec<GenomicInterpretation> = vec![];
         let phenopacket_id = self.generate_phenopacket_id(patient_id);
 
-        let (disease_term, res_ref) = Self::resolve_term(self.ctx.disease_bidict_lib(), disease)?;
-
-        self.ensure_resource(patient_id, &res_ref);
+        self.ensure_resource(patient_id, &res_ref)?;
 
         if let PathogenicGeneVariantData::CausativeGene(gene) = gene_variant_data {
-            let (symbol, id) = self
-                .ctx
-                .hgnc_client()
-                .request_gene_identifier_pair(GeneQuery::from(gene.as_str()))?;
-            self.ensure_resource(patient_id, &ResourceRef::from(KnownResourcePrefixes::HGNC));
+            let (symbol, id) = self.resolve_gene(gene)?;
+            self.ensure_resource(patient_id, &ResourceRef::from(KnownResourcePrefixes::HGNC))?;
 
             let gi = GenomicInterpretation {
                 subject_or_biosample_id: patient_id.to_string(),
@@ -321,22 +439,92 @@ impl PhenopacketBuilding for PhenopacketBuilder {
         ) {
             let chromosomal_sex = chromosomal_sex_from_str(subject_sex)?;
 
-            for var in gene_variant_data.get_vars() {
-                let validated_hgvs = self.ctx.hgvs_client().request_and_validate_hgvs(var)?;
-                self.ensure_resource(patient_id, &ResourceRef::from(KnownResourcePrefixes::HGNC));
+            for (var_idx, (var, origin)) in gene_variant_data
+                .get_vars()
+                .into_iter()
+                .zip(gene_variant_data.get_var_origins())
+                .enumerate()
+            {
+                let annotations = variant_annotations.get(var_idx);
+                let acmg_classification = annotations
+                    .and_then(|annotations| annotations.acmg_classification.as_deref())
+                    .map(Self::parse_acmg_classification)
+                    .transpose()?;
+                let therapeutic_actionability = annotations
+                    .and_then(|annotations| annotations.therapeutic_actionability.as_deref())
+                    .map(Self::parse_therapeutic_actionability)
+                    .transpose()?;
+                let declared_assembly = annotations
+                    .and_then(|annotations| annotations.genome_assembly.as_deref())
+                    .map(Self::parse_genome_assembly)
+                    .transpose()?
+                    .or(self.ctx.genome_assembly());
+                HgvsSyntax::try_from_hgvs(var).map_err(|reason| {
+                    PhenopacketBuilderError::InvalidHgvs {
+                        variant: var.to_string(),
+                        reason,
+                    }
+                })?;
+                let validated_hgvs = self.validate_hgvs(var)?;
+                self.ensure_resource(patient_id, &ResourceRef::from(KnownResourcePrefixes::HGNC))?;
                 self.ensure_resource(
                     patient_id,
                     &ResourceRef::from("geno").with_version("2025-07-25"),
-                );
+                )?;
 
                 if let Some(gene) = gene_variant_data.get_gene() {
                     validated_hgvs.validate_against_gene(gene)?;
                 }
 
-                let vi = validated_hgvs.create_variant_interpretation(
+                let mut vi = validated_hgvs.create_variant_interpretation(
                     AlleleCount::try_from(gene_variant_data.get_allelic_count() as u8)?,
                     &chromosomal_sex,
                 )?;
+                if let Some(acmg_classification) = acmg_classification {
+                    vi.acmg_pathogenicity_classification = acmg_classification as i32;
+                }
+                if let Some(therapeutic_actionability) = therapeutic_actionability {
+                    vi.therapeutic_actionability = therapeutic_actionability as i32;
+                }
+
+                if let Some(chrom) = vi
+                    .variation_descriptor
+                    .as_ref()
+                    .and_then(|vd| vd.vcf_record.as_ref())
+                    .map(|vcf_record| vcf_record.chrom.as_str())
+                    && let Err(reason) =
+                        gene_variant_data.validate_zygosity(chrom, &chromosomal_sex)
+                {
+                    if !self.ctx.lenient() {
+                        return Err(PhenopacketBuilderError::ImpossibleZygosity {
+                            patient_id: patient_id.to_string(),
+                            reason,
+                        });
+                    }
+                    warn!("Impossible genotype for patient '{patient_id}': {reason}");
+                }
+
+                if let Some(declared_assembly) = declared_assembly
+                    && let Some(vcf_record) = vi
+                        .variation_descriptor
+                        .as_ref()
+                        .and_then(|vd| vd.vcf_record.as_ref())
+                    && GenomeAssembly::from_str(&vcf_record.genome_assembly).ok()
+                        != Some(declared_assembly)
+                {
+                    return Err(PhenopacketBuilderError::GenomeAssemblyMismatch {
+                        variant: var.to_string(),
+                        declared: declared_assembly.to_string(),
+                        reported: vcf_record.genome_assembly.clone(),
+                    });
+                }
+
+                if let Some(vd) = vi.variation_descriptor.as_mut() {
+                    vd.id = Self::stable_variation_descriptor_id(vd);
+                    if let Some(origin) = origin {
+                        vd.extensions.push(Self::variant_origin_extension(origin));
+                    }
+                }
 
                 let gi = GenomicInterpretation {
                     subject_or_biosample_id: patient_id.to_string(),
@@ -373,35 +561,27 @@ impl PhenopacketBuilding for PhenopacketBuilder {
         primary_site: Option<&str>,
         laterality: Option<&str>,
     ) -> Result<(), PhenopacketBuilderError> {
-        if excluded.is_some() {
-            warn!("excluded disease not implemented yet");
-        }
-        if disease_stage.is_some() {
-            warn!("disease stage of disease not implemented yet");
-        }
-        if clinical_tnm_finding.is_some() {
-            warn!("clinical_tnm_finding disease not implemented yet");
-        }
-        if laterality.is_some() {
-            warn!("laterality disease not implemented yet");
-        }
-
-        let (disease_term, disease_ref) =
-            Self::resolve_term(self.ctx.disease_bidict_lib(), disease)?;
+        let Some((disease_term, disease_ref)) = self.resolve_or_skip_term(
+            self.ctx.disease_bidict_lib().clone(),
+            DroppedFieldKind::UnresolvedDisease,
+            patient_id,
+            disease,
+        )?
+        else {
+            return Ok(());
+        };
 
         let mut disease_element = Disease {
             term: Some(disease_term),
+            excluded: excluded.unwrap_or(false),
             ..Default::default()
         };
 
+        let mut onset_ref = None;
         if let Some(onset) = onset {
-            let onset_te = try_parse_time_element(onset).ok_or_else(|| {
-                PhenopacketBuilderError::ParsingError {
-                    what: "TimeElement".to_string(),
-                    value: onset.to_string(),
-                }
-            })?;
+            let (onset_te, hpo_ref) = Self::parse_onset(self.ctx.hpo_bidict_lib(), onset)?;
             disease_element.onset = Some(onset_te);
+            onset_ref = hpo_ref;
         }
 
         if let Some(resolution) = resolution {
@@ -414,18 +594,50 @@ impl PhenopacketBuilding for PhenopacketBuilder {
             disease_element.resolution = Some(resolution_te);
         }
 
+        let mut disease_attribute_refs = vec![];
+        for stage in disease_stage.unwrap_or_default() {
+            let (stage_term, stage_ref) =
+                Self::resolve_term(self.ctx.disease_attributes_bi_dict_lib(), stage)?;
+            disease_element.disease_stage.push(stage_term);
+            disease_attribute_refs.push(stage_ref);
+        }
+        for tnm_finding in clinical_tnm_finding.unwrap_or_default() {
+            let (tnm_term, tnm_ref) =
+                Self::resolve_term(self.ctx.disease_attributes_bi_dict_lib(), tnm_finding)?;
+            disease_element.clinical_tnm_finding.push(tnm_term);
+            disease_attribute_refs.push(tnm_ref);
+        }
+
         if let Some(primary_site) = primary_site {
             let (primary_site_term, primary_site_ref) =
                 Self::resolve_term(self.ctx.anatomy_bi_dict_lib(), primary_site)?;
             disease_element.primary_site = Some(primary_site_term);
-            self.ensure_resource(patient_id, &primary_site_ref);
+            self.ensure_resource(patient_id, &primary_site_ref)?;
+        }
+
+        if let Some(laterality) = laterality {
+            let (laterality_term, laterality_ref) =
+                Self::resolve_term(self.ctx.hpo_bidict_lib(), laterality)?;
+            disease_element.laterality = Some(laterality_term);
+            disease_attribute_refs.push(laterality_ref);
         }
 
+        let keep_duplicate_diseases = self.ctx.keep_duplicate_diseases();
         let pp = self.get_or_create_phenopacket(patient_id);
 
-        pp.push_disease(disease_element);
+        if keep_duplicate_diseases {
+            pp.push_disease(disease_element);
+        } else {
+            pp.merge_disease(disease_element);
+        }
 
-        self.ensure_resource(patient_id, &disease_ref);
+        self.ensure_resource(patient_id, &disease_ref)?;
+        if let Some(onset_ref) = onset_ref {
+            self.ensure_resource(patient_id, &onset_ref)?;
+        }
+        for disease_attribute_ref in disease_attribute_refs {
+            self.ensure_resource(patient_id, &disease_attribute_ref)?;
+        }
 
         Ok(())
     }
@@ -441,7 +653,10 @@ impl PhenopacketBuilding for PhenopacketBuilder {
     ) -> Result<(), PhenopacketBuilderError> {
         let quantity =
             self.parse_quantity(patient_id, unit_id, quant_measurement, reference_range)?;
-        let (assay_term, assay_ref) = Self::resolve_term(self.ctx.assay_bidict_lib(), assay_id)?;
+        let (assay_term, assay_ref) = Self::resolve_term(
+            self.ctx.assay_bidict_lib(),
+            &LoincClient::normalize_loinc_id(assay_id),
+        )?;
 
         let mut measurement_element = Measurement {
             assay: Some(assay_term),
@@ -465,7 +680,73 @@ impl PhenopacketBuilding for PhenopacketBuilder {
 
         pp.push_measurement(measurement_element);
 
-        self.ensure_resource(patient_id, &assay_ref);
+        self.ensure_resource(patient_id, &assay_ref)?;
+
+        Ok(())
+    }
+
+    /// Inserts a measurement, whose value is the ratio of two quantities.
+    ///
+    /// The measurement carries both quantities as a `ComplexValue`,
+    /// each typed by the assay of the numerator or denominator respectively.
+    fn insert_ratio_measurement(
+        &mut self,
+        patient_id: &str,
+        time_observed: Option<&str>,
+        assay_id: &str,
+        numerator: f64,
+        numerator_type_id: &str,
+        numerator_unit_id: &str,
+        denominator: f64,
+        denominator_type_id: &str,
+        denominator_unit_id: &str,
+    ) -> Result<(), PhenopacketBuilderError> {
+        let (assay_term, assay_ref) = Self::resolve_term(
+            self.ctx.assay_bidict_lib(),
+            &LoincClient::normalize_loinc_id(assay_id),
+        )?;
+
+        let mut typed_quantities = vec![];
+        for (value, type_id, unit_id) in [
+            (numerator, numerator_type_id, numerator_unit_id),
+            (denominator, denominator_type_id, denominator_unit_id),
+        ] {
+            let (type_term, type_ref) = Self::resolve_term(
+                self.ctx.assay_bidict_lib(),
+                &LoincClient::normalize_loinc_id(type_id),
+            )?;
+            let quantity = self.parse_quantity(patient_id, unit_id, value, None)?;
+            self.ensure_resource(patient_id, &type_ref)?;
+
+            typed_quantities.push(TypedQuantity {
+                r#type: Some(type_term),
+                quantity: Some(quantity),
+            });
+        }
+
+        let mut measurement_element = Measurement {
+            assay: Some(assay_term),
+            measurement_value: Some(MeasurementValue::ComplexValue(ComplexValue {
+                typed_quantities,
+            })),
+            ..Default::default()
+        };
+
+        if let Some(time_observed) = time_observed {
+            let time_observed_te = try_parse_time_element(time_observed).ok_or_else(|| {
+                PhenopacketBuilderError::ParsingError {
+                    what: "TimeElement".to_string(),
+                    value: time_observed.to_string(),
+                }
+            })?;
+            measurement_element.time_observed = Some(time_observed_te);
+        }
+
+        let pp = self.get_or_create_phenopacket(patient_id);
+
+        pp.push_measurement(measurement_element);
+
+        self.ensure_resource(patient_id, &assay_ref)?;
 
         Ok(())
     }
@@ -477,7 +758,10 @@ impl PhenopacketBuilding for PhenopacketBuilder {
         time_observed: Option<&str>,
         assay_id: &str,
     ) -> Result<(), PhenopacketBuilderError> {
-        let (assay_term, assay_ref) = Self::resolve_term(self.ctx.assay_bidict_lib(), assay_id)?;
+        let (assay_term, assay_ref) = Self::resolve_term(
+            self.ctx.assay_bidict_lib(),
+            &LoincClient::normalize_loinc_id(assay_id),
+        )?;
         let (qualitative_measurement_term, qualitative_measurement_ontology_ref) =
             Self::resolve_term(
                 self.ctx.qualitative_measurement_bidict_lib(),
@@ -506,8 +790,46 @@ impl PhenopacketBuilding for PhenopacketBuilder {
 
         pp.push_measurement(measurement_element);
 
-        self.ensure_resource(patient_id, &assay_ref);
-        self.ensure_resource(patient_id, &qualitative_measurement_ontology_ref);
+        self.ensure_resource(patient_id, &assay_ref)?;
+        self.ensure_resource(patient_id, &qualitative_measurement_ontology_ref)?;
+
+        Ok(())
+    }
+
+    /// Upserts a biosample of the patient, identified by its id. The biosample is linked to the individual.
+    ///
+    /// The sampled tissue is resolved against the anatomy bidicts (e.g. UBERON) and only overwritten, if it is given.
+    fn upsert_biosample(
+        &mut self,
+        patient_id: &str,
+        biosample_id: &str,
+        sampled_tissue: Option<&str>,
+    ) -> Result<(), PhenopacketBuilderError> {
+        let sampled_tissue = match sampled_tissue {
+            Some(sampled_tissue) => {
+                let (tissue_term, tissue_ref) =
+                    Self::resolve_term(self.ctx.anatomy_bi_dict_lib(), sampled_tissue)?;
+                self.ensure_resource(patient_id, &tissue_ref)?;
+                Some(tissue_term)
+            }
+            None => None,
+        };
+
+        let pp = self.get_or_create_phenopacket(patient_id);
+        if pp.find_biosample_mut(biosample_id).is_none() {
+            pp.push_biosample(Biosample {
+                id: biosample_id.to_string(),
+                individual_id: patient_id.to_string(),
+                ..Default::default()
+            });
+        }
+        let biosample = pp
+            .find_biosample_mut(biosample_id)
+            .expect("Biosample was inserted, if it was missing.");
+
+        if sampled_tissue.is_some() {
+            biosample.sampled_tissue = sampled_tissue;
+        }
 
         Ok(())
     }
@@ -565,13 +887,13 @@ impl PhenopacketBuilding for PhenopacketBuilder {
 
         let (agent_oc, agent_ref) = Self::resolve_term(self.ctx.drug_bi_dict_lib(), agent)?;
         treatment.agent = Some(agent_oc);
-        self.ensure_resource(patient_id, &agent_ref);
+        self.ensure_resource(patient_id, &agent_ref)?;
 
         if let Some(roa) = route_of_administration {
             let (route_of_administration, roa_ref) =
                 Self::resolve_term(self.ctx.treatment_attributes_bi_dict(), roa)?;
             treatment.route_of_administration = Some(route_of_administration);
-            self.ensure_resource(patient_id, &roa_ref);
+            self.ensure_resource(patient_id, &roa_ref)?;
         }
 
         if let Some(drug_type) = drug_type {
@@ -611,136 +933,593 @@ impl PhenopacketBuilder {
         Self {
             subject_to_phenopacket: HashMap::new(),
             ctx,
-            resource_resolver: CachedResourceResolver::default(),
+            dropped_fields: vec![],
         }
     }
-    fn generate_phenopacket_id(&self, patient_id: &str) -> String {
-        if patient_id.starts_with(self.ctx.meta_data().cohort_name()) {
-            return patient_id.to_string();
-        }
-        format!("{}-{}", self.ctx.meta_data().cohort_name(), patient_id)
+
+    /// The supplied values, which were not emitted into the phenopackets, because their fields are not supported yet
+    /// or, in lenient mode, their terms couldn't be resolved.
+    pub fn dropped_fields(&self) -> &[DroppedField] {
+        &self.dropped_fields
     }
 
-    fn get_or_create_phenopacket(&mut self, patient_id: &str) -> &mut Phenopacket {
-        let phenopacket_id = self.generate_phenopacket_id(patient_id);
-        self.subject_to_phenopacket
-            .entry(phenopacket_id.clone())
-            .or_insert_with(|| Phenopacket {
-                id: phenopacket_id.to_string(),
-                ..Default::default()
-            })
+    /// The number of distinct patients, for which each kind of field was supplied, but not emitted.
+    pub fn dropped_field_summary(&self) -> Vec<(DroppedFieldKind, usize)> {
+        let mut patients_per_kind: HashMap<DroppedFieldKind, HashSet<&str>> = HashMap::new();
+        for dropped_field in self.dropped_fields.iter() {
+            patients_per_kind
+                .entry(dropped_field.kind)
+                .or_default()
+                .insert(dropped_field.patient_id.as_str());
+        }
+
+        let mut summary: Vec<(DroppedFieldKind, usize)> = patients_per_kind
+            .into_iter()
+            .map(|(kind, patients)| (kind, patients.len()))
+            .collect();
+        summary.sort();
+        summary
     }
 
-    fn get_or_create_phenotypic_feature(
+    /// Resolves the term of a phenotype or disease. In lenient mode, a term, which can't be resolved, is recorded
+    /// as dropped and `None` is returned, so that only the phenotype or disease it belongs to is skipped.
+    /// In strict mode, it is an error.
+    fn resolve_or_skip_term(
         &mut self,
+        bidict_lib: Arc<BiDictLibrary>,
+        kind: DroppedFieldKind,
         patient_id: &str,
-        phenotype: OntologyClass,
-    ) -> &mut PhenotypicFeature {
-        let pp = self.get_or_create_phenopacket(patient_id);
-        let target_id = phenotype.id.clone();
+        term: &str,
+    ) -> Result<Option<(OntologyClass, ResourceRef)>, PhenopacketBuilderError> {
+        match Self::resolve_term(&bidict_lib, term) {
+            Ok(resolved) => Ok(Some(resolved)),
+            Err(_) if self.ctx.lenient() => {
+                self.record_dropped_field(kind, patient_id, term);
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
 
-        if pp.phenotypes_with_id(&target_id).is_empty() {
-            pp.push_phenotype(PhenotypicFeature {
-                r#type: Some(phenotype),
-                ..Default::default()
-            });
+    pub(crate) fn ctx(&self) -> &TransformContext {
+        &self.ctx
+    }
+
+    pub(crate) fn ctx_mut(&mut self) -> &mut TransformContext {
+        &mut self.ctx
+    }
+
+    /// Resolves a gene to its symbol and HGNC id. Genes, which were resolved before, are taken from the cache.
+    fn resolve_gene(&self, gene: &str) -> Result<(String, String), PhenopacketBuilderError> {
+        let now = self.ctx.clock().now();
+        if let Some(pair) = self.ctx.hgnc_gene_cache().get(gene, now) {
+            return Ok(pair);
         }
 
-        pp.first_phenotype_with_id_mut(&target_id)
-            .expect("PhenotypicFeature was just created or already existed")
+        let negative_cache_key = format!("hgnc:{gene}");
+        self.check_recently_failed_lookup(&negative_cache_key)?;
+
+        let result = self
+            .ctx
+            .hgnc_client()
+            .request_gene_identifier_pair(GeneQuery::from(gene));
+        let pair = match result {
+            Ok(pair) => pair,
+            Err(err) => match self.resolve_gene_alias(gene) {
+                Some(pair) => pair,
+                None => {
+                    self.remember_failed_lookup(&negative_cache_key, &err);
+                    return Err(err.into());
+                }
+            },
+        };
+        self.ctx.hgnc_gene_cache().insert(gene, pair.clone(), now);
+        Ok(pair)
     }
 
-    fn get_or_create_interpretation(
-        &mut self,
-        patient_id: &str,
-        phenopacket_id: &str,
-        disease_term: OntologyClass,
-    ) -> &mut Interpretation {
-        let phenopacket = self.get_or_create_phenopacket(patient_id);
+    /// Validates an HGVS variant. Variants, which were validated before, are taken from the cache.
+    fn validate_hgvs(&self, hgvs: &str) -> Result<HgvsVariant, PhenopacketBuilderError> {
+        if let Some(variant) = self.ctx.hgvs_variant_cache().get(hgvs) {
+            return Ok(variant);
+        }
 
-        let interpretation_id = format!("{}-{}", phenopacket_id, disease_term.id);
+        let negative_cache_key = format!("hgvs:{hgvs}");
+        self.check_recently_failed_lookup(&negative_cache_key)?;
 
-        if phenopacket
-            .find_interpretation_mut(interpretation_id.as_str())
-            .is_none()
+        let variant = self
+            .ctx
+            .hgvs_client()
+            .request_and_validate_hgvs(hgvs)
+            .inspect_err(|err| self.remember_failed_lookup(&negative_cache_key, err))?;
+        self.ctx.hgvs_variant_cache().insert(hgvs, variant.clone());
+        Ok(variant)
+    }
+
+    /// Fails, if the query is in the negative lookup cache, i.e. it failed recently.
+    fn check_recently_failed_lookup(&self, query: &str) -> Result<(), PhenopacketBuilderError> {
+        if let Some(cache) = self.ctx.negative_lookup_cache()
+            && let Some(failed) = cache.get(query, self.ctx.clock().now())
         {
-            phenopacket.push_interpretation(Interpretation {
-                id: interpretation_id.to_string(),
-                progress_status: ProgressStatus::UnknownProgress.into(),
-                diagnosis: Some(Diagnosis {
-                    disease: Some(disease_term),
-                    genomic_interpretations: vec![],
-                }),
-                ..Default::default()
+            return Err(PhenopacketBuilderError::RecentlyFailedLookup {
+                query: query.to_string(),
+                reason: failed.reason,
             });
         }
+        Ok(())
+    }
 
-        phenopacket
-            .find_interpretation_mut(interpretation_id.as_str())
-            .expect("Interpretation was just created or already existed")
+    fn remember_failed_lookup(&self, query: &str, err: &(dyn std::error::Error + 'static)) {
+        if let Some(cache) = self.ctx.negative_lookup_cache() {
+            cache.insert(query, err, self.ctx.clock().now());
+        }
     }
 
-    fn ensure_resource(&mut self, patient_id: &str, resource_ref: &ResourceRef) {
-        let needs_resource = self
-            .get_or_create_phenopacket(patient_id)
-            .resources()
-            .iter()
-            .all(|resource| {
-                resource.id.to_lowercase() != resource_ref.prefix_id().to_lowercase()
-                    && resource.version.to_lowercase() != resource_ref.version().to_lowercase()
-            });
+    /// Resolves a previous or alias gene symbol via its current symbol and records the remapping.
+    ///
+    /// Returns `None`, if alias resolution is disabled, the gene is queried by its HGNC id,
+    /// or the symbol has no unique current symbol.
+    fn resolve_gene_alias(&self, gene: &str) -> Option<(String, String)> {
+        let aliases = self.ctx.gene_symbol_aliases()?;
+        if gene.to_uppercase().starts_with("HGNC:") {
+            return None;
+        }
 
-        if needs_resource {
-            let resource = self
-                .resource_resolver
-                .resolve(resource_ref)
-                .expect("Could not resolve resource");
+        let current_symbol = {
+            let _permit = self.ctx.request_limiter().acquire();
+            aliases.current_symbol(gene)
+        };
+        let current_symbol = match current_symbol {
+            Ok(current_symbol) => current_symbol?,
+            Err(err) => {
+                debug!("Could not look up aliases of gene '{gene}': {err}");
+                return None;
+            }
+        };
 
-            let phenopacket = self.get_or_create_phenopacket(patient_id);
-            phenopacket.push_resource(resource);
-        }
+        let pair = self
+            .ctx
+            .hgnc_client()
+            .request_gene_identifier_pair(GeneQuery::from(current_symbol.as_str()))
+            .ok()?;
+        info!("Gene symbol '{gene}' was remapped to its current symbol '{current_symbol}'.");
+        self.ctx
+            .remapped_gene_symbols()
+            .insert(gene.to_string(), current_symbol);
+        Some(pair)
     }
 
-    fn parse_phenotypic_feature(
-        hpo_bidict_lib: &Arc<BiDictLibrary>,
-        phenotype: &str,
-        description: Option<&str>,
-        excluded: Option<bool>,
-        severity: Option<&str>,
-        modifiers: Option<Vec<&str>>,
-        onset: Option<&str>,
-        resolution: Option<&str>,
-        evidence: Option<&str>,
-    ) -> Result<(PhenotypicFeature, OntologyClass, ResourceRef), PhenopacketBuilderError> {
-        if modifiers.is_some() {
-            warn!("modifiers phenotypic feature not implemented yet");
-        }
-        if evidence.is_some() {
-            warn!("evidence phenotypic feature not implemented yet");
+    /// The previous or alias gene symbols, which were remapped to their current symbol, sorted by the original symbol.
+    pub fn remapped_gene_symbols(&self) -> Vec<(String, String)> {
+        let mut remapped: Vec<(String, String)> = self
+            .ctx
+            .remapped_gene_symbols()
+            .iter()
+            .map(|(gene, current_symbol)| (gene.as_ref().clone(), current_symbol))
+            .collect();
+        remapped.sort();
+        remapped
+    }
+
+    /// Resolves a batch of genes to their symbols and HGNC ids, so that later lookups are served from the cache.
+    ///
+    /// Every distinct gene is requested at most once and cached genes aren't requested at all. If a batch lookup is set,
+    /// the genes are first requested together. The genes, which it doesn't resolve, are requested one by one, so that
+    /// their aliases are tried and their failure is remembered in the negative lookup cache.
+    ///
+    /// Returns the genes, which can't be resolved, sorted.
+    pub(crate) fn resolve_genes<'a>(
+        &self,
+        genes: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<String> {
+        let now = self.ctx.clock().now();
+        let distinct_genes: HashSet<&str> = genes.into_iter().collect();
+        let mut uncached_genes: Vec<&str> = distinct_genes
+            .into_iter()
+            .filter(|gene| self.ctx.hgnc_gene_cache().get(gene, now).is_none())
+            .collect();
+        uncached_genes.sort();
+
+        if let Some(gene_batch_lookup) = self.ctx.gene_batch_lookup()
+            && !uncached_genes.is_empty()
+        {
+            let result = {
+                let _permit = self.ctx.request_limiter().acquire();
+                gene_batch_lookup.request_gene_identifier_pairs(&uncached_genes)
+            };
+            match result {
+                Ok(pairs) => {
+                    uncached_genes.retain(|gene| !pairs.contains_key(*gene));
+                    for (gene, pair) in pairs {
+                        self.ctx.hgnc_gene_cache().insert(gene, pair, now);
+                    }
+                }
+                Err(err) => warn!(
+                    "Could not resolve {} genes in one batch, resolving them one by one: {err}",
+                    uncached_genes.len()
+                ),
+            }
         }
 
-        let mut feature = PhenotypicFeature::default();
-        let (hpo_term, hpo_ref) = Self::resolve_term(hpo_bidict_lib, phenotype)?;
-        feature.r#type = Some(hpo_term.clone());
+        uncached_genes
+            .into_iter()
+            .filter(|gene| self.resolve_gene(gene).is_err())
+            .map(str::to_string)
+            .collect()
+    }
 
-        let sev = severity
-            .map(|s| Self::resolve_term(hpo_bidict_lib, s).map(|(term, _)| term))
-            .transpose()?;
-        feature.severity = sev;
+    /// Splits off a builder for the given patients, which can be filled independently of this one, e.g. on another thread.
+    ///
+    /// Phenopackets, which already exist for these patients, are moved into the new builder.
+    /// Use [`PhenopacketBuilder::merge`] to bring the results back.
+    pub(crate) fn split_off<'a>(
+        &mut self,
+        patient_ids: impl IntoIterator<Item = &'a str>,
+    ) -> PhenopacketBuilder {
+        let subject_to_phenopacket = patient_ids
+            .into_iter()
+            .filter_map(|patient_id| {
+                let phenopacket_id = self.generate_phenopacket_id(patient_id);
+                self.subject_to_phenopacket.remove_entry(&phenopacket_id)
+            })
+            .collect();
 
-        if let Some(desc) = description {
+        PhenopacketBuilder {
+            subject_to_phenopacket,
+            ctx: self.ctx.clone(),
+            dropped_fields: vec![],
+        }
+    }
+
+    /// Moves the phenopackets and dropped fields of a builder created by [`PhenopacketBuilder::split_off`] back into this one.
+    pub(crate) fn merge(&mut self, other: PhenopacketBuilder) {
+        self.subject_to_phenopacket
+            .extend(other.subject_to_phenopacket);
+        self.dropped_fields.extend(other.dropped_fields);
+    }
+
+    /// Derives the id of a variation descriptor from its gene, its HGVS expressions (which include the transcript)
+    /// and its allelic state. The same variant always gets the same id, independent of the run.
+    fn stable_variation_descriptor_id(vd: &VariationDescriptor) -> String {
+        let mut key = vec![
+            vd.gene_context
+                .as_ref()
+                .map(|gene| gene.value_id.clone())
+                .unwrap_or_default(),
+        ];
+        key.extend(
+            vd.expressions
+                .iter()
+                .map(|expression| format!("{}={}", expression.syntax, expression.value)),
+        );
+        key.push(
+            vd.allelic_state
+                .as_ref()
+                .map(|allelic_state| allelic_state.id.clone())
+                .unwrap_or_default(),
+        );
+        format!("vd-{:016x}", stable_content_hash(key.join("|").as_bytes()))
+    }
+
+    /// Turns a value like "Likely benign" into the name of an enum value like "LIKELY_BENIGN".
+    fn normalise_enum_name(value: &str) -> String {
+        value.trim().to_uppercase().replace([' ', '-'], "_")
+    }
+
+    /// Parses an ACMG classification like "LIKELY_BENIGN", "Likely benign" or "VUS".
+    fn parse_acmg_classification(
+        classification: &str,
+    ) -> Result<AcmgPathogenicityClassification, PhenopacketBuilderError> {
+        let normalised = Self::normalise_enum_name(classification);
+        let normalised = match normalised.as_str() {
+            "VUS" => "UNCERTAIN_SIGNIFICANCE",
+            other => other,
+        };
+
+        AcmgPathogenicityClassification::from_str_name(normalised).ok_or_else(|| {
+            PhenopacketBuilderError::ParsingError {
+                what: "ACMG pathogenicity classification".to_string(),
+                value: classification.to_string(),
+            }
+        })
+    }
+
+    /// Parses a therapeutic actionability like "ACTIONABLE" or "Not actionable".
+    fn parse_therapeutic_actionability(
+        actionability: &str,
+    ) -> Result<TherapeuticActionability, PhenopacketBuilderError> {
+        TherapeuticActionability::from_str_name(&Self::normalise_enum_name(actionability))
+            .ok_or_else(|| PhenopacketBuilderError::ParsingError {
+                what: "therapeutic actionability".to_string(),
+                value: actionability.to_string(),
+            })
+    }
+
+    /// Parses a genome assembly like "hg38" or "GRCh37".
+    fn parse_genome_assembly(assembly: &str) -> Result<GenomeAssembly, PhenopacketBuilderError> {
+        GenomeAssembly::from_str(assembly.trim()).map_err(|_| {
+            PhenopacketBuilderError::ParsingError {
+                what: "genome assembly".to_string(),
+                value: assembly.to_string(),
+            }
+        })
+    }
+
+    /// The phenopacket schema has no field for the parental origin of a variant, so it is recorded as an extension
+    /// of the variation descriptor, e.g. `allele_origin: maternal`.
+    fn variant_origin_extension(origin: VariantOrigin) -> Extension {
+        Extension {
+            name: "allele_origin".to_string(),
+            value: vec![Any {
+                type_url: "type.googleapis.com/google.protobuf.StringValue".to_string(),
+                value: origin.to_string().encode_to_vec(),
+            }],
+        }
+    }
+
+    /// Parses a sex value. Values are first looked up in the `SexVocabulary` of the context. Values, which are
+    /// neither in the vocabulary nor a `Sex` name, are handled by the `UnknownSexPolicy` of the context. By default,
+    /// they become `UNKNOWN_SEX` with a warning, unless they are clearly no sex, which is an error.
+    fn parse_sex(&self, sex: &str) -> Result<Sex, PhenopacketBuilderError> {
+        if let Some(parsed) = self
+            .ctx
+            .sex_vocabulary()
+            .get(sex)
+            .or_else(|| Sex::from_str_name(sex))
+        {
+            return Ok(parsed);
+        }
+
+        match self.ctx.unknown_sex_policy() {
+            UnknownSexPolicy::Strict if Self::is_invalid_sex(sex) => {
+                Err(PhenopacketBuilderError::ParsingError {
+                    what: "Sex".to_string(),
+                    value: sex.to_string(),
+                })
+            }
+            UnknownSexPolicy::Other => {
+                warn!("Mapping unrecognised sex '{sex}' to OTHER_SEX.");
+                Ok(Sex::OtherSex)
+            }
+            UnknownSexPolicy::Strict | UnknownSexPolicy::Unknown => {
+                warn!("Mapping unrecognised sex '{sex}' to UNKNOWN_SEX.");
+                Ok(Sex::UnknownSex)
+            }
+        }
+    }
+
+    /// Whether a sex value is clearly no sex, because it is not made of words, e.g. "3", "m?" or "".
+    fn is_invalid_sex(sex: &str) -> bool {
+        let sex = sex.trim();
+        !sex.chars().any(char::is_alphabetic)
+            || !sex
+                .chars()
+                .all(|c| c.is_alphabetic() || c.is_whitespace() || c == '-' || c == '_')
+    }
+
+    fn finish_meta_data(&self, pp: &mut Phenopacket, now: &str) {
+        let metadata = pp.meta_data.get_or_insert(Default::default());
+        metadata.created = (!self.ctx.omit_created()).then(|| {
+            try_parse_timestamp(now)
+                .expect("Failed to parse current timestamp for phenopacket metadata")
+        });
+        metadata.created_by = self.ctx.meta_data().created_by().to_string();
+        metadata.submitted_by = self.ctx.meta_data().submitted_by().to_string();
+        metadata.phenopacket_schema_version = phenopacket_schema_version();
+        metadata.resources.sort_by(|a, b| a.id.cmp(&b.id));
+    }
+
+    pub(crate) fn generate_phenopacket_id(&self, patient_id: &str) -> String {
+        if patient_id.starts_with(self.ctx.meta_data().cohort_name()) {
+            return patient_id.to_string();
+        }
+        format!("{}-{}", self.ctx.meta_data().cohort_name(), patient_id)
+    }
+
+    fn get_or_create_phenopacket(&mut self, patient_id: &str) -> &mut Phenopacket {
+        let phenopacket_id = self.generate_phenopacket_id(patient_id);
+        self.subject_to_phenopacket
+            .entry(phenopacket_id.clone())
+            .or_insert_with(|| Phenopacket {
+                id: phenopacket_id.to_string(),
+                ..Default::default()
+            })
+    }
+
+    /// Merges the data of another row (e.g. of another building block) into an existing phenotypic feature.
+    ///
+    /// - `excluded`: observed wins, i.e. the feature is only excluded, if every row excludes it.
+    /// - `onset`: the earlier onset is kept, if both onsets are ages or both are timestamps. Otherwise, the existing
+    ///   onset is kept.
+    /// - `severity`, `description` and `resolution`: the new value wins, but a missing value never erases an existing one.
+    /// - `modifiers` and `evidence`: the union, in the order they were first seen.
+    ///
+    /// Returns the contradictions between the rows, i.e. a conflicting observation status and an incomparable onset.
+    fn merge_phenotypic_feature(
+        feature: &mut PhenotypicFeature,
+        other: PhenotypicFeature,
+    ) -> Vec<DroppedFieldKind> {
+        let mut conflicts = vec![];
+
+        if feature.excluded != other.excluded {
+            conflicts.push(DroppedFieldKind::ConflictingObservationStatus);
+        }
+        feature.excluded = feature.excluded && other.excluded;
+
+        feature.onset = match (feature.onset.take(), other.onset) {
+            (Some(existing), Some(new)) => match compare_time_elements(&existing, &new) {
+                Some(Ordering::Greater) => Some(new),
+                Some(Ordering::Less | Ordering::Equal) => Some(existing),
+                None => {
+                    conflicts.push(DroppedFieldKind::IncomparableOnset);
+                    Some(existing)
+                }
+            },
+            (existing, new) => new.or(existing),
+        };
+
+        if other.severity.is_some() {
+            feature.severity = other.severity;
+        }
+        if !other.description.is_empty() {
+            feature.description = other.description;
+        }
+        if other.resolution.is_some() {
+            feature.resolution = other.resolution;
+        }
+
+        for modifier in other.modifiers {
+            if !feature.modifiers.contains(&modifier) {
+                feature.modifiers.push(modifier);
+            }
+        }
+        for evidence in other.evidence {
+            if !feature.evidence.contains(&evidence) {
+                feature.evidence.push(evidence);
+            }
+        }
+
+        conflicts
+    }
+
+    fn get_or_create_phenotypic_feature(
+        &mut self,
+        patient_id: &str,
+        phenotype: OntologyClass,
+    ) -> &mut PhenotypicFeature {
+        let pp = self.get_or_create_phenopacket(patient_id);
+        let target_id = phenotype.id.clone();
+
+        if pp.phenotypes_with_id(&target_id).is_empty() {
+            pp.push_phenotype(PhenotypicFeature {
+                r#type: Some(phenotype),
+                ..Default::default()
+            });
+        }
+
+        pp.first_phenotype_with_id_mut(&target_id)
+            .expect("PhenotypicFeature was just created or already existed")
+    }
+
+    fn get_or_create_interpretation(
+        &mut self,
+        patient_id: &str,
+        phenopacket_id: &str,
+        disease_term: OntologyClass,
+    ) -> &mut Interpretation {
+        let phenopacket = self.get_or_create_phenopacket(patient_id);
+
+        let interpretation_id = format!("{}-{}", phenopacket_id, disease_term.id);
+
+        if phenopacket
+            .find_interpretation_mut(interpretation_id.as_str())
+            .is_none()
+        {
+            phenopacket.push_interpretation(Interpretation {
+                id: interpretation_id.to_string(),
+                progress_status: ProgressStatus::UnknownProgress.into(),
+                diagnosis: Some(Diagnosis {
+                    disease: Some(disease_term),
+                    genomic_interpretations: vec![],
+                }),
+                ..Default::default()
+            });
+        }
+
+        phenopacket
+            .find_interpretation_mut(interpretation_id.as_str())
+            .expect("Interpretation was just created or already existed")
+    }
+
+    /// Adds the resource of `resource_ref` to the meta data of the phenopacket, unless it already has the same version
+    /// of that resource. Different versions of the same ontology are kept as separate resources.
+    fn ensure_resource(
+        &mut self,
+        patient_id: &str,
+        resource_ref: &ResourceRef,
+    ) -> Result<(), PhenopacketBuilderError> {
+        let resource = self.ctx.resource_resolver().resolve(resource_ref)?;
+
+        let phenopacket = self.get_or_create_phenopacket(patient_id);
+        let same_ontology: Vec<&Resource> = phenopacket
+            .resources()
+            .iter()
+            .filter(|existing| existing.id.to_lowercase() == resource.id.to_lowercase())
+            .collect();
+
+        if same_ontology
+            .iter()
+            .any(|existing| existing.version == resource.version)
+        {
+            return Ok(());
+        }
+        if !same_ontology.is_empty() {
+            warn!(
+                "Phenopacket '{}' references version '{}' of resource '{}', but also {:?}.",
+                phenopacket.id,
+                resource.version,
+                resource.id,
+                same_ontology
+                    .iter()
+                    .map(|existing| existing.version.as_str())
+                    .collect::<Vec<_>>()
+            );
+        }
+        phenopacket.push_resource(resource);
+        Ok(())
+    }
+
+    fn parse_phenotypic_feature(
+        hpo_bidict_lib: &Arc<BiDictLibrary>,
+        evidence_bidict_lib: &Arc<BiDictLibrary>,
+        hpo_term: OntologyClass,
+        hpo_ref: ResourceRef,
+        description: Option<&str>,
+        excluded: Option<bool>,
+        severity: Option<&str>,
+        onset: Option<&str>,
+        resolution: Option<&str>,
+        evidence: Option<&str>,
+        reference: Option<&str>,
+    ) -> Result<(PhenotypicFeature, OntologyClass, Vec<ResourceRef>), PhenopacketBuilderError> {
+        let mut feature = PhenotypicFeature::default();
+        feature.r#type = Some(hpo_term.clone());
+        let mut resource_refs = vec![hpo_ref];
+
+        if let Some(severity) = severity {
+            let (severity_term, severity_ref) = Self::resolve_severity(hpo_bidict_lib, severity)?;
+            feature.severity = Some(severity_term);
+            resource_refs.push(severity_ref);
+        }
+
+        match (evidence, reference) {
+            (Some(evidence), reference) => {
+                let (evidence_code, evidence_ref) =
+                    Self::resolve_term(evidence_bidict_lib, evidence)?;
+                feature.evidence.push(Evidence {
+                    evidence_code: Some(evidence_code),
+                    reference: reference.map(Self::parse_external_reference).transpose()?,
+                });
+                resource_refs.push(evidence_ref);
+            }
+            (None, Some(_)) => {
+                return Err(PhenopacketBuilderError::MissingPrerequisiteError {
+                    missing: "evidence code".to_string(),
+                    required_for: "evidence reference".to_string(),
+                });
+            }
+            (None, None) => {}
+        }
+
+        if let Some(desc) = description {
             feature.description = desc.to_string();
         }
         if let Some(excluded) = excluded {
             feature.excluded = excluded;
         }
         if let Some(onset) = onset {
-            feature.onset = Some(try_parse_time_element(onset).ok_or_else(|| {
-                PhenopacketBuilderError::ParsingError {
-                    what: "TimeElement".to_string(),
-                    value: onset.to_string(),
-                }
-            })?);
+            let (onset_te, onset_ref) = Self::parse_onset(hpo_bidict_lib, onset)?;
+            feature.onset = Some(onset_te);
+            resource_refs.extend(onset_ref);
         }
         if let Some(resolution) = resolution {
             feature.resolution = Some(try_parse_time_element(resolution).ok_or_else(|| {
@@ -751,7 +1530,108 @@ impl PhenopacketBuilder {
             })?);
         }
 
-        Ok((feature, hpo_term, hpo_ref))
+        Ok((feature, hpo_term, resource_refs))
+    }
+
+    /// Parses an onset, which is a timestamp, an age or a term of the HPO "Onset" (HP:0003674) subhierarchy,
+    /// e.g. "Congenital onset". Onset terms come with the resource of the HPO, which resolved them.
+    fn parse_onset(
+        hpo_bidict_lib: &Arc<BiDictLibrary>,
+        onset: &str,
+    ) -> Result<(TimeElement, Option<ResourceRef>), PhenopacketBuilderError> {
+        try_parse_onset(onset, hpo_bidict_lib).ok_or_else(|| {
+            PhenopacketBuilderError::ParsingError {
+                what: "TimeElement".to_string(),
+                value: onset.to_string(),
+            }
+        })
+    }
+
+    /// Resolves a severity (e.g. "Severe") to a term of the HPO "Severity" (HP:0012824) subhierarchy.
+    fn resolve_severity(
+        hpo_bidict_lib: &Arc<BiDictLibrary>,
+        severity: &str,
+    ) -> Result<(OntologyClass, ResourceRef), PhenopacketBuilderError> {
+        let (severity_term, severity_ref) = Self::resolve_term(hpo_bidict_lib, severity)?;
+
+        if !HPO_SEVERITY_TERMS.contains(&severity_term.id.as_str()) {
+            return Err(PhenopacketBuilderError::ParsingError {
+                what: "Severity".to_string(),
+                value: severity.to_string(),
+            });
+        }
+
+        Ok((severity_term, severity_ref))
+    }
+
+    /// Parses an external reference, i.e. a PubMed id (e.g. "PMID:12345678"), a DOI (e.g. "doi:10.1038/nature12373"),
+    /// any other CURIE or a URL. PubMed ids and DOIs are normalised and linked to their canonical URL.
+    fn parse_external_reference(
+        reference: &str,
+    ) -> Result<ExternalReference, PhenopacketBuilderError> {
+        let reference = reference.trim();
+        if reference.starts_with("http://") || reference.starts_with("https://") {
+            return Ok(ExternalReference {
+                id: String::new(),
+                reference: reference.to_string(),
+                description: String::new(),
+            });
+        }
+
+        let parsing_error = || PhenopacketBuilderError::ParsingError {
+            what: "ExternalReference".to_string(),
+            value: reference.to_string(),
+        };
+        let (prefix, local_id) = reference
+            .split_once(':')
+            .or_else(|| reference.split_once(' '))
+            .map(|(prefix, local_id)| (prefix.trim(), local_id.trim()))
+            .filter(|(prefix, local_id)| !prefix.is_empty() && !local_id.is_empty())
+            .ok_or_else(parsing_error)?;
+
+        match prefix.to_uppercase().as_str() {
+            "PMID" => {
+                if !local_id.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(parsing_error());
+                }
+                Ok(ExternalReference {
+                    id: format!("PMID:{local_id}"),
+                    reference: format!("https://pubmed.ncbi.nlm.nih.gov/{local_id}"),
+                    description: String::new(),
+                })
+            }
+            "DOI" => Ok(ExternalReference {
+                id: format!("DOI:{local_id}"),
+                reference: format!("https://doi.org/{local_id}"),
+                description: String::new(),
+            }),
+            _ => Ok(ExternalReference {
+                id: format!("{prefix}:{local_id}"),
+                reference: String::new(),
+                description: String::new(),
+            }),
+        }
+    }
+
+    /// Resolves an NCBITaxon id, scientific name or common name of a known organism.
+    fn resolve_taxonomy(taxonomy: &str) -> Result<OntologyClass, PhenopacketBuilderError> {
+        let query = taxonomy.trim();
+
+        NCBI_TAXA
+            .iter()
+            .find(|(id, label, common_name)| {
+                [id, label, common_name]
+                    .iter()
+                    .any(|candidate| candidate.eq_ignore_ascii_case(query))
+            })
+            .map(|(id, label, _)| OntologyClass {
+                id: id.to_string(),
+                label: label.to_string(),
+            })
+            .ok_or_else(|| PhenopacketBuilderError::ParsingError {
+                what: "Taxonomy".to_string(),
+                value: taxonomy.to_string(),
+            })
     }
 
     fn parse_medical_action(
@@ -770,12 +1650,14 @@ impl PhenopacketBuilder {
                 Self::resolve_term(self.ctx.disease_bidict_lib(), tt)
             {
                 medical_action.treatment_target = Some(disease_oc);
-                self.ensure_resource(patient_id, &disease_ref);
+                self.ensure_resource(patient_id, &disease_ref)?;
             } else if let Ok((hpo_oc, hpo_ref)) = Self::resolve_term(self.ctx.hpo_bidict_lib(), tt)
             {
                 medical_action.treatment_target = Some(hpo_oc);
-                self.ensure_resource(patient_id, &hpo_ref);
+                self.ensure_resource(patient_id, &hpo_ref)?;
             } else {
+                let mut suggestions = self.ctx.disease_bidict_lib().suggest_labels(tt);
+                suggestions.extend(self.ctx.hpo_bidict_lib().suggest_labels(tt));
                 return Err(Self::cant_resolve_term_error(
                     &format!(
                         "{} and {}",
@@ -783,6 +1665,7 @@ impl PhenopacketBuilder {
                         self.ctx.hpo_bidict_lib().name()
                     ),
                     tt,
+                    suggestions,
                 ));
             }
         }
@@ -791,21 +1674,21 @@ impl PhenopacketBuilder {
             let (treatment_intent_oc, treatment_intent_ref) =
                 Self::resolve_term(self.ctx.treatment_attributes_bi_dict(), ti)?;
             medical_action.treatment_intent = Some(treatment_intent_oc);
-            self.ensure_resource(patient_id, &treatment_intent_ref);
+            self.ensure_resource(patient_id, &treatment_intent_ref)?;
         }
 
         if let Some(tr) = response_to_treatment {
             let (treatment_response_oc, response_to_treatment_ref) =
                 Self::resolve_term(self.ctx.treatment_attributes_bi_dict(), tr)?;
             medical_action.response_to_treatment = Some(treatment_response_oc);
-            self.ensure_resource(patient_id, &response_to_treatment_ref);
+            self.ensure_resource(patient_id, &response_to_treatment_ref)?;
         }
 
         if let Some(ttr) = treatment_termination_reason {
             let (treatment_termination_reason_oc, treatment_termination_reason_ref) =
                 Self::resolve_term(self.ctx.treatment_attributes_bi_dict(), ttr)?;
             medical_action.treatment_termination_reason = Some(treatment_termination_reason_oc);
-            self.ensure_resource(patient_id, &treatment_termination_reason_ref);
+            self.ensure_resource(patient_id, &treatment_termination_reason_ref)?;
         };
 
         medical_action.action = Some(medical_action_type);
@@ -824,13 +1707,13 @@ impl PhenopacketBuilder {
         let (procedure_oc, procedure_ref) =
             Self::resolve_term(self.ctx.procedure_bi_dict_lib(), procedure_code)?;
         procedure.code = Some(procedure_oc);
-        self.ensure_resource(patient_id, &procedure_ref);
+        self.ensure_resource(patient_id, &procedure_ref)?;
 
         if let Some(bp) = body_part {
             let (body_part_oc, body_part_ref) =
                 Self::resolve_term(self.ctx.anatomy_bi_dict_lib(), bp)?;
             procedure.body_site = Some(body_part_oc);
-            self.ensure_resource(patient_id, &body_part_ref);
+            self.ensure_resource(patient_id, &body_part_ref)?;
         }
 
         if let Some(pte) = procedure_time_element
@@ -853,7 +1736,7 @@ impl PhenopacketBuilder {
 
         let (unit_term, unit_ref) = Self::resolve_term(self.ctx.unit_bidict_lib(), unit)?;
         quantity.unit = Some(unit_term.clone());
-        self.ensure_resource(patient_id, &unit_ref);
+        self.ensure_resource(patient_id, &unit_ref)?;
 
         quantity.value = value;
 
@@ -877,18 +1760,37 @@ impl PhenopacketBuilder {
             });
         }
 
-        bi_dict_lib
-            .lookup(label_or_id)
-            .ok_or_else(|| Self::cant_resolve_term_error(bi_dict_lib.name(), label_or_id))
+        let (term, resource_ref) = bi_dict_lib.lookup(label_or_id).ok_or_else(|| {
+            Self::cant_resolve_term_error(
+                bi_dict_lib.name(),
+                label_or_id,
+                bi_dict_lib.suggest_labels(label_or_id),
+            )
+        })?;
+        debug!(
+            "Resolved {} term '{label_or_id}' to {} from {}",
+            bi_dict_lib.name(),
+            term.id,
+            resource_ref.prefix_id()
+        );
+        Ok((term, resource_ref))
     }
 
     fn cant_resolve_term_error(
         bi_dict_lib_name: &str,
         label_or_id: &str,
+        suggestions: Vec<String>,
     ) -> PhenopacketBuilderError {
-        PhenopacketBuilderError::ParsingError {
-            what: format!("{} Term", bi_dict_lib_name),
-            value: label_or_id.to_string(),
+        let what = format!("{} Term", bi_dict_lib_name);
+        let value = label_or_id.to_string();
+
+        match suggestions.is_empty() {
+            true => PhenopacketBuilderError::ParsingError { what, value },
+            false => PhenopacketBuilderError::UnknownTerm {
+                what,
+                value,
+                suggestions,
+            },
         }
     }
 }
@@ -896,9 +1798,17 @@ impl PhenopacketBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ontology::bioportal_client::BioPortalClient;
+    use crate::ontology::error::ClientError;
+    use crate::ontology::hgnc_alias_client::GeneSymbolAliases;
+    use crate::ontology::hgnc_batch_client::GeneBatchLookup;
     use crate::ontology::resource_references::ResourceRef;
     use crate::test_suite::cdf_generation::{default_patient_id, generate_patient_ids};
-    use crate::test_suite::component_building::build_test_phenopacket_builder;
+    use crate::test_suite::component_building::{
+        build_test_hpo_bidict_library, build_test_phenopacket_builder,
+    };
+    use crate::test_suite::ontology_mocking::MONDO_BIDICT;
+    use crate::test_suite::phenopacket_component_generation::default_meta_data;
     use crate::test_suite::phenopacket_component_generation::{
         default_age_element, default_anatomy_region, default_cohort_id, default_datetime,
         default_disease, default_disease_oc, default_drug_type, default_iso_age,
@@ -909,12 +1819,23 @@ mod tests {
         default_treatment_agent_oc, default_treatment_intent, default_treatment_response,
         default_treatment_termination_reason, default_unit_oc, generate_phenotype,
     };
-    use crate::test_suite::resource_references::mondo_meta_data_resource;
+    use crate::test_suite::resource_references::{
+        mondo_meta_data_resource, uberon_meta_data_resource,
+    };
     use crate::test_suite::utils::assert_phenopackets;
+    use crate::transform::clock::FixedClock;
+    use crate::transform::hgnc_gene_cache::HgncGeneCache;
+    use crate::transform::negative_lookup_cache::NegativeLookupCache;
+    use crate::transform::transform_context::SexVocabulary;
+    use chrono::{DateTime, Duration, Utc};
     use phenopackets::ga4gh::vrsatile::v1::Expression;
+    use phenopackets::schema::v2::core::time_element::Element;
     use phenopackets::schema::v2::core::{DrugType, Individual, MetaData, Resource};
+    use pivotal::hgnc::{HGNCData, MockHGNCClient};
+    use pivotal::hgvs::MockHGVSClient;
     use pretty_assertions::assert_eq;
     use rstest::*;
+    use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
 
     #[rstest]
     fn test_build() {
@@ -969,6 +1890,7 @@ mod tests {
                 Some(default_iso_age().as_str()),
                 None,
                 None,
+                None,
             )
             .unwrap();
 
@@ -1037,6 +1959,7 @@ mod tests {
                 Some(default_iso_age().as_str()),
                 None,
                 None,
+                None,
             )
             .unwrap();
 
@@ -1051,6 +1974,7 @@ mod tests {
                 Some(default_timestamp().to_string().as_str()),
                 None,
                 None,
+                None,
             )
             .unwrap();
 
@@ -1095,23 +2019,24 @@ mod tests {
             None,
             None,
             None,
+            None,
         );
 
         assert!(result.is_err());
     }
 
     #[rstest]
-    fn test_multiple_phenotypic_features_same_phenopacket() {
+    fn test_upsert_phenotypic_feature_severity() {
         let mut builder = build_test_phenopacket_builder();
-        let phenotype = default_phenotype_oc();
-        let pp_id = default_patient_id();
+        let patient_id = default_patient_id();
 
         builder
             .upsert_phenotypic_feature(
-                pp_id.as_str(),
-                &phenotype.id.to_string(),
+                &patient_id,
+                &default_phenotype_oc().id,
                 None,
                 None,
+                Some("Severe"),
                 None,
                 None,
                 None,
@@ -1120,38 +2045,258 @@ mod tests {
             )
             .unwrap();
 
+        let pp = builder.subject_to_phenopacket.values().next().unwrap();
+        assert_eq!(
+            pp.phenotypic_features[0].severity,
+            Some(OntologyClass {
+                id: "HP:0012828".to_string(),
+                label: "Severe".to_string(),
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_upsert_phenotypic_feature_merges_building_blocks() {
+        let mut builder = build_test_phenopacket_builder();
+        let patient_id = default_patient_id();
+        let phenotype = default_phenotype_oc();
+
+        // One block reports the onset, while another one excludes the phenotype and reports the severity.
         builder
             .upsert_phenotypic_feature(
-                pp_id.as_str(),
-                "HP:0000234",
+                &patient_id,
+                &phenotype.id,
+                None,
+                Some(false),
+                None,
+                None,
+                Some("P5Y"),
                 None,
                 None,
                 None,
+            )
+            .unwrap();
+        builder
+            .upsert_phenotypic_feature(
+                &patient_id,
+                &phenotype.id,
+                None,
+                Some(true),
+                Some("Severe"),
                 None,
+                Some("P7Y"),
                 None,
                 None,
                 None,
             )
             .unwrap();
 
-        let phenopacket = builder
-            .subject_to_phenopacket
-            .get(&default_phenopacket_id())
-            .unwrap();
-        assert_eq!(phenopacket.phenotypic_features.len(), 2);
+        let pp = builder.subject_to_phenopacket.values().next().unwrap();
+        assert_eq!(
+            pp.phenotypic_features,
+            vec![PhenotypicFeature {
+                r#type: Some(phenotype),
+                excluded: false,
+                severity: Some(OntologyClass {
+                    id: "HP:0012828".to_string(),
+                    label: "Severe".to_string(),
+                }),
+                onset: try_parse_time_element("P5Y"),
+                ..Default::default()
+            }]
+        );
+        assert_eq!(
+            builder.dropped_fields(),
+            &[DroppedField {
+                kind: DroppedFieldKind::ConflictingObservationStatus,
+                patient_id: patient_id.clone(),
+                value: format!("exclusion of {}", phenotype.id),
+            }]
+        );
     }
 
     #[rstest]
-    fn test_different_phenopacket_ids() {
+    #[case("P5Y", "2020-01-01")]
+    #[case("2020-01-01", "P5Y")]
+    fn test_upsert_phenotypic_feature_keeps_incomparable_onset(
+        #[case] first_onset: &str,
+        #[case] second_onset: &str,
+    ) {
         let mut builder = build_test_phenopacket_builder();
+        let patient_id = default_patient_id();
+        let phenotype = default_phenotype_oc();
 
-        let p_ids = generate_patient_ids(2);
-
-        for p_id in &p_ids {
+        for onset in [first_onset, second_onset] {
             builder
                 .upsert_phenotypic_feature(
-                    p_id,
-                    &default_phenotype_oc().id.to_string(),
+                    &patient_id,
+                    &phenotype.id,
+                    None,
+                    None,
+                    None,
+                    None,
+                    Some(onset),
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+
+        let pp = builder.subject_to_phenopacket.values().next().unwrap();
+        assert_eq!(
+            pp.phenotypic_features[0].onset,
+            try_parse_time_element(first_onset)
+        );
+        assert_eq!(builder.dropped_fields().len(), 1);
+        assert_eq!(
+            builder.dropped_fields()[0].kind,
+            DroppedFieldKind::IncomparableOnset
+        );
+    }
+
+    #[rstest]
+    #[case("no severity at all")]
+    #[case("Seizure")]
+    fn test_upsert_phenotypic_feature_invalid_severity(#[case] severity: &str) {
+        let mut builder = build_test_phenopacket_builder();
+
+        let result = builder.upsert_phenotypic_feature(
+            &default_patient_id(),
+            &default_phenotype_oc().id,
+            None,
+            None,
+            Some(severity),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    #[case("PMID:12345678", "PMID:12345678", "https://pubmed.ncbi.nlm.nih.gov/12345678")]
+    #[case("pmid 12345678", "PMID:12345678", "https://pubmed.ncbi.nlm.nih.gov/12345678")]
+    #[case("doi:10.1000/182", "DOI:10.1000/182", "https://doi.org/10.1000/182")]
+    #[case("ISBN:9780123456789", "ISBN:9780123456789", "")]
+    #[case("https://example.org/paper", "", "https://example.org/paper")]
+    fn test_parse_external_reference(
+        #[case] reference: &str,
+        #[case] expected_id: &str,
+        #[case] expected_reference: &str,
+    ) {
+        let external_reference = PhenopacketBuilder::parse_external_reference(reference).unwrap();
+
+        assert_eq!(external_reference.id, expected_id);
+        assert_eq!(external_reference.reference, expected_reference);
+    }
+
+    #[rstest]
+    #[case("12345678")]
+    #[case("PMID:12a45")]
+    #[case("PMID:")]
+    fn test_parse_external_reference_invalid(#[case] reference: &str) {
+        assert!(PhenopacketBuilder::parse_external_reference(reference).is_err());
+    }
+
+    #[rstest]
+    fn test_upsert_phenotypic_feature_reference_without_evidence() {
+        let mut builder = build_test_phenopacket_builder();
+
+        let result = builder.upsert_phenotypic_feature(
+            &default_patient_id(),
+            &default_phenotype_oc().id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some("PMID:12345678"),
+        );
+
+        assert!(matches!(
+            result,
+            Err(PhenopacketBuilderError::MissingPrerequisiteError { .. })
+        ));
+    }
+
+    #[rstest]
+    fn test_resolve_term_suggests_labels() {
+        let hpo_lib = Arc::new(build_test_hpo_bidict_library().with_label_suggestions(true));
+
+        let err = PhenopacketBuilder::resolve_term(&hpo_lib, "Seizur").unwrap_err();
+
+        match err {
+            PhenopacketBuilderError::UnknownTerm {
+                value, suggestions, ..
+            } => {
+                assert_eq!(value, "Seizur");
+                assert_eq!(suggestions[0], "Seizure");
+            }
+            other => panic!("Expected UnknownTerm error, got {other:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_multiple_phenotypic_features_same_phenopacket() {
+        let mut builder = build_test_phenopacket_builder();
+        let phenotype = default_phenotype_oc();
+        let pp_id = default_patient_id();
+
+        builder
+            .upsert_phenotypic_feature(
+                pp_id.as_str(),
+                &phenotype.id.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        builder
+            .upsert_phenotypic_feature(
+                pp_id.as_str(),
+                "HP:0000234",
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        let phenopacket = builder
+            .subject_to_phenopacket
+            .get(&default_phenopacket_id())
+            .unwrap();
+        assert_eq!(phenopacket.phenotypic_features.len(), 2);
+    }
+
+    #[rstest]
+    fn test_different_phenopacket_ids() {
+        let mut builder = build_test_phenopacket_builder();
+
+        let p_ids = generate_patient_ids(2);
+
+        for p_id in &p_ids {
+            builder
+                .upsert_phenotypic_feature(
+                    p_id,
+                    &default_phenotype_oc().id.to_string(),
+                    None,
                     None,
                     None,
                     None,
@@ -1207,6 +2352,7 @@ mod tests {
                 None,
                 None,
                 None,
+                None,
             )
             .unwrap();
 
@@ -1230,6 +2376,7 @@ mod tests {
                 Some(default_iso_age().as_str()),
                 None,
                 None,
+                None,
             )
             .unwrap();
 
@@ -1245,6 +2392,7 @@ mod tests {
                 Some(default_timestamp().to_string().as_str()),
                 None,
                 None,
+                None,
             )
             .unwrap();
 
@@ -1272,6 +2420,7 @@ mod tests {
                 &default_patient_id(),
                 &disease_id,
                 &PathogenicGeneVariantData::None,
+                &[],
                 Some("MALE"),
             )
             .unwrap();
@@ -1297,6 +2446,7 @@ mod tests {
                 &default_patient_id(),
                 &disease_id,
                 &homozygous_variant,
+                &[],
                 Some("FEMALE"),
             )
             .unwrap();
@@ -1342,145 +2492,542 @@ mod tests {
         }
     }
 
-    #[rstest]
-    fn test_upsert_interpretation_heterozygous_variant_pair() {
+    fn deterministic_run(created: chrono::DateTime<chrono::Utc>) -> Vec<Phenopacket> {
         let mut builder = build_test_phenopacket_builder();
-        let disease_id = default_disease_oc().id.clone();
+        builder
+            .ctx_mut()
+            .set_clock(Arc::new(crate::transform::clock::FixedClock::new(created)));
 
-        let compound_heterozygous_pair =
-            PathogenicGeneVariantData::CompoundHeterozygousVariantPair {
-                gene: Some("H19".to_string()),
-                var1: "NR_002196.1:n.601G>T".to_string(),
-                var2: "NR_002196.1:n.602C>T".to_string(),
-            };
+        for patient_id in ["P2", "P1", "P3"] {
+            builder
+                .upsert_interpretation(
+                    patient_id,
+                    &default_disease_oc().id,
+                    &PathogenicGeneVariantData::HomozygousVariant {
+                        gene: Some("KIF21A".to_string()),
+                        var: "NM_001173464.1:c.2860C>T".to_string(),
+                    },
+                    &[],
+                    Some("FEMALE"),
+                )
+                .unwrap();
+        }
+
+        builder.build()
+    }
 
+    #[rstest]
+    fn test_build_with_fixed_created() {
+        let created = chrono::DateTime::from_timestamp(1_234_567_890, 0).unwrap();
+        let mut builder = build_test_phenopacket_builder();
         builder
-            .upsert_interpretation(
+            .ctx_mut()
+            .set_clock(Arc::new(crate::transform::clock::FixedClock::new(created)));
+        builder
+            .upsert_individual(
                 &default_patient_id(),
-                &disease_id,
-                &compound_heterozygous_pair,
-                Some("FEMALE"),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
             )
             .unwrap();
 
-        let pp = builder.subject_to_phenopacket.values().next().unwrap();
+        let created_ts = builder.build()[0]
+            .meta_data
+            .as_ref()
+            .unwrap()
+            .created
+            .clone()
+            .unwrap();
 
-        assert_eq!(pp.interpretations.len(), 1);
+        assert_eq!(created_ts.seconds, 1_234_567_890);
+        assert_eq!(created_ts.nanos, 0);
+    }
 
-        let pp_interpretation = &pp.interpretations[0];
+    #[rstest]
+    fn test_build_omit_created() {
+        let mut builder = build_test_phenopacket_builder();
+        builder.ctx_mut().set_omit_created(true);
+        builder
+            .upsert_individual(
+                &default_patient_id(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
 
-        assert_eq!(
-            pp_interpretation
-                .clone()
-                .diagnosis
+        let phenopackets = builder.build();
+
+        assert!(
+            phenopackets[0]
+                .meta_data
+                .as_ref()
                 .unwrap()
-                .genomic_interpretations
-                .len(),
-            2
+                .created
+                .is_none()
         );
-
-        let pp_gis = &pp_interpretation
-            .clone()
-            .diagnosis
-            .unwrap()
-            .genomic_interpretations;
-
-        for pp_gi in pp_gis {
-            match pp_gi.clone().call.unwrap() {
-                Call::Gene(_) => {
-                    panic!("Call should be a VariantInterpretation!")
-                }
-                Call::VariantInterpretation(vi) => {
-                    let vd = vi.variation_descriptor.unwrap();
-                    assert_eq!(vd.allelic_state.unwrap().label, "heterozygous");
-                    assert_eq!(vd.gene_context.unwrap().symbol, "H19");
-                    let non_coding_expressions = vd
-                        .expressions
-                        .iter()
-                        .filter(|exp| exp.syntax == "hgvs.n")
-                        .collect::<Vec<&Expression>>();
-                    assert_eq!(non_coding_expressions.len(), 1);
-                }
-            }
-        }
     }
 
     #[rstest]
-    fn test_upsert_interpretation_autosomal_heterozygous_variant() {
+    fn test_build_phenopacket() {
         let mut builder = build_test_phenopacket_builder();
-        let disease_id = default_disease_oc().id.clone();
-
-        let heterozygous_variant = PathogenicGeneVariantData::SingleVariant {
-            gene: Some("KIF21A".to_string()),
-            var: "NM_001173464.1:c.2860C>T".to_string(),
-        };
-
+        builder.ctx_mut().set_omit_created(true);
         builder
-            .upsert_interpretation(
+            .upsert_individual(
                 &default_patient_id(),
-                &disease_id,
-                &heterozygous_variant,
+                None,
+                None,
+                None,
+                Some("FEMALE"),
+                None,
+                None,
                 None,
             )
             .unwrap();
+        let expected = builder.build();
 
-        let pp = builder.subject_to_phenopacket.values().next().unwrap();
+        let phenopacket = builder.build_phenopacket(&default_patient_id());
 
-        assert_eq!(pp.interpretations.len(), 1);
+        assert_eq!(phenopacket.as_ref(), expected.first());
+        assert!(builder.build().is_empty());
+        assert!(builder.build_phenopacket(&default_patient_id()).is_none());
+    }
 
-        let pp_interpretation = &pp.interpretations[0];
+    #[rstest]
+    fn test_build_deterministic() {
+        let created = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
 
-        assert_eq!(
-            pp_interpretation
-                .clone()
-                .diagnosis
-                .unwrap()
-                .genomic_interpretations
-                .len(),
-            1
-        );
+        let first_run = deterministic_run(created);
+        let second_run = deterministic_run(created);
 
-        let pp_gi = &pp_interpretation
-            .clone()
-            .diagnosis
-            .unwrap()
-            .genomic_interpretations[0];
+        assert_eq!(first_run, second_run);
 
-        match pp_gi.clone().call.unwrap() {
-            Call::Gene(_) => {
+        let ids: Vec<&str> = first_run.iter().map(|pp| pp.id.as_str()).collect();
+        let mut sorted_ids = ids.clone();
+        sorted_ids.sort();
+        assert_eq!(ids, sorted_ids);
+
+        for pp in &first_run {
+            let created_ts = pp.meta_data.as_ref().unwrap().created.as_ref().unwrap();
+            assert_eq!(created_ts.seconds, 1_700_000_000);
+
+            let gi = &pp.interpretations[0]
+                .diagnosis
+                .as_ref()
+                .unwrap()
+                .genomic_interpretations[0];
+            let Some(Call::VariantInterpretation(vi)) = &gi.call else {
                 panic!("Call should be a VariantInterpretation!")
-            }
-            Call::VariantInterpretation(vi) => {
-                let vd = vi.variation_descriptor.unwrap();
-                assert_eq!(vd.allelic_state.unwrap().label, "heterozygous");
-                assert_eq!(vd.gene_context.unwrap().symbol, "KIF21A");
-                let coding_expressions = vd
-                    .expressions
-                    .iter()
-                    .filter(|exp| exp.syntax == "hgvs.c")
-                    .collect::<Vec<&Expression>>();
-                assert_eq!(coding_expressions.len(), 1);
-                assert_eq!(coding_expressions[0].value, "NM_001173464.1:c.2860C>T");
+            };
+            assert!(
+                vi.variation_descriptor
+                    .as_ref()
+                    .unwrap()
+                    .id
+                    .starts_with("vd-")
+            );
+        }
+    }
+
+    #[rstest]
+    fn test_variation_descriptor_ids_are_stable() {
+        let variation_descriptor_id = |builder: &PhenopacketBuilder| {
+            let pp = &builder.build()[0];
+            let gi = &pp.interpretations[0]
+                .diagnosis
+                .as_ref()
+                .unwrap()
+                .genomic_interpretations[0];
+            let Some(Call::VariantInterpretation(vi)) = &gi.call else {
+                panic!("Call should be a VariantInterpretation!")
+            };
+            vi.variation_descriptor.as_ref().unwrap().id.clone()
+        };
+        let homozygous_variant = PathogenicGeneVariantData::HomozygousVariant {
+            gene: Some("KIF21A".to_string()),
+            var: "NM_001173464.1:c.2860C>T".to_string(),
+        };
+
+        let mut ids = vec![];
+        for _ in 0..2 {
+            let mut builder = build_test_phenopacket_builder();
+            builder
+                .upsert_interpretation(
+                    &default_patient_id(),
+                    &default_disease_oc().id,
+                    &homozygous_variant,
+                    &[],
+                    Some("FEMALE"),
+                )
+                .unwrap();
+            ids.push(variation_descriptor_id(&builder));
+        }
+
+        assert_eq!(ids[0], ids[1]);
+        assert!(ids[0].starts_with("vd-"));
+    }
+
+    #[rstest]
+    #[case("LIKELY_BENIGN", AcmgPathogenicityClassification::LikelyBenign)]
+    #[case("Likely pathogenic", AcmgPathogenicityClassification::LikelyPathogenic)]
+    #[case("vus", AcmgPathogenicityClassification::UncertainSignificance)]
+    fn test_upsert_interpretation_acmg_classification(
+        #[case] classification: &str,
+        #[case] expected: AcmgPathogenicityClassification,
+    ) {
+        let mut builder = build_test_phenopacket_builder();
+
+        builder
+            .upsert_interpretation(
+                &default_patient_id(),
+                &default_disease_oc().id,
+                &PathogenicGeneVariantData::SingleVariant {
+                    gene: Some("KIF21A".to_string()),
+                    var: "NM_001173464.1:c.2860C>T".to_string(),
+                },
+                &[VariantAnnotations {
+                    acmg_classification: Some(classification.to_string()),
+                    ..Default::default()
+                }],
+                Some("FEMALE"),
+            )
+            .unwrap();
+
+        let pp = builder.subject_to_phenopacket.values().next().unwrap();
+        let gi = &pp.interpretations[0]
+            .diagnosis
+            .as_ref()
+            .unwrap()
+            .genomic_interpretations[0];
+        let Some(Call::VariantInterpretation(vi)) = &gi.call else {
+            panic!("Call should be a VariantInterpretation!")
+        };
+        assert_eq!(vi.acmg_pathogenicity_classification, expected as i32);
+    }
+
+    #[rstest]
+    #[case(Some("ACTIONABLE"), TherapeuticActionability::Actionable)]
+    #[case(Some("not actionable"), TherapeuticActionability::NotActionable)]
+    #[case(None, TherapeuticActionability::UnknownActionability)]
+    fn test_upsert_interpretation_therapeutic_actionability(
+        #[case] actionability: Option<&str>,
+        #[case] expected: TherapeuticActionability,
+    ) {
+        let mut builder = build_test_phenopacket_builder();
+
+        builder
+            .upsert_interpretation(
+                &default_patient_id(),
+                &default_disease_oc().id,
+                &PathogenicGeneVariantData::SingleVariant {
+                    gene: Some("KIF21A".to_string()),
+                    var: "NM_001173464.1:c.2860C>T".to_string(),
+                },
+                &[VariantAnnotations {
+                    therapeutic_actionability: actionability.map(str::to_string),
+                    ..Default::default()
+                }],
+                Some("FEMALE"),
+            )
+            .unwrap();
+
+        let pp = builder.subject_to_phenopacket.values().next().unwrap();
+        let gi = &pp.interpretations[0]
+            .diagnosis
+            .as_ref()
+            .unwrap()
+            .genomic_interpretations[0];
+        let Some(Call::VariantInterpretation(vi)) = &gi.call else {
+            panic!("Call should be a VariantInterpretation!")
+        };
+        assert_eq!(vi.therapeutic_actionability, expected as i32);
+    }
+
+    #[rstest]
+    fn test_upsert_interpretation_therapeutic_actionability_invalid() {
+        let mut builder = build_test_phenopacket_builder();
+
+        let result = builder.upsert_interpretation(
+            &default_patient_id(),
+            &default_disease_oc().id,
+            &PathogenicGeneVariantData::SingleVariant {
+                gene: Some("KIF21A".to_string()),
+                var: "NM_001173464.1:c.2860C>T".to_string(),
+            },
+            &[VariantAnnotations {
+                therapeutic_actionability: Some("maybe".to_string()),
+                ..Default::default()
+            }],
+            Some("FEMALE"),
+        );
+
+        assert!(matches!(
+            result,
+            Err(PhenopacketBuilderError::ParsingError { what, .. })
+                if what == "therapeutic actionability"
+        ));
+    }
+
+    #[rstest]
+    fn test_upsert_interpretation_genomic_variant() {
+        let mut builder = build_test_phenopacket_builder();
+
+        builder
+            .upsert_interpretation(
+                &default_patient_id(),
+                &default_disease_oc().id,
+                &PathogenicGeneVariantData::SingleVariant {
+                    gene: Some("KIF21A".to_string()),
+                    var: "NC_000012.12:g.39332405G>A".to_string(),
+                },
+                &[],
+                Some("FEMALE"),
+            )
+            .unwrap();
+
+        let pp = builder.subject_to_phenopacket.values().next().unwrap();
+        let Some(Call::VariantInterpretation(vi)) = pp.interpretations[0]
+            .diagnosis
+            .as_ref()
+            .unwrap()
+            .genomic_interpretations[0]
+            .call
+            .clone()
+        else {
+            panic!("Call should be a VariantInterpretation!")
+        };
+        let vd = vi.variation_descriptor.unwrap();
+        assert!(
+            vd.expressions
+                .iter()
+                .any(|exp| exp.syntax == "hgvs.g" && exp.value == "NC_000012.12:g.39332405G>A")
+        );
+    }
+
+    #[rstest]
+    #[case("c.2860C>T")]
+    #[case("NP_001166935.1:p.Arg954Trp")]
+    #[case("NC_000012.12:c.2860C>T")]
+    #[case("NC_000012.12:NM_001173464.1:c.2860C>T")]
+    fn test_upsert_interpretation_invalid_hgvs(#[case] var: &str) {
+        let mut builder = build_test_phenopacket_builder();
+
+        let result = builder.upsert_interpretation(
+            &default_patient_id(),
+            &default_disease_oc().id,
+            &PathogenicGeneVariantData::SingleVariant {
+                gene: Some("KIF21A".to_string()),
+                var: var.to_string(),
+            },
+            &[],
+            Some("FEMALE"),
+        );
+
+        assert!(matches!(
+            result,
+            Err(PhenopacketBuilderError::InvalidHgvs { variant, .. }) if variant == var
+        ));
+    }
+
+    #[rstest]
+    #[case(Some(GenomeAssembly::Hg38), None, true)]
+    #[case(Some(GenomeAssembly::Hg19), Some("GRCh38"), true)]
+    #[case(None, None, true)]
+    #[case(Some(GenomeAssembly::Hg19), None, false)]
+    #[case(Some(GenomeAssembly::Hg38), Some("hg19"), false)]
+    #[case(None, Some("GRCh37"), false)]
+    fn test_upsert_interpretation_genome_assembly(
+        #[case] configured: Option<GenomeAssembly>,
+        #[case] declared_in_row: Option<&str>,
+        #[case] accepted: bool,
+    ) {
+        let mut builder = build_test_phenopacket_builder();
+        if let Some(configured) = configured {
+            builder.ctx_mut().set_genome_assembly(configured);
+        }
+
+        let result = builder.upsert_interpretation(
+            &default_patient_id(),
+            &default_disease_oc().id,
+            &PathogenicGeneVariantData::SingleVariant {
+                gene: Some("KIF21A".to_string()),
+                var: "NM_001173464.1:c.2860C>T".to_string(),
+            },
+            &[VariantAnnotations {
+                genome_assembly: declared_in_row.map(str::to_string),
+                ..Default::default()
+            }],
+            Some("FEMALE"),
+        );
+
+        if accepted {
+            result.unwrap();
+        } else {
+            assert!(matches!(
+                result,
+                Err(PhenopacketBuilderError::GenomeAssemblyMismatch { reported, .. }) if reported == "hg38"
+            ));
+        }
+    }
+
+    #[rstest]
+    fn test_upsert_interpretation_acmg_classification_invalid() {
+        let mut builder = build_test_phenopacket_builder();
+
+        let result = builder.upsert_interpretation(
+            &default_patient_id(),
+            &default_disease_oc().id,
+            &PathogenicGeneVariantData::SingleVariant {
+                gene: Some("KIF21A".to_string()),
+                var: "NM_001173464.1:c.2860C>T".to_string(),
+            },
+            &[VariantAnnotations {
+                acmg_classification: Some("probably fine".to_string()),
+                ..Default::default()
+            }],
+            Some("FEMALE"),
+        );
+
+        assert!(matches!(
+            result,
+            Err(PhenopacketBuilderError::ParsingError { what, .. })
+                if what == "ACMG pathogenicity classification"
+        ));
+    }
+
+    #[rstest]
+    fn test_upsert_interpretation_heterozygous_variant_pair_with_origins() {
+        let mut builder = build_test_phenopacket_builder();
+
+        let compound_heterozygous_pair =
+            PathogenicGeneVariantData::CompoundHeterozygousVariantPair {
+                gene: Some("H19".to_string()),
+                var1: "NR_002196.1:n.601G>T".to_string(),
+                var2: "NR_002196.1:n.602C>T".to_string(),
+                origins: [Some(VariantOrigin::Maternal), Some(VariantOrigin::Paternal)],
+            };
+
+        builder
+            .upsert_interpretation(
+                &default_patient_id(),
+                &default_disease_oc().id,
+                &compound_heterozygous_pair,
+                &[],
+                Some("FEMALE"),
+            )
+            .unwrap();
+
+        let pp = builder.subject_to_phenopacket.values().next().unwrap();
+        let origins: Vec<String> = pp.interpretations[0]
+            .diagnosis
+            .as_ref()
+            .unwrap()
+            .genomic_interpretations
+            .iter()
+            .map(|gi| {
+                let Some(Call::VariantInterpretation(vi)) = &gi.call else {
+                    panic!("Call should be a VariantInterpretation!")
+                };
+                let extension = &vi.variation_descriptor.as_ref().unwrap().extensions[0];
+                assert_eq!(extension.name, "allele_origin");
+                String::decode(extension.value[0].value.as_slice()).unwrap()
+            })
+            .collect();
+
+        assert_eq!(origins, vec!["maternal", "paternal"]);
+    }
+
+    #[rstest]
+    fn test_upsert_interpretation_heterozygous_variant_pair() {
+        let mut builder = build_test_phenopacket_builder();
+        let disease_id = default_disease_oc().id.clone();
+
+        let compound_heterozygous_pair =
+            PathogenicGeneVariantData::CompoundHeterozygousVariantPair {
+                gene: Some("H19".to_string()),
+                var1: "NR_002196.1:n.601G>T".to_string(),
+                var2: "NR_002196.1:n.602C>T".to_string(),
+                origins: [None, None],
+            };
+
+        builder
+            .upsert_interpretation(
+                &default_patient_id(),
+                &disease_id,
+                &compound_heterozygous_pair,
+                &[],
+                Some("FEMALE"),
+            )
+            .unwrap();
+
+        let pp = builder.subject_to_phenopacket.values().next().unwrap();
+
+        assert_eq!(pp.interpretations.len(), 1);
+
+        let pp_interpretation = &pp.interpretations[0];
+
+        assert_eq!(
+            pp_interpretation
+                .clone()
+                .diagnosis
+                .unwrap()
+                .genomic_interpretations
+                .len(),
+            2
+        );
+
+        let pp_gis = &pp_interpretation
+            .clone()
+            .diagnosis
+            .unwrap()
+            .genomic_interpretations;
+
+        for pp_gi in pp_gis {
+            match pp_gi.clone().call.unwrap() {
+                Call::Gene(_) => {
+                    panic!("Call should be a VariantInterpretation!")
+                }
+                Call::VariantInterpretation(vi) => {
+                    let vd = vi.variation_descriptor.unwrap();
+                    assert_eq!(vd.allelic_state.unwrap().label, "heterozygous");
+                    assert_eq!(vd.gene_context.unwrap().symbol, "H19");
+                    let non_coding_expressions = vd
+                        .expressions
+                        .iter()
+                        .filter(|exp| exp.syntax == "hgvs.n")
+                        .collect::<Vec<&Expression>>();
+                    assert_eq!(non_coding_expressions.len(), 1);
+                }
             }
         }
     }
 
     #[rstest]
-    fn test_upsert_interpretation_hemizygous_x_variant() {
+    fn test_upsert_interpretation_autosomal_heterozygous_variant() {
         let mut builder = build_test_phenopacket_builder();
         let disease_id = default_disease_oc().id.clone();
 
-        let single_variant = PathogenicGeneVariantData::SingleVariant {
-            gene: None,
-            var: "NM_000132.4:c.3637A>T".to_string(),
+        let heterozygous_variant = PathogenicGeneVariantData::SingleVariant {
+            gene: Some("KIF21A".to_string()),
+            var: "NM_001173464.1:c.2860C>T".to_string(),
         };
 
         builder
             .upsert_interpretation(
                 &default_patient_id(),
                 &disease_id,
-                &single_variant,
-                Some("MALE"),
+                &heterozygous_variant,
+                &[],
+                None,
             )
             .unwrap();
 
@@ -1512,21 +3059,21 @@ mod tests {
             }
             Call::VariantInterpretation(vi) => {
                 let vd = vi.variation_descriptor.unwrap();
-                assert_eq!(vd.allelic_state.unwrap().label, "hemizygous");
-                assert_eq!(vd.gene_context.unwrap().symbol, "F8");
+                assert_eq!(vd.allelic_state.unwrap().label, "heterozygous");
+                assert_eq!(vd.gene_context.unwrap().symbol, "KIF21A");
                 let coding_expressions = vd
                     .expressions
                     .iter()
                     .filter(|exp| exp.syntax == "hgvs.c")
                     .collect::<Vec<&Expression>>();
                 assert_eq!(coding_expressions.len(), 1);
-                assert_eq!(coding_expressions[0].value, "NM_000132.4:c.3637A>T");
+                assert_eq!(coding_expressions[0].value, "NM_001173464.1:c.2860C>T");
             }
         }
     }
 
     #[rstest]
-    fn test_upsert_interpretation_heterozygous_x_variant() {
+    fn test_upsert_interpretation_hemizygous_x_variant() {
         let mut builder = build_test_phenopacket_builder();
         let disease_id = default_disease_oc().id.clone();
 
@@ -1540,7 +3087,8 @@ mod tests {
                 &default_patient_id(),
                 &disease_id,
                 &single_variant,
-                Some("FEMALE"),
+                &[],
+                Some("MALE"),
             )
             .unwrap();
 
@@ -1572,7 +3120,7 @@ mod tests {
             }
             Call::VariantInterpretation(vi) => {
                 let vd = vi.variation_descriptor.unwrap();
-                assert_eq!(vd.allelic_state.unwrap().label, "heterozygous");
+                assert_eq!(vd.allelic_state.unwrap().label, "hemizygous");
                 assert_eq!(vd.gene_context.unwrap().symbol, "F8");
                 let coding_expressions = vd
                     .expressions
@@ -1586,28 +3134,90 @@ mod tests {
     }
 
     #[rstest]
-    fn test_upsert_interpretation_update(basic_pp_with_disease_info: Phenopacket) {
+    fn test_upsert_interpretation_heterozygous_x_variant() {
         let mut builder = build_test_phenopacket_builder();
-        let patient_id = default_patient_id();
-
-        let existing_pp = basic_pp_with_disease_info;
-        builder
-            .subject_to_phenopacket
-            .insert(default_phenopacket_id(), existing_pp.clone());
+        let disease_id = default_disease_oc().id.clone();
 
-        let heterozygous_variant = PathogenicGeneVariantData::SingleVariant {
-            gene: Some("KIF21A".to_string()),
-            var: "NM_001173464.1:c.2860C>T".to_string(),
+        let single_variant = PathogenicGeneVariantData::SingleVariant {
+            gene: None,
+            var: "NM_000132.4:c.3637A>T".to_string(),
         };
 
         builder
             .upsert_interpretation(
-                &patient_id,
-                &default_disease_oc().label,
-                &heterozygous_variant,
-                None,
-            )
-            .unwrap();
+                &default_patient_id(),
+                &disease_id,
+                &single_variant,
+                &[],
+                Some("FEMALE"),
+            )
+            .unwrap();
+
+        let pp = builder.subject_to_phenopacket.values().next().unwrap();
+
+        assert_eq!(pp.interpretations.len(), 1);
+
+        let pp_interpretation = &pp.interpretations[0];
+
+        assert_eq!(
+            pp_interpretation
+                .clone()
+                .diagnosis
+                .unwrap()
+                .genomic_interpretations
+                .len(),
+            1
+        );
+
+        let pp_gi = &pp_interpretation
+            .clone()
+            .diagnosis
+            .unwrap()
+            .genomic_interpretations[0];
+
+        match pp_gi.clone().call.unwrap() {
+            Call::Gene(_) => {
+                panic!("Call should be a VariantInterpretation!")
+            }
+            Call::VariantInterpretation(vi) => {
+                let vd = vi.variation_descriptor.unwrap();
+                assert_eq!(vd.allelic_state.unwrap().label, "heterozygous");
+                assert_eq!(vd.gene_context.unwrap().symbol, "F8");
+                let coding_expressions = vd
+                    .expressions
+                    .iter()
+                    .filter(|exp| exp.syntax == "hgvs.c")
+                    .collect::<Vec<&Expression>>();
+                assert_eq!(coding_expressions.len(), 1);
+                assert_eq!(coding_expressions[0].value, "NM_000132.4:c.3637A>T");
+            }
+        }
+    }
+
+    #[rstest]
+    fn test_upsert_interpretation_update(basic_pp_with_disease_info: Phenopacket) {
+        let mut builder = build_test_phenopacket_builder();
+        let patient_id = default_patient_id();
+
+        let existing_pp = basic_pp_with_disease_info;
+        builder
+            .subject_to_phenopacket
+            .insert(default_phenopacket_id(), existing_pp.clone());
+
+        let heterozygous_variant = PathogenicGeneVariantData::SingleVariant {
+            gene: Some("KIF21A".to_string()),
+            var: "NM_001173464.1:c.2860C>T".to_string(),
+        };
+
+        builder
+            .upsert_interpretation(
+                &patient_id,
+                &default_disease_oc().label,
+                &heterozygous_variant,
+                &[],
+                None,
+            )
+            .unwrap();
 
         let pp = builder.subject_to_phenopacket.values().next().unwrap();
 
@@ -1644,6 +3254,7 @@ mod tests {
                 &patient_id,
                 &default_disease_oc().label,
                 &heterozygous_variant,
+                &[],
                 None,
             )
             .unwrap();
@@ -1658,6 +3269,7 @@ mod tests {
                 &patient_id,
                 &default_disease_oc().label,
                 &homozygous_variant,
+                &[],
                 None,
             )
             .unwrap();
@@ -1685,7 +3297,7 @@ mod tests {
         let gene_data = PathogenicGeneVariantData::CausativeGene("CLOCK".to_string());
 
         builder
-            .upsert_interpretation(&default_patient_id(), &disease_id, &gene_data, None)
+            .upsert_interpretation(&default_patient_id(), &disease_id, &gene_data, &[], None)
             .unwrap();
 
         let pp = builder.subject_to_phenopacket.values().next().unwrap();
@@ -1699,148 +3311,1270 @@ mod tests {
                 .clone()
                 .diagnosis
                 .unwrap()
-                .genomic_interpretations
-                .len(),
-            1
+                .genomic_interpretations
+                .len(),
+            1
+        );
+
+        let pp_gi = &pp_interpretation
+            .clone()
+            .diagnosis
+            .unwrap()
+            .genomic_interpretations[0];
+
+        match pp_gi.clone().call.unwrap() {
+            Call::Gene(gd) => {
+                assert_eq!(gd.symbol.clone(), "CLOCK");
+            }
+            Call::VariantInterpretation(_) => {
+                panic!("Call should be a GeneDescriptor!")
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct StaticGeneSymbolAliases(HashMap<String, String>);
+
+    impl GeneSymbolAliases for StaticGeneSymbolAliases {
+        fn current_symbol(&self, symbol: &str) -> Result<Option<String>, ClientError> {
+            Ok(self.0.get(symbol).cloned())
+        }
+    }
+
+    #[rstest]
+    fn test_resolve_gene_previous_symbol() {
+        let mut builder = build_test_phenopacket_builder();
+        builder
+            .ctx_mut()
+            .set_gene_symbol_aliases(Arc::new(StaticGeneSymbolAliases(HashMap::from([(
+                "KIAA0573".to_string(),
+                "CLOCK".to_string(),
+            )]))));
+
+        let (_, current_id) = builder.resolve_gene("CLOCK").unwrap();
+        let (symbol, id) = builder.resolve_gene("KIAA0573").unwrap();
+
+        assert_eq!(symbol, "CLOCK");
+        assert_eq!(id, current_id);
+        assert_eq!(
+            builder.remapped_gene_symbols(),
+            vec![("KIAA0573".to_string(), "CLOCK".to_string())]
+        );
+    }
+
+    #[rstest]
+    fn test_resolve_gene_previous_symbol_strict() {
+        let builder = build_test_phenopacket_builder();
+
+        assert!(builder.resolve_gene("KIAA0573").is_err());
+        assert!(builder.remapped_gene_symbols().is_empty());
+    }
+
+    #[rstest]
+    fn test_resolve_gene_negative_cache() {
+        let mut builder = build_test_phenopacket_builder();
+        builder
+            .ctx_mut()
+            .set_negative_lookup_cache(Arc::new(NegativeLookupCache::new(Duration::hours(1))));
+
+        let first = builder.resolve_gene("NOT_A_GENE");
+        let second = builder.resolve_gene("NOT_A_GENE");
+
+        assert!(matches!(first, Err(PhenopacketBuilderError::HgncError(_))));
+        assert!(matches!(
+            second,
+            Err(PhenopacketBuilderError::RecentlyFailedLookup { query, .. }) if query == "hgnc:NOT_A_GENE"
+        ));
+    }
+
+    #[rstest]
+    fn test_resolve_genes() {
+        let builder = build_test_phenopacket_builder();
+        let cached_pair = ("FAKE".to_string(), "HGNC:0".to_string());
+        builder
+            .ctx
+            .hgnc_gene_cache()
+            .insert("FAKE", cached_pair.clone(), Utc::now());
+
+        let unresolved = builder.resolve_genes(["CLOCK", "CLOCK", "FAKE", "NOT_A_GENE"]);
+
+        assert_eq!(unresolved, vec!["NOT_A_GENE".to_string()]);
+        let (symbol, _) = builder
+            .ctx
+            .hgnc_gene_cache()
+            .get("CLOCK", Utc::now())
+            .unwrap();
+        assert_eq!(symbol, "CLOCK");
+        assert_eq!(
+            builder.ctx.hgnc_gene_cache().get("FAKE", Utc::now()),
+            Some(cached_pair)
+        );
+    }
+
+    /// Looks up the genes one by one with the mocked HGNC client and counts the batch requests.
+    #[derive(Debug, Default)]
+    struct CountingGeneBatchLookup {
+        inner: MockHGNCClient,
+        n_requests: AtomicUsize,
+    }
+
+    impl GeneBatchLookup for CountingGeneBatchLookup {
+        fn request_gene_identifier_pairs(
+            &self,
+            genes: &[&str],
+        ) -> Result<HashMap<String, (String, String)>, ClientError> {
+            self.n_requests.fetch_add(1, AtomicOrdering::SeqCst);
+            Ok(genes
+                .iter()
+                .filter_map(|gene| {
+                    let query = GeneQuery::from(*gene);
+                    let pair = self.inner.request_gene_identifier_pair(query).ok()?;
+                    Some((gene.to_string(), pair))
+                })
+                .collect())
+        }
+    }
+
+    #[rstest]
+    fn test_resolve_genes_in_batch() {
+        let mut builder = build_test_phenopacket_builder();
+        let gene_batch_lookup = Arc::new(CountingGeneBatchLookup::default());
+        builder
+            .ctx_mut()
+            .set_gene_batch_lookup(gene_batch_lookup.clone());
+        builder
+            .ctx_mut()
+            .set_negative_lookup_cache(Arc::new(NegativeLookupCache::new(Duration::hours(1))));
+
+        let unresolved = builder.resolve_genes(["CLOCK", "SHH", "CLOCK", "NOT_A_GENE"]);
+
+        assert_eq!(unresolved, vec!["NOT_A_GENE".to_string()]);
+        assert_eq!(gene_batch_lookup.n_requests.load(AtomicOrdering::SeqCst), 1);
+        let (symbol, _) = builder
+            .ctx
+            .hgnc_gene_cache()
+            .get("SHH", Utc::now())
+            .unwrap();
+        assert_eq!(symbol, "SHH");
+        assert!(matches!(
+            builder.resolve_gene("NOT_A_GENE"),
+            Err(PhenopacketBuilderError::RecentlyFailedLookup { query, .. }) if query == "hgnc:NOT_A_GENE"
+        ));
+    }
+
+    #[rstest]
+    fn test_resolve_genes_in_batch_same_phenopackets() {
+        let disease_id = default_disease_oc().id.clone();
+        let gene_data = PathogenicGeneVariantData::CausativeGene("CLOCK".to_string());
+        let mut batch_builder = build_test_phenopacket_builder();
+        batch_builder
+            .ctx_mut()
+            .set_gene_batch_lookup(Arc::new(CountingGeneBatchLookup::default()));
+        let mut single_builder = build_test_phenopacket_builder();
+
+        for builder in [&mut batch_builder, &mut single_builder] {
+            let created = DateTime::from_timestamp(1_735_689_600, 0).unwrap();
+            builder
+                .ctx_mut()
+                .set_clock(Arc::new(FixedClock::new(created)));
+            assert!(builder.resolve_genes(["CLOCK"]).is_empty());
+            builder
+                .upsert_interpretation(&default_patient_id(), &disease_id, &gene_data, &[], None)
+                .unwrap();
+        }
+
+        assert_eq!(batch_builder.build(), single_builder.build());
+    }
+
+    #[rstest]
+    fn test_resolve_gene_expired_cache_entry() {
+        let mut builder = build_test_phenopacket_builder();
+        builder
+            .ctx_mut()
+            .set_hgnc_gene_cache(HgncGeneCache::new(Some(Duration::hours(1))));
+        let stale_pair = ("OLD_SYMBOL".to_string(), "HGNC:0".to_string());
+        builder
+            .ctx
+            .hgnc_gene_cache()
+            .insert("CLOCK", stale_pair, Utc::now() - Duration::hours(2));
+
+        let (symbol, id) = builder.resolve_gene("CLOCK").unwrap();
+
+        assert_eq!(symbol, "CLOCK");
+        assert_ne!(id, "HGNC:0");
+        assert_eq!(
+            builder.ctx.hgnc_gene_cache().get("CLOCK", Utc::now()),
+            Some((symbol, id))
+        );
+    }
+
+    #[rstest]
+    fn test_omim_disease_and_interpretation() {
+        let mut server = mockito::Server::new();
+        let _class_mock = server
+            .mock("GET", mockito::Matcher::Regex("OMIM%2F135700$".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                  "prefLabel": "FIBROSIS OF EXTRAOCULAR MUSCLES, CONGENITAL, 1",
+                  "@id": "http://purl.bioontology.org/ontology/OMIM/135700"
+                }"#,
+            )
+            .create();
+        let omim_bidict = BioPortalClient::new("TEST_KEY", "OMIM", None)
+            .unwrap()
+            .with_base_url(server.url());
+
+        let mut ctx_builder = TransformContext::builder(
+            default_meta_data().into(),
+            Arc::new(MockHGNCClient::default()),
+            Arc::new(MockHGVSClient::default()),
+        );
+        ctx_builder.add_disease_bidict(Box::new(omim_bidict));
+        ctx_builder.add_disease_bidict(Box::new(MONDO_BIDICT.clone()));
+        let mut builder = PhenopacketBuilder::new(ctx_builder.build());
+        let patient_id = default_patient_id();
+
+        builder
+            .insert_disease(
+                &patient_id,
+                "OMIM:135700",
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        builder
+            .upsert_interpretation(
+                &patient_id,
+                "OMIM:135700",
+                &PathogenicGeneVariantData::CausativeGene("KIF21A".to_string()),
+                &[],
+                None,
+            )
+            .unwrap();
+
+        let pp = builder.build().pop().unwrap();
+        let expected_term = OntologyClass {
+            id: "OMIM:135700".to_string(),
+            label: "FIBROSIS OF EXTRAOCULAR MUSCLES, CONGENITAL, 1".to_string(),
+        };
+        assert_eq!(pp.diseases[0].term, Some(expected_term.clone()));
+        assert_eq!(
+            pp.interpretations[0].diagnosis.as_ref().unwrap().disease,
+            Some(expected_term)
+        );
+        let omim_resources: Vec<&Resource> = pp
+            .meta_data
+            .as_ref()
+            .unwrap()
+            .resources
+            .iter()
+            .filter(|resource| resource.id == "omim")
+            .collect();
+        assert_eq!(omim_resources.len(), 1);
+        assert_eq!(omim_resources[0].namespace_prefix, "omim");
+    }
+
+    #[rstest]
+    fn test_insert_disease() {
+        let mut builder = build_test_phenopacket_builder();
+
+        let patient_id = default_patient_id();
+        let disease = default_disease_oc();
+        let onset_age = default_iso_age();
+        let resolution_ts = default_timestamp();
+
+        builder
+            .insert_disease(
+                &patient_id,
+                &disease.id,
+                None,
+                Some(&onset_age),
+                Some(resolution_ts.to_string().as_str()),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        let expected_pp = &mut Phenopacket {
+            id: default_phenopacket_id(),
+            diseases: vec![Disease {
+                term: Some(disease),
+                onset: Some(default_age_element()),
+                resolution: Some(default_timestamp_element()),
+                ..Default::default()
+            }],
+            meta_data: Some(MetaData {
+                resources: vec![mondo_meta_data_resource()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let built_pp = builder.subject_to_phenopacket.values().next().unwrap();
+
+        assert_phenopackets(expected_pp, &mut built_pp.clone());
+    }
+
+    #[rstest]
+    #[case("head")]
+    #[case("UBERON:0000033")]
+    fn test_insert_disease_primary_site(#[case] primary_site: &str) {
+        let mut builder = build_test_phenopacket_builder();
+
+        builder
+            .insert_disease(
+                &default_patient_id(),
+                &default_disease_oc().id,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(primary_site),
+                None,
+            )
+            .unwrap();
+
+        let built_pp = builder.subject_to_phenopacket.values().next().unwrap();
+        assert_eq!(
+            built_pp.diseases[0].primary_site,
+            Some(OntologyClass {
+                id: "UBERON:0000033".to_string(),
+                label: "head".to_string(),
+            })
+        );
+        assert!(
+            built_pp
+                .meta_data
+                .as_ref()
+                .unwrap()
+                .resources
+                .iter()
+                .any(|resource| resource.id == "uberon")
+        );
+    }
+
+    #[rstest]
+    fn test_insert_disease_unresolvable_primary_site() {
+        let mut builder = build_test_phenopacket_builder();
+
+        let result = builder.insert_disease(
+            &default_patient_id(),
+            &default_disease_oc().id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some("left big toe nail"),
+            None,
+        );
+
+        assert!(matches!(
+            result,
+            Err(PhenopacketBuilderError::ParsingError { value, .. }) if value == "left big toe nail"
+        ));
+        assert!(builder.subject_to_phenopacket.is_empty());
+    }
+
+    #[rstest]
+    #[case("Left", "HP:0012835", "Left")]
+    #[case("HP:0012834", "HP:0012834", "Right")]
+    fn test_insert_disease_laterality(
+        #[case] laterality: &str,
+        #[case] expected_id: &str,
+        #[case] expected_label: &str,
+    ) {
+        let mut builder = build_test_phenopacket_builder();
+
+        builder
+            .insert_disease(
+                &default_patient_id(),
+                &default_disease_oc().id,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(laterality),
+            )
+            .unwrap();
+
+        let built_pp = builder.subject_to_phenopacket.values().next().unwrap();
+        assert_eq!(
+            built_pp.diseases[0].laterality,
+            Some(OntologyClass {
+                id: expected_id.to_string(),
+                label: expected_label.to_string(),
+            })
+        );
+        assert!(
+            built_pp
+                .meta_data
+                .as_ref()
+                .unwrap()
+                .resources
+                .iter()
+                .any(|resource| resource.id == "hp")
+        );
+    }
+
+    #[rstest]
+    fn test_insert_disease_unresolvable_laterality() {
+        let mut builder = build_test_phenopacket_builder();
+
+        let result = builder.insert_disease(
+            &default_patient_id(),
+            &default_disease_oc().id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some("sideways"),
+        );
+
+        assert!(matches!(
+            result,
+            Err(PhenopacketBuilderError::ParsingError { value, .. }) if value == "sideways"
+        ));
+        assert!(builder.subject_to_phenopacket.is_empty());
+    }
+
+    #[rstest]
+    fn test_insert_disease_stage() {
+        let mut builder = build_test_phenopacket_builder();
+
+        builder
+            .insert_disease(
+                &default_patient_id(),
+                &default_disease_oc().id,
+                None,
+                None,
+                None,
+                Some(&["Stage III", "NCIT:C27971"]),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        let built_pp = builder.subject_to_phenopacket.values().next().unwrap();
+        assert_eq!(
+            built_pp.diseases[0].disease_stage,
+            vec![
+                OntologyClass {
+                    id: "NCIT:C27970".to_string(),
+                    label: "Stage III".to_string(),
+                },
+                OntologyClass {
+                    id: "NCIT:C27971".to_string(),
+                    label: "Stage IV".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_insert_disease_clinical_tnm_finding() {
+        let mut builder = build_test_phenopacket_builder();
+
+        builder
+            .insert_disease(
+                &default_patient_id(),
+                &default_disease_oc().id,
+                None,
+                None,
+                None,
+                None,
+                Some(&["NCIT:C48724", "N1 Stage Finding", "M0 Stage Finding"]),
+                None,
+                None,
+            )
+            .unwrap();
+
+        let built_pp = builder.subject_to_phenopacket.values().next().unwrap();
+        assert_eq!(
+            built_pp.diseases[0].clinical_tnm_finding,
+            vec![
+                OntologyClass {
+                    id: "NCIT:C48724".to_string(),
+                    label: "T2 Stage Finding".to_string(),
+                },
+                OntologyClass {
+                    id: "NCIT:C48706".to_string(),
+                    label: "N1 Stage Finding".to_string(),
+                },
+                OntologyClass {
+                    id: "NCIT:C48699".to_string(),
+                    label: "M0 Stage Finding".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            built_pp
+                .meta_data
+                .as_ref()
+                .unwrap()
+                .resources
+                .iter()
+                .filter(|resource| resource.id == "ncit")
+                .count(),
+            1
+        );
+    }
+
+    #[rstest]
+    fn test_insert_disease_unresolvable_clinical_tnm_finding() {
+        let mut builder = build_test_phenopacket_builder();
+
+        let result = builder.insert_disease(
+            &default_patient_id(),
+            &default_disease_oc().id,
+            None,
+            None,
+            None,
+            None,
+            Some(&["NCIT:C48724", "T9"]),
+            None,
+            None,
+        );
+
+        assert!(matches!(
+            result,
+            Err(PhenopacketBuilderError::ParsingError { value, .. }) if value == "T9"
+        ));
+    }
+
+    #[rstest]
+    #[case(false, 1)]
+    #[case(true, 2)]
+    fn test_insert_same_disease_twice(
+        #[case] keep_duplicate_diseases: bool,
+        #[case] expected_n_diseases: usize,
+    ) {
+        let mut builder = build_test_phenopacket_builder();
+        builder
+            .ctx_mut()
+            .set_keep_duplicate_diseases(keep_duplicate_diseases);
+
+        let patient_id = default_patient_id();
+        let disease = default_disease_oc();
+
+        for _ in 0..2 {
+            builder
+                .insert_disease(
+                    &patient_id,
+                    &disease.id,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+
+        let expected_pp = &mut Phenopacket {
+            id: default_phenopacket_id().to_string(),
+            diseases: vec![default_disease(); expected_n_diseases],
+            meta_data: Some(MetaData {
+                resources: vec![mondo_meta_data_resource()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let built_pp = builder.subject_to_phenopacket.values().next().unwrap();
+
+        assert_phenopackets(expected_pp, &mut built_pp.clone());
+    }
+
+    #[rstest]
+    fn test_insert_duplicate_disease_rows_merge_onset() {
+        let mut builder = build_test_phenopacket_builder();
+        let patient_id = default_patient_id();
+        let onset_age = default_iso_age();
+
+        for onset in [None, Some(onset_age.as_str())] {
+            builder
+                .insert_disease(
+                    &patient_id,
+                    "platelet signal processing defect",
+                    None,
+                    onset,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+
+        let pp = builder.subject_to_phenopacket.values().next().unwrap();
+        assert_eq!(
+            pp.diseases,
+            vec![Disease {
+                term: Some(OntologyClass {
+                    id: "MONDO:0008258".to_string(),
+                    label: "platelet signal processing defect".to_string(),
+                }),
+                onset: Some(default_age_element()),
+                ..Default::default()
+            }]
+        );
+    }
+
+    #[rstest]
+    fn test_insert_disease_with_hpo_onset_term() {
+        let mut builder = build_test_phenopacket_builder();
+        let patient_id = default_patient_id();
+
+        builder
+            .insert_disease(
+                &patient_id,
+                &default_disease_oc().id,
+                None,
+                Some("congenital onset"),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        let pp = builder.subject_to_phenopacket.values().next().unwrap();
+        assert_eq!(
+            pp.diseases[0].onset,
+            Some(TimeElement {
+                element: Some(Element::OntologyClass(OntologyClass {
+                    id: "HP:0003577".to_string(),
+                    label: "Congenital onset".to_string(),
+                })),
+            })
+        );
+        assert!(
+            pp.meta_data
+                .as_ref()
+                .unwrap()
+                .resources
+                .iter()
+                .any(|resource| resource.id == "hp")
+        );
+    }
+
+    #[rstest]
+    fn test_insert_disease_rejects_hpo_onset_term_as_resolution() {
+        let mut builder = build_test_phenopacket_builder();
+
+        let result = builder.insert_disease(
+            &default_patient_id(),
+            &default_disease_oc().id,
+            None,
+            None,
+            Some("HP:0003577"),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        assert!(matches!(
+            result,
+            Err(PhenopacketBuilderError::ParsingError { what, .. }) if what == "TimeElement"
+        ));
+    }
+
+    #[rstest]
+    fn test_insert_contradicting_diseases_are_kept() {
+        let mut builder = build_test_phenopacket_builder();
+        let patient_id = default_patient_id();
+
+        for excluded in [Some(false), Some(true)] {
+            builder
+                .insert_disease(
+                    &patient_id,
+                    &default_disease_oc().id,
+                    excluded,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+
+        let pp = builder.subject_to_phenopacket.values().next().unwrap();
+        assert_eq!(pp.diseases.len(), 2);
+    }
+
+    #[rstest]
+    fn test_upsert_individual() {
+        let mut builder = build_test_phenopacket_builder();
+
+        let phenopacket_id = default_phenopacket_id();
+        let individual_id = default_patient_id();
+
+        builder
+            .upsert_individual(&individual_id, None, None, None, None, None, None, None)
+            .unwrap();
+
+        let phenopacket = builder.subject_to_phenopacket.get(&phenopacket_id).unwrap();
+        let individual = phenopacket.subject.as_ref().unwrap();
+        assert_eq!(individual.id, individual_id.clone());
+        assert_eq!(individual.sex, 0);
+        assert_eq!(individual.vital_status, None);
+
+        // Test upserting the other entries
+        builder
+            .upsert_individual(
+                &individual_id,
+                None,
+                Some(default_datetime().to_string().as_str()),
+                Some(default_iso_age().as_str()),
+                Some("MALE"),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        let phenopacket = builder.subject_to_phenopacket.get(&phenopacket_id).unwrap();
+        let individual = phenopacket.subject.as_ref().unwrap();
+
+        assert_eq!(individual.sex, Sex::Male as i32);
+        assert_eq!(individual.date_of_birth, Some(default_timestamp()));
+        assert_eq!(
+            individual.time_at_last_encounter,
+            Some(default_age_element())
+        );
+    }
+
+    #[rstest]
+    #[case(UnknownSexPolicy::Other, Sex::OtherSex)]
+    #[case(UnknownSexPolicy::Unknown, Sex::UnknownSex)]
+    fn test_upsert_individual_unrecognised_sex_lenient(
+        #[case] policy: UnknownSexPolicy,
+        #[case] expected_sex: Sex,
+    ) {
+        let ctx = TransformContext::builder(
+            default_meta_data().into(),
+            Arc::new(MockHGNCClient::default()),
+            Arc::new(MockHGVSClient::default()),
+        )
+        .unknown_sex_policy(policy)
+        .build();
+        let mut builder = PhenopacketBuilder::new(ctx);
+        let individual_id = default_patient_id();
+
+        builder
+            .upsert_individual(
+                &individual_id,
+                None,
+                None,
+                None,
+                Some("intersex"),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        let phenopacket = builder
+            .subject_to_phenopacket
+            .get(&default_phenopacket_id())
+            .unwrap();
+        assert_eq!(
+            phenopacket.subject.as_ref().unwrap().sex,
+            expected_sex as i32
+        );
+    }
+
+    #[rstest]
+    #[case("M", Sex::Male)]
+    #[case("female", Sex::Female)]
+    #[case(" Female ", Sex::Female)]
+    #[case("2", Sex::Female)]
+    #[case("MALE", Sex::Male)]
+    #[case("OTHER_SEX", Sex::OtherSex)]
+    fn test_upsert_individual_sex_vocabulary(#[case] sex: &str, #[case] expected_sex: Sex) {
+        let mut builder = build_test_phenopacket_builder();
+
+        builder
+            .upsert_individual(
+                &default_patient_id(),
+                None,
+                None,
+                None,
+                Some(sex),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        let phenopacket = builder
+            .subject_to_phenopacket
+            .get(&default_phenopacket_id())
+            .unwrap();
+        assert_eq!(
+            phenopacket.subject.as_ref().unwrap().sex,
+            expected_sex as i32
+        );
+    }
+
+    #[rstest]
+    fn test_upsert_individual_custom_sex_vocabulary() {
+        let ctx = TransformContext::builder(
+            default_meta_data().into(),
+            Arc::new(MockHGNCClient::default()),
+            Arc::new(MockHGVSClient::default()),
+        )
+        .sex_vocabulary(SexVocabulary::default().with_term("männlich", Sex::Male))
+        .build();
+        let mut builder = PhenopacketBuilder::new(ctx);
+
+        builder
+            .upsert_individual(
+                &default_patient_id(),
+                None,
+                None,
+                None,
+                Some("Männlich"),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        let phenopacket = builder
+            .subject_to_phenopacket
+            .get(&default_phenopacket_id())
+            .unwrap();
+        assert_eq!(phenopacket.subject.as_ref().unwrap().sex, Sex::Male as i32);
+    }
+
+    #[rstest]
+    #[case("intersex", Some(Sex::UnknownSex))]
+    #[case("not specified", Some(Sex::UnknownSex))]
+    #[case("3", None)]
+    #[case("m?", None)]
+    #[case(" ", None)]
+    fn test_upsert_individual_unrecognised_sex_strict(
+        #[case] sex: &str,
+        #[case] expected_sex: Option<Sex>,
+    ) {
+        let mut builder = build_test_phenopacket_builder();
+
+        let result = builder.upsert_individual(
+            &default_patient_id(),
+            None,
+            None,
+            None,
+            Some(sex),
+            None,
+            None,
+            None,
+        );
+
+        match expected_sex {
+            Some(expected_sex) => {
+                result.unwrap();
+                let phenopacket = builder
+                    .subject_to_phenopacket
+                    .get(&default_phenopacket_id())
+                    .unwrap();
+                assert_eq!(
+                    phenopacket.subject.as_ref().unwrap().sex,
+                    expected_sex as i32
+                );
+            }
+            None => assert!(matches!(
+                result,
+                Err(PhenopacketBuilderError::ParsingError { what, .. }) if what == "Sex"
+            )),
+        }
+    }
+
+    #[rstest]
+    #[case(UnknownSexPolicy::Strict, "weiblich", Some(Sex::UnknownSex))]
+    #[case(UnknownSexPolicy::Strict, "w.", None)]
+    #[case(UnknownSexPolicy::Other, "weiblich", Some(Sex::OtherSex))]
+    #[case(UnknownSexPolicy::Unknown, "w.", Some(Sex::UnknownSex))]
+    fn test_upsert_individual_sex_not_in_vocabulary(
+        #[case] policy: UnknownSexPolicy,
+        #[case] sex: &str,
+        #[case] expected_sex: Option<Sex>,
+    ) {
+        let ctx = TransformContext::builder(
+            default_meta_data().into(),
+            Arc::new(MockHGNCClient::default()),
+            Arc::new(MockHGVSClient::default()),
+        )
+        .sex_vocabulary(SexVocabulary::default().with_term("männlich", Sex::Male))
+        .unknown_sex_policy(policy)
+        .build();
+        let mut builder = PhenopacketBuilder::new(ctx);
+
+        let result = builder.upsert_individual(
+            &default_patient_id(),
+            None,
+            None,
+            None,
+            Some(sex),
+            None,
+            None,
+            None,
+        );
+
+        match expected_sex {
+            Some(expected_sex) => {
+                result.unwrap();
+                let phenopacket = builder
+                    .subject_to_phenopacket
+                    .get(&default_phenopacket_id())
+                    .unwrap();
+                assert_eq!(
+                    phenopacket.subject.as_ref().unwrap().sex,
+                    expected_sex as i32
+                );
+            }
+            None => assert!(matches!(
+                result,
+                Err(PhenopacketBuilderError::ParsingError { what, value }) if what == "Sex" && value == sex
+            )),
+        }
+    }
+
+    #[rstest]
+    fn test_dropped_field_summary() {
+        let mut builder = build_test_phenopacket_builder();
+
+        for patient_id in ["P001", "P002"] {
+            builder
+                .upsert_individual(patient_id, None, None, None, None, Some("XX"), None, None)
+                .unwrap();
+        }
+        builder
+            .upsert_phenotypic_feature(
+                "P001",
+                &default_phenotype_oc().id,
+                None,
+                None,
+                None,
+                Some(vec!["Left", "Severe"]),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(
+            builder.dropped_field_summary(),
+            vec![
+                (DroppedFieldKind::KaryotypicSex, 2),
+                (DroppedFieldKind::PhenotypicFeatureModifiers, 1),
+            ]
+        );
+        assert_eq!(builder.dropped_fields()[2].value, "Left, Severe");
+        assert!(
+            builder
+                .build()
+                .iter()
+                .all(|pp| pp.subject.as_ref().unwrap().karyotypic_sex == 0)
+        );
+    }
+
+    #[rstest]
+    fn test_lenient_skips_unresolved_terms() {
+        let mut builder = build_test_phenopacket_builder();
+        builder.ctx_mut().set_lenient(true);
+        let patient_id = default_patient_id();
+
+        for phenotype in ["not a phenotype", default_phenotype_oc().id.as_str()] {
+            builder
+                .insert_phenotypic_feature(
+                    &patient_id,
+                    phenotype,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        builder
+            .insert_disease(
+                &patient_id,
+                "not a disease",
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(
+            builder.dropped_field_summary(),
+            vec![
+                (DroppedFieldKind::UnresolvedPhenotype, 1),
+                (DroppedFieldKind::UnresolvedDisease, 1),
+            ]
+        );
+        let phenopackets = builder.build();
+        assert_eq!(phenopackets.len(), 1);
+        assert_eq!(
+            phenopackets[0].phenotypic_features[0].r#type,
+            Some(default_phenotype_oc())
+        );
+        assert!(phenopackets[0].diseases.is_empty());
+    }
+
+    #[rstest]
+    fn test_lenient_skips_interpretation_of_unresolved_disease() {
+        let mut builder = build_test_phenopacket_builder();
+        builder.ctx_mut().set_lenient(true);
+
+        builder
+            .upsert_interpretation(
+                &default_patient_id(),
+                "not a disease",
+                &PathogenicGeneVariantData::None,
+                &[],
+                Some("MALE"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            builder.dropped_field_summary(),
+            vec![(DroppedFieldKind::UnresolvedDisease, 1)]
+        );
+        assert!(
+            builder
+                .build()
+                .iter()
+                .all(|pp| pp.interpretations.is_empty())
+        );
+    }
+
+    #[rstest]
+    fn test_strict_errors_on_unresolved_terms() {
+        let mut builder = build_test_phenopacket_builder();
+
+        let result = builder.insert_phenotypic_feature(
+            &default_patient_id(),
+            "not a phenotype",
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        assert!(result.is_err());
+        assert!(builder.dropped_fields().is_empty());
+    }
+
+    #[rstest]
+    #[case("woman", "GSSO:000369", "female gender identity")]
+    #[case("Non-binary gender", "GSSO:000132", "non-binary gender")]
+    #[case("GSSO:000371", "GSSO:000371", "male gender identity")]
+    fn test_upsert_individual_gender(
+        #[case] gender: &str,
+        #[case] expected_id: &str,
+        #[case] expected_label: &str,
+    ) {
+        let mut builder = build_test_phenopacket_builder();
+
+        builder
+            .upsert_individual(
+                &default_patient_id(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(gender),
+                None,
+            )
+            .unwrap();
+
+        let phenopacket = builder
+            .subject_to_phenopacket
+            .get(&default_phenopacket_id())
+            .unwrap();
+        assert_eq!(
+            phenopacket.subject.as_ref().unwrap().gender,
+            Some(OntologyClass {
+                id: expected_id.to_string(),
+                label: expected_label.to_string(),
+            })
+        );
+        assert!(
+            phenopacket
+                .meta_data
+                .as_ref()
+                .unwrap()
+                .resources
+                .iter()
+                .any(|resource| resource.id == "gsso")
         );
-
-        let pp_gi = &pp_interpretation
-            .clone()
-            .diagnosis
-            .unwrap()
-            .genomic_interpretations[0];
-
-        match pp_gi.clone().call.unwrap() {
-            Call::Gene(gd) => {
-                assert_eq!(gd.symbol.clone(), "CLOCK");
-            }
-            Call::VariantInterpretation(_) => {
-                panic!("Call should be a GeneDescriptor!")
-            }
-        }
     }
 
     #[rstest]
-    fn test_insert_disease() {
+    #[case("NCBITaxon:10090", "NCBITaxon:10090", "Mus musculus")]
+    #[case("mus musculus", "NCBITaxon:10090", "Mus musculus")]
+    #[case("Zebrafish", "NCBITaxon:7955", "Danio rerio")]
+    fn test_upsert_individual_taxonomy(
+        #[case] taxonomy: &str,
+        #[case] expected_id: &str,
+        #[case] expected_label: &str,
+    ) {
         let mut builder = build_test_phenopacket_builder();
 
-        let patient_id = default_patient_id();
-        let disease = default_disease_oc();
-        let onset_age = default_iso_age();
-        let resolution_ts = default_timestamp();
-
         builder
-            .insert_disease(
-                &patient_id,
-                &disease.id,
+            .upsert_individual(
+                &default_patient_id(),
+                None,
                 None,
-                Some(&onset_age),
-                Some(resolution_ts.to_string().as_str()),
                 None,
                 None,
                 None,
                 None,
+                Some(taxonomy),
             )
             .unwrap();
 
-        let expected_pp = &mut Phenopacket {
-            id: default_phenopacket_id(),
-            diseases: vec![Disease {
-                term: Some(disease),
-                onset: Some(default_age_element()),
-                resolution: Some(default_timestamp_element()),
-                ..Default::default()
-            }],
-            meta_data: Some(MetaData {
-                resources: vec![mondo_meta_data_resource()],
-                ..Default::default()
-            }),
-            ..Default::default()
-        };
-
-        let built_pp = builder.subject_to_phenopacket.values().next().unwrap();
-
-        assert_phenopackets(expected_pp, &mut built_pp.clone());
+        let phenopacket = builder
+            .subject_to_phenopacket
+            .get(&default_phenopacket_id())
+            .unwrap();
+        assert_eq!(
+            phenopacket.subject.as_ref().unwrap().taxonomy,
+            Some(OntologyClass {
+                id: expected_id.to_string(),
+                label: expected_label.to_string(),
+            })
+        );
+        assert!(
+            phenopacket
+                .meta_data
+                .as_ref()
+                .unwrap()
+                .resources
+                .iter()
+                .any(|resource| resource.id == "ncbitaxon")
+        );
     }
 
     #[rstest]
-    fn test_insert_same_disease_twice() {
+    fn test_upsert_individual_unknown_taxonomy() {
         let mut builder = build_test_phenopacket_builder();
 
-        let patient_id = default_patient_id();
-        let disease = default_disease_oc();
-
-        for _ in 0..2 {
-            builder
-                .insert_disease(
-                    &patient_id,
-                    &disease.id,
-                    None,
-                    None,
-                    None,
-                    None,
-                    None,
-                    None,
-                    None,
-                )
-                .unwrap();
-        }
-
-        let expected_pp = &mut Phenopacket {
-            id: default_phenopacket_id().to_string(),
-            diseases: vec![default_disease(), default_disease()],
-            meta_data: Some(MetaData {
-                resources: vec![mondo_meta_data_resource()],
-                ..Default::default()
-            }),
-            ..Default::default()
-        };
-
-        let built_pp = builder.subject_to_phenopacket.values().next().unwrap();
+        let result = builder.upsert_individual(
+            &default_patient_id(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some("unicorn"),
+        );
 
-        assert_phenopackets(expected_pp, &mut built_pp.clone());
+        assert!(matches!(
+            result,
+            Err(PhenopacketBuilderError::ParsingError { what, value }) if what == "Taxonomy" && value == "unicorn"
+        ));
     }
 
     #[rstest]
-    fn test_upsert_individual() {
+    fn test_upsert_individual_alternate_ids() {
         let mut builder = build_test_phenopacket_builder();
-
-        let phenopacket_id = default_phenopacket_id();
         let individual_id = default_patient_id();
 
         builder
-            .upsert_individual(&individual_id, None, None, None, None, None, None, None)
+            .upsert_individual(
+                &individual_id,
+                Some(&["LAB-17", "EXT-3", "LAB-17"]),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
             .unwrap();
-
-        let phenopacket = builder.subject_to_phenopacket.get(&phenopacket_id).unwrap();
-        let individual = phenopacket.subject.as_ref().unwrap();
-        assert_eq!(individual.id, individual_id.clone());
-        assert_eq!(individual.sex, 0);
-        assert_eq!(individual.vital_status, None);
-
-        // Test upserting the other entries
         builder
             .upsert_individual(
                 &individual_id,
+                Some(&["EXT-3", "BIOBANK-9"]),
+                None,
+                None,
                 None,
-                Some(default_datetime().to_string().as_str()),
-                Some(default_iso_age().as_str()),
-                Some("MALE"),
                 None,
                 None,
                 None,
             )
             .unwrap();
 
-        let phenopacket = builder.subject_to_phenopacket.get(&phenopacket_id).unwrap();
-        let individual = phenopacket.subject.as_ref().unwrap();
-
-        assert_eq!(individual.sex, Sex::Male as i32);
-        assert_eq!(individual.date_of_birth, Some(default_timestamp()));
+        let phenopacket = builder
+            .subject_to_phenopacket
+            .get(&default_phenopacket_id())
+            .unwrap();
         assert_eq!(
-            individual.time_at_last_encounter,
-            Some(default_age_element())
+            phenopacket.subject.as_ref().unwrap().alternate_ids,
+            vec!["LAB-17", "EXT-3", "BIOBANK-9"]
+        );
+    }
+
+    #[rstest]
+    fn test_upsert_individual_unknown_gender() {
+        let mut builder = build_test_phenopacket_builder();
+
+        let result = builder.upsert_individual(
+            &default_patient_id(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some("prefer not to say"),
+            None,
         );
+
+        assert!(matches!(
+            result,
+            Err(PhenopacketBuilderError::ParsingError { value, .. }) if value == "prefer not to say"
+        ));
     }
 
     #[rstest]
@@ -1876,6 +4610,69 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn test_upsert_biosample() {
+        let mut builder = build_test_phenopacket_builder();
+        let patient_id = default_patient_id();
+
+        builder.upsert_biosample(&patient_id, "BS-1", None).unwrap();
+        builder
+            .upsert_biosample(&patient_id, "BS-1", Some(&default_anatomy_region().label))
+            .unwrap();
+
+        let pp = builder.build().first().unwrap().clone();
+        assert_eq!(
+            pp.biosamples,
+            vec![Biosample {
+                id: "BS-1".to_string(),
+                individual_id: patient_id,
+                sampled_tissue: Some(default_anatomy_region()),
+                ..Default::default()
+            }]
+        );
+        assert_eq!(
+            pp.meta_data.unwrap().resources,
+            vec![uberon_meta_data_resource()]
+        );
+    }
+
+    #[rstest]
+    fn test_upsert_vital_status_cause_of_death_by_label() {
+        let mut builder = build_test_phenopacket_builder();
+
+        builder
+            .upsert_vital_status(
+                &default_patient_id(),
+                "DECEASED",
+                None,
+                Some(default_disease_oc().label.as_str()),
+                None,
+            )
+            .unwrap();
+
+        let pp = builder.build().first().unwrap().clone();
+        let vital_status = pp.subject.unwrap().vital_status.unwrap();
+        assert_eq!(vital_status.cause_of_death, Some(default_disease_oc()));
+        assert_eq!(
+            pp.meta_data.unwrap().resources,
+            vec![mondo_meta_data_resource()]
+        );
+    }
+
+    #[rstest]
+    fn test_upsert_vital_status_without_cause_of_death() {
+        let mut builder = build_test_phenopacket_builder();
+
+        builder
+            .upsert_vital_status(&default_patient_id(), "ALIVE", None, None, None)
+            .unwrap();
+
+        let pp = builder.build().first().unwrap().clone();
+        let vital_status = pp.subject.unwrap().vital_status.unwrap();
+        assert_eq!(vital_status.cause_of_death, None);
+        assert!(pp.meta_data.unwrap().resources.is_empty());
+    }
+
     #[rstest]
     fn test_insert_quantitative_measurement() {
         let mut builder = build_test_phenopacket_builder();
@@ -1905,6 +4702,86 @@ mod tests {
         assert_eq!(quant_measurement, &default_quant_measurement());
     }
 
+    #[rstest]
+    fn test_insert_ratio_measurement() {
+        let mut builder = build_test_phenopacket_builder();
+
+        builder
+            .insert_ratio_measurement(
+                &default_patient_id(),
+                None,
+                default_quant_loinc().id.as_str(),
+                30.0,
+                default_quant_loinc().id.as_str(),
+                default_unit_oc().id.as_str(),
+                2.0,
+                default_qual_loinc().id.as_str(),
+                default_unit_oc().id.as_str(),
+            )
+            .unwrap();
+
+        let phenopacket = builder
+            .subject_to_phenopacket
+            .get(&default_phenopacket_id())
+            .unwrap();
+        let measurement = phenopacket.measurements.first().unwrap();
+
+        assert_eq!(measurement.assay, Some(default_quant_loinc()));
+        let Some(MeasurementValue::ComplexValue(complex_value)) = &measurement.measurement_value
+        else {
+            panic!("Ratio measurement should carry a ComplexValue");
+        };
+        let typed_quantities: Vec<(Option<OntologyClass>, f64)> = complex_value
+            .typed_quantities
+            .iter()
+            .map(|tq| (tq.r#type.clone(), tq.quantity.as_ref().unwrap().value))
+            .collect();
+        assert_eq!(
+            typed_quantities,
+            vec![
+                (Some(default_quant_loinc()), 30.0),
+                (Some(default_qual_loinc()), 2.0)
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_insert_quantitative_measurement_bare_loinc_number() {
+        let mut builder = build_test_phenopacket_builder();
+
+        let patient_id = default_patient_id();
+        let loinc_number = default_quant_loinc().id.replace("LOINC:", "");
+
+        builder
+            .insert_quantitative_measurement(
+                patient_id.as_str(),
+                1.1,
+                Some(default_iso_age().as_str()),
+                &loinc_number,
+                default_unit_oc().id.as_str(),
+                Some(default_reference_range()),
+            )
+            .unwrap();
+
+        let phenopacket = builder
+            .subject_to_phenopacket
+            .get(&default_phenopacket_id())
+            .unwrap();
+        assert_eq!(
+            phenopacket.measurements.first().unwrap(),
+            &default_quant_measurement()
+        );
+
+        let loinc_resource = phenopacket
+            .resources()
+            .iter()
+            .find(|resource| resource.id == "loinc")
+            .expect("LOINC resource should have been added")
+            .clone();
+        assert!(!loinc_resource.version.is_empty());
+        assert_ne!(loinc_resource.version, "latest");
+    }
+
     #[rstest]
     fn test_insert_qualitative_measurement() {
         let mut builder = build_test_phenopacket_builder();
@@ -1952,7 +4829,7 @@ mod tests {
         builder.ensure_resource(
             &pp_id,
             &ResourceRef::from(KnownResourcePrefixes::OMIM).with_latest(),
-        );
+        ).unwrap();
 
         let pp = builder.build().first().unwrap().clone();
         let omim_resrouce = pp.meta_data.as_ref().unwrap().resources.first().unwrap();
@@ -1968,6 +4845,41 @@ mod tests {
         assert_eq!(omim_resrouce, &expected_resource);
     }
 
+    #[rstest]
+    fn test_ensure_resource_keeps_different_versions() {
+        let mut builder = build_test_phenopacket_builder();
+        let pp_id = default_phenopacket_id();
+        let hpo_2024 = ResourceRef::from(KnownResourcePrefixes::HP).with_version("2024-04-26");
+        let hpo_2025 = ResourceRef::from(KnownResourcePrefixes::HP).with_version("2025-05-06");
+
+        builder.ensure_resource(&pp_id, &hpo_2024).unwrap();
+        builder.ensure_resource(&pp_id, &hpo_2025).unwrap();
+        builder.ensure_resource(&pp_id, &hpo_2024).unwrap();
+
+        let pp = builder.build().first().unwrap().clone();
+        let versions: Vec<&str> = pp
+            .meta_data
+            .as_ref()
+            .unwrap()
+            .resources
+            .iter()
+            .map(|resource| resource.version.as_str())
+            .collect();
+        assert_eq!(versions, vec!["2024-04-26", "2025-05-06"]);
+    }
+
+    #[rstest]
+    fn test_split_off_shares_resource_resolver() {
+        let mut builder = build_test_phenopacket_builder();
+
+        let split_builder = builder.split_off([default_patient_id().as_str()]);
+
+        assert!(Arc::ptr_eq(
+            builder.ctx.resource_resolver(),
+            split_builder.ctx.resource_resolver()
+        ));
+    }
+
     #[rstest]
     fn test_generate_phenopacket_id() {
         let builder = build_test_phenopacket_builder();
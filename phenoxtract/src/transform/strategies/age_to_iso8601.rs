@@ -1,24 +1,35 @@
 use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
-use crate::transform::error::StrategyError::MappingError;
-use crate::transform::error::{MappingErrorInfo, PushMappingError, StrategyError};
+use crate::transform::error::StrategyError;
 use crate::transform::strategies::traits::Strategy;
-use log::info;
+use log::{info, warn};
 
 use crate::extract::enums::Filter;
 
 use crate::config::context::{Context, TimeElementType};
 use crate::transform::utils::is_iso8601_duration;
-use polars::prelude::{DataType, IntoSeries};
-use std::any::type_name;
-use std::collections::{HashMap, HashSet};
+use polars::prelude::{Column, DataType};
+use regex::Regex;
 
 #[derive(Debug)]
-/// Converts integer ages to ISO8601 durations.
+/// Converts ages to ISO8601 durations.
 ///
 /// Given a column whose cells contains ages (e.g. subject age, age of death, age of onset)
-/// this strategy converts integer entries to ISO8601 durations: 47 -> P47Y
+/// this strategy converts the entries to ISO8601 durations: 47 -> P47Y
 ///
-/// If an entry is already in ISO8601 duration format, it will be left unchanged.
+/// The following formats are accepted (case-insensitive, surrounding whitespace is ignored):
+///
+/// | Format                     | Example                | Result    |
+/// |----------------------------|------------------------|-----------|
+/// | ISO8601 duration           | `P56Y12M3D`            | unchanged |
+/// | Integer years              | `47`                   | `P47Y`    |
+/// | Decimal years              | `5.5`                  | `P5Y6M`   |
+/// | Numbers with units         | `5y3m`, `5 years 2 d`  | `P5Y3M`, `P5Y2D` |
+/// | Months only                | `60 months`            | `P5Y`     |
+///
+/// The units are `y`/`yr`/`yrs`/`year`/`years`, `m`/`mo`/`mos`/`month`/`months`, `w`/`wk`/`wks`/`week`/`weeks`
+/// and `d`/`day`/`days`, and may be separated from their number and from each other by whitespace or commas.
+/// Only years may have decimals. Decimal years are rounded to whole months, months are carried over into years
+/// and weeks are converted into days.
 ///
 /// # Example
 ///
@@ -27,6 +38,8 @@ use std::collections::{HashMap, HashSet};
 /// PatientId, age_at_last_encounter
 /// P001, 47
 /// P002, P56Y12M3D
+/// P003, 5 years 3 months
+/// P004, about five
 /// ```
 ///
 /// will be mapped to
@@ -34,16 +47,16 @@ use std::collections::{HashMap, HashSet};
 /// PatientId, age_at_last_encounter
 /// P001, P47Y
 /// P002, P56Y12M3D
+/// P003, P5Y3M
+/// P004,
 /// ```
 ///
-/// # Errors
-///
-/// If there are cell values which are neither ISO8601 durations nor integers (between 0 and 150)
-/// an error will be returned.
+/// Cells which cannot be parsed, or whose ages are not between 0 and 150 years, are set to null and a warning is logged.
 ///
 pub struct AgeToIso8601Strategy {
-    min_age: i32,
-    max_age: i32,
+    min_age: u32,
+    max_age: u32,
+    age_component_regex: Regex,
 }
 
 impl Default for AgeToIso8601Strategy {
@@ -57,11 +70,85 @@ impl AgeToIso8601Strategy {
         AgeToIso8601Strategy {
             min_age: 0,
             max_age: 150,
+            age_component_regex: Regex::new(r"(\d+(?:\.\d+)?)\s*([a-z]+)")
+                .expect("Age component regex is valid."),
+        }
+    }
+
+    /// Parses an age in one of the accepted formats and returns it as an ISO8601 duration.
+    fn parse_age(&self, cell_value: &str) -> Option<String> {
+        let cell_value = cell_value.trim();
+
+        if is_iso8601_duration(cell_value) {
+            Some(cell_value.to_string())
+        } else if let Ok(years) = cell_value.parse::<f64>() {
+            self.to_iso8601(years, 0, 0)
+        } else {
+            self.parse_age_with_units(cell_value)
+        }
+    }
+
+    fn parse_age_with_units(&self, cell_value: &str) -> Option<String> {
+        let cell_value = cell_value.to_lowercase();
+        let is_separator = |c: char| c.is_whitespace() || c == ',';
+
+        let mut years = 0.0;
+        let mut months = 0;
+        let mut days = 0;
+        let mut last_end = 0;
+
+        for captures in self.age_component_regex.captures_iter(&cell_value) {
+            let component = captures.get(0)?;
+            if !cell_value[last_end..component.start()]
+                .trim_matches(is_separator)
+                .is_empty()
+            {
+                return None;
+            }
+            last_end = component.end();
+
+            let number = &captures[1];
+            match &captures[2] {
+                "y" | "yr" | "yrs" | "year" | "years" => years += number.parse::<f64>().ok()?,
+                "m" | "mo" | "mos" | "month" | "months" => months += number.parse::<u32>().ok()?,
+                "w" | "wk" | "wks" | "week" | "weeks" => days += 7 * number.parse::<u32>().ok()?,
+                "d" | "day" | "days" => days += number.parse::<u32>().ok()?,
+                _ => return None,
+            }
         }
+
+        if last_end == 0 || !cell_value[last_end..].trim_matches(is_separator).is_empty() {
+            return None;
+        }
+
+        self.to_iso8601(years, months, days)
     }
 
-    fn is_valid_age(&self, age: i32) -> bool {
-        age >= self.min_age && age <= self.max_age
+    fn to_iso8601(&self, years: f64, months: u32, days: u32) -> Option<String> {
+        if !years.is_finite() || years < 0.0 || years > (self.max_age + 1) as f64 {
+            return None;
+        }
+
+        let total_months = (years * 12.0).round() as u32 + months;
+        let (years, months) = (total_months / 12, total_months % 12);
+        if years < self.min_age || years > self.max_age {
+            return None;
+        }
+
+        let mut duration = "P".to_string();
+        if years > 0 {
+            duration.push_str(&format!("{years}Y"));
+        }
+        if months > 0 {
+            duration.push_str(&format!("{months}M"));
+        }
+        if days > 0 {
+            duration.push_str(&format!("{days}D"));
+        }
+        if duration == "P" {
+            duration.push_str("0Y");
+        }
+        Some(duration)
     }
 }
 
@@ -85,12 +172,6 @@ impl Strategy for AgeToIso8601Strategy {
     ) -> Result<(), StrategyError> {
         info!("Applying AgeToISO8601 strategy to data.");
 
-        let years_to_iso8601: HashMap<i32, String> = (self.min_age..=self.max_age)
-            .map(|n| (n, format!("P{n}Y")))
-            .collect();
-
-        let mut error_info: HashSet<MappingErrorInfo> = HashSet::new();
-
         for table in tables.iter_mut() {
             let column_names = table
                 .filter_columns()
@@ -109,52 +190,39 @@ impl Strategy for AgeToIso8601Strategy {
                     col
                 };
 
-                let mapped_column = cast_col.str()?.apply_mut(|cell_value| {
-                    if is_iso8601_duration(cell_value) {
-                        cell_value
-                    } else if let Ok(years) = cell_value.parse::<i32>()
-                        && self.is_valid_age(years)
-                    {
-                        years_to_iso8601
-                            .get(&years)
-                            .expect("Age was too high or too low.")
-                    } else if let Ok(years) = cell_value.parse::<f64>()
-                        && years.fract() == 0.0
-                        && self.is_valid_age(years as i32)
-                    {
-                        let years_i32 = years as i32;
-                        years_to_iso8601
-                            .get(&years_i32)
-                            .expect("Age was too high or too low")
-                    } else {
-                        if !cell_value.is_empty() {
-                            error_info.insert_error(
-                                col.name().to_string(),
-                                table.context().name().to_string(),
-                                cell_value.to_string(),
-                                vec![],
-                            );
+                let mut unparseable: Vec<&str> = vec![];
+                let ages: Vec<Option<String>> = cast_col
+                    .str()?
+                    .iter()
+                    .map(|cell_value| {
+                        let cell_value = cell_value?;
+                        let age = self.parse_age(cell_value);
+                        if age.is_none() && !cell_value.trim().is_empty() {
+                            unparseable.push(cell_value);
                         }
-                        cell_value
-                    }
-                });
+                        age
+                    })
+                    .collect();
+
+                if !unparseable.is_empty() {
+                    warn!(
+                        "Set {} cell(s) in column '{}' of table '{}' to null, because they could not be parsed as ages: {:?}",
+                        unparseable.len(),
+                        col_name,
+                        table.context().name(),
+                        unparseable
+                    );
+                }
+
+                let mapped_column = Column::new(col_name.as_str().into(), ages);
                 table
                     .builder()
-                    .replace_col(&col_name, mapped_column.into_series())?
+                    .replace_col(&col_name, mapped_column.take_materialized_series())?
                     .build()?;
             }
         }
 
-        // return an error if not every cell term could be parsed
-        if !error_info.is_empty() {
-            Err(MappingError {
-                strategy_name: type_name::<Self>().split("::").last().unwrap().to_string(),
-                message: "These cell values were neither ISO8601 nor years.".to_string(),
-                info: error_info.into_iter().collect(),
-            })
-        } else {
-            Ok(())
-        }
+        Ok(())
     }
 }
 
@@ -164,7 +232,6 @@ mod tests {
     use crate::config::table_context::{Identifier, SeriesContext, TableContext};
     use crate::config::traits::SeriesContextBuilding;
     use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
-    use crate::transform::error::{MappingErrorInfo, StrategyError};
     use crate::transform::strategies::age_to_iso8601::AgeToIso8601Strategy;
     use crate::transform::strategies::traits::Strategy;
     use polars::datatypes::AnyValue;
@@ -288,13 +355,13 @@ mod tests {
     }
 
     #[rstest]
-    fn test_age_to_iso8601_err(tc: TableContext) {
+    fn test_age_to_iso8601_unparseable_to_null(tc: TableContext) {
         let col_pid = Column::new("subject_ids".into(), ["1", "2", "3", "4"]);
         let age_col = Column::new(
             "age".into(),
             [
                 AnyValue::String("321"),
-                AnyValue::String("47Y5M12D"),
+                AnyValue::String("about five"),
                 AnyValue::Null,
                 AnyValue::String("15"),
             ],
@@ -303,36 +370,59 @@ mod tests {
         let mut cdf = ContextualizedDataFrame::new(tc, df).unwrap();
 
         let age_to_iso8601_strat = AgeToIso8601Strategy::default();
-        let result = age_to_iso8601_strat.transform(&mut [&mut cdf]);
-
-        assert!(result.is_err());
-
-        if let Err(StrategyError::MappingError {
-            strategy_name,
-            message,
-            info,
-        }) = result
-        {
-            assert_eq!(strategy_name, "AgeToIso8601Strategy");
-            assert_eq!(message, "These cell values were neither ISO8601 nor years.");
-            let expected_error_info: Vec<MappingErrorInfo> = Vec::from([
-                MappingErrorInfo {
-                    column: "age".to_string(),
-                    table: "patient_data".to_string(),
-                    old_value: "321".to_string(),
-                    possible_mappings: vec![],
-                },
-                MappingErrorInfo {
-                    column: "age".to_string(),
-                    table: "patient_data".to_string(),
-                    old_value: "47Y5M12D".to_string(),
-                    possible_mappings: vec![],
-                },
-            ]);
-
-            for i in info {
-                assert!(expected_error_info.contains(&i));
-            }
-        }
+        age_to_iso8601_strat.transform(&mut [&mut cdf]).unwrap();
+
+        let expected_transformed_age_col = Column::new(
+            "age".into(),
+            [
+                AnyValue::Null,
+                AnyValue::Null,
+                AnyValue::Null,
+                AnyValue::String("P15Y"),
+            ],
+        );
+        assert_eq!(
+            cdf.data().column("age").unwrap(),
+            &expected_transformed_age_col
+        );
+    }
+
+    #[rstest]
+    #[case("P56Y12M3D", "P56Y12M3D")]
+    #[case("47", "P47Y")]
+    #[case(" 47 ", "P47Y")]
+    #[case("0", "P0Y")]
+    #[case("150", "P150Y")]
+    #[case("32.0", "P32Y")]
+    #[case("5.5", "P5Y6M")]
+    #[case("0.25", "P3M")]
+    #[case("5y3m", "P5Y3M")]
+    #[case("5Y 3M 2D", "P5Y3M2D")]
+    #[case("5 years", "P5Y")]
+    #[case("1 year, 2 months", "P1Y2M")]
+    #[case("5.5 yrs", "P5Y6M")]
+    #[case("60 months", "P5Y")]
+    #[case("18 mo", "P1Y6M")]
+    #[case("3 weeks", "P21D")]
+    #[case("10 days", "P10D")]
+    fn test_parse_age(#[case] cell_value: &str, #[case] expected: &str) {
+        let strategy = AgeToIso8601Strategy::default();
+
+        assert_eq!(strategy.parse_age(cell_value), Some(expected.to_string()));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("-5")]
+    #[case("151")]
+    #[case("about five")]
+    #[case("5 decades")]
+    #[case("5 years and 3 months")]
+    #[case("5.5 months")]
+    #[case("years")]
+    fn test_parse_age_rejected(#[case] cell_value: &str) {
+        let strategy = AgeToIso8601Strategy::default();
+
+        assert_eq!(strategy.parse_age(cell_value), None);
     }
 }
@@ -0,0 +1,236 @@
+use crate::config::context::Context;
+use crate::config::table_context::{Identifier, SeriesContext};
+use crate::config::traits::SeriesContextBuilding;
+use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
+use crate::transform::error::StrategyError;
+use crate::transform::strategies::traits::Strategy;
+use log::{info, warn};
+use polars::prelude::{Column, DataType, IntoSeries, StringChunked};
+use serde::{Deserialize, Serialize};
+
+/// How nulls in the source columns are handled, when they are concatenated.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConcatenationNullHandling {
+    /// The concatenation is null, if any of the source cells is null.
+    #[default]
+    Propagate,
+    /// Null source cells are left out. The concatenation is only null, if all source cells are null.
+    Skip,
+}
+
+/// The configuration of a [`ColumnConcatenationStrategy`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ColumnConcatenation {
+    pub table_name: String,
+    pub source_columns: Vec<String>,
+    #[serde(default)]
+    pub separator: String,
+    pub output_column: String,
+    #[serde(default)]
+    pub output_data_context: Context,
+    #[serde(default)]
+    pub output_building_block_id: Option<String>,
+    #[serde(default)]
+    pub null_handling: ConcatenationNullHandling,
+}
+
+/// Joins the cells of several columns into a new column, e.g. to build an HGVS string from a transcript and a variant.
+///
+/// # Example
+///
+/// With the source columns `transcript, variant`, the separator `:` and the output column `hgvs` the table
+/// ```csv
+/// PatientId, transcript, variant
+/// P001, NM_001173464.1, c.2860C>T
+/// ```
+///
+/// will be mapped to
+/// ```csv
+/// PatientId, transcript, variant, hgvs
+/// P001, NM_001173464.1, c.2860C>T, NM_001173464.1:c.2860C>T
+/// ```
+///
+/// The source columns are kept.
+#[derive(Debug)]
+pub struct ColumnConcatenationStrategy {
+    config: ColumnConcatenation,
+}
+
+impl ColumnConcatenationStrategy {
+    pub fn new(config: ColumnConcatenation) -> Self {
+        ColumnConcatenationStrategy { config }
+    }
+
+    fn concatenate(&self, cells: &[Option<&str>]) -> Option<String> {
+        let present_cells: Vec<&str> = cells.iter().flatten().copied().collect();
+
+        let is_null = match self.config.null_handling {
+            ConcatenationNullHandling::Propagate => present_cells.len() != cells.len(),
+            ConcatenationNullHandling::Skip => present_cells.is_empty(),
+        };
+
+        (!is_null).then(|| present_cells.join(&self.config.separator))
+    }
+
+    fn has_source_columns(&self, table: &ContextualizedDataFrame) -> bool {
+        table.context().name() == self.config.table_name
+            && self
+                .config
+                .source_columns
+                .iter()
+                .all(|source_column| table.data().column(source_column).is_ok())
+    }
+}
+
+impl Strategy for ColumnConcatenationStrategy {
+    fn is_valid(&self, tables: &[&mut ContextualizedDataFrame]) -> bool {
+        tables.iter().any(|table| self.has_source_columns(table))
+    }
+
+    fn internal_transform(
+        &self,
+        tables: &mut [&mut ContextualizedDataFrame],
+    ) -> Result<(), StrategyError> {
+        info!("Applying ColumnConcatenation strategy to data.");
+
+        for table in tables.iter_mut() {
+            if table.context().name() != self.config.table_name {
+                continue;
+            }
+            if !self.has_source_columns(table) {
+                warn!(
+                    "Table '{}' is missing some of the columns {:?}. Skipping concatenation.",
+                    self.config.table_name, self.config.source_columns
+                );
+                continue;
+            }
+
+            let source_columns = self
+                .config
+                .source_columns
+                .iter()
+                .map(|source_column| table.data().column(source_column)?.cast(&DataType::String))
+                .collect::<Result<Vec<Column>, _>>()?;
+            let source_columns = source_columns
+                .iter()
+                .map(|source_column| source_column.str())
+                .collect::<Result<Vec<&StringChunked>, _>>()?;
+
+            let concatenated: StringChunked = (0..table.data().height())
+                .map(|row_idx| {
+                    let cells: Vec<Option<&str>> = source_columns
+                        .iter()
+                        .map(|source_column| source_column.get(row_idx))
+                        .collect();
+                    self.concatenate(&cells)
+                })
+                .collect();
+            let concatenated = Column::from(
+                concatenated
+                    .with_name(self.config.output_column.as_str().into())
+                    .into_series(),
+            );
+
+            let output_sc = SeriesContext::from_identifier(Identifier::from(
+                self.config.output_column.as_str(),
+            ))
+            .with_data_context(self.config.output_data_context.clone())
+            .with_building_block_id(self.config.output_building_block_id.clone());
+
+            table
+                .builder()
+                .insert_sc_alongside_cols(output_sc, &[concatenated])?
+                .build()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::table_context::TableContext;
+    use crate::extract::enums::Filter;
+    use polars::frame::DataFrame;
+    use pretty_assertions::assert_eq;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn variant_cdf() -> ContextualizedDataFrame {
+        let tc = TableContext::new(
+            "genetics",
+            vec![
+                SeriesContext::from_identifier("subject_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("transcript"),
+                SeriesContext::from_identifier("variant"),
+            ],
+        );
+        let df = DataFrame::new(
+            3,
+            vec![
+                Column::new("subject_id".into(), ["P001", "P002", "P003"]),
+                Column::new(
+                    "transcript".into(),
+                    [Some("NM_001173464.1"), Some("NM_000540.3"), None],
+                ),
+                Column::new("variant".into(), [Some("c.2860C>T"), None, None]),
+            ],
+        )
+        .unwrap();
+        ContextualizedDataFrame::new(tc, df).unwrap()
+    }
+
+    fn config(null_handling: ConcatenationNullHandling) -> ColumnConcatenation {
+        ColumnConcatenation {
+            table_name: "genetics".to_string(),
+            source_columns: vec!["transcript".to_string(), "variant".to_string()],
+            separator: ":".to_string(),
+            output_column: "hgvs".to_string(),
+            output_data_context: Context::Hgvs,
+            output_building_block_id: Some("variant_1".to_string()),
+            null_handling,
+        }
+    }
+
+    #[rstest]
+    #[case(
+        ConcatenationNullHandling::Propagate,
+        [Some("NM_001173464.1:c.2860C>T"), None, None]
+    )]
+    #[case(
+        ConcatenationNullHandling::Skip,
+        [Some("NM_001173464.1:c.2860C>T"), Some("NM_000540.3"), None]
+    )]
+    fn test_column_concatenation(
+        mut variant_cdf: ContextualizedDataFrame,
+        #[case] null_handling: ConcatenationNullHandling,
+        #[case] expected: [Option<&str>; 3],
+    ) {
+        let strategy = ColumnConcatenationStrategy::new(config(null_handling));
+        strategy.transform(&mut [&mut variant_cdf]).unwrap();
+
+        assert_eq!(
+            variant_cdf.data().column("hgvs").unwrap(),
+            &Column::new("hgvs".into(), expected)
+        );
+        let hgvs_scs = variant_cdf
+            .filter_series_context()
+            .where_data_context(Filter::Is(&Context::Hgvs))
+            .where_building_block(Filter::Is("variant_1"))
+            .collect();
+        assert_eq!(hgvs_scs.len(), 1);
+    }
+
+    #[rstest]
+    fn test_column_concatenation_missing_source_column(mut variant_cdf: ContextualizedDataFrame) {
+        let mut config = config(ConcatenationNullHandling::Propagate);
+        config.source_columns.push("protein".to_string());
+
+        let strategy = ColumnConcatenationStrategy::new(config);
+
+        assert!(!strategy.is_valid(&[&mut variant_cdf]));
+    }
+}
@@ -10,7 +10,7 @@ use crate::transform::data_processing::parsing::{
     try_parse_string_date, try_parse_string_datetime,
 };
 use crate::transform::strategies::traits::Strategy;
-use chrono::NaiveDateTime;
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
 use date_differencer::date_diff;
 use iso8601_duration::Duration;
 use polars::prelude::{AnyValue, Column, DataType};
@@ -24,7 +24,11 @@ use std::collections::{HashMap, HashSet};
 /// # Fields
 ///
 /// * `strict` - Determines whether there will be an error if there exists a date but no DOB data for a patient.
+///   Otherwise, the date is set to null and a warning is logged.
 ///
+/// Dates, which are only known to the year or month (e.g. "2019" or "2019-03"), lead to ages of the same precision.
+/// If the dates are in a table, which is read in chunks, the dates of birth are looked up in the tables, which are read
+/// at once, and in the same chunk.
 /// # Example
 ///
 /// The table
@@ -40,22 +44,30 @@ use std::collections::{HashMap, HashSet};
 /// is mapped to
 /// ```csv
 /// PatientId, DOB, TimeAtLastEncounter
-/// P001, 1990, 5
+/// P001, 1990, P5Y
 /// P002, 1992,
-/// P003, 2000, 4
+/// P003, 2000, P4Y
 /// P004,,
 /// ```
 ///
 /// # Errors
 ///
 /// An error will be thrown if
-/// - A date of birth is earlier than a date for a patient, leading to a negative age.
+/// - A date precedes the date of birth of a patient, leading to a negative age.
 /// - If strict is true,
 ///   and there exists a date which cannot be converted to an age due to missing DOB data.
 pub struct DateToAgeStrategy {
     strict: bool,
 }
 
+/// How much of a date is known. Partial dates lead to coarser ages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum DatePrecision {
+    Year,
+    Month,
+    Day,
+}
+
 impl DateToAgeStrategy {
     pub fn new(strict: bool) -> DateToAgeStrategy {
         DateToAgeStrategy { strict }
@@ -97,6 +109,10 @@ impl Strategy for DateToAgeStrategy {
         }
     }
 
+    fn reads_other_tables(&self) -> bool {
+        true
+    }
+
     fn internal_transform(
         &self,
         tables: &mut [&mut ContextualizedDataFrame],
@@ -150,6 +166,8 @@ impl Strategy for DateToAgeStrategy {
                     .iter()
                     .zip(stringified_date_col.iter());
 
+                let mut subjects_without_dob: Vec<&str> = vec![];
+
                 let ages: Vec<AnyValue> = subject_id_date_zip
                     .map(|(subject_id_opt, date_opt)| {
                         let subject_id =
@@ -176,7 +194,8 @@ impl Strategy for DateToAgeStrategy {
                                     );
                                     AnyValue::Null
                                 } else {
-                                    AnyValue::String(date)
+                                    subjects_without_dob.push(subject_id);
+                                    AnyValue::Null
                                 }
                             }
                             Some(subject_dob) => {
@@ -192,6 +211,16 @@ impl Strategy for DateToAgeStrategy {
                     })
                     .collect();
 
+                if !subjects_without_dob.is_empty() {
+                    warn!(
+                        "Dropped {} date(s) in column '{}' of table '{}', because there was no DOB data for the subjects: {:?}",
+                        subjects_without_dob.len(),
+                        date_col_name,
+                        table.context().name(),
+                        subjects_without_dob
+                    );
+                }
+
                 let ages_column = Column::new(date_col_name.clone().into(), ages);
 
                 table
@@ -273,24 +302,82 @@ impl DateToAgeStrategy {
     /// Given the date of birth of a patient, and a date in their life
     /// this will calculate the age of a patient at that date.
     ///
-    /// An error will be thrown if the date of birth, or the date, cannot be interpreted as
-    /// chrono::NaiveDate.
-    fn date_and_dob_to_age(dob: String, date: &str) -> Result<String, String> {
-        let dob_object = if let Some(dob) = try_parse_string_date(dob.as_str()) {
-            dob.and_hms_opt(0, 0, 0).unwrap()
-        } else {
-            try_parse_string_datetime(dob.as_str())
-                .ok_or_else(|| format!("Could not parse DOB: {dob}"))?
+    /// Partial dates ("2019" or "2019-03") are supported for both, in which case the age is only as precise
+    /// as the coarser of the two, e.g. "P5Y" if either is only known to the year.
+    ///
+    /// An error will be thrown if the date of birth, or the date, cannot be interpreted as a (partial) date,
+    /// or if the date precedes the date of birth.
+    pub(crate) fn date_and_dob_to_age(dob: String, date: &str) -> Result<String, String> {
+        let (dob_object, dob_precision) = Self::try_parse_partial_date(dob.as_str())
+            .ok_or_else(|| format!("Could not parse DOB: {dob}"))?;
+        let (date_object, date_precision) = Self::try_parse_partial_date(date)
+            .ok_or_else(|| format!("Could not parse date: {date}"))?;
+
+        match dob_precision.min(date_precision) {
+            DatePrecision::Day => Self::date_difference(dob_object, date_object),
+            DatePrecision::Month => {
+                let months = (date_object.year() * 12 + date_object.month0() as i32)
+                    - (dob_object.year() * 12 + dob_object.month0() as i32);
+                Self::coarse_age(months, dob_object, date_object)
+            }
+            DatePrecision::Year => Self::coarse_age(
+                (date_object.year() - dob_object.year()) * 12,
+                dob_object,
+                date_object,
+            ),
+        }
+    }
+
+    /// Parses full dates and datetimes, as well as dates which are only known to the year or month.
+    fn try_parse_partial_date(date: &str) -> Option<(NaiveDateTime, DatePrecision)> {
+        let date = date.trim();
+        let is_number = |part: &str, max_len: usize| {
+            !part.is_empty() && part.len() <= max_len && part.chars().all(|c| c.is_ascii_digit())
         };
 
-        let date_object = if let Some(date) = try_parse_string_date(date) {
-            date.and_hms_opt(0, 0, 0).unwrap()
-        } else {
-            try_parse_string_datetime(date)
-                .ok_or_else(|| format!("Could not parse date: {date}"))?
+        if date.len() == 4 && is_number(date, 4) {
+            let year = date.parse().ok()?;
+            return Some((
+                NaiveDate::from_ymd_opt(year, 1, 1)?.and_hms_opt(0, 0, 0)?,
+                DatePrecision::Year,
+            ));
+        }
+
+        if let Some((year, month)) = date.split_once('-')
+            && year.len() == 4
+            && is_number(year, 4)
+            && is_number(month, 2)
+        {
+            return Some((
+                NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, 1)?
+                    .and_hms_opt(0, 0, 0)?,
+                DatePrecision::Month,
+            ));
+        }
+
+        let date_object = match try_parse_string_date(date) {
+            Some(date) => date.and_hms_opt(0, 0, 0)?,
+            None => try_parse_string_datetime(date)?,
         };
+        Some((date_object, DatePrecision::Day))
+    }
 
-        Self::date_difference(dob_object, date_object)
+    fn coarse_age(months: i32, dob: NaiveDateTime, date: NaiveDateTime) -> Result<String, String> {
+        if months < 0 {
+            Err(format!("Negative Age. DOB: {dob}, date: {date}"))
+        } else if months == 0 {
+            Ok("P0Y".to_string())
+        } else {
+            let dur = Duration::new(
+                (months / 12) as f32,
+                (months % 12) as f32,
+                0f32,
+                0f32,
+                0f32,
+                0f32,
+            );
+            Ok(dur.to_string())
+        }
     }
 
     fn date_to_age_contexts_hash_map() -> HashMap<Context, Context> {
@@ -612,12 +699,7 @@ mod tests {
     fn test_date_to_age_strategy(
         #[values(df_with_string_dob(), df_with_date_dob(), df_with_datetime_dob())]
         dob_df: DataFrame,
-        #[values(
-            df_with_str_onset(),
-            df_with_date_onset(),
-            df_with_datetime_onset(),
-            df_with_int_onset()
-        )]
+        #[values(df_with_str_onset(), df_with_date_onset(), df_with_datetime_onset())]
         onset_df: DataFrame,
     ) {
         let mut cdf1 = ContextualizedDataFrame::new(dob_tc(), dob_df).unwrap();
@@ -680,6 +762,57 @@ mod tests {
         let result = non_strict_date_to_age_strat.transform(tables);
 
         assert!(result.is_ok());
+        assert_eq!(cdf2.data().column("onset").unwrap().null_count(), 3);
+    }
+
+    #[rstest]
+    fn test_date_to_age_year_only_dates(
+        df_with_string_dob: DataFrame,
+        df_with_int_onset: DataFrame,
+    ) {
+        let mut cdf1 = ContextualizedDataFrame::new(dob_tc(), df_with_string_dob).unwrap();
+        let mut cdf2 = ContextualizedDataFrame::new(onset_tc(), df_with_int_onset).unwrap();
+        let tables = &mut [&mut cdf1, &mut cdf2];
+
+        DateToAgeStrategy { strict: true }
+            .transform(tables)
+            .unwrap();
+
+        assert_eq!(
+            cdf2.data().column("onset").unwrap(),
+            &Column::new(
+                "onset".into(),
+                vec![
+                    AnyValue::Null,
+                    AnyValue::String("P1Y"),
+                    AnyValue::String("P45Y")
+                ]
+            )
+        );
+    }
+
+    #[rstest]
+    fn test_date_to_age_date_before_birth(df_with_string_dob: DataFrame) {
+        let onset_df = df!(
+        "subject_id" => &["Alice", "Bob", "Charlie"],
+        "pneumonia" => &["Observed", "Observed", "Observed"],
+        "onset" => &["1995-05-31", "1991-01-01", "1979"],
+        )
+        .unwrap();
+        let mut cdf1 = ContextualizedDataFrame::new(dob_tc(), df_with_string_dob).unwrap();
+        let mut cdf2 = ContextualizedDataFrame::new(onset_tc(), onset_df).unwrap();
+        let tables = &mut [&mut cdf1, &mut cdf2];
+
+        let err = DateToAgeStrategy { strict: false }
+            .transform(tables)
+            .unwrap_err();
+
+        let StrategyError::DateToAgeError { info } = err else {
+            panic!("Wrong error")
+        };
+        let mut subjects: Vec<&str> = info.iter().map(|i| i.subject_id.as_str()).collect();
+        subjects.sort();
+        assert_eq!(subjects, vec!["Alice", "Charlie"]);
     }
 
     #[rstest]
@@ -724,6 +857,31 @@ mod tests {
         assert_eq!(onset_age_bob, "P1M");
     }
 
+    #[rstest]
+    #[case("1990-12-01", "1991", "P1Y")]
+    #[case("1990", "1995", "P5Y")]
+    #[case("1990", "1990-06-15", "P0Y")]
+    #[case("1990-12-01", "2019-03", "P28Y3M")]
+    #[case("1990-12", "2019-03-20", "P28Y3M")]
+    #[case("1990-12", "1990-12-15", "P0Y")]
+    fn test_date_and_dob_to_age_partial_dates(
+        #[case] dob: &str,
+        #[case] date: &str,
+        #[case] expected_age: &str,
+    ) {
+        assert_eq!(
+            DateToAgeStrategy::date_and_dob_to_age(dob.to_string(), date).unwrap(),
+            expected_age
+        );
+    }
+
+    #[rstest]
+    #[case("1990-12-01", "1989")]
+    #[case("1990-12", "1990-11")]
+    fn test_date_and_dob_to_age_partial_dates_before_birth(#[case] dob: &str, #[case] date: &str) {
+        assert!(DateToAgeStrategy::date_and_dob_to_age(dob.to_string(), date).is_err());
+    }
+
     #[rstest]
     fn test_date_and_dob_to_age_err() {
         let result = DateToAgeStrategy::date_and_dob_to_age("2000-13-50".to_string(), "2025-11-21");
@@ -0,0 +1,193 @@
+use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
+use crate::transform::error::StrategyError;
+use crate::transform::strategies::traits::Strategy;
+use log::info;
+use polars::prelude::{DataType, IdxCa, IdxSize, StringChunked};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// The configuration of a [`DeduplicateRowsStrategy`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DeduplicateRows {
+    pub table_name: String,
+    /// The columns, which decide whether two rows are duplicates. If empty, all columns are compared.
+    #[serde(default)]
+    pub columns: Vec<String>,
+}
+
+/// Drops rows, which are exact duplicates of an earlier row.
+///
+/// # Example
+///
+/// The table
+/// ```csv
+/// PatientId, phenotype
+/// P001, HP:0001250
+/// P001, HP:0001250
+/// P002, HP:0001250
+/// ```
+///
+/// will be mapped to
+/// ```csv
+/// PatientId, phenotype
+/// P001, HP:0001250
+/// P002, HP:0001250
+/// ```
+///
+/// The first occurrence of a row is kept and the order of the rows is preserved.
+/// If columns are configured, only their cells are compared.
+#[derive(Debug)]
+pub struct DeduplicateRowsStrategy {
+    table_name: String,
+    columns: Vec<String>,
+}
+
+impl DeduplicateRowsStrategy {
+    pub fn new(config: &DeduplicateRows) -> Self {
+        DeduplicateRowsStrategy {
+            table_name: config.table_name.clone(),
+            columns: config.columns.clone(),
+        }
+    }
+
+    fn key_columns(
+        &self,
+        table: &ContextualizedDataFrame,
+    ) -> Result<Vec<StringChunked>, StrategyError> {
+        let data = table.data();
+        let column_names: Vec<&str> = match self.columns.is_empty() {
+            true => data
+                .get_column_names()
+                .into_iter()
+                .map(|name| name.as_str())
+                .collect(),
+            false => self.columns.iter().map(String::as_str).collect(),
+        };
+
+        column_names
+            .into_iter()
+            .map(|name| Ok(data.column(name)?.cast(&DataType::String)?.str()?.clone()))
+            .collect()
+    }
+}
+
+impl Strategy for DeduplicateRowsStrategy {
+    fn is_valid(&self, tables: &[&mut ContextualizedDataFrame]) -> bool {
+        tables
+            .iter()
+            .any(|table| table.context().name() == self.table_name)
+    }
+
+    fn supports_chunks_of(&self, table_name: &str) -> bool {
+        table_name != self.table_name
+    }
+
+    fn internal_transform(
+        &self,
+        tables: &mut [&mut ContextualizedDataFrame],
+    ) -> Result<(), StrategyError> {
+        info!("Applying DeduplicateRows strategy to data.");
+
+        for table in tables
+            .iter_mut()
+            .filter(|table| table.context().name() == self.table_name)
+        {
+            let key_columns = self.key_columns(table)?;
+
+            let mut seen_rows = HashSet::new();
+            let mut kept_rows: Vec<IdxSize> = vec![];
+            for row_idx in 0..table.data().height() {
+                let row: Vec<Option<&str>> =
+                    key_columns.iter().map(|col| col.get(row_idx)).collect();
+                if seen_rows.insert(row) {
+                    kept_rows.push(row_idx as IdxSize);
+                }
+            }
+
+            if kept_rows.len() == table.data().height() {
+                continue;
+            }
+
+            let deduplicated_data = table
+                .data()
+                .take(&IdxCa::from_vec("row_idx".into(), kept_rows))?;
+            **table = ContextualizedDataFrame::new(table.context().clone(), deduplicated_data)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::context::{Context, TimeElementType};
+    use crate::config::table_context::{SeriesContext, TableContext};
+    use crate::config::traits::SeriesContextBuilding;
+    use polars::frame::DataFrame;
+    use polars::prelude::Column;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn phenotype_cdf() -> ContextualizedDataFrame {
+        let tc = TableContext::new(
+            "phenotypes",
+            vec![
+                SeriesContext::from_identifier("subject_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("phenotype").with_data_context(Context::Hpo),
+                SeriesContext::from_identifier("onset")
+                    .with_data_context(Context::Onset(TimeElementType::Age)),
+            ],
+        );
+        let df = DataFrame::new(
+            5,
+            vec![
+                Column::new("subject_id".into(), ["P1", "P1", "P2", "P1", "P1"]),
+                Column::new(
+                    "phenotype".into(),
+                    [
+                        "HP:0001250",
+                        "HP:0001250",
+                        "HP:0001250",
+                        "HP:0001166",
+                        "HP:0001250",
+                    ],
+                ),
+                Column::new("onset".into(), [Some("P1Y"), Some("P1Y"), None, None, None]),
+            ],
+        )
+        .unwrap();
+        ContextualizedDataFrame::new(tc, df).unwrap()
+    }
+
+    #[rstest]
+    #[case(vec![], vec!["P1", "P2", "P1", "P1"], vec!["HP:0001250", "HP:0001250", "HP:0001166", "HP:0001250"])]
+    #[case(
+        vec!["subject_id".to_string(), "phenotype".to_string()],
+        vec!["P1", "P2", "P1"],
+        vec!["HP:0001250", "HP:0001250", "HP:0001166"]
+    )]
+    fn test_deduplicate_rows(
+        #[case] columns: Vec<String>,
+        #[case] expected_ids: Vec<&str>,
+        #[case] expected_phenotypes: Vec<&str>,
+    ) {
+        let mut cdf = phenotype_cdf();
+
+        let strategy = DeduplicateRowsStrategy::new(&DeduplicateRows {
+            table_name: "phenotypes".to_string(),
+            columns,
+        });
+        strategy.transform(&mut [&mut cdf]).unwrap();
+
+        assert_eq!(
+            cdf.data().column("subject_id").unwrap(),
+            &Column::new("subject_id".into(), expected_ids)
+        );
+        assert_eq!(
+            cdf.data().column("phenotype").unwrap(),
+            &Column::new("phenotype".into(), expected_phenotypes)
+        );
+    }
+}
@@ -9,8 +9,28 @@ pub use age_to_iso8601::AgeToIso8601Strategy;
 
 pub mod date_to_age;
 pub use date_to_age::DateToAgeStrategy;
+pub mod deduplicate_rows;
+pub use deduplicate_rows::DeduplicateRowsStrategy;
 pub mod multi_hpo_col_expansion;
 pub use multi_hpo_col_expansion::MultiHPOColExpansionStrategy;
+pub mod observation_status_encoding;
+pub use observation_status_encoding::ObservationStatusEncodingStrategy;
+pub mod normalize_subject_id;
+pub use normalize_subject_id::NormalizeSubjectIdStrategy;
+pub mod column_concatenation;
+pub use column_concatenation::ColumnConcatenationStrategy;
+pub mod numeric_range_bucketing;
+pub use numeric_range_bucketing::NumericRangeBucketingStrategy;
+pub mod regex_extract;
+pub use regex_extract::RegexExtractStrategy;
+pub mod split_delimited_column;
+pub use split_delimited_column::SplitDelimitedColumnStrategy;
+pub mod unit_normalisation;
+pub use unit_normalisation::UnitNormalisationStrategy;
+pub mod onset_category;
+pub use onset_category::OnsetCategoryStrategy;
+pub mod whitespace_normalisation;
+pub use whitespace_normalisation::WhitespaceNormalisationStrategy;
 
 pub mod hpo_disease_splitter;
 pub mod strategy_factory;
@@ -0,0 +1,272 @@
+use crate::config::context::Context;
+use crate::error::ConstructionError;
+use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
+use crate::extract::enums::Filter;
+use crate::transform::error::StrategyError;
+use crate::transform::strategies::traits::Strategy;
+use log::info;
+use polars::prelude::{DataType, IntoSeries, StringChunked};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// A regex based rewrite of subject ids. `replacement` may reference capture groups, e.g. `$1`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SubjectIdRewrite {
+    pub pattern: String,
+    pub replacement: String,
+}
+
+/// The transformations applied to subject ids. They are applied in the order of the fields.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SubjectIdFormat {
+    #[serde(default)]
+    pub rewrite: Option<SubjectIdRewrite>,
+    #[serde(default)]
+    pub strip_prefix: Option<String>,
+    /// Pads the trailing number of an id with zeros to this width: P1 -> P001 for a width of 3.
+    #[serde(default)]
+    pub zero_pad_width: Option<usize>,
+    #[serde(default)]
+    pub add_prefix: Option<String>,
+}
+
+/// Brings all subject ids into one format, so that the same patient is not split into several phenopackets.
+///
+/// # Example
+///
+/// With `zero_pad_width: 3` the tables
+/// ```csv
+/// PatientId, sex
+/// P1, MALE
+/// ```
+/// ```csv
+/// PatientId, phenotype
+/// P001, HP:0001250
+/// ```
+///
+/// will both refer to the subject `P001`.
+#[derive(Debug)]
+pub struct NormalizeSubjectIdStrategy {
+    rewrite: Option<(Regex, String)>,
+    strip_prefix: Option<String>,
+    zero_pad_width: Option<usize>,
+    add_prefix: Option<String>,
+}
+
+impl NormalizeSubjectIdStrategy {
+    pub fn try_new(format: &SubjectIdFormat) -> Result<Self, ConstructionError> {
+        let rewrite = format
+            .rewrite
+            .as_ref()
+            .map(|rewrite| {
+                Regex::new(&rewrite.pattern)
+                    .map(|re| (re, rewrite.replacement.clone()))
+                    .map_err(|err| ConstructionError::InvalidRegex {
+                        pattern: rewrite.pattern.clone(),
+                        reason: err.to_string(),
+                    })
+            })
+            .transpose()?;
+
+        Ok(NormalizeSubjectIdStrategy {
+            rewrite,
+            strip_prefix: format.strip_prefix.clone(),
+            zero_pad_width: format.zero_pad_width,
+            add_prefix: format.add_prefix.clone(),
+        })
+    }
+
+    fn normalize(&self, subject_id: &str) -> String {
+        let mut normalized = subject_id.trim().to_string();
+
+        if let Some((re, replacement)) = &self.rewrite {
+            normalized = re.replace(&normalized, replacement.as_str()).into_owned();
+        }
+
+        if let Some(prefix) = &self.strip_prefix
+            && let Some(stripped) = normalized.strip_prefix(prefix.as_str())
+        {
+            normalized = stripped.to_string();
+        }
+
+        if let Some(width) = self.zero_pad_width {
+            normalized = Self::zero_pad(&normalized, width).into_owned();
+        }
+
+        if let Some(prefix) = &self.add_prefix
+            && !normalized.starts_with(prefix.as_str())
+        {
+            normalized = format!("{prefix}{normalized}");
+        }
+
+        normalized
+    }
+
+    fn zero_pad(subject_id: &str, width: usize) -> Cow<'_, str> {
+        let digits_start = subject_id
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| c.is_ascii_digit())
+            .last()
+            .map(|(idx, _)| idx);
+
+        match digits_start {
+            Some(idx) => {
+                let (head, digits) = subject_id.split_at(idx);
+                let number = digits.trim_start_matches('0');
+                let number = if number.is_empty() { "0" } else { number };
+                Cow::Owned(format!("{head}{number:0>width$}"))
+            }
+            None => Cow::Borrowed(subject_id),
+        }
+    }
+}
+
+impl Strategy for NormalizeSubjectIdStrategy {
+    fn is_valid(&self, tables: &[&mut ContextualizedDataFrame]) -> bool {
+        tables.iter().any(|table| {
+            !table
+                .filter_columns()
+                .where_header_context(Filter::Is(&Context::None))
+                .where_data_context(Filter::Is(&Context::SubjectId))
+                .collect()
+                .is_empty()
+        })
+    }
+
+    fn internal_transform(
+        &self,
+        tables: &mut [&mut ContextualizedDataFrame],
+    ) -> Result<(), StrategyError> {
+        info!("Applying NormalizeSubjectId strategy to data.");
+
+        for table in tables.iter_mut() {
+            let column_names = table
+                .filter_columns()
+                .where_header_context(Filter::Is(&Context::None))
+                .where_data_context(Filter::Is(&Context::SubjectId))
+                .collect_owned_names();
+
+            for col_name in column_names {
+                let col = table.data().column(&col_name)?;
+
+                let cast_col = if col.dtype() != &DataType::String {
+                    &col.cast(&DataType::String)?
+                } else {
+                    col
+                };
+
+                let normalized_column: StringChunked = cast_col
+                    .str()?
+                    .into_iter()
+                    .map(|subject_id| subject_id.map(|subject_id| self.normalize(subject_id)))
+                    .collect();
+                let normalized_column = normalized_column.with_name(col.name().clone());
+                table
+                    .builder()
+                    .replace_col(&col_name, normalized_column.into_series())?
+                    .build()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::table_context::{Identifier, SeriesContext, TableContext};
+    use crate::config::traits::SeriesContextBuilding;
+    use polars::frame::DataFrame;
+    use polars::prelude::Column;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn subject_cdf(name: &str, subject_ids: &[&str]) -> ContextualizedDataFrame {
+        let tc = TableContext::new(
+            name.to_string(),
+            vec![
+                SeriesContext::from_identifier(Identifier::from("subject_id"))
+                    .with_data_context(Context::SubjectId),
+            ],
+        );
+        let col = Column::new("subject_id".into(), subject_ids);
+        let df = DataFrame::new(col.len(), vec![col]).unwrap();
+        ContextualizedDataFrame::new(tc, df).unwrap()
+    }
+
+    fn subject_ids(cdf: &ContextualizedDataFrame) -> Vec<String> {
+        cdf.data()
+            .column("subject_id")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[rstest]
+    fn test_zero_padding_merges_subjects_across_tables() {
+        let mut basic_info = subject_cdf("basic_info", &["P1", "P2"]);
+        let mut phenotypes = subject_cdf("phenotypes", &["P001", "P002"]);
+
+        let strategy = NormalizeSubjectIdStrategy::try_new(&SubjectIdFormat {
+            zero_pad_width: Some(3),
+            ..Default::default()
+        })
+        .unwrap();
+        strategy
+            .transform(&mut [&mut basic_info, &mut phenotypes])
+            .unwrap();
+
+        assert_eq!(subject_ids(&basic_info), vec!["P001", "P002"]);
+        assert_eq!(subject_ids(&basic_info), subject_ids(&phenotypes));
+    }
+
+    #[rstest]
+    #[case(SubjectIdFormat { zero_pad_width: Some(4), ..Default::default() }, "P12", "P0012")]
+    #[case(SubjectIdFormat { zero_pad_width: Some(2), ..Default::default() }, "P0012", "P12")]
+    #[case(SubjectIdFormat { zero_pad_width: Some(3), ..Default::default() }, "abc", "abc")]
+    #[case(SubjectIdFormat { strip_prefix: Some("patient_".to_string()), ..Default::default() }, "patient_7", "7")]
+    #[case(SubjectIdFormat { add_prefix: Some("P".to_string()), zero_pad_width: Some(3), ..Default::default() }, "7", "P007")]
+    #[case(SubjectIdFormat { add_prefix: Some("P".to_string()), ..Default::default() }, "P7", "P7")]
+    #[case(
+        SubjectIdFormat {
+            rewrite: Some(SubjectIdRewrite { pattern: r"^ID-(\d+)-X$".to_string(), replacement: "P$1".to_string() }),
+            zero_pad_width: Some(3),
+            ..Default::default()
+        },
+        "ID-5-X",
+        "P005"
+    )]
+    fn test_normalize(
+        #[case] format: SubjectIdFormat,
+        #[case] subject_id: &str,
+        #[case] expected: &str,
+    ) {
+        let strategy = NormalizeSubjectIdStrategy::try_new(&format).unwrap();
+
+        assert_eq!(strategy.normalize(subject_id), expected);
+    }
+
+    #[rstest]
+    fn test_invalid_regex() {
+        let result = NormalizeSubjectIdStrategy::try_new(&SubjectIdFormat {
+            rewrite: Some(SubjectIdRewrite {
+                pattern: "(".to_string(),
+                replacement: String::new(),
+            }),
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            result,
+            Err(ConstructionError::InvalidRegex { pattern, .. }) if pattern == "("
+        ));
+    }
+}
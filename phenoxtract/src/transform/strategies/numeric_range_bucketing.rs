@@ -0,0 +1,301 @@
+use crate::config::table_context::Identifier;
+use crate::error::ConstructionError;
+use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
+use crate::transform::error::StrategyError::MappingError;
+use crate::transform::error::{MappingErrorInfo, PushMappingError, StrategyError};
+use crate::transform::strategies::traits::Strategy;
+use log::info;
+use polars::prelude::{DataType, IntoSeries, StringChunked};
+use serde::{Deserialize, Serialize};
+use std::any::type_name;
+use std::collections::HashSet;
+
+/// A bucket of the [`NumericRangeBucketingStrategy`]: `(lower, upper, label)`.
+///
+/// The lower bound is inclusive, the upper bound is exclusive. A missing upper bound is unbounded.
+pub type NumericBucket = (f64, Option<f64>, String);
+
+/// The configuration of a [`NumericRangeBucketingStrategy`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct NumericRangeBucketing {
+    pub table_name: String,
+    pub column: String,
+    pub buckets: Vec<NumericBucket>,
+    /// The label of values, which fall into none of the buckets. If absent, these values become null.
+    #[serde(default)]
+    pub fill: Option<String>,
+}
+
+/// Maps the numeric values of a single column to ordinal category labels.
+///
+/// # Example
+///
+/// With the buckets `(0, 18, pediatric), (18, 65, adult), (65, None, geriatric)` the table
+/// ```csv
+/// PatientId, age
+/// P001, 4
+/// P002, 18
+/// P003, 80
+/// P004,
+/// ```
+///
+/// will be mapped to
+/// ```csv
+/// PatientId, age
+/// P001, pediatric
+/// P002, adult
+/// P003, geriatric
+/// P004,
+/// ```
+///
+/// # Errors
+///
+/// Cells, which are not numeric, are reported.
+#[derive(Debug)]
+pub struct NumericRangeBucketingStrategy {
+    table_name: String,
+    column: Identifier,
+    buckets: Vec<NumericBucket>,
+    fill: Option<String>,
+}
+
+impl NumericRangeBucketingStrategy {
+    pub fn try_new(config: &NumericRangeBucketing) -> Result<Self, ConstructionError> {
+        let mut buckets = config.buckets.clone();
+
+        if let Some((lower, upper, label)) = buckets
+            .iter()
+            .find(|(lower, upper, _)| upper.is_some_and(|upper| upper <= *lower))
+        {
+            return Err(ConstructionError::InvalidBucket {
+                label: label.clone(),
+                reason: format!("lower bound {lower} is not below upper bound {upper:?}"),
+            });
+        }
+
+        buckets.sort_by(|(lower_a, _, _), (lower_b, _, _)| lower_a.total_cmp(lower_b));
+
+        for window in buckets.windows(2) {
+            let ((_, upper, first), (lower, _, second)) = (&window[0], &window[1]);
+            if upper.is_none_or(|upper| upper > *lower) {
+                return Err(ConstructionError::OverlappingBuckets {
+                    first: first.clone(),
+                    second: second.clone(),
+                });
+            }
+        }
+
+        Ok(NumericRangeBucketingStrategy {
+            table_name: config.table_name.clone(),
+            column: Identifier::Single(config.column.clone()),
+            buckets,
+            fill: config.fill.clone(),
+        })
+    }
+
+    fn bucket(&self, value: f64) -> Option<&str> {
+        self.buckets
+            .iter()
+            .find(|(lower, upper, _)| value >= *lower && upper.is_none_or(|upper| value < upper))
+            .map(|(_, _, label)| label.as_str())
+            .or(self.fill.as_deref())
+    }
+}
+
+impl Strategy for NumericRangeBucketingStrategy {
+    fn is_valid(&self, tables: &[&mut ContextualizedDataFrame]) -> bool {
+        tables.iter().any(|table| {
+            table.context().name() == self.table_name
+                && !table.identify_columns(&self.column).is_empty()
+        })
+    }
+
+    fn internal_transform(
+        &self,
+        tables: &mut [&mut ContextualizedDataFrame],
+    ) -> Result<(), StrategyError> {
+        info!("Applying NumericRangeBucketing strategy to data.");
+
+        let mut error_info: HashSet<MappingErrorInfo> = HashSet::new();
+
+        for table in tables
+            .iter_mut()
+            .filter(|table| table.context().name() == self.table_name)
+        {
+            let column_names: Vec<String> = table
+                .identify_columns(&self.column)
+                .iter()
+                .map(|col| col.name().to_string())
+                .collect();
+
+            for col_name in column_names {
+                let col = table.data().column(&col_name)?;
+                let cast_col = col.cast(&DataType::String)?;
+
+                let bucketed_column: StringChunked = cast_col
+                    .str()?
+                    .into_iter()
+                    .map(|cell_value| {
+                        let cell_value = cell_value?;
+                        match cell_value.trim().parse::<f64>() {
+                            Ok(value) => self.bucket(value).map(str::to_string),
+                            Err(_) => {
+                                error_info.insert_error(
+                                    col_name.clone(),
+                                    table.context().name().to_string(),
+                                    cell_value.to_string(),
+                                    vec![],
+                                );
+                                Some(cell_value.to_string())
+                            }
+                        }
+                    })
+                    .collect();
+                let bucketed_column = bucketed_column.with_name(col.name().clone());
+                table
+                    .builder()
+                    .replace_col(&col_name, bucketed_column.into_series())?
+                    .build()?;
+            }
+        }
+
+        if !error_info.is_empty() {
+            Err(MappingError {
+                strategy_name: type_name::<Self>().split("::").last().unwrap().to_string(),
+                message: "These values are not numeric and could not be bucketed.".to_string(),
+                info: error_info.into_iter().collect(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::context::Context;
+    use crate::config::table_context::{SeriesContext, TableContext};
+    use crate::config::traits::SeriesContextBuilding;
+    use polars::frame::DataFrame;
+    use polars::prelude::{AnyValue, Column};
+    use pretty_assertions::assert_eq;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn config() -> NumericRangeBucketing {
+        NumericRangeBucketing {
+            table_name: "patient_data".to_string(),
+            column: "age".to_string(),
+            buckets: vec![
+                (18.0, Some(65.0), "adult".to_string()),
+                (0.0, Some(18.0), "pediatric".to_string()),
+                (65.0, None, "geriatric".to_string()),
+            ],
+            fill: None,
+        }
+    }
+
+    fn age_cdf(ages: &[AnyValue]) -> ContextualizedDataFrame {
+        let tc = TableContext::new(
+            "patient_data".to_string(),
+            vec![
+                SeriesContext::from_identifier(Identifier::from("subject_id"))
+                    .with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier(Identifier::from("age")),
+            ],
+        );
+        let ids: Vec<String> = (0..ages.len()).map(|i| format!("P{i}")).collect();
+        let id_col = Column::new("subject_id".into(), ids);
+        let age_col = Column::new("age".into(), ages);
+        let df = DataFrame::new(id_col.len(), vec![id_col, age_col]).unwrap();
+        ContextualizedDataFrame::new(tc, df).unwrap()
+    }
+
+    #[rstest]
+    fn test_numeric_range_bucketing(config: NumericRangeBucketing) {
+        let mut cdf = age_cdf(&[
+            AnyValue::Int32(4),
+            AnyValue::Int32(18),
+            AnyValue::Int32(80),
+            AnyValue::Null,
+            AnyValue::Int32(-1),
+        ]);
+
+        let strategy = NumericRangeBucketingStrategy::try_new(&config).unwrap();
+        strategy.transform(&mut [&mut cdf]).unwrap();
+
+        let expected = Column::new(
+            "age".into(),
+            [
+                Some("pediatric"),
+                Some("adult"),
+                Some("geriatric"),
+                None,
+                None,
+            ],
+        );
+        assert_eq!(cdf.data().column("age").unwrap(), &expected);
+    }
+
+    #[rstest]
+    fn test_numeric_range_bucketing_fill(mut config: NumericRangeBucketing) {
+        config.fill = Some("unknown".to_string());
+        let mut cdf = age_cdf(&[AnyValue::Float64(-0.5), AnyValue::Float64(17.9)]);
+
+        let strategy = NumericRangeBucketingStrategy::try_new(&config).unwrap();
+        strategy.transform(&mut [&mut cdf]).unwrap();
+
+        let expected = Column::new("age".into(), ["unknown", "pediatric"]);
+        assert_eq!(cdf.data().column("age").unwrap(), &expected);
+    }
+
+    #[rstest]
+    fn test_numeric_range_bucketing_reports_non_numeric(config: NumericRangeBucketing) {
+        let mut cdf = age_cdf(&[AnyValue::String("old"), AnyValue::String("42")]);
+
+        let strategy = NumericRangeBucketingStrategy::try_new(&config).unwrap();
+        let result = strategy.transform(&mut [&mut cdf]);
+
+        let Err(StrategyError::MappingError { info, .. }) = result else {
+            panic!("Expected a MappingError, got {result:?}")
+        };
+        assert_eq!(
+            info,
+            vec![MappingErrorInfo {
+                column: "age".to_string(),
+                table: "patient_data".to_string(),
+                old_value: "old".to_string(),
+                possible_mappings: vec![],
+            }]
+        );
+    }
+
+    #[rstest]
+    fn test_numeric_range_bucketing_rejects_overlap(mut config: NumericRangeBucketing) {
+        config
+            .buckets
+            .push((60.0, Some(70.0), "senior".to_string()));
+
+        let result = NumericRangeBucketingStrategy::try_new(&config);
+
+        assert!(matches!(
+            result,
+            Err(ConstructionError::OverlappingBuckets { first, second })
+                if first == "adult" && second == "senior"
+        ));
+    }
+
+    #[rstest]
+    fn test_numeric_range_bucketing_rejects_empty_range(mut config: NumericRangeBucketing) {
+        config.buckets = vec![(10.0, Some(5.0), "inverted".to_string())];
+
+        let result = NumericRangeBucketingStrategy::try_new(&config);
+
+        assert!(matches!(
+            result,
+            Err(ConstructionError::InvalidBucket { label, .. }) if label == "inverted"
+        ));
+    }
+}
@@ -0,0 +1,284 @@
+use crate::config::context::Context;
+use crate::config::table_context::{AliasMap, OutputDataType};
+use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
+use crate::extract::enums::Filter;
+use crate::transform::data_processing::casting::polars_column_cast_specific;
+use crate::transform::error::StrategyError;
+use crate::transform::strategies::traits::Strategy;
+use log::{info, warn};
+use polars::prelude::{Column, DataType, StringChunked};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The cell values, which mean that a phenotype or disease was observed or excluded.
+///
+/// The values are matched case-insensitively and without surrounding whitespace.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ObservationStatusEncoding {
+    #[serde(default = "default_observed")]
+    pub observed: Vec<String>,
+    #[serde(default = "default_excluded")]
+    pub excluded: Vec<String>,
+}
+
+impl Default for ObservationStatusEncoding {
+    fn default() -> Self {
+        Self {
+            observed: default_observed(),
+            excluded: default_excluded(),
+        }
+    }
+}
+
+fn default_observed() -> Vec<String> {
+    ["observed", "present", "yes", "y", "true", "1", "+"]
+        .map(String::from)
+        .to_vec()
+}
+
+fn default_excluded() -> Vec<String> {
+    ["not observed", "absent", "no", "n", "false", "0", "-"]
+        .map(String::from)
+        .to_vec()
+}
+
+/// Converts string observation statuses (e.g. "Y"/"N", "+"/"-" or "present"/"absent") into booleans.
+///
+/// Applies to all string columns with the data context [`Context::ObservationStatus`], e.g. the columns
+/// with an HPO term in their header. The vocabulary is turned into an [`AliasMap`] with boolean output,
+/// so the cells are converted just like by the [`crate::transform::strategies::AliasMapStrategy`].
+///
+/// Cells, which are in neither vocabulary, are set to null and a warning is logged.
+///
+/// # Example
+///
+/// ```csv
+/// PatientId, HP:0001250, HP:0001166
+/// P001, Y, +
+/// P002, N, maybe
+/// ```
+/// is mapped to
+/// ```csv
+/// PatientId, HP:0001250, HP:0001166
+/// P001, true, true
+/// P002, false,
+/// ```
+#[derive(Debug)]
+pub struct ObservationStatusEncodingStrategy {
+    alias_map: AliasMap,
+}
+
+impl ObservationStatusEncodingStrategy {
+    pub fn new(encoding: &ObservationStatusEncoding) -> Self {
+        let observed = encoding
+            .observed
+            .iter()
+            .map(|value| (value.trim().to_lowercase(), Some(true.to_string())));
+        let excluded = encoding
+            .excluded
+            .iter()
+            .map(|value| (value.trim().to_lowercase(), Some(false.to_string())));
+
+        Self {
+            alias_map: AliasMap::new(
+                observed.chain(excluded).collect::<HashMap<_, _>>(),
+                OutputDataType::Boolean,
+            ),
+        }
+    }
+}
+
+impl Default for ObservationStatusEncodingStrategy {
+    fn default() -> Self {
+        Self::new(&ObservationStatusEncoding::default())
+    }
+}
+
+impl Strategy for ObservationStatusEncodingStrategy {
+    fn is_valid(&self, tables: &[&mut ContextualizedDataFrame]) -> bool {
+        tables.iter().any(|table| {
+            !table
+                .filter_columns()
+                .where_data_context(Filter::Is(&Context::ObservationStatus))
+                .where_data_type(Filter::Is(&DataType::String))
+                .collect()
+                .is_empty()
+        })
+    }
+
+    fn internal_transform(
+        &self,
+        tables: &mut [&mut ContextualizedDataFrame],
+    ) -> Result<(), StrategyError> {
+        info!("Applying ObservationStatusEncoding strategy to data.");
+        let hash_map = self.alias_map.get_hash_map();
+
+        for table in tables.iter_mut() {
+            let column_names = table
+                .filter_columns()
+                .where_data_context(Filter::Is(&Context::ObservationStatus))
+                .where_data_type(Filter::Is(&DataType::String))
+                .collect_owned_names();
+
+            for col_name in column_names {
+                let col = table.data().column(&col_name)?;
+
+                let mut unrecognised: Vec<&str> = vec![];
+                let encoded: StringChunked = col
+                    .str()?
+                    .iter()
+                    .map(|cell_value| {
+                        let cell_value = cell_value?;
+                        let encoded = hash_map
+                            .get(cell_value.trim().to_lowercase().as_str())
+                            .cloned()
+                            .flatten();
+                        if encoded.is_none() && !cell_value.trim().is_empty() {
+                            unrecognised.push(cell_value);
+                        }
+                        encoded
+                    })
+                    .collect();
+
+                if !unrecognised.is_empty() {
+                    warn!(
+                        "Set {} cell(s) in column '{}' of table '{}' to null, because they are not a known observation status: {:?}",
+                        unrecognised.len(),
+                        col_name,
+                        table.context().name(),
+                        unrecognised
+                    );
+                }
+
+                let encoded_col = polars_column_cast_specific(
+                    &Column::new(col_name.as_str().into(), encoded),
+                    &self.alias_map.get_output_dtype().as_polars(),
+                )?;
+                table
+                    .builder()
+                    .replace_col(&col_name, encoded_col.take_materialized_series())?
+                    .build()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::table_context::{SeriesContext, TableContext};
+    use crate::config::traits::SeriesContextBuilding;
+    use crate::test_suite::cdf_generation::default_patient_id;
+    use crate::test_suite::component_building::build_test_phenopacket_builder;
+    use crate::test_suite::phenopacket_component_generation::default_phenotype_oc;
+    use crate::transform::collecting::hpo_in_header_collector::HpoInHeaderCollector;
+    use crate::transform::collecting::traits::Collect;
+    use polars::frame::DataFrame;
+    use polars::prelude::AnyValue;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn observation_status_cdf(observation_status: &str) -> ContextualizedDataFrame {
+        let hpo_id = default_phenotype_oc().id;
+        let tc = TableContext::new(
+            "phenotypes",
+            vec![
+                SeriesContext::from_identifier("subject_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier(hpo_id.as_str())
+                    .with_header_context(Context::Hpo)
+                    .with_data_context(Context::ObservationStatus),
+            ],
+        );
+        let df = DataFrame::new(
+            1,
+            vec![
+                Column::new("subject_id".into(), [default_patient_id()]),
+                Column::new(hpo_id.as_str().into(), [observation_status]),
+            ],
+        )
+        .unwrap();
+        ContextualizedDataFrame::new(tc, df).unwrap()
+    }
+
+    #[rstest]
+    #[case("Observed", false)]
+    #[case("Not observed", true)]
+    #[case("Y", false)]
+    #[case("N", true)]
+    #[case("1", false)]
+    #[case("0", true)]
+    #[case("+", false)]
+    #[case("-", true)]
+    #[case(" present ", false)]
+    #[case("ABSENT", true)]
+    fn test_observation_status_encoding_collected(
+        #[case] observation_status: &str,
+        #[case] expected_excluded: bool,
+    ) {
+        let mut cdf = observation_status_cdf(observation_status);
+
+        ObservationStatusEncodingStrategy::default()
+            .transform(&mut [&mut cdf])
+            .unwrap();
+
+        let mut builder = build_test_phenopacket_builder();
+        HpoInHeaderCollector::default()
+            .collect(&mut builder, &[cdf], &default_patient_id())
+            .unwrap();
+
+        let phenopackets = builder.build();
+        assert_eq!(
+            phenopackets[0].phenotypic_features[0].excluded,
+            expected_excluded
+        );
+    }
+
+    #[rstest]
+    fn test_observation_status_encoding_custom() {
+        let encoding = ObservationStatusEncoding {
+            observed: vec!["ja".to_string()],
+            excluded: vec!["nein".to_string()],
+        };
+        let tc = TableContext::new(
+            "phenotypes",
+            vec![
+                SeriesContext::from_identifier("subject_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("HP:0001250")
+                    .with_header_context(Context::Hpo)
+                    .with_data_context(Context::ObservationStatus),
+            ],
+        );
+        let df = DataFrame::new(
+            4,
+            vec![
+                Column::new("subject_id".into(), ["P1", "P2", "P3", "P4"]),
+                Column::new(
+                    "HP:0001250".into(),
+                    [Some("Ja"), Some("nein"), Some("Y"), None],
+                ),
+            ],
+        )
+        .unwrap();
+        let mut cdf = ContextualizedDataFrame::new(tc, df).unwrap();
+
+        ObservationStatusEncodingStrategy::new(&encoding)
+            .transform(&mut [&mut cdf])
+            .unwrap();
+
+        assert_eq!(
+            cdf.data().column("HP:0001250").unwrap(),
+            &Column::new(
+                "HP:0001250".into(),
+                [
+                    AnyValue::Boolean(true),
+                    AnyValue::Boolean(false),
+                    AnyValue::Null,
+                    AnyValue::Null
+                ]
+            )
+        );
+    }
+}
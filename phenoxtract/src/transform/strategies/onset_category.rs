@@ -0,0 +1,288 @@
+use crate::config::context::Context;
+use crate::error::ConstructionError;
+use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
+use crate::extract::enums::Filter;
+use crate::transform::error::StrategyError::MappingError;
+use crate::transform::error::{MappingErrorInfo, PushMappingError, StrategyError};
+use crate::transform::strategies::traits::Strategy;
+use crate::transform::bidict_library::BiDictLibrary;
+use crate::transform::utils::{
+    is_iso8601_duration, try_parse_time_element, try_resolve_hpo_onset_term,
+};
+use log::info;
+use polars::prelude::{DataType, IntoSeries};
+use std::any::type_name;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+#[derive(Debug)]
+/// Maps onset categories (e.g. "congenital", "childhood", "adult") to time elements.
+///
+/// Every category is mapped either to an HPO onset term, which can be given as id or label and is resolved
+/// through the HPO bidicts, or to a representative ISO8601 duration. Categories are matched case-insensitively.
+/// The strategy is applied to all onset columns, so it has to run before the onset is collected.
+///
+/// # Example
+///
+/// With the mapping `congenital -> HP:0003577, adult -> P18Y` the table
+/// ```csv
+/// PatientId, onset
+/// P001, Congenital
+/// P002, adult
+/// P003, P3Y
+/// ```
+///
+/// will be mapped to
+/// ```csv
+/// PatientId, onset
+/// P001, HP:0003577
+/// P002, P18Y
+/// P003, P3Y
+/// ```
+///
+/// # Errors
+///
+/// Cells, which are neither a configured category nor already a valid time element, are reported.
+pub struct OnsetCategoryStrategy {
+    categories: HashMap<String, String>,
+    hpo_bidict_lib: Arc<BiDictLibrary>,
+}
+
+impl OnsetCategoryStrategy {
+    pub fn try_new(
+        categories: &HashMap<String, String>,
+        hpo_bidict_lib: Arc<BiDictLibrary>,
+    ) -> Result<Self, ConstructionError> {
+        let categories = categories
+            .iter()
+            .map(|(category, target)| {
+                let canonical_target = if is_iso8601_duration(target) {
+                    target.clone()
+                } else if let Some((onset_term, _)) =
+                    try_resolve_hpo_onset_term(&hpo_bidict_lib, target)
+                {
+                    onset_term.id
+                } else {
+                    return Err(ConstructionError::InvalidOnsetCategory {
+                        category: category.clone(),
+                        target: target.clone(),
+                    });
+                };
+                Ok((Self::normalise(category), canonical_target))
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        Ok(OnsetCategoryStrategy {
+            categories,
+            hpo_bidict_lib,
+        })
+    }
+
+    fn normalise(category: &str) -> String {
+        category.trim().to_lowercase()
+    }
+
+    /// Whether a cell is already a time element or an HPO onset term.
+    fn is_onset(&self, cell_value: &str) -> bool {
+        try_parse_time_element(cell_value).is_some()
+            || try_resolve_hpo_onset_term(&self.hpo_bidict_lib, cell_value).is_some()
+    }
+}
+
+impl Strategy for OnsetCategoryStrategy {
+    fn is_valid(&self, tables: &[&mut ContextualizedDataFrame]) -> bool {
+        tables.iter().any(|table| {
+            !table
+                .filter_columns()
+                .where_header_context(Filter::Is(&Context::None))
+                .where_data_contexts_are(Context::ONSET_VARIANTS)
+                .collect()
+                .is_empty()
+        })
+    }
+
+    fn internal_transform(
+        &self,
+        tables: &mut [&mut ContextualizedDataFrame],
+    ) -> Result<(), StrategyError> {
+        info!("Applying OnsetCategory strategy to data.");
+
+        let mut error_info: HashSet<MappingErrorInfo> = HashSet::new();
+
+        for table in tables.iter_mut() {
+            let column_names = table
+                .filter_columns()
+                .where_header_context(Filter::Is(&Context::None))
+                .where_data_contexts_are(Context::ONSET_VARIANTS)
+                .collect_owned_names();
+
+            for col_name in column_names {
+                let col = table.data().column(&col_name)?;
+
+                let cast_col = if col.dtype() != &DataType::String {
+                    &col.cast(&DataType::String)?
+                } else {
+                    col
+                };
+
+                let mapped_column = cast_col.str()?.apply_mut(|cell_value| {
+                    if let Some(target) = self.categories.get(&Self::normalise(cell_value)) {
+                        target
+                    } else {
+                        if !cell_value.is_empty() && !self.is_onset(cell_value) {
+                            error_info.insert_error(
+                                col.name().to_string(),
+                                table.context().name().to_string(),
+                                cell_value.to_string(),
+                                vec![],
+                            );
+                        }
+                        cell_value
+                    }
+                });
+                table
+                    .builder()
+                    .replace_col(&col_name, mapped_column.into_series())?
+                    .build()?;
+            }
+        }
+
+        if !error_info.is_empty() {
+            Err(MappingError {
+                strategy_name: type_name::<Self>().split("::").last().unwrap().to_string(),
+                message: "These onset categories could not be mapped to a time element."
+                    .to_string(),
+                info: error_info.into_iter().collect(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::context::TimeElementType;
+    use crate::config::table_context::{Identifier, SeriesContext, TableContext};
+    use crate::config::traits::SeriesContextBuilding;
+    use crate::test_suite::component_building::build_test_hpo_bidict_library;
+    use polars::frame::DataFrame;
+    use polars::prelude::{AnyValue, Column};
+    use pretty_assertions::assert_eq;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn categories() -> HashMap<String, String> {
+        HashMap::from([
+            ("congenital".to_string(), "HP:0003577".to_string()),
+            ("Childhood".to_string(), "Childhood onset".to_string()),
+            ("adult".to_string(), "P18Y".to_string()),
+        ])
+    }
+
+    fn onset_cdf(onsets: &[Option<&str>]) -> ContextualizedDataFrame {
+        let tc = TableContext::new(
+            "patient_data".to_string(),
+            vec![
+                SeriesContext::from_identifier(Identifier::from("subject_ids"))
+                    .with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier(Identifier::from("onset"))
+                    .with_data_context(Context::Onset(TimeElementType::Age)),
+            ],
+        );
+        let ids: Vec<String> = (0..onsets.len()).map(|i| format!("P{i}")).collect();
+        let col_pid = Column::new("subject_ids".into(), ids);
+        let onset_col = Column::new("onset".into(), onsets);
+        let df = DataFrame::new(col_pid.len(), vec![col_pid, onset_col]).unwrap();
+        ContextualizedDataFrame::new(tc, df).unwrap()
+    }
+
+    fn build_strategy(categories: &HashMap<String, String>) -> OnsetCategoryStrategy {
+        OnsetCategoryStrategy::try_new(categories, Arc::new(build_test_hpo_bidict_library()))
+            .unwrap()
+    }
+
+    #[rstest]
+    #[case("Congenital", "HP:0003577")]
+    #[case(" childhood ", "HP:0011463")]
+    #[case("ADULT", "P18Y")]
+    fn test_onset_category_to_time_element(
+        categories: HashMap<String, String>,
+        #[case] category: &str,
+        #[case] expected: &str,
+    ) {
+        let mut cdf = onset_cdf(&[Some(category)]);
+
+        let strategy = build_strategy(&categories);
+        strategy.transform(&mut [&mut cdf]).unwrap();
+
+        let mapped = cdf.data().column("onset").unwrap().get(0).unwrap();
+        assert_eq!(mapped, AnyValue::String(expected));
+    }
+
+    #[rstest]
+    fn test_onset_category_keeps_hpo_onset_terms(categories: HashMap<String, String>) {
+        let mut cdf = onset_cdf(&[Some("Late onset"), Some("HP:0003584")]);
+
+        let strategy = build_strategy(&categories);
+        strategy.transform(&mut [&mut cdf]).unwrap();
+
+        let expected = Column::new("onset".into(), [Some("Late onset"), Some("HP:0003584")]);
+        assert_eq!(cdf.data().column("onset").unwrap(), &expected);
+    }
+
+    #[rstest]
+    fn test_onset_category_keeps_time_elements_and_nulls(categories: HashMap<String, String>) {
+        let mut cdf = onset_cdf(&[Some("P3Y"), None, Some("adult")]);
+
+        let strategy = build_strategy(&categories);
+        strategy.transform(&mut [&mut cdf]).unwrap();
+
+        let expected = Column::new("onset".into(), [Some("P3Y"), None, Some("P18Y")]);
+        assert_eq!(cdf.data().column("onset").unwrap(), &expected);
+    }
+
+    #[rstest]
+    fn test_onset_category_reports_unmapped(categories: HashMap<String, String>) {
+        let mut cdf = onset_cdf(&[Some("adolescent"), Some("adult")]);
+
+        let strategy = build_strategy(&categories);
+        let result = strategy.transform(&mut [&mut cdf]);
+
+        let Err(StrategyError::MappingError {
+            strategy_name,
+            info,
+            ..
+        }) = result
+        else {
+            panic!("Expected a MappingError, got {result:?}")
+        };
+        assert_eq!(strategy_name, "OnsetCategoryStrategy");
+        assert_eq!(
+            info,
+            vec![MappingErrorInfo {
+                column: "onset".to_string(),
+                table: "patient_data".to_string(),
+                old_value: "adolescent".to_string(),
+                possible_mappings: vec![],
+            }]
+        );
+    }
+
+    #[rstest]
+    #[case("teenage years")]
+    #[case("HP:0001250")]
+    fn test_onset_category_rejects_invalid_target(#[case] invalid_target: &str) {
+        let categories = HashMap::from([("teen".to_string(), invalid_target.to_string())]);
+
+        let result =
+            OnsetCategoryStrategy::try_new(&categories, Arc::new(build_test_hpo_bidict_library()));
+
+        assert!(matches!(
+            result,
+            Err(ConstructionError::InvalidOnsetCategory { category, target })
+                if category == "teen" && target == invalid_target
+        ));
+    }
+}
@@ -1,9 +1,10 @@
 use crate::config::context::ContextKind;
 use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
+use crate::ontology::obsolete_terms::ObsoleteReplacement;
 use crate::ontology::ontology_bidict::OntologyBiDict;
 use crate::transform::error::{MappingErrorInfo, PushMappingError, StrategyError};
 use crate::transform::strategies::traits::Strategy;
-use log::info;
+use log::{info, warn};
 
 use crate::extract::enums::Filter;
 
@@ -15,6 +16,15 @@ use std::cell::RefCell;
 use std::collections::HashSet;
 use std::sync::Arc;
 
+/// An obsolete ontology term, which the [`OntologyNormaliserStrategy`] replaced with its current equivalent.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObsoleteTermRemapping {
+    pub table: String,
+    pub column: String,
+    pub obsolete_id: String,
+    pub replacement_id: String,
+}
+
 #[derive(Debug)]
 /// A strategy that converts ontology labels in cells (or synonyms of them) to the corresponding IDs.
 ///
@@ -37,6 +47,9 @@ use std::sync::Arc;
 ///   maps between HPO labels and their primary identifiers. E.g. the HPO bidirectional dictionary
 /// * `data_context` - The specific [`crate::config::context::Context`] that columns must match to be processed
 ///   by this strategy. E.g. [`crate::config::context::Context::Hpo`]
+/// * `replace_obsolete` - If set, ids of obsolete terms are replaced by the id of the term they were `replaced_by`.
+///   Obsolete terms, which only have `consider` candidates, are left unchanged and a warning is logged.
+///   The replacements can be inspected via [`OntologyNormaliserStrategy::remapped_terms`].
 ///
 /// # Example
 ///
@@ -61,6 +74,8 @@ use std::sync::Arc;
 pub struct OntologyNormaliserStrategy {
     ontology_dict: Arc<OntologyBiDict>,
     data_context_kind: ContextKind,
+    replace_obsolete: bool,
+    remapped_terms: RefCell<HashSet<ObsoleteTermRemapping>>,
 }
 
 impl OntologyNormaliserStrategy {
@@ -68,6 +83,51 @@ impl OntologyNormaliserStrategy {
         Self {
             ontology_dict,
             data_context_kind,
+            replace_obsolete: false,
+            remapped_terms: RefCell::new(HashSet::new()),
+        }
+    }
+
+    pub fn with_replace_obsolete(mut self, replace_obsolete: bool) -> Self {
+        self.replace_obsolete = replace_obsolete;
+        self
+    }
+
+    /// The obsolete terms, which were replaced during the transformations so far.
+    pub fn remapped_terms(&self) -> Vec<ObsoleteTermRemapping> {
+        let mut remapped_terms: Vec<ObsoleteTermRemapping> =
+            self.remapped_terms.borrow().iter().cloned().collect();
+        remapped_terms.sort();
+        remapped_terms
+    }
+
+    /// Looks up the current equivalent of an obsolete term id.
+    ///
+    /// Returns `None`, if the id does not belong to an obsolete term, or if there is no single replacement.
+    fn replace_obsolete_term(
+        &self,
+        cell_value: &str,
+        table_name: &str,
+        col_name: &str,
+    ) -> Option<String> {
+        match self.ontology_dict.get_obsolete_replacement(cell_value)? {
+            ObsoleteReplacement::ReplacedBy(replacement_id) => {
+                self.remapped_terms
+                    .borrow_mut()
+                    .insert(ObsoleteTermRemapping {
+                        table: table_name.to_string(),
+                        column: col_name.to_string(),
+                        obsolete_id: cell_value.to_string(),
+                        replacement_id: replacement_id.clone(),
+                    });
+                Some(replacement_id.clone())
+            }
+            ObsoleteReplacement::Consider(candidates) => {
+                warn!(
+                    "Obsolete term '{cell_value}' in column '{col_name}' of table '{table_name}' has no single replacement. Consider one of {candidates:?}. Leaving it unchanged."
+                );
+                None
+            }
         }
     }
 }
@@ -106,6 +166,18 @@ impl Strategy for OntologyNormaliserStrategy {
                             Some(Cow::Borrowed(cell_value))
                         } else if let Ok(curie_id) = self.ontology_dict.get(cell_value) {
                             Some(Cow::Owned(curie_id))
+                        } else if self.replace_obsolete
+                            && self
+                                .ontology_dict
+                                .get_obsolete_replacement(cell_value)
+                                .is_some()
+                        {
+                            let replacement = self.replace_obsolete_term(
+                                cell_value,
+                                table.context().name(),
+                                col.name(),
+                            );
+                            Some(replacement.map_or(Cow::Borrowed(cell_value), Cow::Owned))
                         } else {
                             if !cell_value.is_empty() {
                                 error_info.borrow_mut().insert_error(
@@ -147,15 +219,22 @@ mod tests {
     use crate::config::table_context::{Identifier, SeriesContext, TableContext};
     use crate::config::traits::SeriesContextBuilding;
     use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
+    use crate::ontology::obsolete_terms::ObsoleteReplacement;
+    use crate::ontology::ontology_bidict::OntologyBiDict;
     use crate::test_suite::ontology_mocking::HPO_BIDICT;
+    use crate::test_suite::resource_references::HPO_REF;
     use crate::transform::error::{MappingErrorInfo, StrategyError};
-    use crate::transform::strategies::ontology_normaliser::OntologyNormaliserStrategy;
+    use crate::transform::strategies::ontology_normaliser::{
+        ObsoleteTermRemapping, OntologyNormaliserStrategy,
+    };
     use crate::transform::strategies::traits::Strategy;
     use polars::datatypes::AnyValue;
     use polars::frame::DataFrame;
     use polars::prelude::Column;
     use pretty_assertions::assert_eq;
     use rstest::{fixture, rstest};
+    use std::collections::HashMap;
+    use std::sync::Arc;
 
     #[fixture]
     fn tc() -> TableContext {
@@ -193,10 +272,8 @@ mod tests {
         let df = DataFrame::new(col1.len(), vec![col1, col2, col_pid.clone()]).unwrap();
         let mut cdf = ContextualizedDataFrame::new(tc, df).unwrap();
 
-        let get_hpo_labels_strat = OntologyNormaliserStrategy {
-            ontology_dict: HPO_BIDICT.clone(),
-            data_context_kind: ContextKind::Hpo,
-        };
+        let get_hpo_labels_strat =
+            OntologyNormaliserStrategy::new(HPO_BIDICT.clone(), ContextKind::Hpo);
         get_hpo_labels_strat.transform(&mut [&mut cdf]).unwrap();
 
         let expected_col1 = Column::new(
@@ -235,10 +312,8 @@ mod tests {
         let df = DataFrame::new(col1.len(), vec![col1, col2, col_pid.clone()]).unwrap();
         let mut cdf = ContextualizedDataFrame::new(tc, df).unwrap();
 
-        let get_hpo_labels_strat = OntologyNormaliserStrategy {
-            ontology_dict: HPO_BIDICT.clone(),
-            data_context_kind: ContextKind::Hpo,
-        };
+        let get_hpo_labels_strat =
+            OntologyNormaliserStrategy::new(HPO_BIDICT.clone(), ContextKind::Hpo);
         let strat_result = get_hpo_labels_strat.transform(&mut [&mut cdf]);
 
         if let Err(StrategyError::MappingError {
@@ -310,10 +385,8 @@ mod tests {
         let df = DataFrame::new(col1.len(), vec![col1, col_subject_id.clone()]).unwrap();
         let mut cdf = ContextualizedDataFrame::new(tc, df).unwrap();
 
-        let get_hpo_labels_strat = OntologyNormaliserStrategy {
-            ontology_dict: HPO_BIDICT.clone(),
-            data_context_kind: ContextKind::Hpo,
-        };
+        let get_hpo_labels_strat =
+            OntologyNormaliserStrategy::new(HPO_BIDICT.clone(), ContextKind::Hpo);
         let res = get_hpo_labels_strat.transform(&mut [&mut cdf]);
 
         if let Err(err) = res {
@@ -335,4 +408,70 @@ mod tests {
             DataFrame::new(expected_col1.len(), vec![expected_col1, col_subject_id]).unwrap();
         assert_eq!(cdf.data(), &expected_df);
     }
+
+    fn obsolete_cdf(tc: TableContext) -> ContextualizedDataFrame {
+        let col1 = Column::new("phenotypic_features".into(), ["HP:0000004", "Nystagmus"]);
+        let col_subject_id = Column::new("subject_ids".into(), ["1", "2"]);
+        let df = DataFrame::new(col1.len(), vec![col1, col_subject_id]).unwrap();
+        ContextualizedDataFrame::new(tc, df).unwrap()
+    }
+
+    #[rstest]
+    fn test_replace_obsolete(tc: TableContext) {
+        let mut cdf = obsolete_cdf(tc);
+
+        let strategy = OntologyNormaliserStrategy::new(HPO_BIDICT.clone(), ContextKind::Hpo)
+            .with_replace_obsolete(true);
+        strategy.transform(&mut [&mut cdf]).unwrap();
+
+        assert_eq!(
+            cdf.data().column("phenotypic_features").unwrap(),
+            &Column::new("phenotypic_features".into(), ["HP:0012823", "HP:0000639"])
+        );
+        assert_eq!(
+            strategy.remapped_terms(),
+            vec![ObsoleteTermRemapping {
+                table: "patient_data".to_string(),
+                column: "phenotypic_features".to_string(),
+                obsolete_id: "HP:0000004".to_string(),
+                replacement_id: "HP:0012823".to_string(),
+            }]
+        );
+    }
+
+    #[rstest]
+    fn test_obsolete_not_replaced_by_default(tc: TableContext) {
+        let mut cdf = obsolete_cdf(tc);
+
+        let strategy = OntologyNormaliserStrategy::new(HPO_BIDICT.clone(), ContextKind::Hpo);
+        let result = strategy.transform(&mut [&mut cdf]);
+
+        assert!(matches!(result, Err(StrategyError::MappingError { .. })));
+        assert!(strategy.remapped_terms().is_empty());
+    }
+
+    #[rstest]
+    fn test_replace_obsolete_with_consider_candidates(tc: TableContext) {
+        let mut cdf = obsolete_cdf(tc);
+        let hpo_dict = OntologyBiDict::new(
+            HPO_REF.clone(),
+            HashMap::from([("nystagmus".to_string(), "HP:0000639".to_string())]),
+            HashMap::new(),
+            HashMap::from([("HP:0000639".to_string(), "Nystagmus".to_string())]),
+        )
+        .with_obsolete_terms(HashMap::from([(
+            "HP:0000004".to_string(),
+            ObsoleteReplacement::Consider(vec!["HP:0012823".to_string(), "HP:0000005".to_string()]),
+        )]));
+
+        let strategy = OntologyNormaliserStrategy::new(Arc::new(hpo_dict), ContextKind::Hpo)
+            .with_replace_obsolete(true);
+        strategy.transform(&mut [&mut cdf]).unwrap();
+
+        assert_eq!(
+            cdf.data().column("phenotypic_features").unwrap(),
+            &Column::new("phenotypic_features".into(), ["HP:0000004", "HP:0000639"])
+        );
+        assert!(strategy.remapped_terms().is_empty());
+    }
 }
@@ -0,0 +1,211 @@
+use crate::config::table_context::Identifier;
+use crate::error::ConstructionError;
+use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
+use crate::transform::error::StrategyError;
+use crate::transform::strategies::traits::Strategy;
+use log::info;
+use polars::prelude::{DataType, IntoSeries, StringChunked};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+fn default_group() -> usize {
+    1
+}
+
+/// The configuration of a [`RegexExtractStrategy`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RegexExtract {
+    pub table_name: String,
+    pub column: String,
+    pub pattern: String,
+    #[serde(default = "default_group")]
+    pub group: usize,
+}
+
+/// Replaces every cell of a single column with the substring captured by a regex.
+///
+/// # Example
+///
+/// With the pattern `\((HP:\d{7})\)` the table
+/// ```csv
+/// PatientId, phenotype
+/// P001, patient has Seizure (HP:0001250)
+/// P002, no phenotype
+/// ```
+///
+/// will be mapped to
+/// ```csv
+/// PatientId, phenotype
+/// P001, HP:0001250
+/// P002,
+/// ```
+///
+/// Cells, which do not match the pattern, become null.
+#[derive(Debug)]
+pub struct RegexExtractStrategy {
+    table_name: String,
+    column: Identifier,
+    regex: Regex,
+    group: usize,
+}
+
+impl RegexExtractStrategy {
+    pub fn try_new(config: &RegexExtract) -> Result<Self, ConstructionError> {
+        let regex = Regex::new(&config.pattern).map_err(|err| ConstructionError::InvalidRegex {
+            pattern: config.pattern.clone(),
+            reason: err.to_string(),
+        })?;
+
+        if config.group >= regex.captures_len() {
+            return Err(ConstructionError::InvalidRegex {
+                pattern: config.pattern.clone(),
+                reason: format!("the pattern has no capture group {}", config.group),
+            });
+        }
+
+        Ok(RegexExtractStrategy {
+            table_name: config.table_name.clone(),
+            column: Identifier::Single(config.column.clone()),
+            regex,
+            group: config.group,
+        })
+    }
+
+    fn extract<'a>(&self, cell_value: &'a str) -> Option<&'a str> {
+        self.regex
+            .captures(cell_value)
+            .and_then(|captures| captures.get(self.group))
+            .map(|capture| capture.as_str())
+    }
+}
+
+impl Strategy for RegexExtractStrategy {
+    fn is_valid(&self, tables: &[&mut ContextualizedDataFrame]) -> bool {
+        tables.iter().any(|table| {
+            table.context().name() == self.table_name
+                && !table.identify_columns(&self.column).is_empty()
+        })
+    }
+
+    fn internal_transform(
+        &self,
+        tables: &mut [&mut ContextualizedDataFrame],
+    ) -> Result<(), StrategyError> {
+        info!("Applying RegexExtract strategy to data.");
+
+        for table in tables
+            .iter_mut()
+            .filter(|table| table.context().name() == self.table_name)
+        {
+            let column_names: Vec<String> = table
+                .identify_columns(&self.column)
+                .iter()
+                .map(|col| col.name().to_string())
+                .collect();
+
+            for col_name in column_names {
+                let col = table.data().column(&col_name)?;
+                let cast_col = col.cast(&DataType::String)?;
+
+                let extracted_column: StringChunked = cast_col
+                    .str()?
+                    .into_iter()
+                    .map(|cell_value| cell_value.and_then(|cell_value| self.extract(cell_value)))
+                    .collect();
+                let extracted_column = extracted_column.with_name(col.name().clone());
+                table
+                    .builder()
+                    .replace_col(&col_name, extracted_column.into_series())?
+                    .build()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::context::Context;
+    use crate::config::table_context::{SeriesContext, TableContext};
+    use crate::config::traits::SeriesContextBuilding;
+    use polars::frame::DataFrame;
+    use polars::prelude::Column;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn phenotype_cdf(phenotypes: &[Option<&str>]) -> ContextualizedDataFrame {
+        let tc = TableContext::new(
+            "phenotypes",
+            vec![
+                SeriesContext::from_identifier("subject_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("phenotype").with_data_context(Context::Hpo),
+            ],
+        );
+        let ids: Vec<String> = (0..phenotypes.len()).map(|i| format!("P{i}")).collect();
+        let df = DataFrame::new(
+            phenotypes.len(),
+            vec![
+                Column::new("subject_id".into(), ids),
+                Column::new("phenotype".into(), phenotypes),
+            ],
+        )
+        .unwrap();
+        ContextualizedDataFrame::new(tc, df).unwrap()
+    }
+
+    fn config(pattern: &str, group: usize) -> RegexExtract {
+        RegexExtract {
+            table_name: "phenotypes".to_string(),
+            column: "phenotype".to_string(),
+            pattern: pattern.to_string(),
+            group,
+        }
+    }
+
+    #[rstest]
+    #[case(
+        r"\((HP:\d{7})\)",
+        1,
+        Some("patient has Seizure (HP:0001250)"),
+        Some("HP:0001250")
+    )]
+    #[case(
+        r"(\w+) \((HP:\d{7})\)",
+        1,
+        Some("patient has Seizure (HP:0001250)"),
+        Some("Seizure")
+    )]
+    #[case(r"\((HP:\d{7})\)", 1, Some("no phenotype"), None)]
+    #[case(r"\((HP:\d{7})\)", 1, None, None)]
+    fn test_regex_extract(
+        #[case] pattern: &str,
+        #[case] group: usize,
+        #[case] cell: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let mut cdf = phenotype_cdf(&[cell]);
+
+        let strategy = RegexExtractStrategy::try_new(&config(pattern, group)).unwrap();
+        strategy.transform(&mut [&mut cdf]).unwrap();
+
+        assert_eq!(
+            cdf.data().column("phenotype").unwrap(),
+            &Column::new("phenotype".into(), [expected])
+        );
+    }
+
+    #[rstest]
+    #[case("(HP:", 1)]
+    #[case(r"HP:\d{7}", 1)]
+    fn test_regex_extract_invalid_config(#[case] pattern: &str, #[case] group: usize) {
+        let result = RegexExtractStrategy::try_new(&config(pattern, group));
+
+        assert!(matches!(
+            result,
+            Err(ConstructionError::InvalidRegex { pattern: invalid, .. }) if invalid == pattern
+        ));
+    }
+}
@@ -0,0 +1,223 @@
+use crate::config::table_context::Identifier;
+use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
+use crate::transform::error::StrategyError;
+use crate::transform::strategies::traits::Strategy;
+use log::info;
+use polars::prelude::{DataType, IdxCa, IdxSize, IntoSeries, StringChunked};
+use serde::{Deserialize, Serialize};
+
+fn default_trim_whitespace() -> bool {
+    true
+}
+
+/// The configuration of a [`SplitDelimitedColumnStrategy`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SplitDelimitedColumn {
+    pub table_name: String,
+    pub column: String,
+    pub delimiter: String,
+    #[serde(default = "default_trim_whitespace")]
+    pub trim_whitespace: bool,
+}
+
+/// Explodes a column, whose cells contain a delimited list, into one row per list element.
+///
+/// This is the row-wise counterpart of the [`crate::transform::strategies::MultiHPOColExpansionStrategy`].
+///
+/// # Example
+///
+/// With the delimiter `;` the table
+/// ```csv
+/// PatientId, phenotype
+/// P001, HP:0001250; HP:0001166
+/// P002,
+/// ```
+///
+/// will be mapped to
+/// ```csv
+/// PatientId, phenotype
+/// P001, HP:0001250
+/// P001, HP:0001166
+/// P002,
+/// ```
+///
+/// The other cells of a row are duplicated for each of its elements. Null cells are kept as they are.
+/// Empty elements are dropped, a cell without any elements becomes null.
+#[derive(Debug)]
+pub struct SplitDelimitedColumnStrategy {
+    table_name: String,
+    column: Identifier,
+    delimiter: String,
+    trim_whitespace: bool,
+}
+
+impl SplitDelimitedColumnStrategy {
+    pub fn new(config: &SplitDelimitedColumn) -> Self {
+        SplitDelimitedColumnStrategy {
+            table_name: config.table_name.clone(),
+            column: Identifier::Single(config.column.clone()),
+            delimiter: config.delimiter.clone(),
+            trim_whitespace: config.trim_whitespace,
+        }
+    }
+
+    fn split<'a>(&self, cell_value: &'a str) -> Vec<&'a str> {
+        cell_value
+            .split(self.delimiter.as_str())
+            .map(|element| match self.trim_whitespace {
+                true => element.trim(),
+                false => element,
+            })
+            .filter(|element| !element.is_empty())
+            .collect()
+    }
+}
+
+impl Strategy for SplitDelimitedColumnStrategy {
+    fn is_valid(&self, tables: &[&mut ContextualizedDataFrame]) -> bool {
+        tables.iter().any(|table| {
+            table.context().name() == self.table_name
+                && !table.identify_columns(&self.column).is_empty()
+        })
+    }
+
+    fn internal_transform(
+        &self,
+        tables: &mut [&mut ContextualizedDataFrame],
+    ) -> Result<(), StrategyError> {
+        info!("Applying SplitDelimitedColumn strategy to data.");
+
+        for table in tables
+            .iter_mut()
+            .filter(|table| table.context().name() == self.table_name)
+        {
+            let column_names: Vec<String> = table
+                .identify_columns(&self.column)
+                .iter()
+                .map(|col| col.name().to_string())
+                .collect();
+
+            for col_name in column_names {
+                let col = table.data().column(&col_name)?;
+                let cast_col = col.cast(&DataType::String)?;
+
+                let mut row_indices: Vec<IdxSize> = vec![];
+                let mut split_values: Vec<Option<&str>> = vec![];
+                for (row_idx, cell_value) in cast_col.str()?.into_iter().enumerate() {
+                    let elements = cell_value
+                        .map(|cell_value| self.split(cell_value))
+                        .unwrap_or_default();
+
+                    if elements.is_empty() {
+                        row_indices.push(row_idx as IdxSize);
+                        split_values.push(None);
+                    }
+                    for element in elements {
+                        row_indices.push(row_idx as IdxSize);
+                        split_values.push(Some(element));
+                    }
+                }
+
+                let exploded_data = table
+                    .data()
+                    .take(&IdxCa::from_vec("row_idx".into(), row_indices))?;
+                let split_column: StringChunked = split_values.into_iter().collect();
+                let split_column = split_column.with_name(col.name().clone());
+
+                **table = ContextualizedDataFrame::new(table.context().clone(), exploded_data)?;
+                table
+                    .builder()
+                    .replace_col(&col_name, split_column.into_series())?
+                    .build()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::context::Context;
+    use crate::config::table_context::{SeriesContext, TableContext};
+    use crate::config::traits::SeriesContextBuilding;
+    use polars::frame::DataFrame;
+    use polars::prelude::Column;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn phenotype_cdf(phenotypes: &[Option<&str>]) -> ContextualizedDataFrame {
+        let tc = TableContext::new(
+            "phenotypes",
+            vec![
+                SeriesContext::from_identifier("subject_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("phenotype").with_data_context(Context::Hpo),
+            ],
+        );
+        let ids: Vec<String> = (0..phenotypes.len()).map(|i| format!("P{i}")).collect();
+        let df = DataFrame::new(
+            phenotypes.len(),
+            vec![
+                Column::new("subject_id".into(), ids),
+                Column::new("phenotype".into(), phenotypes),
+            ],
+        )
+        .unwrap();
+        ContextualizedDataFrame::new(tc, df).unwrap()
+    }
+
+    fn config(delimiter: &str, trim_whitespace: bool) -> SplitDelimitedColumn {
+        SplitDelimitedColumn {
+            table_name: "phenotypes".to_string(),
+            column: "phenotype".to_string(),
+            delimiter: delimiter.to_string(),
+            trim_whitespace,
+        }
+    }
+
+    #[rstest]
+    #[case(
+        ";",
+        true,
+        vec![Some("HP:0001250; HP:0001166"), None, Some("HP:0001250")],
+        vec!["P0", "P0", "P1", "P2"],
+        vec![Some("HP:0001250"), Some("HP:0001166"), None, Some("HP:0001250")]
+    )]
+    #[case(
+        ";",
+        false,
+        vec![Some("HP:0001250; HP:0001166")],
+        vec!["P0", "P0"],
+        vec![Some("HP:0001250"), Some(" HP:0001166")]
+    )]
+    #[case(
+        "|",
+        true,
+        vec![Some("HP:0001250|"), Some(" | ")],
+        vec!["P0", "P1"],
+        vec![Some("HP:0001250"), None]
+    )]
+    fn test_split_delimited_column(
+        #[case] delimiter: &str,
+        #[case] trim_whitespace: bool,
+        #[case] phenotypes: Vec<Option<&str>>,
+        #[case] expected_ids: Vec<&str>,
+        #[case] expected_phenotypes: Vec<Option<&str>>,
+    ) {
+        let mut cdf = phenotype_cdf(&phenotypes);
+
+        let strategy = SplitDelimitedColumnStrategy::new(&config(delimiter, trim_whitespace));
+        strategy.transform(&mut [&mut cdf]).unwrap();
+
+        assert_eq!(
+            cdf.data().column("subject_id").unwrap(),
+            &Column::new("subject_id".into(), expected_ids)
+        );
+        assert_eq!(
+            cdf.data().column("phenotype").unwrap(),
+            &Column::new("phenotype".into(), expected_phenotypes)
+        );
+    }
+}
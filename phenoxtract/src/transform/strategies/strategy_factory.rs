@@ -1,3 +1,4 @@
+use crate::config::context::Context;
 use crate::config::strategy_config::StrategyConfig;
 use crate::error::ConstructionError;
 use crate::ontology::CachedOntologyFactory;
@@ -6,11 +7,15 @@ use crate::transform::strategies::hpo_disease_splitter::HpoDiseaseSplitterStrate
 use crate::transform::strategies::mapping::DefaultMapping;
 use crate::transform::strategies::traits::Strategy;
 use crate::transform::strategies::{
-    AliasMapStrategy, DateToAgeStrategy, MappingStrategy, MultiHPOColExpansionStrategy,
-    OntologyNormaliserStrategy,
+    AliasMapStrategy, ColumnConcatenationStrategy, DateToAgeStrategy, DeduplicateRowsStrategy,
+    MappingStrategy, MultiHPOColExpansionStrategy, NormalizeSubjectIdStrategy,
+    NumericRangeBucketingStrategy, ObservationStatusEncodingStrategy, OnsetCategoryStrategy,
+    OntologyNormaliserStrategy, RegexExtractStrategy, SplitDelimitedColumnStrategy,
+    UnitNormalisationStrategy, WhitespaceNormalisationStrategy,
 };
 use crate::transform::transform_context::TransformContext;
 use ontology_registry::traits::OntologyRegistration;
+use std::collections::HashMap;
 
 pub struct StrategyFactory<OR: OntologyRegistration> {
     ontology_factory: CachedOntologyFactory<OR>,
@@ -31,12 +36,66 @@ impl<OR: OntologyRegistration> StrategyFactory<OR> {
     where
         Self: Sized,
     {
+        Self::validate_order(configs)?;
+
         configs
             .iter()
             .map(|config| self.try_from_config(config))
             .collect()
     }
 
+    /// Checks, that the strategies can be applied in the configured order.
+    ///
+    /// A strategy may neither run after a context it requires was consumed by an earlier strategy,
+    /// nor before a later strategy produces it. Errors with the first unmet dependency.
+    pub fn validate_order(configs: &[StrategyConfig]) -> Result<(), ConstructionError> {
+        let mut consumed_by: HashMap<Context, (usize, &str)> = HashMap::new();
+
+        for (idx, config) in configs.iter().enumerate() {
+            let position = idx + 1;
+
+            for context in config.required_contexts() {
+                let reason = if let Some((consumer_position, consumer)) = consumed_by.get(&context)
+                {
+                    Some(format!(
+                        "it was consumed by '{consumer}' at position {consumer_position}"
+                    ))
+                } else {
+                    configs
+                        .iter()
+                        .enumerate()
+                        .skip(position)
+                        .find(|(_, later)| later.produced_contexts().contains(&context))
+                        .map(|(later_idx, later)| {
+                            format!(
+                                "it is produced by '{}' at position {}, which runs afterward",
+                                later.name(),
+                                later_idx + 1
+                            )
+                        })
+                };
+
+                if let Some(reason) = reason {
+                    return Err(ConstructionError::UnmetStrategyDependency {
+                        strategy: config.name().to_string(),
+                        position,
+                        context,
+                        reason,
+                    });
+                }
+            }
+
+            for context in config.consumed_contexts() {
+                consumed_by.insert(context, (position, config.name()));
+            }
+            for context in config.produced_contexts() {
+                consumed_by.remove(&context);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn try_from_config(
         &mut self,
         config: &StrategyConfig,
@@ -55,13 +114,14 @@ impl<OR: OntologyRegistration> StrategyFactory<OR> {
             StrategyConfig::OntologyNormaliser {
                 ontology: ontology_ref,
                 data_context_kind,
+                replace_obsolete,
             } => {
                 let ontology_bi_dict = self.ontology_factory.build_bidict(ontology_ref)?;
 
-                Ok(Box::new(OntologyNormaliserStrategy::new(
-                    ontology_bi_dict,
-                    *data_context_kind,
-                )))
+                Ok(Box::new(
+                    OntologyNormaliserStrategy::new(ontology_bi_dict, *data_context_kind)
+                        .with_replace_obsolete(*replace_obsolete),
+                ))
             }
             StrategyConfig::AgeToIso8601 => Ok(Box::new(AgeToIso8601Strategy::default())),
             StrategyConfig::DateToAge { strict } => Ok(Box::new(DateToAgeStrategy::new(*strict))),
@@ -69,6 +129,40 @@ impl<OR: OntologyRegistration> StrategyFactory<OR> {
                 self.ctx.hpo_bidict_lib().clone(),
                 self.ctx.disease_bidict_lib().clone(),
             ))),
+            StrategyConfig::OnsetCategory { categories } => Ok(Box::new(
+                OnsetCategoryStrategy::try_new(categories, self.ctx.hpo_bidict_lib().clone())?,
+            )),
+            StrategyConfig::NormalizeSubjectId(format) => {
+                Ok(Box::new(NormalizeSubjectIdStrategy::try_new(format)?))
+            }
+            StrategyConfig::NumericRangeBucketing(config) => {
+                Ok(Box::new(NumericRangeBucketingStrategy::try_new(config)?))
+            }
+            StrategyConfig::ColumnConcatenation(config) => {
+                Ok(Box::new(ColumnConcatenationStrategy::new(config.clone())))
+            }
+            StrategyConfig::RegexExtract(config) => {
+                Ok(Box::new(RegexExtractStrategy::try_new(config)?))
+            }
+            StrategyConfig::SplitDelimitedColumn(config) => {
+                Ok(Box::new(SplitDelimitedColumnStrategy::new(config)))
+            }
+            StrategyConfig::DeduplicateRows(config) => {
+                Ok(Box::new(DeduplicateRowsStrategy::new(config)))
+            }
+            StrategyConfig::WhitespaceNormalisation {
+                data_context_kinds,
+                unicode_nfc,
+            } => Ok(Box::new(
+                WhitespaceNormalisationStrategy::new(data_context_kinds.clone())
+                    .with_unicode_nfc(*unicode_nfc),
+            )),
+            StrategyConfig::UnitNormalisation { conversions } => Ok(Box::new(
+                UnitNormalisationStrategy::new(conversions.clone()),
+            )),
+            StrategyConfig::ObservationStatusEncoding(encoding) => {
+                Ok(Box::new(ObservationStatusEncodingStrategy::new(encoding)))
+            }
         }
     }
 
@@ -85,6 +179,9 @@ mod tests {
     use crate::test_suite::component_building::default_builder_context;
     use crate::test_suite::mocks::MockOntologyRegistry;
     use crate::test_suite::resource_references::MONDO_REF;
+    use crate::transform::strategies::column_concatenation::{
+        ColumnConcatenation, ConcatenationNullHandling,
+    };
     use crate::transform::strategies::mapping::DefaultMapping;
     use rstest::rstest;
 
@@ -153,6 +250,7 @@ mod tests {
         let config = StrategyConfig::OntologyNormaliser {
             ontology: MONDO_REF.clone(),
             data_context_kind: ContextKind::Disease,
+            replace_obsolete: true,
         };
 
         let result = factory.try_from_config(&config);
@@ -210,6 +308,81 @@ mod tests {
         assert_eq!(result.unwrap().len(), 3, "Should create three strategies");
     }
 
+    fn date_of_birth_concatenation() -> StrategyConfig {
+        StrategyConfig::ColumnConcatenation(ColumnConcatenation {
+            table_name: "demographics".to_string(),
+            source_columns: vec!["year_of_birth".to_string(), "month_of_birth".to_string()],
+            separator: "-".to_string(),
+            output_column: "date_of_birth".to_string(),
+            output_data_context: Context::DateOfBirth,
+            output_building_block_id: None,
+            null_handling: ConcatenationNullHandling::Propagate,
+        })
+    }
+
+    #[rstest]
+    fn test_validate_order() {
+        let configs = vec![
+            StrategyConfig::AliasMap,
+            date_of_birth_concatenation(),
+            StrategyConfig::DateToAge { strict: false },
+            StrategyConfig::MultiHpoColExpansion,
+        ];
+
+        assert!(StrategyFactory::<MockOntologyRegistry>::validate_order(&configs).is_ok());
+    }
+
+    #[rstest]
+    fn test_validate_order_produced_afterward() {
+        let configs = vec![
+            StrategyConfig::DateToAge { strict: false },
+            date_of_birth_concatenation(),
+        ];
+
+        let err = StrategyFactory::<MockOntologyRegistry>::validate_order(&configs).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Strategy 'date_to_age' at position 1 requires 'DateOfBirth', but it is produced by 'column_concatenation' at position 2, which runs afterward."
+        );
+    }
+
+    #[rstest]
+    fn test_validate_order_consumed() {
+        let configs = vec![
+            StrategyConfig::HpoDiseaseSplitter,
+            StrategyConfig::AliasMap,
+            StrategyConfig::HpoDiseaseSplitter,
+        ];
+
+        let err = StrategyFactory::<MockOntologyRegistry>::validate_order(&configs).unwrap_err();
+
+        assert!(matches!(
+            err,
+            ConstructionError::UnmetStrategyDependency {
+                position: 3,
+                context: Context::HpoOrDisease,
+                ..
+            }
+        ));
+    }
+
+    #[rstest]
+    fn test_try_from_configs_misordered() {
+        let mut factory = create_test_factory();
+        let configs = vec![
+            StrategyConfig::DateToAge { strict: true },
+            date_of_birth_concatenation(),
+        ];
+
+        let result = factory.try_from_configs(&configs);
+
+        assert!(matches!(
+            result,
+            Err(ConstructionError::UnmetStrategyDependency { .. })
+        ));
+    }
+
     #[rstest]
     fn test_strategy_trait_object_creation() {
         let mut factory = create_test_factory();
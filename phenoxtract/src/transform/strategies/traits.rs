@@ -23,6 +23,20 @@ pub trait Strategy: Debug {
 
     fn is_valid(&self, tables: &[&mut ContextualizedDataFrame]) -> bool;
 
+    /// Whether the strategy looks up data in other tables, e.g. the dates of birth of the patients.
+    ///
+    /// Tables, which are read in chunks, are transformed chunk by chunk. Such strategies see every chunk together
+    /// with the tables, which are read at once.
+    fn reads_other_tables(&self) -> bool {
+        false
+    }
+
+    /// Whether the strategy can transform the chunks of a table one by one. Strategies, which compare the rows of
+    /// the table with each other, e.g. to drop duplicates, can't.
+    fn supports_chunks_of(&self, _table_name: &str) -> bool {
+        true
+    }
+
     fn internal_transform(
         &self,
         tables: &mut [&mut ContextualizedDataFrame],
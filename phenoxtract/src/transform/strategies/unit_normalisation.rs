@@ -0,0 +1,446 @@
+use crate::config::context::{Boundary, Context, ContextKind};
+use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
+use crate::extract::enums::Filter;
+use crate::transform::error::StrategyError::MappingError;
+use crate::transform::error::{MappingErrorInfo, PushMappingError, StrategyError};
+use crate::transform::strategies::traits::Strategy;
+use log::info;
+use polars::prelude::{DataType, Float64Chunked, IntoSeries, StringChunked};
+use serde::{Deserialize, Serialize};
+use std::any::type_name;
+use std::collections::{HashMap, HashSet};
+
+/// The conversion of a source unit: values are multiplied by `factor` and the unit is replaced by `target_unit`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct UnitConversion {
+    pub target_unit: String,
+    pub factor: f64,
+}
+
+/// Rescales numeric measurements into a common unit, e.g. glucose from mg/dL to mmol/L.
+///
+/// The conversions are keyed by the CURIE of the source unit. Two kinds of columns are rescaled:
+///
+/// - `QuantitativeMeasurement` columns, whose `unit_ontology_id` is a source unit. Their values and the
+///   `ReferenceRange` columns of their building block are rescaled and the unit in their context is replaced.
+/// - `QuantityValue` columns, which are linked to a `QuantityUnit` column by a building block.
+///   Only rows, whose unit is a source unit, are rescaled and their unit cell is replaced.
+///
+/// # Example
+///
+/// With the conversion `UO:0000301 (mg/dL) -> UO:0000065 (mmol/L), factor 0.0555` the table
+/// ```csv
+/// PatientId, dose, unit
+/// P001, 90, UO:0000301
+/// P002, 5, UO:0000065
+/// ```
+///
+/// will be mapped to
+/// ```csv
+/// PatientId, dose, unit
+/// P001, 4.995, UO:0000065
+/// P002, 5, UO:0000065
+/// ```
+///
+/// # Errors
+///
+/// Values, which are not numeric, are reported.
+#[derive(Debug)]
+pub struct UnitNormalisationStrategy {
+    conversions: HashMap<String, UnitConversion>,
+}
+
+impl UnitNormalisationStrategy {
+    pub fn new(conversions: HashMap<String, UnitConversion>) -> Self {
+        UnitNormalisationStrategy { conversions }
+    }
+
+    /// Parses every cell of a column as f64 and multiplies it by the factor returned for the row.
+    ///
+    /// Cells, which can not be parsed, become null and are added to `error_info`.
+    fn rescale_col(
+        table: &mut ContextualizedDataFrame,
+        col_name: &str,
+        factor: impl Fn(usize) -> Option<f64>,
+        error_info: &mut HashSet<MappingErrorInfo>,
+    ) -> Result<(), StrategyError> {
+        let col = table.data().column(col_name)?;
+        let cast_col = col.cast(&DataType::String)?;
+
+        let rescaled_col: Float64Chunked = cast_col
+            .str()?
+            .into_iter()
+            .enumerate()
+            .map(|(row_idx, cell_value)| {
+                let cell_value = cell_value?;
+                match cell_value.trim().parse::<f64>() {
+                    Ok(value) => Some(factor(row_idx).map_or(value, |factor| value * factor)),
+                    Err(_) => {
+                        error_info.insert_error(
+                            col_name.to_string(),
+                            table.context().name().to_string(),
+                            cell_value.to_string(),
+                            vec![],
+                        );
+                        None
+                    }
+                }
+            })
+            .collect();
+        let rescaled_col = rescaled_col.with_name(col.name().clone());
+
+        table
+            .builder()
+            .replace_col(col_name, rescaled_col.into_series())?
+            .build()?;
+        Ok(())
+    }
+
+    fn normalise_quantitative_measurements(
+        &self,
+        table: &mut ContextualizedDataFrame,
+        error_info: &mut HashSet<MappingErrorInfo>,
+    ) -> Result<(), StrategyError> {
+        let mut replaced_contexts: HashMap<Context, Context> = HashMap::new();
+        let mut cols_to_rescale: Vec<(String, f64)> = vec![];
+        let mut rescaled_building_blocks: HashSet<String> = HashSet::new();
+
+        for sc in table
+            .filter_series_context()
+            .where_data_context_kind(Filter::Is(&ContextKind::QuantitativeMeasurement))
+            .collect()
+        {
+            let Context::QuantitativeMeasurement {
+                assay_id,
+                unit_ontology_id,
+            } = sc.get_data_context()
+            else {
+                continue;
+            };
+            let Some(conversion) = self.conversions.get(unit_ontology_id) else {
+                continue;
+            };
+
+            replaced_contexts.insert(
+                sc.get_data_context().clone(),
+                Context::QuantitativeMeasurement {
+                    assay_id: assay_id.clone(),
+                    unit_ontology_id: conversion.target_unit.clone(),
+                },
+            );
+            cols_to_rescale.extend(
+                table
+                    .identify_columns(sc.get_identifier())
+                    .iter()
+                    .map(|col| (col.name().to_string(), conversion.factor)),
+            );
+
+            if let Some(bb_id) = sc.get_building_block_id()
+                && rescaled_building_blocks.insert(bb_id.to_string())
+            {
+                for boundary in [Boundary::Start, Boundary::End] {
+                    cols_to_rescale.extend(
+                        table
+                            .get_linked_cols_with_context(
+                                Some(bb_id),
+                                &Context::ReferenceRange(boundary),
+                                &Context::None,
+                            )
+                            .into_iter()
+                            .map(|col_name| (col_name, conversion.factor)),
+                    );
+                }
+            }
+        }
+
+        for (col_name, factor) in cols_to_rescale {
+            Self::rescale_col(table, &col_name, |_| Some(factor), error_info)?;
+        }
+
+        if !replaced_contexts.is_empty() {
+            table
+                .builder()
+                .replace_data_contexts(replaced_contexts)
+                .build()?;
+        }
+        Ok(())
+    }
+
+    fn normalise_quantities(
+        &self,
+        table: &mut ContextualizedDataFrame,
+        error_info: &mut HashSet<MappingErrorInfo>,
+    ) -> Result<(), StrategyError> {
+        let building_block_ids: Vec<String> = table
+            .get_building_block_ids()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+
+        for bb_id in building_block_ids {
+            let unit_col_names = table.get_linked_cols_with_context(
+                Some(&bb_id),
+                &Context::QuantityUnit,
+                &Context::None,
+            );
+            let [unit_col_name] = unit_col_names.as_slice() else {
+                continue;
+            };
+
+            let unit_col = table
+                .data()
+                .column(unit_col_name)?
+                .cast(&DataType::String)?;
+            let conversions: Vec<Option<&UnitConversion>> = unit_col
+                .str()?
+                .into_iter()
+                .map(|unit| unit.and_then(|unit| self.conversions.get(unit.trim())))
+                .collect();
+
+            for value_col_name in table.get_linked_cols_with_context(
+                Some(&bb_id),
+                &Context::QuantityValue,
+                &Context::None,
+            ) {
+                Self::rescale_col(
+                    table,
+                    &value_col_name,
+                    |row_idx| conversions[row_idx].map(|conversion| conversion.factor),
+                    error_info,
+                )?;
+            }
+
+            let normalised_unit_col: StringChunked = unit_col
+                .str()?
+                .into_iter()
+                .zip(conversions.iter())
+                .map(|(unit, conversion)| match conversion {
+                    Some(conversion) => Some(conversion.target_unit.as_str()),
+                    None => unit,
+                })
+                .collect();
+            let normalised_unit_col = normalised_unit_col.with_name(unit_col.name().clone());
+
+            table
+                .builder()
+                .replace_col(unit_col_name, normalised_unit_col.into_series())?
+                .build()?;
+        }
+        Ok(())
+    }
+}
+
+impl Strategy for UnitNormalisationStrategy {
+    fn is_valid(&self, tables: &[&mut ContextualizedDataFrame]) -> bool {
+        tables.iter().any(|table| {
+            !table
+                .filter_series_context()
+                .where_data_context_kind(Filter::Is(&ContextKind::QuantitativeMeasurement))
+                .collect()
+                .is_empty()
+                || !table
+                    .filter_series_context()
+                    .where_data_context(Filter::Is(&Context::QuantityUnit))
+                    .collect()
+                    .is_empty()
+        })
+    }
+
+    fn internal_transform(
+        &self,
+        tables: &mut [&mut ContextualizedDataFrame],
+    ) -> Result<(), StrategyError> {
+        info!("Applying UnitNormalisation strategy to data.");
+
+        let mut error_info: HashSet<MappingErrorInfo> = HashSet::new();
+
+        for table in tables.iter_mut() {
+            self.normalise_quantitative_measurements(table, &mut error_info)?;
+            self.normalise_quantities(table, &mut error_info)?;
+        }
+
+        if !error_info.is_empty() {
+            Err(MappingError {
+                strategy_name: type_name::<Self>().split("::").last().unwrap().to_string(),
+                message: "These values are not numeric and could not be rescaled.".to_string(),
+                info: error_info.into_iter().collect(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::table_context::{SeriesContext, TableContext};
+    use crate::config::traits::SeriesContextBuilding;
+    use polars::frame::DataFrame;
+    use polars::prelude::{AnyValue, Column};
+    use pretty_assertions::assert_eq;
+    use rstest::{fixture, rstest};
+
+    const MG_PER_DL: &str = "UO:0000301";
+    const MMOL_PER_L: &str = "UO:0000065";
+    const GLUCOSE: &str = "LOINC:2345-7";
+
+    #[fixture]
+    fn strategy() -> UnitNormalisationStrategy {
+        UnitNormalisationStrategy::new(HashMap::from([(
+            MG_PER_DL.to_string(),
+            UnitConversion {
+                target_unit: MMOL_PER_L.to_string(),
+                factor: 0.5,
+            },
+        )]))
+    }
+
+    fn glucose_context(unit: &str) -> Context {
+        Context::QuantitativeMeasurement {
+            assay_id: GLUCOSE.to_string(),
+            unit_ontology_id: unit.to_string(),
+        }
+    }
+
+    fn measurement_cdf(unit: &str) -> ContextualizedDataFrame {
+        let tc = TableContext::new(
+            "labs",
+            vec![
+                SeriesContext::from_identifier("subject_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("glucose")
+                    .with_data_context(glucose_context(unit))
+                    .with_building_block_id(Some("glucose_1".to_string())),
+                SeriesContext::from_identifier("glucose_low")
+                    .with_data_context(Context::ReferenceRange(Boundary::Start))
+                    .with_building_block_id(Some("glucose_1".to_string())),
+                SeriesContext::from_identifier("glucose_high")
+                    .with_data_context(Context::ReferenceRange(Boundary::End))
+                    .with_building_block_id(Some("glucose_1".to_string())),
+            ],
+        );
+        let df = DataFrame::new(
+            2,
+            vec![
+                Column::new("subject_id".into(), ["P001", "P002"]),
+                Column::new("glucose".into(), [Some(90.0), None]),
+                Column::new("glucose_low".into(), [70.0, 70.0]),
+                Column::new("glucose_high".into(), [100.0, 100.0]),
+            ],
+        )
+        .unwrap();
+        ContextualizedDataFrame::new(tc, df).unwrap()
+    }
+
+    fn quantity_cdf(values: &[AnyValue], units: &[Option<&str>]) -> ContextualizedDataFrame {
+        let tc = TableContext::new(
+            "treatments",
+            vec![
+                SeriesContext::from_identifier("subject_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("dose")
+                    .with_data_context(Context::QuantityValue)
+                    .with_building_block_id(Some("dose_1".to_string())),
+                SeriesContext::from_identifier("unit")
+                    .with_data_context(Context::QuantityUnit)
+                    .with_building_block_id(Some("dose_1".to_string())),
+            ],
+        );
+        let ids: Vec<String> = (0..values.len()).map(|i| format!("P{i}")).collect();
+        let df = DataFrame::new(
+            values.len(),
+            vec![
+                Column::new("subject_id".into(), ids),
+                Column::new("dose".into(), values),
+                Column::new("unit".into(), units),
+            ],
+        )
+        .unwrap();
+        ContextualizedDataFrame::new(tc, df).unwrap()
+    }
+
+    #[rstest]
+    fn test_unit_normalisation_quantitative_measurement(strategy: UnitNormalisationStrategy) {
+        let mut cdf = measurement_cdf(MG_PER_DL);
+
+        strategy.transform(&mut [&mut cdf]).unwrap();
+
+        assert_eq!(
+            cdf.data().column("glucose").unwrap(),
+            &Column::new("glucose".into(), [Some(45.0), None])
+        );
+        assert_eq!(
+            cdf.data().column("glucose_low").unwrap(),
+            &Column::new("glucose_low".into(), [35.0, 35.0])
+        );
+        assert_eq!(
+            cdf.data().column("glucose_high").unwrap(),
+            &Column::new("glucose_high".into(), [50.0, 50.0])
+        );
+        let glucose_scs = cdf
+            .filter_series_context()
+            .where_data_context(Filter::Is(&glucose_context(MMOL_PER_L)))
+            .collect();
+        assert_eq!(glucose_scs.len(), 1);
+    }
+
+    #[rstest]
+    fn test_unit_normalisation_unmapped_measurement_unit(strategy: UnitNormalisationStrategy) {
+        let mut cdf = measurement_cdf(MMOL_PER_L);
+        let expected = cdf.data().clone();
+
+        strategy.transform(&mut [&mut cdf]).unwrap();
+
+        assert_eq!(cdf.data(), &expected);
+    }
+
+    #[rstest]
+    fn test_unit_normalisation_quantity(strategy: UnitNormalisationStrategy) {
+        let mut cdf = quantity_cdf(
+            &[
+                AnyValue::Int32(90),
+                AnyValue::Int32(5),
+                AnyValue::Null,
+                AnyValue::Int32(7),
+            ],
+            &[Some(MG_PER_DL), Some(MMOL_PER_L), Some(MG_PER_DL), None],
+        );
+
+        strategy.transform(&mut [&mut cdf]).unwrap();
+
+        assert_eq!(
+            cdf.data().column("dose").unwrap(),
+            &Column::new("dose".into(), [Some(45.0), Some(5.0), None, Some(7.0)])
+        );
+        assert_eq!(
+            cdf.data().column("unit").unwrap(),
+            &Column::new(
+                "unit".into(),
+                [Some(MMOL_PER_L), Some(MMOL_PER_L), Some(MMOL_PER_L), None]
+            )
+        );
+    }
+
+    #[rstest]
+    fn test_unit_normalisation_reports_non_numeric(strategy: UnitNormalisationStrategy) {
+        let mut cdf = quantity_cdf(
+            &[AnyValue::String("high"), AnyValue::String("90")],
+            &[Some(MG_PER_DL), Some(MG_PER_DL)],
+        );
+
+        let result = strategy.transform(&mut [&mut cdf]);
+
+        let Err(StrategyError::MappingError { info, .. }) = result else {
+            panic!("Expected a MappingError, got {result:?}")
+        };
+        assert_eq!(
+            info,
+            vec![MappingErrorInfo {
+                column: "dose".to_string(),
+                table: "treatments".to_string(),
+                old_value: "high".to_string(),
+                possible_mappings: vec![],
+            }]
+        );
+    }
+}
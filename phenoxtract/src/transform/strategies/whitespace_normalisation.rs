@@ -0,0 +1,191 @@
+use crate::config::context::ContextKind;
+use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
+use crate::extract::enums::Filter;
+use crate::transform::error::StrategyError;
+use crate::transform::strategies::traits::Strategy;
+use log::info;
+use polars::prelude::{ChunkApply, DataType, IntoSeries};
+use std::borrow::Cow;
+use unicode_normalization::UnicodeNormalization;
+
+/// Trims the cells of string columns and collapses internal runs of whitespace to a single space.
+///
+/// Only columns, whose data context is one of the configured [`ContextKind`]s, are processed.
+/// Stray whitespace (e.g. "  Seizure " or "Seizure\t") would otherwise prevent labels from being found in the bidicts.
+///
+/// If `unicode_nfc` is set, the cells are additionally brought into Unicode normalization form C,
+/// so that e.g. a decomposed "é" matches its composed form.
+///
+/// # Example
+///
+/// ```csv
+/// PatientId, Hpo
+/// P001, "  Seizure "
+/// P002, "Abnormal   nasal\tmorphology"
+/// ```
+/// is mapped to
+/// ```csv
+/// PatientId, Hpo
+/// P001, Seizure
+/// P002, Abnormal nasal morphology
+/// ```
+#[derive(Debug)]
+pub struct WhitespaceNormalisationStrategy {
+    data_context_kinds: Vec<ContextKind>,
+    unicode_nfc: bool,
+}
+
+impl WhitespaceNormalisationStrategy {
+    pub fn new(data_context_kinds: Vec<ContextKind>) -> Self {
+        Self {
+            data_context_kinds,
+            unicode_nfc: false,
+        }
+    }
+
+    pub fn with_unicode_nfc(mut self, unicode_nfc: bool) -> Self {
+        self.unicode_nfc = unicode_nfc;
+        self
+    }
+
+    fn normalise<'a>(&self, cell_value: &'a str) -> Cow<'a, str> {
+        let collapsed = cell_value
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        let normalised = match self.unicode_nfc {
+            true => collapsed.nfc().collect(),
+            false => collapsed,
+        };
+
+        match normalised == cell_value {
+            true => Cow::Borrowed(cell_value),
+            false => Cow::Owned(normalised),
+        }
+    }
+}
+
+impl Strategy for WhitespaceNormalisationStrategy {
+    fn is_valid(&self, tables: &[&mut ContextualizedDataFrame]) -> bool {
+        tables.iter().any(|table| {
+            !table
+                .filter_columns()
+                .where_data_context_kinds_are(&self.data_context_kinds)
+                .where_data_type(Filter::Is(&DataType::String))
+                .collect()
+                .is_empty()
+        })
+    }
+
+    fn internal_transform(
+        &self,
+        tables: &mut [&mut ContextualizedDataFrame],
+    ) -> Result<(), StrategyError> {
+        info!("Applying WhitespaceNormalisation strategy to data.");
+
+        for table in tables.iter_mut() {
+            let column_names = table
+                .filter_columns()
+                .where_data_context_kinds_are(&self.data_context_kinds)
+                .where_data_type(Filter::Is(&DataType::String))
+                .collect_owned_names();
+
+            for col_name in column_names {
+                let col = table.data().column(&col_name)?;
+                let normalised_column = col
+                    .str()?
+                    .apply(|cell_value| cell_value.map(|cell_value| self.normalise(cell_value)));
+                table
+                    .builder()
+                    .replace_col(&col_name, normalised_column.into_series())?
+                    .build()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::context::Context;
+    use crate::config::table_context::{SeriesContext, TableContext};
+    use crate::config::traits::SeriesContextBuilding;
+    use crate::test_suite::ontology_mocking::HPO_BIDICT;
+    use crate::transform::strategies::OntologyNormaliserStrategy;
+    use polars::frame::DataFrame;
+    use polars::prelude::Column;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn phenotype_cdf(phenotypes: &[Option<&str>]) -> ContextualizedDataFrame {
+        let tc = TableContext::new(
+            "phenotypes",
+            vec![
+                SeriesContext::from_identifier("subject_id").with_data_context(Context::SubjectId),
+                SeriesContext::from_identifier("phenotype").with_data_context(Context::Hpo),
+            ],
+        );
+        let ids: Vec<String> = (0..phenotypes.len()).map(|i| format!("P{i}")).collect();
+        let df = DataFrame::new(
+            phenotypes.len(),
+            vec![
+                Column::new("subject_id".into(), ids),
+                Column::new("phenotype".into(), phenotypes),
+            ],
+        )
+        .unwrap();
+        ContextualizedDataFrame::new(tc, df).unwrap()
+    }
+
+    #[rstest]
+    #[case(Some("  Nystagmus "), false, Some("Nystagmus"))]
+    #[case(
+        Some("Abnormal \t nasal\n morphology"),
+        false,
+        Some("Abnormal nasal morphology")
+    )]
+    #[case(Some("Cafe\u{301}"), false, Some("Cafe\u{301}"))]
+    #[case(Some("Cafe\u{301}"), true, Some("Caf\u{e9}"))]
+    #[case(None, true, None)]
+    fn test_whitespace_normalisation(
+        #[case] cell: Option<&str>,
+        #[case] unicode_nfc: bool,
+        #[case] expected: Option<&str>,
+    ) {
+        let mut cdf = phenotype_cdf(&[cell]);
+
+        let strategy = WhitespaceNormalisationStrategy::new(vec![ContextKind::Hpo])
+            .with_unicode_nfc(unicode_nfc);
+        strategy.transform(&mut [&mut cdf]).unwrap();
+
+        assert_eq!(
+            cdf.data().column("phenotype").unwrap(),
+            &Column::new("phenotype".into(), [expected])
+        );
+    }
+
+    #[rstest]
+    fn test_whitespace_normalisation_resolves_labels() {
+        let mut cdf = phenotype_cdf(&[Some("  Nystagmus "), Some("Abnormal\tnasal morphology")]);
+        let ontology_normaliser =
+            OntologyNormaliserStrategy::new(HPO_BIDICT.clone(), ContextKind::Hpo);
+
+        assert!(
+            ontology_normaliser
+                .transform(&mut [&mut cdf.clone()])
+                .is_err()
+        );
+
+        WhitespaceNormalisationStrategy::new(vec![ContextKind::Hpo])
+            .transform(&mut [&mut cdf])
+            .unwrap();
+        ontology_normaliser.transform(&mut [&mut cdf]).unwrap();
+
+        assert_eq!(
+            cdf.data().column("phenotype").unwrap(),
+            &Column::new("phenotype".into(), ["HP:0000639", "HP:0005105"])
+        );
+    }
+}
@@ -1,14 +1,25 @@
 #![allow(clippy::too_many_arguments)]
 use crate::transform::collecting::medical_actions::medical_treatment_data::DoseIntervalRow;
+use crate::transform::dropped_fields::DroppedFieldKind;
 use crate::transform::error::PhenopacketBuilderError;
-use crate::transform::pathogenic_gene_variant_info::PathogenicGeneVariantData;
+use crate::transform::pathogenic_gene_variant_info::{
+    PathogenicGeneVariantData, VariantAnnotations,
+};
 use phenopackets::schema::v2::Phenopacket;
 use phenopackets::schema::v2::core::{
-    Disease, Individual, Interpretation, Measurement, MedicalAction, PhenotypicFeature, Resource,
+    Biosample, Disease, Individual, Interpretation, Measurement, MedicalAction, PhenotypicFeature,
+    Resource,
 };
 pub trait PhenopacketBuilding {
     fn build(&self) -> Vec<Phenopacket>;
 
+    /// Finishes the phenopacket of a single patient and hands it out, so that it can be loaded before the
+    /// remaining patients are collected. Returns `None`, if nothing was collected for the patient.
+    fn build_phenopacket(&mut self, patient_id: &str) -> Option<Phenopacket>;
+
+    /// Records a value, which was supplied for a patient, but is not emitted into the phenopacket.
+    fn record_dropped_field(&mut self, kind: DroppedFieldKind, patient_id: &str, value: &str);
+
     fn upsert_individual(
         &mut self,
         patient_id: &str,
@@ -41,6 +52,7 @@ pub trait PhenopacketBuilding {
         onset: Option<&str>,
         resolution: Option<&str>,
         evidence: Option<&str>,
+        reference: Option<&str>,
     ) -> Result<(), PhenopacketBuilderError>;
 
     fn insert_phenotypic_feature(
@@ -54,13 +66,17 @@ pub trait PhenopacketBuilding {
         onset: Option<&str>,
         resolution: Option<&str>,
         evidence: Option<&str>,
+        reference: Option<&str>,
     ) -> Result<(), PhenopacketBuilderError>;
 
+    /// `variant_annotations` holds the annotations of each variant, in the order of [`PathogenicGeneVariantData::get_vars`].
+    /// Variants without a classification are pathogenic, and their therapeutic actionability is unknown.
     fn upsert_interpretation(
         &mut self,
         patient_id: &str,
         disease: &str,
         gene_variant_data: &PathogenicGeneVariantData,
+        variant_annotations: &[VariantAnnotations],
         subject_sex: Option<&str>,
     ) -> Result<(), PhenopacketBuilderError>;
 
@@ -87,6 +103,19 @@ pub trait PhenopacketBuilding {
         reference_range: Option<(f64, f64)>,
     ) -> Result<(), PhenopacketBuilderError>;
 
+    fn insert_ratio_measurement(
+        &mut self,
+        patient_id: &str,
+        time_observed: Option<&str>,
+        assay_id: &str,
+        numerator: f64,
+        numerator_type_id: &str,
+        numerator_unit_id: &str,
+        denominator: f64,
+        denominator_type_id: &str,
+        denominator_unit_id: &str,
+    ) -> Result<(), PhenopacketBuilderError>;
+
     fn insert_qualitative_measurement(
         &mut self,
         patient_id: &str,
@@ -95,6 +124,13 @@ pub trait PhenopacketBuilding {
         assay_id: &str,
     ) -> Result<(), PhenopacketBuilderError>;
 
+    fn upsert_biosample(
+        &mut self,
+        patient_id: &str,
+        biosample_id: &str,
+        sampled_tissue: Option<&str>,
+    ) -> Result<(), PhenopacketBuilderError>;
+
     fn insert_medical_procedure(
         &mut self,
         patient_id: &str,
@@ -141,5 +177,11 @@ pub(crate) trait PhenopacketAccessors {
     fn push_measurement(&mut self, measurement: Measurement);
 
     fn push_disease(&mut self, disease: Disease);
+    /// Merges the disease into an earlier disease with the same term, if their fields don't contradict each other.
+    /// Otherwise, the disease is pushed.
+    fn merge_disease(&mut self, disease: Disease);
     fn push_medical_action(&mut self, medical_action: MedicalAction);
+
+    fn find_biosample_mut(&mut self, id: &str) -> Option<&mut Biosample>;
+    fn push_biosample(&mut self, biosample: Biosample);
 }
@@ -1,8 +1,21 @@
 use crate::config::MetaData;
+use crate::ontology::concurrency_limiter::ConcurrencyLimiter;
+use crate::ontology::hgnc_alias_client::GeneSymbolAliases;
+use crate::ontology::hgnc_batch_client::GeneBatchLookup;
 use crate::ontology::traits::BiDict;
 use crate::transform::bidict_library::BiDictLibrary;
+use crate::transform::cached_resource_resolver::CachedResourceResolver;
+use crate::transform::clock::{Clock, SystemClock};
+use crate::transform::hgnc_gene_cache::HgncGeneCache;
+use crate::transform::hgvs_variant_cache::HgvsVariantCache;
+use crate::transform::negative_lookup_cache::NegativeLookupCache;
+use crate::transform::pathogenic_gene_variant_info::GenomeAssembly;
+use moka::sync::Cache;
+use phenopackets::schema::v2::core::{OntologyClass, Sex};
 use pivotal::hgnc::HGNCData;
 use pivotal::hgvs::HGVSData;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -46,11 +59,106 @@ impl From<MetaData> for BuilderMetaData {
     }
 }
 
-#[derive(Debug)]
+/// Decides what happens with sex values, which are present but neither in the `SexVocabulary` nor a `Sex` name
+/// (e.g. "intersex").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnknownSexPolicy {
+    /// Unrecognised words become `UNKNOWN_SEX` with a warning. Values, which are clearly no sex, because they are not
+    /// a word (e.g. "3" or "m?"), are an error.
+    #[default]
+    Strict,
+    /// Unrecognised values become `OTHER_SEX`.
+    Other,
+    /// Unrecognised values become `UNKNOWN_SEX`.
+    Unknown,
+}
+
+/// Maps the sex values of the source data (e.g. "M", "female" or "2") to a `Sex`, before they are parsed as a `Sex` name.
+/// Values are matched case-insensitively and ignoring surrounding whitespace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SexVocabulary {
+    terms: HashMap<String, Sex>,
+}
+
+impl Default for SexVocabulary {
+    /// The common spellings and the ISO/IEC 5218 codes of the sexes.
+    fn default() -> Self {
+        let defaults = [
+            ("m", Sex::Male),
+            ("male", Sex::Male),
+            ("man", Sex::Male),
+            ("1", Sex::Male),
+            ("f", Sex::Female),
+            ("female", Sex::Female),
+            ("woman", Sex::Female),
+            ("2", Sex::Female),
+            ("o", Sex::OtherSex),
+            ("other", Sex::OtherSex),
+            ("u", Sex::UnknownSex),
+            ("unknown", Sex::UnknownSex),
+            ("0", Sex::UnknownSex),
+        ];
+
+        Self {
+            terms: defaults
+                .into_iter()
+                .map(|(value, sex)| (value.to_string(), sex))
+                .collect(),
+        }
+    }
+}
+
+impl SexVocabulary {
+    /// Adds a value to the vocabulary. It replaces a default mapping of the same value.
+    pub fn with_term(mut self, value: &str, sex: Sex) -> Self {
+        self.terms.insert(Self::normalize(value), sex);
+        self
+    }
+
+    pub fn get(&self, value: &str) -> Option<Sex> {
+        self.terms.get(&Self::normalize(value)).copied()
+    }
+
+    fn normalize(value: &str) -> String {
+        value.trim().to_lowercase()
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct TransformContext {
     meta_data: BuilderMetaData,
-    hgnc_client: Arc<dyn HGNCData>,
-    hgvs_client: Arc<dyn HGVSData>,
+    hgnc_client: Arc<dyn HGNCData + Send + Sync>,
+    hgvs_client: Arc<dyn HGVSData + Send + Sync>,
+    /// The (symbol, HGNC id) pairs of the genes, which were already resolved. Clones of the context share the cache.
+    hgnc_gene_cache: HgncGeneCache,
+    /// The HGVS variants, which were already validated. Clones of the context share the cache.
+    hgvs_variant_cache: HgvsVariantCache,
+    /// If set, gene symbols, which HGNC doesn't know, are looked up as previous or alias symbols.
+    gene_symbol_aliases: Option<Arc<dyn GeneSymbolAliases>>,
+    /// If set, the genes of a batch of tables are resolved together, instead of one request per gene.
+    gene_batch_lookup: Option<Arc<dyn GeneBatchLookup>>,
+    /// The current symbols of the previous or alias symbols, which were remapped so far.
+    /// Clones of the context share the remappings.
+    remapped_gene_symbols: Cache<String, String>,
+    /// If set, gene and variant lookups, which failed recently, fail again without a request.
+    negative_lookup_cache: Option<Arc<NegativeLookupCache>>,
+    /// Resolves the resources of the meta data. Clones of the context share the resolved resources.
+    resource_resolver: Arc<CachedResourceResolver>,
+    request_limiter: Arc<ConcurrencyLimiter>,
+    unknown_sex_policy: UnknownSexPolicy,
+    sex_vocabulary: SexVocabulary,
+    clock: Arc<dyn Clock>,
+    /// If set, `MetaData.created` is left empty instead of being stamped with the time of the clock.
+    omit_created: bool,
+    /// If set, phenotypes and diseases, whose term can't be resolved, are skipped instead of aborting the run.
+    lenient: bool,
+    /// If set, the VCF records of all variants must be on this genome assembly, unless their row declares another one.
+    genome_assembly: Option<GenomeAssembly>,
+    /// If set, every disease row becomes its own disease, instead of being merged into an identical disease.
+    keep_duplicate_diseases: bool,
+    /// Numeric scores in columns with an HPO term in their header, which are above it, mean the phenotype was observed.
+    hpo_observed_threshold: f64,
     hpo_bidict_lib: Arc<BiDictLibrary>,
     disease_bidict_lib: Arc<BiDictLibrary>,
     unit_bidict_lib: Arc<BiDictLibrary>,
@@ -60,11 +168,22 @@ pub struct TransformContext {
     anatomy_bi_dict_lib: Arc<BiDictLibrary>,
     drug_bi_dict_lib: Arc<BiDictLibrary>,
     treatment_attributes_bi_dict: Arc<BiDictLibrary>,
+    evidence_bi_dict_lib: Arc<BiDictLibrary>,
+    gender_bi_dict_lib: Arc<BiDictLibrary>,
+    disease_attributes_bi_dict_lib: Arc<BiDictLibrary>,
 }
 
 impl PartialEq for TransformContext {
     fn eq(&self, other: &Self) -> bool {
         self.meta_data == other.meta_data
+            && self.request_limiter == other.request_limiter
+            && self.unknown_sex_policy == other.unknown_sex_policy
+            && self.sex_vocabulary == other.sex_vocabulary
+            && self.omit_created == other.omit_created
+            && self.lenient == other.lenient
+            && self.genome_assembly == other.genome_assembly
+            && self.keep_duplicate_diseases == other.keep_duplicate_diseases
+            && self.hpo_observed_threshold == other.hpo_observed_threshold
             && self.hpo_bidict_lib == other.hpo_bidict_lib
             && self.disease_bidict_lib == other.disease_bidict_lib
             && self.unit_bidict_lib == other.unit_bidict_lib
@@ -74,14 +193,17 @@ impl PartialEq for TransformContext {
             && self.anatomy_bi_dict_lib == other.anatomy_bi_dict_lib
             && self.drug_bi_dict_lib == other.drug_bi_dict_lib
             && self.treatment_attributes_bi_dict == other.treatment_attributes_bi_dict
+            && self.evidence_bi_dict_lib == other.evidence_bi_dict_lib
+            && self.gender_bi_dict_lib == other.gender_bi_dict_lib
+            && self.disease_attributes_bi_dict_lib == other.disease_attributes_bi_dict_lib
     }
 }
 
 impl TransformContext {
     pub fn builder(
         meta_data: BuilderMetaData,
-        hgnc_client: Arc<dyn HGNCData>,
-        hgvs_client: Arc<dyn HGVSData>,
+        hgnc_client: Arc<dyn HGNCData + Send + Sync>,
+        hgvs_client: Arc<dyn HGVSData + Send + Sync>,
     ) -> TransformContextBuilder {
         TransformContextBuilder::new(meta_data, hgnc_client, hgvs_client)
     }
@@ -94,14 +216,118 @@ impl TransformContext {
         &self.hpo_bidict_lib
     }
 
-    pub fn hgnc_client(&self) -> &Arc<dyn HGNCData> {
+    pub fn hgnc_client(&self) -> &Arc<dyn HGNCData + Send + Sync> {
         &self.hgnc_client
     }
 
-    pub fn hgvs_client(&self) -> &Arc<dyn HGVSData> {
+    pub fn hgvs_client(&self) -> &Arc<dyn HGVSData + Send + Sync> {
         &self.hgvs_client
     }
 
+    pub fn hgnc_gene_cache(&self) -> &HgncGeneCache {
+        &self.hgnc_gene_cache
+    }
+
+    pub fn hgvs_variant_cache(&self) -> &HgvsVariantCache {
+        &self.hgvs_variant_cache
+    }
+
+    pub fn gene_symbol_aliases(&self) -> Option<&Arc<dyn GeneSymbolAliases>> {
+        self.gene_symbol_aliases.as_ref()
+    }
+
+    pub fn gene_batch_lookup(&self) -> Option<&Arc<dyn GeneBatchLookup>> {
+        self.gene_batch_lookup.as_ref()
+    }
+
+    pub fn remapped_gene_symbols(&self) -> &Cache<String, String> {
+        &self.remapped_gene_symbols
+    }
+
+    pub fn negative_lookup_cache(&self) -> Option<&Arc<NegativeLookupCache>> {
+        self.negative_lookup_cache.as_ref()
+    }
+
+    pub(crate) fn resource_resolver(&self) -> &Arc<CachedResourceResolver> {
+        &self.resource_resolver
+    }
+
+    pub fn request_limiter(&self) -> &Arc<ConcurrencyLimiter> {
+        &self.request_limiter
+    }
+
+    pub fn unknown_sex_policy(&self) -> UnknownSexPolicy {
+        self.unknown_sex_policy
+    }
+
+    pub fn sex_vocabulary(&self) -> &SexVocabulary {
+        &self.sex_vocabulary
+    }
+
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    pub fn omit_created(&self) -> bool {
+        self.omit_created
+    }
+
+    pub fn lenient(&self) -> bool {
+        self.lenient
+    }
+
+    pub fn keep_duplicate_diseases(&self) -> bool {
+        self.keep_duplicate_diseases
+    }
+
+    pub fn hpo_observed_threshold(&self) -> f64 {
+        self.hpo_observed_threshold
+    }
+
+    pub fn genome_assembly(&self) -> Option<GenomeAssembly> {
+        self.genome_assembly
+    }
+
+    pub(crate) fn set_genome_assembly(&mut self, genome_assembly: GenomeAssembly) {
+        self.genome_assembly = Some(genome_assembly);
+    }
+
+    pub(crate) fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    pub(crate) fn set_omit_created(&mut self, omit_created: bool) {
+        self.omit_created = omit_created;
+    }
+
+    pub(crate) fn set_lenient(&mut self, lenient: bool) {
+        self.lenient = lenient;
+    }
+
+    pub(crate) fn set_keep_duplicate_diseases(&mut self, keep_duplicate_diseases: bool) {
+        self.keep_duplicate_diseases = keep_duplicate_diseases;
+    }
+
+    pub(crate) fn set_hpo_observed_threshold(&mut self, hpo_observed_threshold: f64) {
+        self.hpo_observed_threshold = hpo_observed_threshold;
+    }
+
+    pub(crate) fn set_negative_lookup_cache(&mut self, cache: Arc<NegativeLookupCache>) {
+        self.negative_lookup_cache = Some(cache);
+    }
+
+    pub(crate) fn set_hgnc_gene_cache(&mut self, cache: HgncGeneCache) {
+        self.hgnc_gene_cache = cache;
+    }
+
+    pub(crate) fn set_gene_symbol_aliases(&mut self, aliases: Arc<dyn GeneSymbolAliases>) {
+        self.gene_symbol_aliases = Some(aliases);
+    }
+
+    pub(crate) fn set_gene_batch_lookup(&mut self, gene_batch_lookup: Arc<dyn GeneBatchLookup>) {
+        self.gene_batch_lookup = Some(gene_batch_lookup);
+    }
+
     pub fn disease_bidict_lib(&self) -> &Arc<BiDictLibrary> {
         &self.disease_bidict_lib
     }
@@ -133,13 +359,64 @@ impl TransformContext {
     pub fn treatment_attributes_bi_dict(&self) -> &Arc<BiDictLibrary> {
         &self.treatment_attributes_bi_dict
     }
+
+    pub fn evidence_bi_dict_lib(&self) -> &Arc<BiDictLibrary> {
+        &self.evidence_bi_dict_lib
+    }
+
+    pub fn gender_bi_dict_lib(&self) -> &Arc<BiDictLibrary> {
+        &self.gender_bi_dict_lib
+    }
+
+    pub fn disease_attributes_bi_dict_lib(&self) -> &Arc<BiDictLibrary> {
+        &self.disease_attributes_bi_dict_lib
+    }
+
+    /// Resolves a single term against all loaded `BiDictLibrary`s, without running a pipeline.
+    ///
+    /// See `BiDictLibrary::resolve_term`.
+    pub fn resolve_term(&self, ontology_prefix: &str, query: &str) -> Option<OntologyClass> {
+        [
+            &self.hpo_bidict_lib,
+            &self.disease_bidict_lib,
+            &self.unit_bidict_lib,
+            &self.assay_bidict_lib,
+            &self.qualitative_measurement_bidict_lib,
+            &self.procedure_bi_dict_lib,
+            &self.anatomy_bi_dict_lib,
+            &self.drug_bi_dict_lib,
+            &self.treatment_attributes_bi_dict,
+            &self.evidence_bi_dict_lib,
+            &self.gender_bi_dict_lib,
+            &self.disease_attributes_bi_dict_lib,
+        ]
+        .into_iter()
+        .find_map(|bi_dict_lib| bi_dict_lib.resolve_term(ontology_prefix, query))
+    }
 }
 
 pub struct TransformContextBuilder {
     meta_data: BuilderMetaData,
     hpo_bidict_lib: BiDictLibrary,
-    hgnc_client: Arc<dyn HGNCData>,
-    hgvs_client: Arc<dyn HGVSData>,
+    hgnc_client: Arc<dyn HGNCData + Send + Sync>,
+    hgvs_client: Arc<dyn HGVSData + Send + Sync>,
+    gene_symbol_aliases: Option<Arc<dyn GeneSymbolAliases>>,
+    gene_batch_lookup: Option<Arc<dyn GeneBatchLookup>>,
+    negative_lookup_cache: Option<Arc<NegativeLookupCache>>,
+    hgnc_gene_cache: HgncGeneCache,
+    hgvs_variant_cache: HgvsVariantCache,
+    resource_resolver: Arc<CachedResourceResolver>,
+    request_limiter: Arc<ConcurrencyLimiter>,
+    unknown_sex_policy: UnknownSexPolicy,
+    sex_vocabulary: SexVocabulary,
+    clock: Arc<dyn Clock>,
+    omit_created: bool,
+    lenient: bool,
+    genome_assembly: Option<GenomeAssembly>,
+    keep_duplicate_diseases: bool,
+    hpo_observed_threshold: f64,
+    label_suggestions: bool,
+    disease_priority: Vec<String>,
     disease_bidict_lib: BiDictLibrary,
     unit_bidict_lib: BiDictLibrary,
     assay_bidict_lib: BiDictLibrary,
@@ -148,19 +425,39 @@ pub struct TransformContextBuilder {
     anatomy_bi_dict_lib: BiDictLibrary,
     drug_bi_dict_lib: BiDictLibrary,
     treatment_attributes_bi_dict: BiDictLibrary,
+    evidence_bi_dict_lib: BiDictLibrary,
+    gender_bi_dict_lib: BiDictLibrary,
+    disease_attributes_bi_dict_lib: BiDictLibrary,
 }
 
 impl TransformContextBuilder {
     pub fn new(
         meta_data: BuilderMetaData,
-        hgnc_client: Arc<dyn HGNCData>,
-        hgvs_client: Arc<dyn HGVSData>,
+        hgnc_client: Arc<dyn HGNCData + Send + Sync>,
+        hgvs_client: Arc<dyn HGVSData + Send + Sync>,
     ) -> Self {
         Self {
             meta_data,
             hpo_bidict_lib: BiDictLibrary::empty_with_name("HPO"),
             hgnc_client,
             hgvs_client,
+            gene_symbol_aliases: None,
+            gene_batch_lookup: None,
+            negative_lookup_cache: None,
+            hgnc_gene_cache: HgncGeneCache::default(),
+            hgvs_variant_cache: HgvsVariantCache::default(),
+            resource_resolver: Arc::new(CachedResourceResolver::default()),
+            request_limiter: Arc::new(ConcurrencyLimiter::default()),
+            unknown_sex_policy: UnknownSexPolicy::default(),
+            sex_vocabulary: SexVocabulary::default(),
+            clock: Arc::new(SystemClock),
+            omit_created: false,
+            lenient: false,
+            genome_assembly: None,
+            keep_duplicate_diseases: false,
+            hpo_observed_threshold: 0.0,
+            label_suggestions: false,
+            disease_priority: vec![],
             disease_bidict_lib: BiDictLibrary::empty_with_name("DISEASE"),
             unit_bidict_lib: BiDictLibrary::empty_with_name("UNIT"),
             assay_bidict_lib: BiDictLibrary::empty_with_name("ASSY"),
@@ -169,19 +466,124 @@ impl TransformContextBuilder {
             anatomy_bi_dict_lib: BiDictLibrary::empty_with_name("ANATOMY"),
             drug_bi_dict_lib: BiDictLibrary::empty_with_name("DRUG"),
             treatment_attributes_bi_dict: BiDictLibrary::empty_with_name("TREATMENT"),
+            evidence_bi_dict_lib: BiDictLibrary::empty_with_name("EVIDENCE"),
+            gender_bi_dict_lib: BiDictLibrary::empty_with_name("GENDER"),
+            disease_attributes_bi_dict_lib: BiDictLibrary::empty_with_name("DISEASE_ATTRIBUTES"),
         }
     }
 
-    pub fn hgnc_client(mut self, client: Arc<dyn HGNCData>) -> Self {
+    pub fn hgnc_client(mut self, client: Arc<dyn HGNCData + Send + Sync>) -> Self {
         self.hgnc_client = client;
         self
     }
 
-    pub fn hgvs_client(mut self, client: Arc<dyn HGVSData>) -> Self {
+    pub fn hgvs_client(mut self, client: Arc<dyn HGVSData + Send + Sync>) -> Self {
         self.hgvs_client = client;
         self
     }
 
+    /// If set, gene symbols, which HGNC can't resolve, are remapped to their current symbol via their previous or alias symbols.
+    pub fn gene_symbol_aliases(mut self, aliases: Arc<dyn GeneSymbolAliases>) -> Self {
+        self.gene_symbol_aliases = Some(aliases);
+        self
+    }
+
+    /// If set, the genes of a batch of tables are resolved with as few requests as possible, before they are collected.
+    pub fn gene_batch_lookup(mut self, gene_batch_lookup: Arc<dyn GeneBatchLookup>) -> Self {
+        self.gene_batch_lookup = Some(gene_batch_lookup);
+        self
+    }
+
+    /// If set, HGNC and HGVS lookups, which failed because the query can't be resolved, are remembered.
+    pub fn negative_lookup_cache(mut self, cache: Arc<NegativeLookupCache>) -> Self {
+        self.negative_lookup_cache = Some(cache);
+        self
+    }
+
+    /// The cache of the resolved genes, e.g. one with a time to live, which is backed by a file.
+    /// By default, genes are cached for the lifetime of the context.
+    pub fn hgnc_gene_cache(mut self, cache: HgncGeneCache) -> Self {
+        self.hgnc_gene_cache = cache;
+        self
+    }
+
+    /// The cache of the validated HGVS variants, e.g. one, which is backed by a file.
+    /// By default, variants are cached for the lifetime of the context.
+    pub fn hgvs_variant_cache(mut self, cache: HgvsVariantCache) -> Self {
+        self.hgvs_variant_cache = cache;
+        self
+    }
+
+    pub(crate) fn resource_resolver(mut self, resolver: Arc<CachedResourceResolver>) -> Self {
+        self.resource_resolver = resolver;
+        self
+    }
+
+    pub fn request_limiter(mut self, limiter: Arc<ConcurrencyLimiter>) -> Self {
+        self.request_limiter = limiter;
+        self
+    }
+
+    pub fn unknown_sex_policy(mut self, policy: UnknownSexPolicy) -> Self {
+        self.unknown_sex_policy = policy;
+        self
+    }
+
+    /// Replaces the default vocabulary, which maps the sex values of the source data to a `Sex`.
+    pub fn sex_vocabulary(mut self, sex_vocabulary: SexVocabulary) -> Self {
+        self.sex_vocabulary = sex_vocabulary;
+        self
+    }
+
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// If enabled, `MetaData.created` is omitted from the phenopackets.
+    pub fn omit_created(mut self, omit_created: bool) -> Self {
+        self.omit_created = omit_created;
+        self
+    }
+
+    /// If enabled, phenotypes and diseases with an unresolvable term are dropped and recorded instead of failing the build.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Forces the VCF records of the variants onto a genome assembly. Variants on another assembly are an error.
+    pub fn genome_assembly(mut self, genome_assembly: GenomeAssembly) -> Self {
+        self.genome_assembly = Some(genome_assembly);
+        self
+    }
+
+    /// If enabled, every disease row becomes its own disease. By default, identical diseases are merged.
+    pub fn keep_duplicate_diseases(mut self, keep_duplicate_diseases: bool) -> Self {
+        self.keep_duplicate_diseases = keep_duplicate_diseases;
+        self
+    }
+
+    /// Numeric scores in the columns, which have an HPO term in their header, above this threshold mean the phenotype
+    /// was observed, otherwise it was excluded. 0 by default.
+    pub fn hpo_observed_threshold(mut self, hpo_observed_threshold: f64) -> Self {
+        self.hpo_observed_threshold = hpo_observed_threshold;
+        self
+    }
+
+    /// If enabled, unresolvable labels are reported together with the closest labels of the bidicts.
+    pub fn label_suggestions(mut self, label_suggestions: bool) -> Self {
+        self.label_suggestions = label_suggestions;
+        self
+    }
+
+    /// The order, in which the disease bidicts are asked for a term, by prefix (e.g. `["MONDO", "OMIM"]`).
+    /// Unlisted bidicts are asked last, in the order they were added.
+    pub fn disease_priority(mut self, disease_priority: Vec<String>) -> Self {
+        self.disease_priority = disease_priority;
+        self
+    }
+
     pub fn add_hpo_bidict(&mut self, bidict: Box<dyn BiDict>) {
         self.hpo_bidict_lib.add_bidict(bidict);
     }
@@ -217,20 +619,59 @@ impl TransformContextBuilder {
         self.treatment_attributes_bi_dict.add_bidict(bidict);
     }
 
+    pub fn add_evidence_bidict(&mut self, bidict: Box<dyn BiDict>) {
+        self.evidence_bi_dict_lib.add_bidict(bidict);
+    }
+
+    pub fn add_gender_bidict(&mut self, bidict: Box<dyn BiDict>) {
+        self.gender_bi_dict_lib.add_bidict(bidict);
+    }
+
+    pub fn add_disease_attributes_bidict(&mut self, bidict: Box<dyn BiDict>) {
+        self.disease_attributes_bi_dict_lib.add_bidict(bidict);
+    }
+
     pub fn build(self) -> TransformContext {
+        let label_suggestions = self.label_suggestions;
+        let finish = |bidict_lib: BiDictLibrary| {
+            Arc::new(bidict_lib.with_label_suggestions(label_suggestions))
+        };
+
         TransformContext {
             meta_data: self.meta_data,
-            hpo_bidict_lib: Arc::new(self.hpo_bidict_lib),
+            hpo_bidict_lib: finish(self.hpo_bidict_lib),
             hgnc_client: self.hgnc_client,
             hgvs_client: self.hgvs_client,
-            disease_bidict_lib: Arc::new(self.disease_bidict_lib),
-            unit_bidict_lib: Arc::new(self.unit_bidict_lib),
-            assay_bidict_lib: Arc::new(self.assay_bidict_lib),
-            qualitative_measurement_bidict_lib: Arc::new(self.qualitative_measurement_bidict_lib),
-            procedure_bi_dict_lib: Arc::new(self.procedure_bi_dict_lib),
-            anatomy_bi_dict_lib: Arc::new(self.anatomy_bi_dict_lib),
-            drug_bi_dict_lib: Arc::new(self.drug_bi_dict_lib),
-            treatment_attributes_bi_dict: Arc::new(self.treatment_attributes_bi_dict),
+            hgnc_gene_cache: self.hgnc_gene_cache,
+            hgvs_variant_cache: self.hgvs_variant_cache,
+            gene_symbol_aliases: self.gene_symbol_aliases,
+            gene_batch_lookup: self.gene_batch_lookup,
+            remapped_gene_symbols: Cache::new(10_000),
+            negative_lookup_cache: self.negative_lookup_cache,
+            resource_resolver: self.resource_resolver,
+            request_limiter: self.request_limiter,
+            unknown_sex_policy: self.unknown_sex_policy,
+            sex_vocabulary: self.sex_vocabulary,
+            clock: self.clock,
+            omit_created: self.omit_created,
+            lenient: self.lenient,
+            genome_assembly: self.genome_assembly,
+            keep_duplicate_diseases: self.keep_duplicate_diseases,
+            hpo_observed_threshold: self.hpo_observed_threshold,
+            disease_bidict_lib: finish(
+                self.disease_bidict_lib
+                    .with_priority(&self.disease_priority),
+            ),
+            unit_bidict_lib: finish(self.unit_bidict_lib),
+            assay_bidict_lib: finish(self.assay_bidict_lib),
+            qualitative_measurement_bidict_lib: finish(self.qualitative_measurement_bidict_lib),
+            procedure_bi_dict_lib: finish(self.procedure_bi_dict_lib),
+            anatomy_bi_dict_lib: finish(self.anatomy_bi_dict_lib),
+            drug_bi_dict_lib: finish(self.drug_bi_dict_lib),
+            treatment_attributes_bi_dict: finish(self.treatment_attributes_bi_dict),
+            evidence_bi_dict_lib: finish(self.evidence_bi_dict_lib),
+            gender_bi_dict_lib: finish(self.gender_bi_dict_lib),
+            disease_attributes_bi_dict_lib: finish(self.disease_attributes_bi_dict_lib),
         }
     }
 }
@@ -1,19 +1,33 @@
 use crate::extract::contextualized_data_frame::ContextualizedDataFrame;
 use crate::transform::collecting::cdf_collector_broker::CdfCollectorBroker;
 use crate::transform::data_processing::preprocessor::CdfPreprocessor;
-use crate::transform::error::TransformError;
+use crate::transform::error::{CollectorError, TransformError};
 use crate::transform::strategies::traits::Strategy;
 use phenopackets::schema::v2::Phenopacket;
+use std::collections::HashSet;
 
 #[derive(Debug)]
 pub struct TransformerModule {
     pub(crate) strategies: Vec<Box<dyn Strategy>>,
     pub(crate) broker: CdfCollectorBroker,
+    /// If set, the original values of the phenotype cells are kept and attached to their phenotypic features.
+    preserve_raw_values: bool,
 }
 
 impl TransformerModule {
     pub fn new(strategies: Vec<Box<dyn Strategy>>, broker: CdfCollectorBroker) -> Self {
-        TransformerModule { strategies, broker }
+        TransformerModule {
+            strategies,
+            broker,
+            preserve_raw_values: false,
+        }
+    }
+
+    /// If enabled, phenotypic features, whose cell value was changed by the strategies (e.g. "fits" to "Seizure"),
+    /// carry the original value in their description.
+    pub fn with_preserve_raw_values(mut self, preserve_raw_values: bool) -> Self {
+        self.preserve_raw_values = preserve_raw_values;
+        self
     }
 
     pub fn add_strategy(&mut self, strategy: Box<dyn Strategy>) {
@@ -27,25 +41,130 @@ impl TransformerModule {
         &mut self,
         mut data: Vec<ContextualizedDataFrame>,
     ) -> Result<Vec<Phenopacket>, TransformError> {
+        self.prepare(&mut data)?;
+        Ok(self.broker.process(data)?)
+    }
+
+    /// Transforms and collects the tables, which are read at once, when other tables are read in chunks.
+    ///
+    /// Starts a new run of the broker. The chunks are collected afterward with [`TransformerModule::collect_chunk`].
+    pub fn collect_unchunked(
+        &mut self,
+        mut data: Vec<ContextualizedDataFrame>,
+    ) -> Result<ChunkContext, TransformError> {
+        self.broker.start_run();
+        self.preprocess(&mut data)?;
+
+        let mut tables_before_strategies = vec![];
         let mut tables_refs = data
             .iter_mut()
             .collect::<Vec<&mut ContextualizedDataFrame>>();
+        for strategy in &self.strategies {
+            let tables_before_strategy = strategy
+                .reads_other_tables()
+                .then(|| tables_refs.iter().map(|table| (**table).clone()).collect());
+            tables_before_strategies.push(tables_before_strategy);
+            strategy.transform(tables_refs.as_mut_slice())?;
+        }
 
-        for table in &mut tables_refs {
-            CdfPreprocessor::process(table)?
+        self.broker.collect(data)?;
+        Ok(ChunkContext {
+            tables_before_strategies,
+        })
+    }
+
+    /// Transforms and collects a chunk of the rows of a table, without building the phenopackets yet.
+    ///
+    /// Strategies, which read other tables, transform the chunk together with the tables, which are read at once.
+    pub fn collect_chunk(
+        &mut self,
+        mut chunk: ContextualizedDataFrame,
+        chunk_context: &ChunkContext,
+    ) -> Result<(), TransformError> {
+        let table_name = chunk.context().name().to_string();
+        if let Some(strategy) = self
+            .strategies
+            .iter()
+            .find(|strategy| !strategy.supports_chunks_of(&table_name))
+        {
+            return Err(TransformError::UnchunkableStrategy {
+                strategy: format!("{strategy:?}"),
+                table_name,
+            });
         }
 
+        self.preprocess(std::slice::from_mut(&mut chunk))?;
+        for (strategy, tables_before_strategy) in self
+            .strategies
+            .iter()
+            .zip(&chunk_context.tables_before_strategies)
+        {
+            let mut other_tables = tables_before_strategy.clone().unwrap_or_default();
+            let mut tables_refs = other_tables
+                .iter_mut()
+                .collect::<Vec<&mut ContextualizedDataFrame>>();
+            tables_refs.push(&mut chunk);
+            strategy.transform(tables_refs.as_mut_slice())?;
+        }
+
+        Ok(self.broker.collect(vec![chunk])?)
+    }
+
+    /// Checks the cohort size guard and builds the phenopackets of all data collected so far.
+    pub fn build(&self) -> Result<Vec<Phenopacket>, TransformError> {
+        self.broker.check_cohort_size()?;
+        Ok(self.broker.build())
+    }
+
+    /// Like [`TransformerModule::run`], but hands every phenopacket to `on_phenopacket` as soon as it is built.
+    /// Patients, whose phenopacket id is in `skipped_phenopacket_ids`, are not transformed.
+    pub fn run_incrementally<E: From<TransformError> + From<CollectorError>>(
+        &mut self,
+        mut data: Vec<ContextualizedDataFrame>,
+        skipped_phenopacket_ids: &HashSet<String>,
+        on_phenopacket: impl FnMut(Phenopacket) -> Result<(), E>,
+    ) -> Result<(), E> {
+        self.prepare(&mut data)?;
+        self.broker
+            .process_incrementally(data, skipped_phenopacket_ids, on_phenopacket)
+    }
+
+    /// Preprocesses the tables and applies the strategies to them.
+    fn prepare(&self, data: &mut [ContextualizedDataFrame]) -> Result<(), TransformError> {
+        self.preprocess(data)?;
+
+        let mut tables_refs = data
+            .iter_mut()
+            .collect::<Vec<&mut ContextualizedDataFrame>>();
         for strategy in &self.strategies {
             strategy.transform(tables_refs.as_mut_slice())?;
         }
 
-        Ok(self.broker.process(data)?)
+        Ok(())
     }
+
+    fn preprocess(&self, data: &mut [ContextualizedDataFrame]) -> Result<(), TransformError> {
+        for table in data.iter_mut() {
+            CdfPreprocessor::process(table)?;
+            if self.preserve_raw_values {
+                CdfPreprocessor::preserve_raw_values(table)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The tables, which are read at once, as they were before each strategy, which reads other tables.
+/// Strategies, which don't read other tables, transform the chunks on their own.
+#[derive(Debug)]
+pub struct ChunkContext {
+    tables_before_strategies: Vec<Option<Vec<ContextualizedDataFrame>>>,
 }
 
 impl PartialEq for TransformerModule {
     fn eq(&self, other: &Self) -> bool {
         self.broker == other.broker
+            && self.preserve_raw_values == other.preserve_raw_values
             && self.strategies.len() == other.strategies.len()
             && self
                 .strategies
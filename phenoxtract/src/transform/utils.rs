@@ -1,4 +1,6 @@
-use crate::constants::ISO8601_DUR_PATTERN;
+use crate::constants::{HPO_ONSET_TERMS, ISO8601_DUR_PATTERN};
+use crate::ontology::resource_references::ResourceRef;
+use crate::transform::bidict_library::BiDictLibrary;
 use crate::transform::data_processing::parsing::{
     try_parse_string_date, try_parse_string_datetime,
 };
@@ -6,13 +8,14 @@ use crate::transform::error::{CollectorError, PhenopacketBuilderError};
 use chrono::{TimeZone, Utc};
 use phenopackets::schema::v2::core::Sex;
 use phenopackets::schema::v2::core::time_element::Element;
-use phenopackets::schema::v2::core::{Age as IndividualAge, TimeElement};
+use phenopackets::schema::v2::core::{Age as IndividualAge, OntologyClass, TimeElement};
 use pivotal::hgvs::ChromosomalSex;
 use polars::datatypes::DataType;
 use polars::prelude::{AnyValue, Column};
 use prost_types::Timestamp;
 use regex::Regex;
 use std::borrow::Cow;
+use std::cmp::Ordering;
 
 pub(crate) fn is_iso8601_duration(dur_string: &str) -> bool {
     let re = Regex::new(ISO8601_DUR_PATTERN).unwrap();
@@ -50,6 +53,94 @@ pub(crate) fn try_parse_time_element(te_string: &str) -> Option<TimeElement> {
     None
 }
 
+/// Parses an onset, which is a timestamp, an age or a term of the HPO "Onset" (HP:0003674) subhierarchy.
+///
+/// Onset terms are returned together with the resource of the HPO, which resolved them.
+pub(crate) fn try_parse_onset(
+    onset: &str,
+    hpo_bidict_lib: &BiDictLibrary,
+) -> Option<(TimeElement, Option<ResourceRef>)> {
+    if let Some(te) = try_parse_time_element(onset) {
+        return Some((te, None));
+    }
+
+    try_resolve_hpo_onset_term(hpo_bidict_lib, onset).map(|(onset_term, onset_ref)| {
+        let te = TimeElement {
+            element: Some(Element::OntologyClass(onset_term)),
+        };
+        (te, Some(onset_ref))
+    })
+}
+
+/// Orders two time elements by when they happened.
+///
+/// Only two timestamps or two ages (ISO 8601 durations) are comparable. Ages are compared approximately,
+/// counting a year as 365.25 days and a month as a twelfth of a year.
+pub(crate) fn compare_time_elements(a: &TimeElement, b: &TimeElement) -> Option<Ordering> {
+    match (a.element.as_ref()?, b.element.as_ref()?) {
+        (Element::Timestamp(a), Element::Timestamp(b)) => {
+            Some((a.seconds, a.nanos).cmp(&(b.seconds, b.nanos)))
+        }
+        (Element::Age(a), Element::Age(b)) => {
+            age_in_days(&a.iso8601duration)?.partial_cmp(&age_in_days(&b.iso8601duration)?)
+        }
+        _ => None,
+    }
+}
+
+/// Converts an age into the timestamp, at which an individual born at `date_of_birth` had that age. Like
+/// [`compare_time_elements`], it counts a year as 365.25 days. Other time elements are returned unchanged.
+pub(crate) fn age_to_timestamp(te: &TimeElement, date_of_birth: &Timestamp) -> TimeElement {
+    let Some(Element::Age(age)) = &te.element else {
+        return te.clone();
+    };
+    let Some(days) = age_in_days(&age.iso8601duration) else {
+        return te.clone();
+    };
+
+    TimeElement {
+        element: Some(Element::Timestamp(Timestamp {
+            seconds: date_of_birth.seconds + (days * 24.0 * 60.0 * 60.0).round() as i64,
+            nanos: date_of_birth.nanos,
+        })),
+    }
+}
+
+fn age_in_days(iso8601duration: &str) -> Option<f64> {
+    let captures = Regex::new(ISO8601_DUR_PATTERN)
+        .unwrap()
+        .captures(iso8601duration)?;
+    let component = |idx: usize| -> f64 {
+        captures
+            .get(idx)
+            .and_then(|m| m.as_str()[..m.as_str().len() - 1].parse::<f64>().ok())
+            .unwrap_or(0.0)
+    };
+
+    Some(
+        component(1) * 365.25
+            + component(2) * 365.25 / 12.0
+            + component(3)
+            + component(5) / 24.0
+            + component(6) / (24.0 * 60.0)
+            + component(7) / (24.0 * 60.0 * 60.0),
+    )
+}
+
+/// Resolves the id or label of an HPO onset term, e.g. "HP:0003577" or "Congenital onset", through the HPO bidicts.
+///
+/// Only the terms of the "Onset" (HP:0003674) subhierarchy are accepted. They always carry their primary label.
+pub(crate) fn try_resolve_hpo_onset_term(
+    hpo_bidict_lib: &BiDictLibrary,
+    term: &str,
+) -> Option<(OntologyClass, ResourceRef)> {
+    let (onset_term, _) = hpo_bidict_lib.lookup(term.trim())?;
+    if !HPO_ONSET_TERMS.contains(&onset_term.id.as_str()) {
+        return None;
+    }
+    hpo_bidict_lib.lookup(&onset_term.id)
+}
+
 /// A struct for creating columns which have HPO IDs in the header
 /// and observation statuses in the cells.
 /// The headers of HPO columns will have the format HP:1234567{separator}A
@@ -131,13 +222,60 @@ pub(crate) fn cow_cast(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_suite::component_building::build_test_hpo_bidict_library;
     use crate::test_suite::phenopacket_component_generation::{
         default_age_element, default_iso_age,
     };
+    use crate::test_suite::resource_references::HPO_REF;
     use polars::prelude::NamedFrom;
     use polars::series::Series;
     use rstest::rstest;
 
+    #[rstest]
+    #[case("HP:0003577", "HP:0003577", "Congenital onset")]
+    #[case("childhood onset", "HP:0011463", "Childhood onset")]
+    fn test_try_parse_onset_hpo_onset(
+        #[case] input: &str,
+        #[case] expected_id: &str,
+        #[case] expected_label: &str,
+    ) {
+        let hpo_bidict_lib = build_test_hpo_bidict_library();
+
+        let (te, onset_ref) = try_parse_onset(input, &hpo_bidict_lib).unwrap();
+
+        assert_eq!(
+            te.element,
+            Some(Element::OntologyClass(OntologyClass {
+                id: expected_id.to_string(),
+                label: expected_label.to_string(),
+            }))
+        );
+        assert_eq!(onset_ref, Some(HPO_REF.clone()));
+    }
+
+    #[rstest]
+    fn test_try_parse_onset_age() {
+        let hpo_bidict_lib = build_test_hpo_bidict_library();
+
+        let (te, onset_ref) = try_parse_onset("P3Y", &hpo_bidict_lib).unwrap();
+
+        assert!(matches!(te.element, Some(Element::Age(_))));
+        assert_eq!(onset_ref, None);
+    }
+
+    #[rstest]
+    fn test_try_resolve_hpo_onset_term_rejects_non_onset_terms() {
+        let hpo_bidict_lib = build_test_hpo_bidict_library();
+
+        assert!(try_resolve_hpo_onset_term(&hpo_bidict_lib, "HP:0001250").is_none());
+        assert!(try_resolve_hpo_onset_term(&hpo_bidict_lib, "childhood").is_none());
+    }
+
+    #[rstest]
+    fn test_try_parse_time_element_rejects_hpo_onset_terms() {
+        assert!(try_parse_time_element("HP:0003577").is_none());
+    }
+
     #[rstest]
     fn test_create_hpo_col() {
         let hpo_col_maker = HpoColMaker::new();
@@ -226,6 +364,23 @@ mod tests {
         assert!(chromosomal_sex_from_str(Some("blah")).is_err());
     }
 
+    #[rstest]
+    #[case("P1Y", "P13M", Some(Ordering::Less))]
+    #[case("P2Y", "P1Y11M30D", Some(Ordering::Greater))]
+    #[case("P3M", "P3M", Some(Ordering::Equal))]
+    #[case("P1Y", "2001-01-29", None)]
+    #[case("2001-01-29", "2015-06-05T09:17:39Z", Some(Ordering::Less))]
+    fn test_compare_time_elements(
+        #[case] a: &str,
+        #[case] b: &str,
+        #[case] expected: Option<Ordering>,
+    ) {
+        let a = try_parse_time_element(a).unwrap();
+        let b = try_parse_time_element(b).unwrap();
+
+        assert_eq!(compare_time_elements(&a, &b), expected);
+    }
+
     #[rstest]
     fn test_parse_time_element_duration() {
         let te = try_parse_time_element(&default_iso_age()).unwrap();
@@ -80,8 +80,13 @@ pub(crate) fn validate_dangling_sc(cdf: &ContextualizedDataFrame) -> Result<(),
     let dangling_scs = cdf.get_dangling_scs();
     if !dangling_scs.is_empty() {
         error.add_param(Cow::from("series_contexts"), &dangling_scs);
+        let descriptions: Vec<String> = dangling_scs
+            .iter()
+            .map(|sc_id| cdf.describe_unmatched_identifier(sc_id))
+            .collect();
         let error_message = format!(
-            "The SeriesContexts with identifiers '{dangling_scs:?}' do not point to any column",
+            "The SeriesContexts with identifiers '{dangling_scs:?}' do not point to any column: {}",
+            descriptions.join("; ")
         );
         Err(error.with_message(Cow::Owned(error_message)))
     } else {
@@ -287,6 +292,27 @@ mod tests {
         }
     }
 
+    #[rstest]
+    fn test_validate_dangling_sc_suggests_closest_column() {
+        let result = ContextualizedDataFrame::new(
+            TableContext::new(
+                "test_table".to_string(),
+                vec![
+                    SeriesContext::default()
+                        .with_identifier(Identifier::from("subject_id"))
+                        .with_data_context(Context::SubjectId),
+                    SeriesContext::default().with_identifier(Identifier::from("sexx")),
+                ],
+            ),
+            df!["subject_id" => ["P001"], "sex" => ["MALE"]].unwrap(),
+        );
+
+        let message = result.unwrap_err().to_string();
+
+        assert!(message.contains("Did you mean 'sex'?"));
+        assert!(message.contains(r#"Available columns: ["subject_id", "sex"]"#));
+    }
+
     #[rstest]
     fn test_validate_subject_id_col_no_nulls_success() {
         let df = df!(
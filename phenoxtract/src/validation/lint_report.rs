@@ -0,0 +1,172 @@
+use crate::transform::dropped_fields::{DroppedField, DroppedFieldKind};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use strum_macros::Display;
+use validator::ValidationError;
+
+/// How severe a [`LintFinding`] is. Errors abort the run, warnings are only reported.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Display, Serialize, Deserialize,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    #[serde(alias = "warn")]
+    Warning,
+    Error,
+}
+
+/// A problem, which a check of the built phenopackets found.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintFinding {
+    /// The check, which found the problem, e.g. `"missing_resource"` or `"unresolved_phenotype"`.
+    pub rule: String,
+    pub severity: Severity,
+    /// The patient, whose phenopacket has the problem. Problems of the whole cohort, e.g. inconsistent resource
+    /// versions, have none.
+    pub patient_id: Option<String>,
+    pub message: String,
+}
+
+impl LintFinding {
+    /// Describes a failed check of the phenopacket validation. Its code is the rule and its message,
+    /// together with its params, is the message of the finding.
+    pub(crate) fn from_validation_error(
+        severity: Severity,
+        patient_id: Option<&str>,
+        err: &ValidationError,
+    ) -> Self {
+        let params: BTreeMap<&str, &serde_json::Value> = err
+            .params
+            .iter()
+            .filter(|(name, _)| *name != "phenopacket_id")
+            .map(|(name, value)| (name.as_ref(), value))
+            .collect();
+        let message = err.message.as_deref().unwrap_or(err.code.as_ref());
+
+        LintFinding {
+            rule: err.code.to_string(),
+            severity,
+            patient_id: patient_id.map(str::to_string),
+            message: format!("{message} {}", serde_json::json!(params)),
+        }
+    }
+
+    /// Describes a dropped value. Contradicting observation statuses are an error, other dropped values a warning.
+    pub(crate) fn from_dropped_field(dropped_field: &DroppedField) -> Self {
+        let severity = match dropped_field.kind {
+            DroppedFieldKind::ConflictingObservationStatus => Severity::Error,
+            _ => Severity::Warning,
+        };
+
+        LintFinding {
+            rule: dropped_field.kind.to_string(),
+            severity,
+            patient_id: Some(dropped_field.patient_id.clone()),
+            message: format!(
+                "Dropped '{}', because {}.",
+                dropped_field.value,
+                dropped_field.kind.reason()
+            ),
+        }
+    }
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.patient_id {
+            Some(patient_id) => write!(
+                f,
+                "{} [{}] Patient '{patient_id}': {}",
+                self.severity, self.rule, self.message
+            ),
+            None => write!(f, "{} [{}] {}", self.severity, self.rule, self.message),
+        }
+    }
+}
+
+/// The findings of the checks of the phenopackets, which a pipeline built, e.g. missing resources, invalid onsets or
+/// unresolved terms.
+///
+/// It can be serialized to JSON, so that a batch can be gated on its errors without scraping the log.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LintReport {
+    findings: Vec<LintFinding>,
+}
+
+impl LintReport {
+    pub fn findings(&self) -> &[LintFinding] {
+        &self.findings
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &LintFinding> {
+        self.findings
+            .iter()
+            .filter(|finding| finding.severity == Severity::Error)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub(crate) fn extend(&mut self, findings: impl IntoIterator<Item = LintFinding>) {
+        self.findings.extend(findings);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use serde_json::json;
+    use std::borrow::Cow;
+
+    #[rstest]
+    fn test_lint_report_to_json() {
+        let mut err = ValidationError::new("conflicting_observation_status");
+        err.add_param(Cow::from("phenopacket_id"), &"pp_1");
+        err.add_param(Cow::from("conflicting_hpo_ids"), &vec!["HP:0000001"]);
+        let err =
+            err.with_message(Cow::Borrowed("Some phenotypes are both observed and excluded."));
+
+        let mut report = LintReport::default();
+        report.extend([
+            LintFinding::from_dropped_field(&DroppedField {
+                kind: DroppedFieldKind::UnresolvedPhenotype,
+                patient_id: "P1".to_string(),
+                value: "Unknown phenotype".to_string(),
+            }),
+            LintFinding::from_validation_error(Severity::Error, Some("P2"), &err),
+        ]);
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+
+        assert_eq!(
+            json,
+            json!({
+                "findings": [
+                    {
+                        "rule": "unresolved_phenotype",
+                        "severity": "warning",
+                        "patient_id": "P1",
+                        "message": "Dropped 'Unknown phenotype', \
+                            because its term could not be resolved."
+                    },
+                    {
+                        "rule": "conflicting_observation_status",
+                        "severity": "error",
+                        "patient_id": "P2",
+                        "message": "Some phenotypes are both observed and excluded. \
+                            {\"conflicting_hpo_ids\":[\"HP:0000001\"]}"
+                    }
+                ]
+            })
+        );
+        assert!(report.has_errors());
+    }
+}
@@ -0,0 +1,312 @@
+use crate::ontology::traits::OntologyLike;
+use crate::transform::dropped_fields::DroppedField;
+use crate::validation::lint_report::{LintFinding, Severity};
+use crate::validation::phenopacket_validation::{
+    check_conflicting_observation_statuses, check_consistent_resource_versions,
+    check_consistent_versions, check_onset_terms, check_onsets_before_death,
+    check_plausible_measurement_values, check_resource_prefixes,
+};
+use phenopackets::schema::v2::Phenopacket;
+use phenopackets::schema::v2::core::Resource;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use validator::ValidationError;
+
+/// The range, in which the values of an assay are plausible, e.g. 0 to 250 g/L for hemoglobin.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PlausibleRange {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl PlausibleRange {
+    pub fn contains(&self, value: f64) -> bool {
+        (self.min..=self.max).contains(&value)
+    }
+}
+
+/// How the findings of a lint rule are reported. `Warn` and `Error` override the severity of the rule and `Off`
+/// drops its findings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintLevel {
+    Off,
+    Warn,
+    Error,
+}
+
+impl LintLevel {
+    fn severity(self) -> Option<Severity> {
+        match self {
+            LintLevel::Off => None,
+            LintLevel::Warn => Some(Severity::Warning),
+            LintLevel::Error => Some(Severity::Error),
+        }
+    }
+}
+
+/// Runs the checks of the phenopacket validation on the built phenopackets and turns their failures into
+/// lint findings.
+#[derive(Debug)]
+pub(crate) struct Linter {
+    /// If set, every onset ontology class has to be a descendant of Onset in this HPO.
+    hpo: Option<Arc<dyn OntologyLike>>,
+    /// The plausible range of the values of each assay, e.g. of `"LOINC:718-7"`.
+    plausible_measurement_ranges: HashMap<String, PlausibleRange>,
+    /// If set, phenopackets, which reference different versions of the same resource, are an error.
+    strict_resource_versions: bool,
+    /// Overrides the severity of single rules, e.g. `{"missing_resource": Off}`.
+    rule_levels: HashMap<String, LintLevel>,
+    /// Findings, which are at least this severe, abort the run.
+    fail_on: Severity,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Linter {
+            hpo: None,
+            plausible_measurement_ranges: HashMap::new(),
+            strict_resource_versions: false,
+            rule_levels: HashMap::new(),
+            fail_on: Severity::Error,
+        }
+    }
+}
+
+impl Linter {
+    /// The rules, whose level can be configured.
+    pub(crate) const RULES: [&'static str; 10] = [
+        "missing_resource",
+        "inconsistent_resource_versions",
+        "conflicting_observation_status",
+        "onset_after_death",
+        "implausible_measurement_value",
+        "invalid_onset_term",
+        "unresolved_phenotype",
+        "unresolved_disease",
+        "malformed_survival_time",
+        "incomparable_onset",
+    ];
+
+    pub(crate) fn set_hpo(&mut self, hpo: Arc<dyn OntologyLike>) {
+        self.hpo = Some(hpo);
+    }
+
+    pub(crate) fn set_plausible_measurement_ranges(
+        &mut self,
+        plausible_measurement_ranges: HashMap<String, PlausibleRange>,
+    ) {
+        self.plausible_measurement_ranges = plausible_measurement_ranges;
+    }
+
+    pub(crate) fn set_strict_resource_versions(&mut self, strict_resource_versions: bool) {
+        self.strict_resource_versions = strict_resource_versions;
+    }
+
+    pub(crate) fn set_rule_levels(&mut self, rule_levels: HashMap<String, LintLevel>) {
+        self.rule_levels = rule_levels;
+    }
+
+    pub(crate) fn set_fail_on(&mut self, fail_on: Severity) {
+        self.fail_on = fail_on;
+    }
+
+    /// Checks a single phenopacket, e.g. for missing resources, contradicting observation statuses, invalid onsets
+    /// and implausible measurement values.
+    pub(crate) fn lint_phenopacket(&self, phenopacket: &Phenopacket) -> Vec<LintFinding> {
+        let mut results: Vec<(Severity, Result<(), ValidationError>)> = vec![
+            (Severity::Warning, check_resource_prefixes(phenopacket)),
+            (Severity::Error, check_conflicting_observation_statuses(phenopacket)),
+            (Severity::Error, check_onsets_before_death(phenopacket)),
+            (
+                Severity::Warning,
+                check_plausible_measurement_values(phenopacket, &self.plausible_measurement_ranges),
+            ),
+        ];
+        if let Some(hpo) = &self.hpo {
+            results.push((Severity::Error, check_onset_terms(phenopacket, hpo.as_ref())));
+        }
+
+        let patient_id = Self::patient_id(phenopacket);
+        let findings = results.into_iter().filter_map(|(severity, result)| {
+            result
+                .err()
+                .map(|err| LintFinding::from_validation_error(severity, Some(patient_id), &err))
+        });
+        self.apply_rule_levels(findings)
+    }
+
+    /// Checks every phenopacket of a cohort, and that all of them reference the same version of each resource.
+    /// Conflicting versions are a warning, or an error, if the resource versions are strict.
+    pub(crate) fn lint_cohort(&self, phenopackets: &[Phenopacket]) -> Vec<LintFinding> {
+        let mut findings: Vec<LintFinding> = phenopackets
+            .iter()
+            .flat_map(|phenopacket| self.lint_phenopacket(phenopacket))
+            .collect();
+
+        if let Err(err) = check_consistent_resource_versions(phenopackets) {
+            let finding = LintFinding::from_validation_error(self.version_severity(), None, &err);
+            findings.extend(self.apply_rule_levels([finding]));
+        }
+        findings
+    }
+
+    /// Checks that a phenopacket references the same version of each resource as the phenopackets, which were checked
+    /// before it, e.g. when phenopackets are loaded one by one. The first version of each resource is added to
+    /// `known_resources`.
+    pub(crate) fn lint_resource_versions(
+        &self,
+        phenopacket: &Phenopacket,
+        known_resources: &mut Vec<Resource>,
+    ) -> Vec<LintFinding> {
+        let resources = phenopacket
+            .meta_data
+            .iter()
+            .flat_map(|meta_data| meta_data.resources.iter());
+        let result = check_consistent_versions(known_resources.iter().chain(resources.clone()));
+
+        for resource in resources {
+            if !known_resources
+                .iter()
+                .any(|known| known.id.eq_ignore_ascii_case(&resource.id))
+            {
+                known_resources.push(resource.clone());
+            }
+        }
+
+        let findings = result.err().map(|err| {
+            LintFinding::from_validation_error(
+                self.version_severity(),
+                Some(Self::patient_id(phenopacket)),
+                &err,
+            )
+        });
+        self.apply_rule_levels(findings)
+    }
+
+    /// Turns the values, which were dropped, because the data is faulty (e.g. unresolved terms in lenient mode),
+    /// into findings.
+    pub(crate) fn lint_dropped_fields(&self, dropped_fields: &[DroppedField]) -> Vec<LintFinding> {
+        self.apply_rule_levels(
+            dropped_fields
+                .iter()
+                .filter(|dropped_field| dropped_field.kind.is_data_issue())
+                .map(LintFinding::from_dropped_field),
+        )
+    }
+
+    /// The first finding, which is severe enough to abort the run.
+    pub(crate) fn first_failure<'a>(
+        &self,
+        findings: &'a [LintFinding],
+    ) -> Option<&'a LintFinding> {
+        findings.iter().find(|finding| finding.severity >= self.fail_on)
+    }
+
+    /// Conflicting resource versions are an error, if the resource versions are strict.
+    fn version_severity(&self) -> Severity {
+        if self.strict_resource_versions {
+            Severity::Error
+        } else {
+            Severity::Warning
+        }
+    }
+
+    fn patient_id(phenopacket: &Phenopacket) -> &str {
+        phenopacket
+            .subject
+            .as_ref()
+            .map_or(phenopacket.id.as_str(), |subject| subject.id.as_str())
+    }
+
+    fn apply_rule_levels(
+        &self,
+        findings: impl IntoIterator<Item = LintFinding>,
+    ) -> Vec<LintFinding> {
+        findings
+            .into_iter()
+            .filter_map(|mut finding| match self.rule_levels.get(&finding.rule) {
+                Some(level) => level.severity().map(|severity| {
+                    finding.severity = severity;
+                    finding
+                }),
+                None => Some(finding),
+            })
+            .collect()
+    }
+}
+
+impl PartialEq for Linter {
+    fn eq(&self, other: &Self) -> bool {
+        self.plausible_measurement_ranges == other.plausible_measurement_ranges
+            && self.strict_resource_versions == other.strict_resource_versions
+            && self.rule_levels == other.rule_levels
+            && self.fail_on == other.fail_on
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_suite::resource_references::hp_meta_data_resource;
+    use phenopackets::schema::v2::core::{Individual, MetaData};
+    use rstest::rstest;
+
+    fn phenopacket_with_hpo_version(patient_id: &str, version: &str) -> Phenopacket {
+        Phenopacket {
+            id: format!("cohort-{patient_id}"),
+            subject: Some(Individual {
+                id: patient_id.to_string(),
+                ..Default::default()
+            }),
+            meta_data: Some(MetaData {
+                resources: vec![Resource {
+                    version: version.to_string(),
+                    ..hp_meta_data_resource()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case(false, Severity::Warning)]
+    #[case(true, Severity::Error)]
+    fn test_lint_resource_versions(
+        #[case] strict_resource_versions: bool,
+        #[case] expected_severity: Severity,
+    ) {
+        let mut linter = Linter::default();
+        linter.set_strict_resource_versions(strict_resource_versions);
+        let mut known_resources = vec![];
+
+        let first = linter.lint_resource_versions(
+            &phenopacket_with_hpo_version("P1", "2024-04-26"),
+            &mut known_resources,
+        );
+        let second = linter.lint_resource_versions(
+            &phenopacket_with_hpo_version("P2", "2025-05-06"),
+            &mut known_resources,
+        );
+        let third = linter.lint_resource_versions(
+            &phenopacket_with_hpo_version("P3", "2024-04-26"),
+            &mut known_resources,
+        );
+
+        assert!(first.is_empty());
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].rule, "inconsistent_resource_versions");
+        assert_eq!(second[0].severity, expected_severity);
+        assert_eq!(second[0].patient_id.as_deref(), Some("P2"));
+        assert!(third.is_empty());
+        assert_eq!(known_resources.len(), 1);
+        assert_eq!(known_resources[0].version, "2024-04-26");
+        assert_eq!(
+            linter.first_failure(&second).is_some(),
+            strict_resource_versions
+        );
+    }
+}
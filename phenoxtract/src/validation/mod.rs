@@ -1,7 +1,10 @@
 pub(crate) mod cdf_checks;
 pub(crate) mod contextualised_dataframe_validation;
 pub(crate) mod data_source_validation;
+pub mod lint_report;
+pub mod linter;
 pub(crate) mod multi_series_context_validation;
+pub(crate) mod phenopacket_validation;
 pub(crate) mod phenoxtractor_config_validation;
 pub(crate) mod table_context_validation;
 mod validation_utils;
@@ -0,0 +1,647 @@
+use crate::constants::HPO_ONSET_ID;
+use crate::ontology::traits::OntologyLike;
+use crate::transform::utils::{age_to_timestamp, compare_time_elements};
+use crate::validation::linter::PlausibleRange;
+use log::warn;
+use phenopackets::schema::v2::Phenopacket;
+use phenopackets::schema::v2::core::{Resource, TimeElement};
+use phenopackets::schema::v2::core::measurement::MeasurementValue;
+use phenopackets::schema::v2::core::time_element::Element;
+use phenopackets::schema::v2::core::value::Value as ValueKind;
+use serde_json::{Value, json};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use validator::ValidationError;
+
+/// Validates that the prefix of every ontology class id in the phenopacket has a resource in its meta data.
+///
+/// A missing resource usually means, that `ensure_resource` was skipped when the term was added.
+pub(crate) fn check_resource_prefixes(phenopacket: &Phenopacket) -> Result<(), ValidationError> {
+    let resource_prefixes: Vec<&str> = phenopacket
+        .meta_data
+        .iter()
+        .flat_map(|meta_data| meta_data.resources.iter())
+        .map(|resource| resource.namespace_prefix.as_str())
+        .collect();
+
+    let mut ontology_class_ids = vec![];
+    if let Ok(value) = serde_json::to_value(phenopacket) {
+        collect_ontology_class_ids(&value, &mut ontology_class_ids);
+    }
+
+    let mut ids_without_resource: Vec<String> = ontology_class_ids
+        .into_iter()
+        .filter(|id| {
+            id.split_once(':').is_some_and(|(prefix, _)| {
+                !resource_prefixes
+                    .iter()
+                    .any(|resource_prefix| resource_prefix.eq_ignore_ascii_case(prefix))
+            })
+        })
+        .collect();
+    ids_without_resource.sort();
+    ids_without_resource.dedup();
+
+    if !ids_without_resource.is_empty() {
+        let mut err = ValidationError::new("missing_resource");
+        err.add_param(Cow::from("phenopacket_id"), &phenopacket.id);
+        err.add_param(Cow::from("ids_without_resource"), &ids_without_resource);
+        let error_message = "Not every ontology class prefix has a resource in the meta data.";
+
+        return Err(err.with_message(Cow::Borrowed(error_message)));
+    }
+    Ok(())
+}
+
+/// Validates that all phenopackets of a cohort reference the same version of each resource.
+///
+/// Submission repositories reject cohorts, whose phenopackets were built against different versions of an ontology.
+pub(crate) fn check_consistent_resource_versions(
+    phenopackets: &[Phenopacket],
+) -> Result<(), ValidationError> {
+    check_consistent_versions(
+        phenopackets
+            .iter()
+            .flat_map(|phenopacket| phenopacket.meta_data.iter())
+            .flat_map(|meta_data| meta_data.resources.iter()),
+    )
+}
+
+/// Validates that the resources reference only one version of each resource, e.g. the resources of the phenopackets,
+/// which were loaded so far, and of the next one.
+pub(crate) fn check_consistent_versions<'a>(
+    resources: impl IntoIterator<Item = &'a Resource>,
+) -> Result<(), ValidationError> {
+    let mut versions_per_resource: BTreeMap<String, BTreeSet<&str>> = BTreeMap::new();
+    for resource in resources {
+        versions_per_resource
+            .entry(resource.id.to_lowercase())
+            .or_default()
+            .insert(resource.version.as_str());
+    }
+
+    let conflicting_versions: BTreeMap<String, Vec<&str>> = versions_per_resource
+        .into_iter()
+        .filter(|(_, versions)| versions.len() > 1)
+        .map(|(resource_id, versions)| (resource_id, versions.into_iter().collect()))
+        .collect();
+
+    if !conflicting_versions.is_empty() {
+        let mut err = ValidationError::new("inconsistent_resource_versions");
+        err.add_param(Cow::from("conflicting_versions"), &conflicting_versions);
+        let error_message =
+            "The phenopackets of the cohort reference different versions of the same resource.";
+
+        return Err(err.with_message(Cow::Borrowed(error_message)));
+    }
+    Ok(())
+}
+
+/// Validates that no phenotype of the phenopacket is both observed and excluded.
+///
+/// The same HPO term can be given in two different columns of the source data, e.g. once as observed and once as
+/// excluded, which results in two contradicting phenotypic features.
+pub(crate) fn check_conflicting_observation_statuses(
+    phenopacket: &Phenopacket,
+) -> Result<(), ValidationError> {
+    let mut statuses_per_hpo_id: BTreeMap<&str, BTreeSet<bool>> = BTreeMap::new();
+    for feature in phenopacket.phenotypic_features.iter() {
+        if let Some(hpo_term) = &feature.r#type {
+            statuses_per_hpo_id
+                .entry(hpo_term.id.as_str())
+                .or_default()
+                .insert(feature.excluded);
+        }
+    }
+
+    let conflicting_hpo_ids: Vec<&str> = statuses_per_hpo_id
+        .into_iter()
+        .filter(|(_, statuses)| statuses.len() > 1)
+        .map(|(hpo_id, _)| hpo_id)
+        .collect();
+
+    if !conflicting_hpo_ids.is_empty() {
+        let mut err = ValidationError::new("conflicting_observation_status");
+        err.add_param(Cow::from("phenopacket_id"), &phenopacket.id);
+        err.add_param(Cow::from("conflicting_hpo_ids"), &conflicting_hpo_ids);
+        let error_message = "Some phenotypes are both observed and excluded.";
+
+        return Err(err.with_message(Cow::Borrowed(error_message)));
+    }
+    Ok(())
+}
+
+/// Validates that every onset, which is given as an ontology class, is Onset (HP:0003674) or one of its descendants
+/// in the HPO. Ages, timestamps and other time elements aren't checked.
+///
+/// If the HPO doesn't know its hierarchy, the check is skipped with a warning.
+pub(crate) fn check_onset_terms(
+    phenopacket: &Phenopacket,
+    hpo: &dyn OntologyLike,
+) -> Result<(), ValidationError> {
+    let onsets = phenopacket
+        .phenotypic_features
+        .iter()
+        .map(|feature| &feature.onset)
+        .chain(phenopacket.diseases.iter().map(|disease| &disease.onset));
+
+    let onset_ids = onsets.filter_map(|onset| match onset {
+        Some(TimeElement {
+            element: Some(Element::OntologyClass(onset)),
+        }) => Some(onset.id.as_str()),
+        _ => None,
+    });
+
+    let mut invalid_onsets = vec![];
+    for onset_id in onset_ids.filter(|onset_id| *onset_id != HPO_ONSET_ID) {
+        match hpo.is_descendant_of(onset_id, HPO_ONSET_ID) {
+            Some(true) => {}
+            Some(false) => invalid_onsets.push(onset_id.to_string()),
+            None => {
+                warn!(
+                    "The onset terms of phenopacket '{}' can't be checked, because the hierarchy of the HPO is unknown.",
+                    phenopacket.id
+                );
+                return Ok(());
+            }
+        }
+    }
+    invalid_onsets.sort();
+    invalid_onsets.dedup();
+
+    if !invalid_onsets.is_empty() {
+        let mut err = ValidationError::new("invalid_onset_term");
+        err.add_param(Cow::from("phenopacket_id"), &phenopacket.id);
+        err.add_param(Cow::from("invalid_onsets"), &invalid_onsets);
+        let error_message = format!("Not every onset term is a descendant of {HPO_ONSET_ID}.");
+
+        return Err(err.with_message(Cow::Owned(error_message)));
+    }
+    Ok(())
+}
+
+/// Validates that no phenotype or disease of the phenopacket has an onset after the time of death of the individual.
+///
+/// Two timestamps or two ages are compared directly. An onset, which is an age, while the time of death is a timestamp
+/// (or vice versa), is compared by converting the age into a timestamp with the date of birth of the individual.
+/// Without a date of birth, it can't be checked and is skipped with a warning.
+pub(crate) fn check_onsets_before_death(phenopacket: &Phenopacket) -> Result<(), ValidationError> {
+    let Some(subject) = phenopacket.subject.as_ref() else {
+        return Ok(());
+    };
+    let Some(time_of_death) = subject
+        .vital_status
+        .as_ref()
+        .and_then(|vital_status| vital_status.time_of_death.as_ref())
+    else {
+        return Ok(());
+    };
+    let compare_with_death = |onset: &TimeElement| {
+        compare_time_elements(onset, time_of_death).or_else(|| {
+            let date_of_birth = subject.date_of_birth.as_ref()?;
+            compare_time_elements(
+                &age_to_timestamp(onset, date_of_birth),
+                &age_to_timestamp(time_of_death, date_of_birth),
+            )
+        })
+    };
+
+    let onsets = phenopacket
+        .phenotypic_features
+        .iter()
+        .filter_map(|feature| feature.r#type.as_ref().zip(feature.onset.as_ref()))
+        .chain(
+            phenopacket
+                .diseases
+                .iter()
+                .filter_map(|disease| disease.term.as_ref().zip(disease.onset.as_ref())),
+        );
+
+    let mut onsets_after_death: Vec<String> = vec![];
+    for (term, onset) in onsets {
+        match compare_with_death(onset) {
+            Some(Ordering::Greater) => onsets_after_death.push(term.id.clone()),
+            Some(_) => {}
+            None if is_age_and_timestamp(onset, time_of_death) => warn!(
+                "Phenopacket '{}': Skipped comparing the onset of {} with the time of death, \
+                because one is an age and the other a timestamp and the date of birth is unknown.",
+                phenopacket.id, term.id
+            ),
+            None => {}
+        }
+    }
+    onsets_after_death.sort();
+    onsets_after_death.dedup();
+
+    if !onsets_after_death.is_empty() {
+        let mut err = ValidationError::new("onset_after_death");
+        err.add_param(Cow::from("phenopacket_id"), &phenopacket.id);
+        err.add_param(Cow::from("onsets_after_death"), &onsets_after_death);
+        let error_message = "Some phenotypes or diseases have an onset after the time of death.";
+
+        return Err(err.with_message(Cow::Borrowed(error_message)));
+    }
+    Ok(())
+}
+
+fn is_age_and_timestamp(a: &TimeElement, b: &TimeElement) -> bool {
+    matches!(
+        (&a.element, &b.element),
+        (Some(Element::Age(_)), Some(Element::Timestamp(_)))
+            | (Some(Element::Timestamp(_)), Some(Element::Age(_)))
+    )
+}
+
+/// Validates that the values of the quantitative measurements are within the plausible range of their assay,
+/// e.g. to catch a hemoglobin of 900 g/L. Measurements without a value, or whose assay has no range, are not checked.
+pub(crate) fn check_plausible_measurement_values(
+    phenopacket: &Phenopacket,
+    plausible_ranges: &HashMap<String, PlausibleRange>,
+) -> Result<(), ValidationError> {
+    let implausible_values: Vec<Value> = phenopacket
+        .measurements
+        .iter()
+        .filter_map(|measurement| {
+            let assay = measurement.assay.as_ref()?;
+            let Some(MeasurementValue::Value(value)) = &measurement.measurement_value else {
+                return None;
+            };
+            let Some(ValueKind::Quantity(quantity)) = &value.value else {
+                return None;
+            };
+            let plausible_range = plausible_ranges.get(&assay.id)?;
+
+            (!plausible_range.contains(quantity.value))
+                .then(|| json!({"assay": assay.id, "value": quantity.value}))
+        })
+        .collect();
+
+    if !implausible_values.is_empty() {
+        let mut err = ValidationError::new("implausible_measurement_value");
+        err.add_param(Cow::from("phenopacket_id"), &phenopacket.id);
+        err.add_param(Cow::from("implausible_values"), &implausible_values);
+        let error_message = "Some measurements are outside of the plausible range of their assay.";
+
+        return Err(err.with_message(Cow::Borrowed(error_message)));
+    }
+    Ok(())
+}
+
+/// Ontology classes are the only objects in a phenopacket, which consist of exactly an `id` and a `label`.
+fn collect_ontology_class_ids(value: &Value, ids: &mut Vec<String>) {
+    match value {
+        Value::Object(object) => {
+            if object.len() == 2
+                && let (Some(Value::String(id)), Some(Value::String(_))) =
+                    (object.get("id"), object.get("label"))
+            {
+                ids.push(id.clone());
+                return;
+            }
+            object
+                .values()
+                .for_each(|value| collect_ontology_class_ids(value, ids));
+        }
+        Value::Array(values) => values
+            .iter()
+            .for_each(|value| collect_ontology_class_ids(value, ids)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ontology::traits::OntologyTermLike;
+    use crate::test_suite::mocks::ONTOLOGY_FACTORY;
+    use crate::test_suite::phenopacket_component_generation::default_phenotype;
+    use crate::test_suite::resource_references::{HPO_REF, hp_meta_data_resource};
+    use crate::transform::utils::{try_parse_time_element, try_parse_timestamp};
+    use phenopackets::schema::v2::core::vital_status::Status;
+    use phenopackets::schema::v2::core::{
+        Disease, Individual, Measurement, MetaData, OntologyClass, PhenotypicFeature, Quantity,
+        Resource, Value as ValueStruct, VitalStatus,
+    };
+    use rstest::rstest;
+    use serde_json::from_value;
+
+    fn phenopacket_with_resources(
+        resources: Vec<phenopackets::schema::v2::core::Resource>,
+    ) -> Phenopacket {
+        Phenopacket {
+            id: "pp_1".to_string(),
+            phenotypic_features: vec![default_phenotype()],
+            meta_data: Some(MetaData {
+                resources,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    fn test_check_resource_prefixes() {
+        let phenopacket = phenopacket_with_resources(vec![hp_meta_data_resource()]);
+
+        assert!(check_resource_prefixes(&phenopacket).is_ok());
+    }
+
+    #[rstest]
+    fn test_check_resource_prefixes_missing_resource() {
+        let phenopacket = phenopacket_with_resources(vec![]);
+
+        let err = check_resource_prefixes(&phenopacket).unwrap_err();
+
+        let ids_without_resource: Vec<String> =
+            from_value(err.params.get("ids_without_resource").unwrap().clone()).unwrap();
+        assert_eq!(
+            ids_without_resource,
+            vec![default_phenotype().r#type.unwrap().id]
+        );
+    }
+
+    #[rstest]
+    fn test_check_consistent_resource_versions() {
+        let phenopackets = vec![
+            phenopacket_with_resources(vec![hp_meta_data_resource()]),
+            phenopacket_with_resources(vec![hp_meta_data_resource()]),
+        ];
+
+        assert!(check_consistent_resource_versions(&phenopackets).is_ok());
+    }
+
+    #[rstest]
+    fn test_check_consistent_resource_versions_two_hpo_versions() {
+        let hpo_2024 = Resource {
+            version: "2024-04-26".to_string(),
+            ..hp_meta_data_resource()
+        };
+        let hpo_2025 = Resource {
+            version: "2025-05-06".to_string(),
+            ..hp_meta_data_resource()
+        };
+        let phenopackets = vec![
+            phenopacket_with_resources(vec![hpo_2024.clone()]),
+            phenopacket_with_resources(vec![hpo_2025]),
+            phenopacket_with_resources(vec![hpo_2024]),
+        ];
+
+        let err = check_consistent_resource_versions(&phenopackets).unwrap_err();
+
+        let conflicting_versions: BTreeMap<String, Vec<String>> =
+            from_value(err.params.get("conflicting_versions").unwrap().clone()).unwrap();
+        assert_eq!(
+            conflicting_versions,
+            BTreeMap::from([(
+                "hp".to_string(),
+                vec!["2024-04-26".to_string(), "2025-05-06".to_string()]
+            )])
+        );
+    }
+
+    fn phenotype(hpo_id: &str, excluded: bool) -> PhenotypicFeature {
+        PhenotypicFeature {
+            r#type: Some(OntologyClass {
+                id: hpo_id.to_string(),
+                label: format!("label of {hpo_id}"),
+            }),
+            excluded,
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    fn test_check_conflicting_observation_statuses() {
+        let phenopacket = Phenopacket {
+            id: "pp_1".to_string(),
+            phenotypic_features: vec![
+                phenotype("HP:0000001", false),
+                phenotype("HP:0000001", false),
+                phenotype("HP:0000002", true),
+            ],
+            ..Default::default()
+        };
+
+        assert!(check_conflicting_observation_statuses(&phenopacket).is_ok());
+    }
+
+    #[rstest]
+    fn test_check_conflicting_observation_statuses_observed_and_excluded() {
+        let phenopacket = Phenopacket {
+            id: "pp_1".to_string(),
+            phenotypic_features: vec![
+                phenotype("HP:0000001", false),
+                phenotype("HP:0000002", false),
+                phenotype("HP:0000001", true),
+            ],
+            ..Default::default()
+        };
+
+        let err = check_conflicting_observation_statuses(&phenopacket).unwrap_err();
+
+        let conflicting_hpo_ids: Vec<String> =
+            from_value(err.params.get("conflicting_hpo_ids").unwrap().clone()).unwrap();
+        assert_eq!(conflicting_hpo_ids, vec!["HP:0000001".to_string()]);
+        assert_eq!(err.params.get("phenopacket_id").unwrap(), &json!("pp_1"));
+    }
+
+    fn deceased_patient(
+        time_of_death: &str,
+        phenotype_onset: &str,
+        disease_onset: &str,
+    ) -> Phenopacket {
+        Phenopacket {
+            id: "pp_1".to_string(),
+            subject: Some(Individual {
+                id: "P1".to_string(),
+                vital_status: Some(VitalStatus {
+                    status: Status::Deceased as i32,
+                    time_of_death: try_parse_time_element(time_of_death),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            phenotypic_features: vec![PhenotypicFeature {
+                onset: try_parse_time_element(phenotype_onset),
+                ..phenotype("HP:0000001", false)
+            }],
+            diseases: vec![Disease {
+                term: Some(OntologyClass {
+                    id: "MONDO:0000001".to_string(),
+                    label: "disease".to_string(),
+                }),
+                onset: try_parse_time_element(disease_onset),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case("2020-05-01", "2019-01-01", "2020-05-01")]
+    #[case("P60Y", "P12Y", "P59Y11M")]
+    #[case("2020-05-01", "P90Y", "2020-01-01")]
+    fn test_check_onsets_before_death(
+        #[case] time_of_death: &str,
+        #[case] phenotype_onset: &str,
+        #[case] disease_onset: &str,
+    ) {
+        let phenopacket = deceased_patient(time_of_death, phenotype_onset, disease_onset);
+
+        assert!(check_onsets_before_death(&phenopacket).is_ok());
+    }
+
+    #[rstest]
+    #[case("2020-05-01", "P19Y", "2019-01-01", vec![])]
+    #[case("2020-05-01", "P21Y", "2019-01-01", vec!["HP:0000001"])]
+    #[case("P20Y", "2019-01-01", "2021-01-01", vec!["MONDO:0000001"])]
+    #[case("P20Y", "P12Y", "2019-01-01", vec![])]
+    fn test_check_onsets_before_death_with_date_of_birth(
+        #[case] time_of_death: &str,
+        #[case] phenotype_onset: &str,
+        #[case] disease_onset: &str,
+        #[case] expected_onsets_after_death: Vec<&str>,
+    ) {
+        let mut phenopacket = deceased_patient(time_of_death, phenotype_onset, disease_onset);
+        phenopacket.subject.as_mut().unwrap().date_of_birth = try_parse_timestamp("2000-01-01");
+
+        let result = check_onsets_before_death(&phenopacket);
+
+        let onsets_after_death: Vec<String> = match result {
+            Ok(()) => vec![],
+            Err(err) => from_value(err.params.get("onsets_after_death").unwrap().clone()).unwrap(),
+        };
+        assert_eq!(onsets_after_death, expected_onsets_after_death);
+    }
+
+    #[rstest]
+    #[case("2020-05-01", "2021-01-01", "2019-01-01", vec!["HP:0000001"])]
+    #[case("P60Y", "P12Y", "P61Y", vec!["MONDO:0000001"])]
+    fn test_check_onsets_before_death_onset_after_death(
+        #[case] time_of_death: &str,
+        #[case] phenotype_onset: &str,
+        #[case] disease_onset: &str,
+        #[case] expected_onsets_after_death: Vec<&str>,
+    ) {
+        let phenopacket = deceased_patient(time_of_death, phenotype_onset, disease_onset);
+
+        let err = check_onsets_before_death(&phenopacket).unwrap_err();
+
+        let onsets_after_death: Vec<String> =
+            from_value(err.params.get("onsets_after_death").unwrap().clone()).unwrap();
+        assert_eq!(onsets_after_death, expected_onsets_after_death);
+    }
+
+    fn hemoglobin_measurement(value: Option<f64>) -> Measurement {
+        Measurement {
+            assay: Some(OntologyClass {
+                id: "LOINC:718-7".to_string(),
+                label: "Hemoglobin [Mass/volume] in Blood".to_string(),
+            }),
+            measurement_value: value.map(|value| {
+                MeasurementValue::Value(ValueStruct {
+                    value: Some(ValueKind::Quantity(Quantity {
+                        value,
+                        ..Default::default()
+                    })),
+                })
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case(Some(140.0), None)]
+    #[case(Some(0.0), None)]
+    #[case(Some(900.0), Some(900.0))]
+    #[case(Some(-1.0), Some(-1.0))]
+    #[case(None, None)]
+    fn test_check_plausible_measurement_values(
+        #[case] value: Option<f64>,
+        #[case] expected_implausible_value: Option<f64>,
+    ) {
+        let plausible_ranges = HashMap::from([(
+            "LOINC:718-7".to_string(),
+            PlausibleRange { min: 0.0, max: 250.0 },
+        )]);
+        let phenopacket = Phenopacket {
+            id: "pp_1".to_string(),
+            measurements: vec![hemoglobin_measurement(value)],
+            ..Default::default()
+        };
+
+        let result = check_plausible_measurement_values(&phenopacket, &plausible_ranges);
+
+        match expected_implausible_value {
+            None => assert!(result.is_ok()),
+            Some(expected_value) => assert_eq!(
+                result.unwrap_err().params.get("implausible_values").unwrap(),
+                &json!([{"assay": "LOINC:718-7", "value": expected_value}])
+            ),
+        }
+    }
+
+    #[rstest]
+    #[case("HP:0003577", true)]
+    #[case("HP:0003584", true)]
+    #[case("HP:0003674", true)]
+    #[case("HP:0041249", false)]
+    #[case("HP:0012823", false)]
+    fn test_check_onset_terms(#[case] onset_id: &str, #[case] is_valid: bool) {
+        let hpo = ONTOLOGY_FACTORY
+            .lock()
+            .unwrap()
+            .build_ontology(&HPO_REF)
+            .unwrap();
+
+        let result = check_onset_terms(&phenopacket_with_onset(onset_id), hpo.as_ref());
+
+        if is_valid {
+            assert!(result.is_ok());
+        } else {
+            let invalid_onsets: Vec<String> =
+                from_value(result.unwrap_err().params.get("invalid_onsets").unwrap().clone())
+                    .unwrap();
+            assert_eq!(invalid_onsets, vec![onset_id.to_string()]);
+        }
+    }
+
+    #[rstest]
+    fn test_check_onset_terms_unknown_hierarchy() {
+        #[derive(Debug)]
+        struct OntologyWithoutHierarchy;
+
+        impl OntologyLike for OntologyWithoutHierarchy {
+            fn iter_ontology_terms<'a>(
+                &'a self,
+                _ontology_prefix: String,
+            ) -> Box<dyn Iterator<Item = &'a dyn OntologyTermLike> + 'a> {
+                Box::new(std::iter::empty())
+            }
+        }
+
+        let result = check_onset_terms(
+            &phenopacket_with_onset("HP:0041249"),
+            &OntologyWithoutHierarchy,
+        );
+
+        assert!(result.is_ok());
+    }
+
+    fn phenopacket_with_onset(onset_id: &str) -> Phenopacket {
+        Phenopacket {
+            id: "pp_1".to_string(),
+            phenotypic_features: vec![PhenotypicFeature {
+                onset: Some(TimeElement {
+                    element: Some(Element::OntologyClass(OntologyClass {
+                        id: onset_id.to_string(),
+                        label: "onset".to_string(),
+                    })),
+                }),
+                ..default_phenotype()
+            }],
+            ..Default::default()
+        }
+    }
+}
@@ -11,6 +11,8 @@ pub fn validate_unique_data_sources(sources: &[DataSourceConfig]) -> Result<(),
         let path_buf = match source {
             DataSourceConfig::Csv(csv_config) => &csv_config.source,
             DataSourceConfig::Excel(excel_config) => &excel_config.source,
+            DataSourceConfig::Phenopacket(phenopacket_config) => &phenopacket_config.source,
+            DataSourceConfig::Json(json_config) => &json_config.source,
         };
 
         if let Some(path_string) = path_buf.to_str() {